@@ -3,6 +3,7 @@
 use crate::bank::BenchmarkBank;
 use crate::options::Opts;
 use crate::util::get_ed25519_keypair_from_keystore;
+use crate::workloads::replay::ReplayedTransactions;
 use crate::{FullNodeProxy, LocalValidatorAggregatorProxy, ValidatorProxy};
 use anyhow::{anyhow, bail, Context, Result};
 use prometheus::Registry;
@@ -16,7 +17,7 @@ use sui_types::base_types::ObjectID;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{deterministic_random_account_key, AccountKeyPair};
 use sui_types::object::generate_max_test_gas_objects_with_owner;
-use sui_types::object::Owner;
+use sui_types::object::{Object, Owner};
 use test_utils::authority::test_and_configure_authority_configs_with_objects;
 use test_utils::authority::{spawn_fullnode, spawn_test_authorities};
 use tokio::runtime::Builder;
@@ -44,15 +45,21 @@ impl Env {
         barrier: Arc<Barrier>,
         registry: &Registry,
         opts: &Opts,
+        replayed_transactions: Option<&ReplayedTransactions>,
     ) -> Result<BenchmarkSetup> {
         match self {
             Env::Local => {
+                // The objects used by replayed transactions must exist before they are replayed.
+                let extra_genesis_objects = replayed_transactions
+                    .map(|replayed| replayed.genesis_objects())
+                    .unwrap_or_default();
                 self.setup_local_env(
                     barrier,
                     registry,
                     opts.committee_size as usize,
                     opts.server_metric_port,
                     opts.num_server_threads,
+                    extra_genesis_objects,
                 )
                 .await
             }
@@ -79,12 +86,14 @@ impl Env {
         committee_size: usize,
         server_metric_port: u16,
         num_server_threads: u64,
+        extra_genesis_objects: Vec<Object>,
     ) -> Result<BenchmarkSetup> {
         info!("Running benchmark setup in local mode..");
         let (address, keypair): (SuiAddress, AccountKeyPair) = deterministic_random_account_key();
-        let generated_gas = generate_max_test_gas_objects_with_owner(1, address);
+        let mut genesis_objects = generate_max_test_gas_objects_with_owner(1, address);
+        genesis_objects.extend(extra_genesis_objects);
         let (mut network_config, generated_gas) =
-            test_and_configure_authority_configs_with_objects(committee_size, generated_gas);
+            test_and_configure_authority_configs_with_objects(committee_size, genesis_objects);
         let mut metric_port = server_metric_port;
         for node_config in network_config.validator_configs.iter_mut() {
             // Benchmark setup allocates very large gas objects, which will lead to overflow if we attempt
@@ -22,6 +22,7 @@ use sui_node::metrics;
 use sui_benchmark::benchmark_setup::Env;
 use sui_benchmark::options::Opts;
 
+use sui_benchmark::workloads::replay::ReplayedTransactions;
 use sui_benchmark::workloads::workload_configuration::WorkloadConfiguration;
 
 use sui_benchmark::system_state_observer::SystemStateObserver;
@@ -91,7 +92,15 @@ async fn main() -> Result<()> {
     let barrier = Arc::new(Barrier::new(2));
     let cloned_barrier = barrier.clone();
     let env = if opts.local { Env::Local } else { Env::Remote };
-    let bench_setup = env.setup(cloned_barrier, &registry, &opts).await?;
+    let replayed_transactions = ReplayedTransactions::load_for_opts(&opts)?;
+    let bench_setup = env
+        .setup(
+            cloned_barrier,
+            &registry,
+            &opts,
+            replayed_transactions.as_deref(),
+        )
+        .await?;
     let system_state_observer = {
         // Only need to get system state from one proxy as it is shared for the
         // whole network.
@@ -138,6 +147,7 @@ async fn main() -> Result<()> {
                 bench_setup.bank,
                 &opts,
                 system_state_observer.clone(),
+                replayed_transactions,
             )
             .await?;
             let interval = opts.run_duration;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::*;
+use std::path::PathBuf;
 
 use strum_macros::EnumString;
 
@@ -151,6 +152,10 @@ pub enum RunSpec {
         // relative weight of adversarial transactions in the benchmark workload
         #[clap(long, default_value = "0")]
         adversarial: u32,
+        // relative weight of transactions replayed from recorded node state dumps in the
+        // benchmark workload
+        #[clap(long, default_value = "0")]
+        replay: u32,
 
         // --- workload-specific options --- (TODO: use subcommands or similar)
         // 100 for max hotness i.e all requests target
@@ -170,6 +175,11 @@ pub enum RunSpec {
         // Default is (0-0.5) implying random load at 50% load. See `AdversarialPayloadType` enum for `adversarial_type`
         #[clap(long, default_value = "0-1.0")]
         adversarial_cfg: String,
+        // directory of `NodeStateDump` JSON files with the transactions to replay in the replay
+        // workload. Only supported by local benchmarks, as the objects these transactions use
+        // are added to the genesis of the local cluster.
+        #[clap(long)]
+        replay_dump_dir: Option<PathBuf>,
 
         // --- generic options ---
         // Target qps
@@ -5,6 +5,7 @@ pub mod adversarial;
 pub mod batch_payment;
 pub mod delegation;
 pub mod payload;
+pub mod replay;
 pub mod shared_counter;
 pub mod transfer_object;
 pub mod workload;
@@ -0,0 +1,703 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use rand::{rngs::StdRng, SeedableRng};
+use sui_core::authority::NodeStateDump;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::object::{Object, Owner};
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, TransactionData, TransactionDataAPI, TransactionKind,
+    VerifiedTransaction,
+};
+use sui_types::utils::to_sender_signed_transaction;
+use sui_types::{is_system_package, SUI_CLOCK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID};
+use tracing::{debug, info};
+
+use crate::options::{Opts, RunSpec};
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{Workload, WorkloadBuilder, MAX_BUDGET, MAX_GAS_FOR_TESTING};
+use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::{ExecutionEffects, ValidatorProxy};
+
+/// Transactions recorded in `NodeStateDump`s, along with the objects needed to replay them.
+///
+/// Each original sender is replaced by a replay account derived from its address, which owns
+/// copies of the sender's owned input objects and pays for gas with a benchmark gas coin.
+/// Packages, shared objects and dynamic fields are replayed as they were recorded, so the
+/// benchmark cluster must have all of these objects at genesis (see `genesis_objects`).
+///
+/// Only programmable transactions that can be executed again and again are kept: transactions
+/// that use the system state, that delete, wrap or transfer away an owned input object, or that
+/// transfer away the gas coin are skipped.
+#[derive(Debug, Default)]
+pub struct ReplayedTransactions {
+    /// Transactions by original sender, in the order they were executed.
+    transactions: BTreeMap<SuiAddress, Vec<TransactionData>>,
+    /// Objects used by the transactions, as of the first transaction using them, with owned
+    /// objects transferred to the replay account of their owner.
+    objects: BTreeMap<ObjectID, Object>,
+}
+
+impl ReplayedTransactions {
+    /// Load the transactions of the benchmark's replay workload, if it has one. They are loaded
+    /// once, and shared by the setup of the local cluster and the workload.
+    pub fn load_for_opts(opts: &Opts) -> anyhow::Result<Option<Arc<Self>>> {
+        let RunSpec::Bench {
+            replay,
+            replay_dump_dir,
+            ..
+        } = &opts.run_spec;
+        if *replay == 0 {
+            return Ok(None);
+        }
+        if !opts.local {
+            bail!("The replay workload is only supported by local benchmarks");
+        }
+        let dir = replay_dump_dir
+            .as_ref()
+            .context("--replay-dump-dir is required by the replay workload")?;
+        Ok(Some(Arc::new(Self::load(dir)?)))
+    }
+
+    /// Load the transactions from a directory of `NodeStateDump` JSON files.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let mut dumps = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            let dump: NodeStateDump = serde_json::from_reader(File::open(&path)?)
+                .with_context(|| format!("Failed to read node state dump {}", path.display()))?;
+            dumps.push(dump);
+        }
+        // Transactions of a sender bump the version of its objects, so ordering by the version
+        // assigned to their outputs restores their execution order.
+        dumps.sort_by_key(|dump| (dump.executed_epoch, dump.computed_effects.gas_object().0 .1));
+
+        let mut replayed = Self::default();
+        let mut skipped = 0;
+        for dump in &dumps {
+            if let Err(reason) = Self::check_replayable(dump) {
+                debug!("Not replaying transaction {}: {reason}", dump.tx_digest);
+                skipped += 1;
+                continue;
+            }
+            replayed.add(dump);
+        }
+        info!(
+            "Loaded {} transactions from {} senders to replay, skipped {skipped}",
+            dumps.len() - skipped,
+            replayed.transactions.len()
+        );
+        Ok(replayed)
+    }
+
+    fn check_replayable(dump: &NodeStateDump) -> Result<(), &'static str> {
+        let data = dump.sender_signed_data.transaction_data();
+        let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+            return Err("not a programmable transaction");
+        };
+        // `TransferObjects` is the only command taking the gas coin by value. Replaying it would
+        // give away the benchmark gas coin of the replay account.
+        let transfers_gas = pt.commands.iter().any(|command| {
+            matches!(command, Command::TransferObjects(objects, _) if objects.contains(&Argument::GasCoin))
+        });
+        if transfers_gas {
+            return Err("transfers the gas coin");
+        }
+        let effects = &dump.computed_effects;
+        for input in &pt.inputs {
+            match input {
+                CallArg::Object(ObjectArg::SharedObject { id, .. })
+                    if *id == SUI_SYSTEM_STATE_OBJECT_ID =>
+                {
+                    return Err("uses the system state");
+                }
+                CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => {
+                    let owned = dump
+                        .input_objects
+                        .iter()
+                        .any(|obj| obj.id() == *id && obj.owner.is_address_owned());
+                    let kept = effects.mutated().iter().any(|(obj_ref, owner)| {
+                        obj_ref.0 == *id && *owner == Owner::AddressOwner(data.sender())
+                    });
+                    if owned && !kept {
+                        return Err("consumes an owned input object");
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, dump: &NodeStateDump) {
+        let data = dump.sender_signed_data.transaction_data();
+        let (replay_account, _) = replay_account(data.sender());
+        let objects = dump
+            .input_objects
+            .iter()
+            .chain(&dump.runtime_reads)
+            .chain(&dump.loaded_child_objects);
+        for object in objects {
+            let id = object.id();
+            if data.gas().iter().any(|gas| gas.0 == id)
+                || is_system_package(id)
+                || id == SUI_CLOCK_OBJECT_ID
+            {
+                continue;
+            }
+            if matches!(self.objects.get(&id), Some(known) if known.version() <= object.version()) {
+                continue;
+            }
+            let mut object = object.clone();
+            if object.owner.is_address_owned() {
+                object.owner = Owner::AddressOwner(replay_account);
+            }
+            self.objects.insert(id, object);
+        }
+        self.transactions
+            .entry(data.sender())
+            .or_default()
+            .push(data.clone());
+    }
+
+    /// Objects that must be created at genesis for the transactions to be replayed.
+    pub fn genesis_objects(&self) -> Vec<Object> {
+        self.objects.values().cloned().collect()
+    }
+
+    pub fn num_senders(&self) -> u64 {
+        self.transactions.len() as u64
+    }
+}
+
+/// The account replaying the transactions of `original_sender`. It is derived from the original
+/// sender so that the objects created at genesis and the benchmark gas agree on it.
+fn replay_account(original_sender: SuiAddress) -> (SuiAddress, AccountKeyPair) {
+    get_key_pair_from_rng(&mut StdRng::from_seed(original_sender.to_inner()))
+}
+
+#[derive(Debug)]
+pub struct ReplayTestPayload {
+    original_sender: SuiAddress,
+    transactions: Vec<TransactionData>,
+    next: usize,
+    /// Current references of the owned and immutable input objects.
+    objects: HashMap<ObjectID, ObjectRef>,
+    /// Initial shared versions of the shared input objects, as created at genesis.
+    shared_objects: HashMap<ObjectID, SequenceNumber>,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for ReplayTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "replay")
+    }
+}
+
+impl Payload for ReplayTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            // Expected once the objects used have diverged from their recorded state.
+            debug!("Replayed tx failed: {}", effects.status());
+        }
+        self.gas.0 = effects.gas_object().0;
+        for (obj_ref, _) in effects.mutated() {
+            if let Some(known) = self.objects.get_mut(&obj_ref.0) {
+                *known = obj_ref;
+            }
+        }
+        self.next = (self.next + 1) % self.transactions.len();
+    }
+
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let data = &self.transactions[self.next];
+        let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+            unreachable!("Only programmable transactions are replayed");
+        };
+        let (gas, replay_account, keypair) = &self.gas;
+        let original_sender = bcs::to_bytes(&self.original_sender).unwrap();
+        let mut pt = pt.clone();
+        for input in pt.inputs.iter_mut() {
+            match input {
+                // Keep transfers to the sender within the replay account.
+                CallArg::Pure(bytes) if *bytes == original_sender => {
+                    *bytes = bcs::to_bytes(replay_account).unwrap();
+                }
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(obj_ref)) => {
+                    *obj_ref = self.objects[&obj_ref.0];
+                }
+                CallArg::Object(ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    ..
+                }) => {
+                    if let Some(version) = self.shared_objects.get(id) {
+                        *initial_shared_version = *version;
+                    }
+                }
+                CallArg::Pure(_) => (),
+            }
+        }
+        let data = TransactionData::new_programmable(
+            *replay_account,
+            vec![*gas],
+            pt,
+            data.gas_budget().min(MAX_BUDGET),
+            self.system_state_observer
+                .state
+                .borrow()
+                .reference_gas_price,
+        );
+        to_sender_signed_transaction(data, keypair.as_ref())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayWorkloadBuilder {
+    replayed_transactions: Arc<ReplayedTransactions>,
+}
+
+impl ReplayWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        replayed_transactions: Option<Arc<ReplayedTransactions>>,
+    ) -> Option<WorkloadBuilderInfo> {
+        let replayed_transactions = replayed_transactions?;
+        // Each sender replays its transactions one at a time, so there can be no more payloads
+        // (and no more workers) than senders.
+        let num_senders = replayed_transactions.num_senders();
+        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let num_workers = ((workload_weight * num_workers as f32).ceil() as u64).min(num_senders);
+        let max_ops = (target_qps * in_flight_ratio).min(num_senders);
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(ReplayWorkloadBuilder {
+                    replayed_transactions,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for ReplayWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        self.replayed_transactions
+            .transactions
+            .keys()
+            .map(|sender| {
+                let (address, keypair) = replay_account(*sender);
+                GasCoinConfig {
+                    amount: MAX_GAS_FOR_TESTING,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(ReplayWorkload {
+            replayed_transactions: self.replayed_transactions.clone(),
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayWorkload {
+    replayed_transactions: Arc<ReplayedTransactions>,
+    payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for ReplayWorkload {
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+
+    async fn make_test_payloads(
+        &self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        let original_senders: HashMap<_, _> = self
+            .replayed_transactions
+            .transactions
+            .keys()
+            .map(|sender| (replay_account(*sender).0, *sender))
+            .collect();
+        let mut payloads = vec![];
+        for gas in &self.payload_gas {
+            let original_sender = original_senders[&gas.1];
+            let transactions = self.replayed_transactions.transactions[&original_sender].clone();
+
+            // Genesis resets the versions of the objects it creates, so look up their references.
+            let mut objects = HashMap::new();
+            let mut shared_objects = HashMap::new();
+            for data in &transactions {
+                let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+                    continue;
+                };
+                for input in &pt.inputs {
+                    let CallArg::Object(arg) = input else {
+                        continue;
+                    };
+                    let id = match arg {
+                        ObjectArg::ImmOrOwnedObject((id, _, _)) => *id,
+                        ObjectArg::SharedObject { id, .. } => *id,
+                    };
+                    if objects.contains_key(&id) || shared_objects.contains_key(&id) {
+                        continue;
+                    }
+                    let object = proxy
+                        .get_object(id)
+                        .await
+                        .unwrap_or_else(|e| panic!("Failed to get replayed object {id}: {e}"));
+                    match object.owner {
+                        Owner::Shared {
+                            initial_shared_version,
+                        } => {
+                            shared_objects.insert(id, initial_shared_version);
+                        }
+                        _ => {
+                            objects.insert(id, object.compute_object_reference());
+                        }
+                    }
+                }
+            }
+
+            payloads.push(Box::new(ReplayTestPayload {
+                original_sender,
+                transactions,
+                next: 0,
+                objects,
+                shared_objects,
+                gas: gas.clone(),
+                system_state_observer: system_state_observer.clone(),
+            }));
+        }
+        payloads
+            .into_iter()
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_state_observer::SystemState;
+    use sui_types::base_types::random_object_ref;
+    use sui_types::crypto::get_key_pair;
+    use sui_types::digests::TransactionEffectsDigest;
+    use sui_types::effects::{TransactionEffects, TransactionEffectsV1};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::ProgrammableTransaction;
+    use tokio::sync::{oneshot, watch};
+
+    /// Objects used by the recorded transactions of `sender`.
+    struct Fixture {
+        sender: SuiAddress,
+        coin: Object,
+        shared: Object,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let sender = SuiAddress::random_for_testing_only();
+            Self {
+                sender,
+                coin: Object::with_id_owner_version_for_testing(
+                    ObjectID::random(),
+                    SequenceNumber::from(5),
+                    sender,
+                ),
+                shared: Object::shared_for_testing(),
+            }
+        }
+
+        fn coin_at(&self, version: u64) -> Object {
+            Object::with_id_owner_version_for_testing(
+                self.coin.id(),
+                SequenceNumber::from(version),
+                self.sender,
+            )
+        }
+
+        /// Splits a coin off `coin` and sends it back to the sender, while using the shared object.
+        fn split_coin(&self, coin: &Object) -> ProgrammableTransaction {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            let coin_arg = builder
+                .obj(ObjectArg::ImmOrOwnedObject(coin.compute_object_reference()))
+                .unwrap();
+            builder
+                .obj(ObjectArg::SharedObject {
+                    id: self.shared.id(),
+                    initial_shared_version: self.shared.version(),
+                    mutable: true,
+                })
+                .unwrap();
+            let amount = builder.pure(100u64).unwrap();
+            let split = builder.command(Command::SplitCoins(coin_arg, vec![amount]));
+            builder.transfer_arg(self.sender, split);
+            builder.finish()
+        }
+
+        /// A dump of `pt`, sent by the sender and reading `coin` and the shared object. The gas
+        /// coin is at `gas_version` once executed. The coin stays with the sender if `keep_coin`.
+        fn dump(
+            &self,
+            pt: ProgrammableTransaction,
+            coin: &Object,
+            gas_version: u64,
+            keep_coin: bool,
+        ) -> NodeStateDump {
+            let (_, keypair): (_, AccountKeyPair) = get_key_pair();
+            let gas = Object::with_id_owner_version_for_testing(
+                ObjectID::random(),
+                SequenceNumber::from(gas_version - 1),
+                self.sender,
+            );
+            let data = TransactionData::new_programmable(
+                self.sender,
+                vec![gas.compute_object_reference()],
+                pt,
+                MAX_BUDGET,
+                1,
+            );
+            let tx = to_sender_signed_transaction(data, &keypair);
+            let mut gas_ref = gas.compute_object_reference();
+            gas_ref.1 = SequenceNumber::from(gas_version);
+            let owner = Owner::AddressOwner(self.sender);
+            let mut mutated = vec![(gas_ref, owner)];
+            if keep_coin {
+                mutated.push((coin.compute_object_reference(), owner));
+            }
+            NodeStateDump {
+                tx_digest: *tx.digest(),
+                sender_signed_data: tx.data().clone(),
+                executed_epoch: 0,
+                reference_gas_price: 1,
+                protocol_version: 1,
+                epoch_start_timestamp_ms: 0,
+                computed_effects: TransactionEffects::V1(TransactionEffectsV1 {
+                    mutated,
+                    gas_object: (gas_ref, owner),
+                    ..Default::default()
+                }),
+                expected_effects_digest: TransactionEffectsDigest::ZERO,
+                relevant_system_packages: vec![],
+                shared_objects: vec![self.shared.clone()],
+                loaded_child_objects: vec![],
+                modified_at_versions: vec![],
+                runtime_reads: vec![],
+                input_objects: vec![coin.clone(), self.shared.clone(), gas],
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_replayable() {
+        let fixture = Fixture::new();
+        let coin = &fixture.coin;
+        let replayable = fixture.dump(fixture.split_coin(coin), coin, 2, true);
+        assert_eq!(ReplayedTransactions::check_replayable(&replayable), Ok(()));
+
+        let consumed = fixture.dump(fixture.split_coin(coin), coin, 2, false);
+        assert_eq!(
+            ReplayedTransactions::check_replayable(&consumed),
+            Err("consumes an owned input object")
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(SuiAddress::random_for_testing_only(), None);
+        let transfers_gas = fixture.dump(builder.finish(), coin, 2, true);
+        assert_eq!(
+            ReplayedTransactions::check_replayable(&transfers_gas),
+            Err("transfers the gas coin")
+        );
+        // Paying from the gas coin by reference can be replayed
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(SuiAddress::random_for_testing_only(), Some(100));
+        let pays_from_gas = fixture.dump(builder.finish(), coin, 2, true);
+        assert_eq!(
+            ReplayedTransactions::check_replayable(&pays_from_gas),
+            Ok(())
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .obj(ObjectArg::SharedObject {
+                id: SUI_SYSTEM_STATE_OBJECT_ID,
+                initial_shared_version: SequenceNumber::from(1),
+                mutable: true,
+            })
+            .unwrap();
+        let uses_system_state = fixture.dump(builder.finish(), coin, 2, true);
+        assert_eq!(
+            ReplayedTransactions::check_replayable(&uses_system_state),
+            Err("uses the system state")
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let fixture = Fixture::new();
+        let (replay_account, _) = replay_account(fixture.sender);
+        let first = fixture.dump(fixture.split_coin(&fixture.coin), &fixture.coin, 6, true);
+        let later_coin = fixture.coin_at(6);
+        let second = fixture.dump(fixture.split_coin(&later_coin), &later_coin, 7, true);
+
+        let mut replayed = ReplayedTransactions::default();
+        replayed.add(&first);
+        replayed.add(&second);
+
+        // Objects are kept as of the first transaction using them, with owned objects moved to
+        // the replay account, while the gas of the recorded transactions is left out.
+        let coin = &replayed.objects[&fixture.coin.id()];
+        assert_eq!(coin.version(), fixture.coin.version());
+        assert_eq!(coin.owner, Owner::AddressOwner(replay_account));
+        assert_eq!(
+            replayed.objects[&fixture.shared.id()].owner,
+            fixture.shared.owner
+        );
+        assert_eq!(replayed.genesis_objects().len(), 2);
+
+        assert_eq!(replayed.num_senders(), 1);
+        let transactions = &replayed.transactions[&fixture.sender];
+        assert_eq!(
+            transactions,
+            &vec![
+                first.sender_signed_data.transaction_data().clone(),
+                second.sender_signed_data.transaction_data().clone(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let later_coin = fixture.coin_at(6);
+        let first = fixture.dump(fixture.split_coin(&fixture.coin), &fixture.coin, 6, true);
+        let second = fixture.dump(fixture.split_coin(&later_coin), &later_coin, 7, true);
+        let consumed = fixture.dump(fixture.split_coin(&later_coin), &later_coin, 8, false);
+
+        let dir = tempfile::tempdir()?;
+        // File names do not follow the execution order, which is restored from the effects
+        for (name, dump) in [
+            ("a.json", &second),
+            ("b.json", &first),
+            ("c.json", &consumed),
+        ] {
+            serde_json::to_writer(File::create(dir.path().join(name))?, dump)?;
+        }
+        std::fs::write(dir.path().join("README.md"), "not a dump")?;
+
+        let replayed = ReplayedTransactions::load(dir.path())?;
+        assert_eq!(
+            replayed.transactions[&fixture.sender],
+            vec![
+                first.sender_signed_data.transaction_data().clone(),
+                second.sender_signed_data.transaction_data().clone(),
+            ]
+        );
+        assert_eq!(
+            replayed.objects[&fixture.coin.id()].version(),
+            fixture.coin.version()
+        );
+
+        std::fs::write(dir.path().join("d.json"), "{}")?;
+        assert!(ReplayedTransactions::load(dir.path()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_make_transaction() {
+        let fixture = Fixture::new();
+        let dump = fixture.dump(fixture.split_coin(&fixture.coin), &fixture.coin, 6, true);
+        let (replay_account, keypair) = replay_account(fixture.sender);
+
+        // References of the objects as created at genesis
+        let coin_ref = random_object_ref();
+        let shared_version = SequenceNumber::from(1);
+        let gas_ref = random_object_ref();
+        let (_sender, _) = oneshot::channel();
+        let (_, state) = watch::channel(SystemState {
+            reference_gas_price: 42,
+            protocol_config: None,
+        });
+        let mut payload = ReplayTestPayload {
+            original_sender: fixture.sender,
+            transactions: vec![dump.sender_signed_data.transaction_data().clone()],
+            next: 0,
+            objects: HashMap::from([(fixture.coin.id(), coin_ref)]),
+            shared_objects: HashMap::from([(fixture.shared.id(), shared_version)]),
+            gas: (gas_ref, replay_account, Arc::new(keypair)),
+            system_state_observer: Arc::new(SystemStateObserver { state, _sender }),
+        };
+
+        let tx = payload.make_transaction();
+        let data = tx.data().transaction_data();
+        assert_eq!(data.sender(), replay_account);
+        assert_eq!(data.gas(), &[gas_ref]);
+        assert_eq!(data.gas_price(), 42);
+        let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+            panic!("Expected a programmable transaction");
+        };
+        assert!(pt
+            .inputs
+            .contains(&CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_ref))));
+        assert!(pt
+            .inputs
+            .contains(&CallArg::Object(ObjectArg::SharedObject {
+                id: fixture.shared.id(),
+                initial_shared_version: shared_version,
+                mutable: true,
+            })));
+        // The split coin is sent to the replay account instead of the original sender
+        assert!(pt
+            .inputs
+            .contains(&CallArg::Pure(bcs::to_bytes(&replay_account).unwrap())));
+        assert!(!pt
+            .inputs
+            .contains(&CallArg::Pure(bcs::to_bytes(&fixture.sender).unwrap())));
+    }
+}
@@ -6,6 +6,7 @@ use crate::options::{Opts, RunSpec};
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::batch_payment::BatchPaymentWorkloadBuilder;
 use crate::workloads::delegation::DelegationWorkloadBuilder;
+use crate::workloads::replay::{ReplayWorkloadBuilder, ReplayedTransactions};
 use crate::workloads::shared_counter::SharedCounterWorkloadBuilder;
 use crate::workloads::transfer_object::TransferObjectWorkloadBuilder;
 use crate::workloads::WorkloadInfo;
@@ -22,6 +23,7 @@ impl WorkloadConfiguration {
         bank: BenchmarkBank,
         opts: &Opts,
         system_state_observer: Arc<SystemStateObserver>,
+        replayed_transactions: Option<Arc<ReplayedTransactions>>,
     ) -> Result<Vec<WorkloadInfo>> {
         match opts.run_spec.clone() {
             RunSpec::Bench {
@@ -33,6 +35,7 @@ impl WorkloadConfiguration {
                 delegation,
                 batch_payment,
                 adversarial,
+                replay,
                 adversarial_cfg,
                 batch_payment_size,
                 shared_counter_hotness_factor,
@@ -46,9 +49,11 @@ impl WorkloadConfiguration {
                     delegation,
                     batch_payment,
                     adversarial,
+                    replay,
                     AdversarialPayloadCfg::from_str(&adversarial_cfg).unwrap(),
                     batch_payment_size,
                     shared_counter_hotness_factor,
+                    replayed_transactions,
                     target_qps,
                     in_flight_ratio,
                     bank,
@@ -68,9 +73,11 @@ impl WorkloadConfiguration {
         delegation_weight: u32,
         batch_payment_weight: u32,
         adversarial_weight: u32,
+        replay_weight: u32,
         adversarial_cfg: AdversarialPayloadCfg,
         batch_payment_size: u32,
         shared_counter_hotness_factor: u32,
+        replayed_transactions: Option<Arc<ReplayedTransactions>>,
         target_qps: u64,
         in_flight_ratio: u64,
         mut bank: BenchmarkBank,
@@ -81,7 +88,8 @@ impl WorkloadConfiguration {
             + transfer_object_weight
             + delegation_weight
             + batch_payment_weight
-            + adversarial_weight;
+            + adversarial_weight
+            + replay_weight;
         let mut workload_builders = vec![];
         let shared_workload = SharedCounterWorkloadBuilder::from(
             shared_counter_weight as f32 / total_weight as f32,
@@ -122,6 +130,14 @@ impl WorkloadConfiguration {
             adversarial_cfg,
         );
         workload_builders.push(adversarial_workload);
+        let replay_workload = ReplayWorkloadBuilder::from(
+            replay_weight as f32 / total_weight as f32,
+            target_qps,
+            num_workers,
+            in_flight_ratio,
+            replayed_transactions,
+        );
+        workload_builders.push(replay_workload);
         let (workload_params, workload_builders): (Vec<_>, Vec<_>) = workload_builders
             .into_iter()
             .flatten()
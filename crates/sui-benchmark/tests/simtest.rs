@@ -457,9 +457,11 @@ mod test {
             delegation_weight,
             batch_payment_weight,
             adversarial_weight,
+            /* replay_weight */ 0,
             adversarial_cfg,
             batch_payment_size,
             shared_counter_hotness_factor,
+            /* replayed_transactions */ None,
             target_qps,
             in_flight_ratio,
             bank,
@@ -123,6 +123,9 @@ pub struct NodeConfig {
 
     #[serde(default)]
     pub state_debug_dump_config: StateDebugDumpConfig,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_sink_config: Option<EventSinkConfig>,
 }
 
 fn default_authority_store_pruning_config() -> AuthorityStorePruningConfig {
@@ -423,6 +426,12 @@ pub struct AuthorityStorePruningConfig {
     /// That ensures that all sst files eventually go through the compaction process
     #[serde(skip_serializing_if = "Option::is_none")]
     pub periodic_compaction_threshold_days: Option<usize>,
+    /// number of epochs to keep transactions, effects, events and checkpoint contents for,
+    /// together with their secondary indexes. Can't be lower than `num_epochs_to_retain`.
+    /// Transaction data is never pruned if not set.
+    /// Peers can't state sync checkpoints whose data has been pruned from this node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_retain_for_transactions: Option<u64>,
 }
 
 impl Default for AuthorityStorePruningConfig {
@@ -439,6 +448,7 @@ impl Default for AuthorityStorePruningConfig {
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
             periodic_compaction_threshold_days: None,
+            num_epochs_to_retain_for_transactions: None,
         }
     }
 }
@@ -457,6 +467,7 @@ impl AuthorityStorePruningConfig {
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
             periodic_compaction_threshold_days: None,
+            num_epochs_to_retain_for_transactions: None,
         }
     }
     pub fn fullnode_config() -> Self {
@@ -472,6 +483,7 @@ impl AuthorityStorePruningConfig {
             max_transactions_in_batch: 1000,
             use_range_deletion: true,
             periodic_compaction_threshold_days: None,
+            num_epochs_to_retain_for_transactions: None,
         }
     }
 }
@@ -687,6 +699,44 @@ pub struct StateDebugDumpConfig {
     pub dump_file_directory: Option<PathBuf>,
 }
 
+/// Forwards the events of executed checkpoints that match a filter to an external destination.
+/// Checkpoints are delivered in order and at least once: the last delivered checkpoint is
+/// recorded in a cursor file, and delivery resumes from the checkpoint after it on restart.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventSinkConfig {
+    /// Only events matching this filter are forwarded. All events are forwarded if not set. The
+    /// filter is written like the `EventFilter` of `suix_subscribeEvent`, and is checked when the
+    /// sink starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
+    /// Also forward the effects of the transactions that emitted the forwarded events.
+    #[serde(default)]
+    pub include_effects: bool,
+    pub destination: EventSinkDestination,
+    /// File recording the last delivered checkpoint. Defaults to `event_sink_cursor` in the db path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor_path: Option<PathBuf>,
+    /// Checkpoint to start from when there is no cursor yet. Defaults to the checkpoint after
+    /// the latest executed one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_checkpoint: Option<u64>,
+    /// Skip checkpoints whose contents were pruned before they could be delivered. The sink stops
+    /// at the first such checkpoint if not set.
+    #[serde(default)]
+    pub skip_pruned_checkpoints: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventSinkDestination {
+    /// POST the forwarded data of each checkpoint as JSON to `url`, retrying until it succeeds.
+    Webhook { url: String },
+    /// Write the forwarded data of each checkpoint to `<directory>/<checkpoint>.jsonl`, one
+    /// transaction per line.
+    File { directory: PathBuf },
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -2,9 +2,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp::{max, min};
 use std::collections::BTreeMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{collections::HashMap, fs, pin::Pin, sync::Arc};
 
 use anyhow::anyhow;
@@ -58,7 +60,7 @@ use sui_json_rpc_types::{
 };
 use sui_macros::{fail_point, fail_point_async};
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
-use sui_storage::indexes::{CoinInfo, ObjectIndexChanges};
+use sui_storage::indexes::{ChildObjectIndexBackfill, CoinInfo, ObjectIndexChanges};
 use sui_storage::IndexStore;
 use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::{
@@ -114,6 +116,7 @@ use crate::authority::authority_per_epoch_store::{AuthorityPerEpochStore, CertTx
 use crate::authority::authority_per_epoch_store_pruner::AuthorityPerEpochStorePruner;
 use crate::authority::authority_store::{ExecutionLockReadGuard, InputKey, ObjectLockStatus};
 use crate::authority::authority_store_pruner::AuthorityStorePruner;
+use crate::authority::authority_store_tables::LiveObject;
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
 use crate::checkpoints::checkpoint_executor::CheckpointExecutor;
@@ -664,13 +667,24 @@ impl AuthorityState {
             return Err(SuiError::ValidatorHaltedAtEpochEnd);
         }
 
-        // Checks to see if the transaction has expired
-        if match &transaction.inner().data().transaction_data().expiration() {
-            TransactionExpiration::None => false,
-            TransactionExpiration::Epoch(epoch) => *epoch < epoch_store.epoch(),
-        } {
-            return Err(SuiError::TransactionExpired);
-        }
+        // Checks to see if the transaction has expired. Only checkpoint bounds need the highest
+        // executed checkpoint.
+        let transaction_data = transaction.data().transaction_data();
+        let highest_executed_checkpoint = match transaction_data.expiration() {
+            TransactionExpiration::Checkpoint(_) => self
+                .checkpoint_store
+                .get_highest_executed_checkpoint_seq_number()?,
+            _ => None,
+        };
+        transaction_input_checker::check_transaction_expiration(
+            transaction_data,
+            epoch_store.epoch(),
+            highest_executed_checkpoint,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+        )?;
 
         let signed = self.handle_transaction_impl(transaction, epoch_store).await;
         match signed {
@@ -791,7 +805,7 @@ impl AuthorityState {
 
         self.metrics.total_cert_attempts.inc();
 
-        if !certificate.contains_shared_object() {
+        if !certificate.requires_sequencing() {
             // Shared object transactions need to be sequenced by Narwhal before enqueueing
             // for execution, done in AuthorityPerEpochStore::handle_consensus_transaction().
             // For owned object transactions, they can be enqueued for execution immediately.
@@ -1315,12 +1329,15 @@ impl AuthorityState {
         ))
     }
 
-    /// The object ID for gas can be any object ID, even for an uncreated object
+    /// The object ID for gas can be any object ID, even for an uncreated object. If `show_trace`
+    /// is set, the results include a trace of the Move calls made, gas charged, and objects
+    /// accessed during execution.
     pub async fn dev_inspect_transaction_block(
         &self,
         sender: SuiAddress,
         transaction_kind: TransactionKind,
         gas_price: Option<u64>,
+        show_trace: bool,
     ) -> SuiResult<DevInspectResults> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         if !self.is_fullnode(&epoch_store) {
@@ -1387,7 +1404,26 @@ impl AuthorityState {
             )
             .expect("We defined natives to not fail here"),
         );
-        let (inner_temp_store, effects, execution_result) =
+        let epoch_data = epoch_store.epoch_start_config().epoch_data();
+        let certificate_deny_set = self.certificate_deny_config.certificate_deny_set();
+        let (inner_temp_store, effects, execution_result) = if show_trace {
+            execution_engine::execute_transaction_to_effects::<execution_mode::DevInspectTrace, _>(
+                shared_object_refs,
+                temporary_store,
+                transaction_kind,
+                sender,
+                &[gas_object_ref],
+                transaction_digest,
+                transaction_dependencies,
+                &move_vm,
+                gas_status,
+                &epoch_data,
+                protocol_config,
+                self.metrics.limits_metrics.clone(),
+                false, // enable_expensive_checks
+                certificate_deny_set,
+            )
+        } else {
             execution_engine::execute_transaction_to_effects::<execution_mode::DevInspect, _>(
                 shared_object_refs,
                 temporary_store,
@@ -1398,12 +1434,13 @@ impl AuthorityState {
                 transaction_dependencies,
                 &move_vm,
                 gas_status,
-                &epoch_store.epoch_start_config().epoch_data(),
+                &epoch_data,
                 protocol_config,
                 self.metrics.limits_metrics.clone(),
                 false, // enable_expensive_checks
-                self.certificate_deny_config.certificate_deny_set(),
-            );
+                certificate_deny_set,
+            )
+        };
 
         let module_cache =
             TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
@@ -1412,6 +1449,7 @@ impl AuthorityState {
             effects,
             inner_temp_store.events.clone(),
             execution_result,
+            inner_temp_store.execution_trace.clone(),
             &module_cache,
         )
     }
@@ -1488,13 +1526,15 @@ impl AuthorityState {
 
         let mut deleted_owners = vec![];
         let mut deleted_dynamic_fields = vec![];
+        let mut deleted_child_objects = vec![];
         for (id, _, _) in effects.deleted().iter().chain(effects.wrapped()) {
             let old_version = modified_at_version.get(id).unwrap();
 
             match self.get_owner_at_version(id, *old_version)? {
                 Owner::AddressOwner(addr) => deleted_owners.push((addr, *id)),
                 Owner::ObjectOwner(object_id) => {
-                    deleted_dynamic_fields.push((ObjectID::from(object_id), *id))
+                    deleted_dynamic_fields.push((ObjectID::from(object_id), *id));
+                    deleted_child_objects.push((ObjectID::from(object_id), *id));
                 }
                 _ => {}
             }
@@ -1502,6 +1542,7 @@ impl AuthorityState {
 
         let mut new_owners = vec![];
         let mut new_dynamic_fields = vec![];
+        let mut new_child_objects = vec![];
 
         for (oref, owner, kind) in effects.all_changed_objects() {
             let id = &oref.0;
@@ -1521,7 +1562,8 @@ impl AuthorityState {
                             deleted_owners.push((addr, *id));
                         }
                         Owner::ObjectOwner(object_id) => {
-                            deleted_dynamic_fields.push((ObjectID::from(object_id), *id))
+                            deleted_dynamic_fields.push((ObjectID::from(object_id), *id));
+                            deleted_child_objects.push((ObjectID::from(object_id), *id));
                         }
                         _ => {}
                     }
@@ -1556,6 +1598,8 @@ impl AuthorityState {
                     let Some(o) = self.database.get_object_by_key(&oref.0, oref.1)? else{
                         continue;
                     };
+                    new_child_objects
+                        .push(((ObjectID::from(*owner), *id), ObjectInfo::new(oref, &o)));
                     let Some(df_info) = self.try_create_dynamic_field_info(&o, epoch_store)? else{
                         // Skip indexing for non dynamic field objects.
                         continue;
@@ -1569,8 +1613,10 @@ impl AuthorityState {
         Ok(ObjectIndexChanges {
             deleted_owners,
             deleted_dynamic_fields,
+            deleted_child_objects,
             new_owners,
             new_dynamic_fields,
+            new_child_objects,
         })
     }
 
@@ -1902,6 +1948,7 @@ impl AuthorityState {
         let _objects_pruner = AuthorityStorePruner::new(
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
+            indexes.clone(),
             store.objects_lock_table.clone(),
             pruning_config,
             epoch_store.epoch_start_state().epoch_duration_ms(),
@@ -1929,6 +1976,12 @@ impl AuthorityState {
             debug_dump_config,
         });
 
+        // Done before executing anything, so that the backfill is not racing with the indexing of
+        // new transactions. It logs its progress and resumes where it stopped if interrupted.
+        state
+            .backfill_child_object_index()
+            .expect("Error backfilling the child object index.");
+
         // Start a task to execute ready certificates.
         let authority_state = Arc::downgrade(&state);
         spawn_monitored_task!(execution_process(
@@ -1961,6 +2014,46 @@ impl AuthorityState {
             .enqueue_certificates(certs, epoch_store)
     }
 
+    /// Indexes the live objects owned by other objects, on nodes which indexed objects before the
+    /// child object index was added.
+    fn backfill_child_object_index(&self) -> SuiResult {
+        let Some(index_store) = &self.indexes else{
+            return Ok(())
+        };
+        let cursor = match index_store.child_object_index_backfill()? {
+            Some(ChildObjectIndexBackfill::Done) => return Ok(()),
+            Some(ChildObjectIndexBackfill::InProgress(cursor)) => {
+                info!(?cursor, "Resuming the child object index backfill");
+                Some(cursor)
+            }
+            // An empty index store is populated with the genesis objects instead.
+            None if index_store.is_empty() => return Ok(()),
+            None => {
+                info!("Backfilling the child object index from the live object set");
+                None
+            }
+        };
+
+        let child_objects = self
+            .database
+            .iter_live_object_set()
+            .skip_while(|object| cursor.map_or(false, |cursor| object.object_id() <= cursor))
+            .filter_map(|object| match object {
+                LiveObject::Normal(o) => match o.owner {
+                    Owner::ObjectOwner(parent) => Some((
+                        (ObjectID::from(parent), o.id()),
+                        ObjectInfo::new(&o.compute_object_reference(), &o),
+                    )),
+                    _ => None,
+                },
+                LiveObject::Wrapped(_) => None,
+            });
+        let count = index_store.insert_child_objects(child_objects)?;
+        index_store.set_child_object_index_backfilled()?;
+        info!(count, "Backfilled the child object index");
+        Ok(())
+    }
+
     fn create_owner_index_if_empty(
         &self,
         genesis_objects: &[Object],
@@ -1975,6 +2068,7 @@ impl AuthorityState {
 
         let mut new_owners = vec![];
         let mut new_dynamic_fields = vec![];
+        let mut new_child_objects = vec![];
         for o in genesis_objects.iter() {
             match o.owner {
                 Owner::AddressOwner(addr) => new_owners.push((
@@ -1983,6 +2077,10 @@ impl AuthorityState {
                 )),
                 Owner::ObjectOwner(object_id) => {
                     let id = o.id();
+                    new_child_objects.push((
+                        (ObjectID::from(object_id), id),
+                        ObjectInfo::new(&o.compute_object_reference(), o),
+                    ));
                     let Some(info) = self.try_create_dynamic_field_info(o, epoch_store)? else{
                         continue;
                     };
@@ -1995,8 +2093,10 @@ impl AuthorityState {
         index_store.insert_genesis_objects(ObjectIndexChanges {
             deleted_owners: vec![],
             deleted_dynamic_fields: vec![],
+            deleted_child_objects: vec![],
             new_owners,
             new_dynamic_fields,
+            new_child_objects,
         })
     }
 
@@ -2521,6 +2621,21 @@ impl AuthorityState {
         Ok(move_objects)
     }
 
+    pub fn get_child_objects(
+        &self,
+        owner: ObjectID,
+        // If `Some`, the query will start from the next item after the specified cursor
+        cursor: Option<ObjectID>,
+        limit: usize,
+        filter: Option<SuiObjectDataFilter>,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        if let Some(indexes) = &self.indexes {
+            indexes.get_child_objects(owner, cursor, limit, filter)
+        } else {
+            Err(SuiError::IndexStoreNotAvailable)
+        }
+    }
+
     pub fn get_dynamic_fields(
         &self,
         owner: ObjectID,
@@ -2572,7 +2687,7 @@ impl AuthorityState {
         let effects = self.database.get_executed_effects(&digest)?;
         match (transaction, effects) {
             (Some(transaction), Some(effects)) => Ok((transaction, effects)),
-            _ => Err(SuiError::TransactionNotFound { digest }),
+            _ => Err(self.transaction_not_found_error(digest)?),
         }
     }
 
@@ -2580,13 +2695,28 @@ impl AuthorityState {
         &self,
         digest: TransactionDigest,
     ) -> SuiResult<VerifiedTransaction> {
-        let transaction = self.database.get_transaction_block(&digest)?;
-        transaction.ok_or(SuiError::TransactionNotFound { digest })
+        match self.database.get_transaction_block(&digest)? {
+            Some(transaction) => Ok(transaction),
+            None => Err(self.transaction_not_found_error(digest)?),
+        }
     }
 
     pub fn get_executed_effects(&self, digest: TransactionDigest) -> SuiResult<TransactionEffects> {
-        let effects = self.database.get_executed_effects(&digest)?;
-        effects.ok_or(SuiError::TransactionNotFound { digest })
+        match self.database.get_executed_effects(&digest)? {
+            Some(effects) => Ok(effects),
+            None => Err(self.transaction_not_found_error(digest)?),
+        }
+    }
+
+    /// Returns the error for a transaction missing from the store, telling apart transactions
+    /// which have been pruned from transactions this node has never executed
+    pub fn transaction_not_found_error(&self, digest: TransactionDigest) -> SuiResult<SuiError> {
+        if let Some((_, checkpoint)) = self.database.get_transaction_checkpoint(&digest)? {
+            if self.database.is_checkpoint_data_pruned(checkpoint)? {
+                return Ok(SuiError::TransactionPruned { digest, checkpoint });
+            }
+        }
+        Ok(SuiError::TransactionNotFound { digest })
     }
 
     pub fn multi_get_executed_transactions(
@@ -2660,25 +2790,85 @@ impl AuthorityState {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        if let Some(TransactionFilter::Checkpoint(sequence_number)) = filter {
+        let checkpoints = match filter {
+            Some(TransactionFilter::Checkpoint(sequence_number)) => {
+                Some(sequence_number..sequence_number + 1)
+            }
+            Some(TransactionFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            }) => Some(start_checkpoint..end_checkpoint),
+            _ => None,
+        };
+        if let Some(checkpoints) = checkpoints {
+            return self.get_transactions_in_checkpoints(checkpoints, cursor, limit, reverse);
+        }
+        self.get_indexes()?
+            .get_transactions(filter, cursor, limit, reverse, &|digest| {
+                Ok(self
+                    .get_transaction_checkpoint_sequence(digest)?
+                    .map(|(_, checkpoint)| checkpoint))
+            })
+    }
+
+    /// Returns the transactions of a range of checkpoints, in execution order
+    fn get_transactions_in_checkpoints(
+        &self,
+        mut checkpoints: Range<CheckpointSequenceNumber>,
+        // If `Some`, the query will start from the next item after the specified cursor
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        let highest_executed = self
+            .get_checkpoint_store()
+            .get_highest_executed_checkpoint_seq_number()?;
+        checkpoints.end = min(
+            checkpoints.end,
+            highest_executed.map_or(0, |highest| highest + 1),
+        );
+        // Resume from the checkpoint of the cursor
+        let cursor_checkpoint = match cursor {
+            Some(cursor) => {
+                let (_, checkpoint) = self
+                    .get_transaction_checkpoint_sequence(&cursor)?
+                    .ok_or(SuiError::TransactionNotFound { digest: cursor })?;
+                if reverse {
+                    checkpoints.end = min(checkpoints.end, checkpoint + 1);
+                } else {
+                    checkpoints.start = max(checkpoints.start, checkpoint);
+                }
+                Some(checkpoint)
+            }
+            None => None,
+        };
+        let limit = limit.unwrap_or(usize::MAX);
+        let checkpoints: Box<dyn Iterator<Item = CheckpointSequenceNumber>> = if reverse {
+            Box::new(checkpoints.rev())
+        } else {
+            Box::new(checkpoints)
+        };
+
+        let mut digests = vec![];
+        for sequence_number in checkpoints {
+            if digests.len() >= limit {
+                break;
+            }
             let checkpoint_contents =
                 self.get_checkpoint_contents_by_sequence_number(sequence_number)?;
             let iter = checkpoint_contents.iter().map(|c| c.transaction);
-            if reverse {
-                let iter = iter
-                    .rev()
-                    .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
-                    .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::max_value())).collect());
+            let iter: Box<dyn Iterator<Item = TransactionDigest>> = if reverse {
+                Box::new(iter.rev())
             } else {
-                let iter = iter
-                    .skip_while(|d| cursor.is_some() && Some(*d) != cursor)
-                    .skip(usize::from(cursor.is_some()));
-                return Ok(iter.take(limit.unwrap_or(usize::max_value())).collect());
-            }
+                Box::new(iter)
+            };
+            let skip_to_cursor = cursor_checkpoint == Some(sequence_number);
+            let iter = iter
+                .skip_while(|d| skip_to_cursor && Some(*d) != cursor)
+                .skip(usize::from(skip_to_cursor));
+            digests.extend(iter.take(limit - digests.len()));
         }
-        self.get_indexes()?
-            .get_transactions(filter, cursor, limit, reverse)
+        Ok(digests)
     }
 
     fn get_checkpoint_store(&self) -> Arc<CheckpointStore> {
@@ -2798,7 +2988,21 @@ impl AuthorityState {
         match verified_checkpoint {
             Some(verified_checkpoint) => {
                 let content_digest = verified_checkpoint.into_inner().content_digest;
-                self.get_checkpoint_contents(content_digest)
+                match self
+                    .get_checkpoint_store()
+                    .get_checkpoint_contents(&content_digest)?
+                {
+                    Some(contents) => Ok(contents),
+                    None if self.database.is_checkpoint_data_pruned(sequence_number)? => {
+                        Err(SuiError::CheckpointContentsPruned {
+                            digest: content_digest,
+                            checkpoint: sequence_number,
+                        })
+                    }
+                    None => Err(SuiError::UserInputError {
+                        error: UserInputError::CheckpointContentsNotFound(content_digest),
+                    }),
+                }
             }
             None => Err(SuiError::UserInputError {
                 error: UserInputError::VerifiedCheckpointNotFound(sequence_number),
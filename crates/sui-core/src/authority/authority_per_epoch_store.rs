@@ -45,6 +45,7 @@ use crate::epoch::reconfiguration::ReconfigState;
 use crate::module_cache_metrics::ResolverMetrics;
 use crate::signature_verifier::*;
 use crate::stake_aggregator::StakeAggregator;
+use crate::transaction_input_checker::check_transaction_expiration;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_runtime::native_functions::NativeFunctionTable;
@@ -289,6 +290,15 @@ pub struct AuthorityEpochTables {
     /// Contains a single key, which overrides the value of
     /// ProtocolConfig::buffer_stake_for_protocol_upgrade_bps
     override_protocol_upgrade_buffer_stake: DBMap<u64, u64>,
+
+    /// Signers of the checkpoint signatures sequenced by consensus, for checkpoints above the
+    /// highest sequenced checkpoint
+    sequenced_checkpoint_signers: DBMap<(CheckpointSequenceNumber, AuthorityName), ()>,
+
+    /// Contains a single key, the highest checkpoint signed by validity threshold stake in
+    /// checkpoint signatures sequenced by consensus. It only depends on the consensus order, so
+    /// transactions which expire at a checkpoint are checked against it.
+    highest_sequenced_checkpoint: DBMap<u64, CheckpointSequenceNumber>,
 }
 
 fn signed_transactions_table_default_config() -> DBOptions {
@@ -1462,16 +1472,24 @@ impl AuthorityPerEpochStore {
     /// - Verify and initialize the state to execute the certificates.
     ///   Return VerifiedCertificates for each executable certificate
     /// - Or update the state for checkpoint or epoch change protocol.
+    /// `commit_timestamp_ms` is the timestamp of the consensus commit the transactions belong to,
+    /// which certificates that expire at a timestamp are checked against.
     pub(crate) async fn process_consensus_transactions<C: CheckpointServiceNotify>(
         &self,
         transactions: Vec<VerifiedSequencedConsensusTransaction>,
+        commit_timestamp_ms: u64,
         checkpoint_service: &Arc<C>,
         parent_sync_store: impl ParentSync,
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         let mut verified_certificates = Vec::new();
         for tx in transactions {
             if let Some(cert) = self
-                .process_consensus_transaction(tx, checkpoint_service, &parent_sync_store)
+                .process_consensus_transaction(
+                    tx,
+                    commit_timestamp_ms,
+                    checkpoint_service,
+                    &parent_sync_store,
+                )
                 .await?
             {
                 verified_certificates.push(cert);
@@ -1483,6 +1501,7 @@ impl AuthorityPerEpochStore {
     async fn process_consensus_transaction<C: CheckpointServiceNotify>(
         &self,
         transaction: VerifiedSequencedConsensusTransaction,
+        commit_timestamp_ms: u64,
         checkpoint_service: &Arc<C>,
         parent_sync_store: impl ParentSync,
     ) -> SuiResult<Option<VerifiedExecutableTransaction>> {
@@ -1536,6 +1555,29 @@ impl AuthorityPerEpochStore {
                     return Ok(None);
                 }
 
+                // Expiration is checked against the consensus commit and the checkpoints
+                // sequenced before it, so that every validator drops the same certificates.
+                if self.protocol_config().transaction_expiration_bounds()
+                    && check_transaction_expiration(
+                        certificate.data().transaction_data(),
+                        self.epoch(),
+                        self.highest_sequenced_checkpoint()?,
+                        commit_timestamp_ms,
+                    )
+                    .is_err()
+                {
+                    debug!(
+                        tx_digest = ?certificate.digest(),
+                        "Dropping certificate which expired before it was sequenced"
+                    );
+                    self.finish_consensus_transaction_process_with_batch(
+                        self.tables.last_consensus_index.batch(),
+                        transaction.key(),
+                        consensus_index,
+                    )?;
+                    return Ok(None);
+                }
+
                 if certificate.contains_shared_object() {
                     self.record_shared_object_cert_from_consensus(
                         &transaction,
@@ -1559,6 +1601,9 @@ impl AuthorityPerEpochStore {
                 kind: ConsensusTransactionKind::CheckpointSignature(info),
                 ..
             }) => {
+                if self.protocol_config().transaction_expiration_bounds() {
+                    self.record_sequenced_checkpoint_signature(info)?;
+                }
                 checkpoint_service.notify_checkpoint_signature(self, info)?;
                 self.record_consensus_transaction_processed(&transaction, consensus_index)?;
                 Ok(None)
@@ -1775,6 +1820,52 @@ impl AuthorityPerEpochStore {
             .unwrap_or_default()
     }
 
+    /// Highest checkpoint signed by validity threshold stake in checkpoint signatures sequenced
+    /// by consensus so far in this epoch
+    pub fn highest_sequenced_checkpoint(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.tables.highest_sequenced_checkpoint.get(&0)?)
+    }
+
+    /// Records the signer of a checkpoint signature sequenced by consensus, and raises the
+    /// highest sequenced checkpoint once the checkpoint is signed by validity threshold stake.
+    /// A signature is sequenced at the same point of consensus on every validator, whether or not
+    /// the validator has certified the checkpoint itself, so all of them agree on the result.
+    fn record_sequenced_checkpoint_signature(
+        &self,
+        info: &CheckpointSignatureMessage,
+    ) -> SuiResult {
+        let sequence = info.summary.sequence_number;
+        if self
+            .highest_sequenced_checkpoint()?
+            .map_or(false, |highest| sequence <= highest)
+        {
+            return Ok(());
+        }
+        let signer = info.summary.auth_sig().authority;
+        self.tables
+            .sequenced_checkpoint_signers
+            .insert(&(sequence, signer), &())?;
+        let mut stake = 0;
+        for key in self
+            .tables
+            .sequenced_checkpoint_signers
+            .keys()
+            .skip_to(&(sequence, AuthorityName::ZERO))?
+        {
+            let (seq, authority) = key?;
+            if seq != sequence {
+                break;
+            }
+            stake += self.committee().weight(&authority);
+        }
+        if stake >= self.committee().validity_threshold() {
+            self.tables
+                .highest_sequenced_checkpoint
+                .insert(&0, &sequence)?;
+        }
+        Ok(())
+    }
+
     pub fn insert_checkpoint_signature(
         &self,
         checkpoint_seq: CheckpointSequenceNumber,
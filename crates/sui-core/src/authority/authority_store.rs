@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::cmp::Ordering;
-use std::ops::Not;
 use std::sync::Arc;
 use std::{iter, mem, thread};
 
@@ -313,15 +312,7 @@ impl AuthorityStore {
         &self,
         event_digest: &TransactionEventsDigest,
     ) -> Result<Option<TransactionEvents>, TypedStoreError> {
-        let data = self
-            .perpetual_tables
-            .events
-            .iter()
-            .skip_to(&(*event_digest, 0))?
-            .take_while(|((digest, _), _)| digest == event_digest)
-            .map(|(_, e)| e)
-            .collect::<Vec<_>>();
-        Ok(data.is_empty().not().then_some(TransactionEvents { data }))
+        self.perpetual_tables.get_events(event_digest)
     }
 
     pub fn multi_get_events(
@@ -458,6 +449,18 @@ impl AuthorityStore {
             .collect())
     }
 
+    /// Returns true if the transactions, effects, events and contents of the checkpoint have been
+    /// pruned. The genesis checkpoint is never pruned.
+    pub fn is_checkpoint_data_pruned(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<bool> {
+        Ok(self
+            .perpetual_tables
+            .get_highest_pruned_transactions_checkpoint()?
+            .map_or(false, |pruned| checkpoint > 0 && checkpoint <= pruned))
+    }
+
     /// Returns true if there are no objects in the database
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         self.perpetual_tables.database_is_empty()
@@ -932,6 +935,7 @@ impl AuthorityStore {
             loaded_child_objects: _,
             no_extraneous_module_bytes: _,
             runtime_read_objects: _,
+            execution_trace: _,
         } = inner_temporary_store;
         trace!(written =? written.values().map(|((obj_id, ver, _), _, _)| (obj_id, ver)).collect::<Vec<_>>(),
                "batch_update_objects: temp store written");
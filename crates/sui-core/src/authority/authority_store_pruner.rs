@@ -14,11 +14,16 @@ use std::collections::HashMap;
 use std::time::SystemTime;
 use std::{sync::Arc, time::Duration};
 use sui_config::node::AuthorityStorePruningConfig;
+use sui_storage::indexes::IndexedTransaction;
 use sui_storage::mutex_table::RwLockTable;
-use sui_types::base_types::SequenceNumber;
+use sui_storage::IndexStore;
+use sui_types::base_types::{ExecutionDigests, SequenceNumber};
+use sui_types::digests::CheckpointContentsDigest;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
+use sui_types::effects::TransactionEvents;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::transaction::TransactionDataAPI;
 use sui_types::{
     base_types::{ObjectID, VersionNumber},
     storage::ObjectKey,
@@ -37,6 +42,8 @@ pub struct AuthorityStorePruner {
 pub struct AuthorityStorePruningMetrics {
     pub last_pruned_checkpoint: IntGauge,
     pub num_pruned_objects: IntCounter,
+    pub last_pruned_transactions_checkpoint: IntGauge,
+    pub num_pruned_transactions: IntCounter,
 }
 
 impl AuthorityStorePruningMetrics {
@@ -54,6 +61,18 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            last_pruned_transactions_checkpoint: register_int_gauge_with_registry!(
+                "last_pruned_transactions_checkpoint",
+                "Last checkpoint whose transactions, effects and events were pruned",
+                registry
+            )
+            .unwrap(),
+            num_pruned_transactions: register_int_counter_with_registry!(
+                "num_pruned_transactions",
+                "Number of pruned transactions",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
@@ -234,6 +253,189 @@ impl AuthorityStorePruner {
         Ok(())
     }
 
+    /// Deletes transactions along with their effects and events, their entries in the secondary
+    /// indexes and the contents of the checkpoints they were executed in
+    fn prune_transactions(
+        transactions: Vec<ExecutionDigests>,
+        checkpoint_contents: Vec<CheckpointContentsDigest>,
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        indexes: Option<&Arc<IndexStore>>,
+        checkpoint_number: CheckpointSequenceNumber,
+        metrics: Arc<AuthorityStorePruningMetrics>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("TransactionsPruner");
+        let effects = perpetual_db
+            .effects
+            .multi_get(transactions.iter().map(|tx| tx.effects))?;
+
+        // Indexes are pruned first, since they are built from the data pruned below. Entries
+        // missing from the store were already pruned by an earlier, interrupted run.
+        if let Some(indexes) = indexes {
+            let mut indexed_transactions = vec![];
+            let transaction_data = perpetual_db
+                .transactions
+                .multi_get(transactions.iter().map(|tx| tx.transaction))?;
+            for ((digests, transaction), effects) in
+                transactions.iter().zip(transaction_data).zip(&effects)
+            {
+                let (Some(transaction), Some(effects)) = (transaction, effects) else {
+                    continue;
+                };
+                let events = match effects.events_digest() {
+                    Some(digest) => perpetual_db.get_events(digest)?.unwrap_or_default(),
+                    None => TransactionEvents::default(),
+                };
+                let data = transaction.inner().data().transaction_data();
+                indexed_transactions.push(IndexedTransaction {
+                    digest: digests.transaction,
+                    sender: data.sender(),
+                    input_objects: data
+                        .input_objects()?
+                        .iter()
+                        .map(|o| o.object_id())
+                        .collect(),
+                    changed_objects: effects
+                        .all_changed_objects()
+                        .into_iter()
+                        .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner))
+                        .collect(),
+                    move_functions: data
+                        .move_calls()
+                        .into_iter()
+                        .map(|(package, module, function)| {
+                            (*package, module.to_owned(), function.to_owned())
+                        })
+                        .collect(),
+                    events,
+                });
+            }
+            indexes.prune_transactions(&indexed_transactions)?;
+        }
+
+        let mut wb = perpetual_db.transactions.batch();
+        wb.delete_batch(
+            &perpetual_db.transactions,
+            transactions.iter().map(|tx| tx.transaction),
+        )?;
+        wb.delete_batch(
+            &perpetual_db.executed_effects,
+            transactions.iter().map(|tx| tx.transaction),
+        )?;
+        wb.delete_batch(
+            &perpetual_db.effects,
+            transactions.iter().map(|tx| tx.effects),
+        )?;
+        for events_digest in effects
+            .iter()
+            .flatten()
+            .filter_map(|effects| effects.events_digest())
+        {
+            wb.delete_range(
+                &perpetual_db.events,
+                &(*events_digest, 0),
+                &(*events_digest, usize::MAX),
+            )?;
+        }
+        perpetual_db.set_highest_pruned_transactions_checkpoint(&mut wb, checkpoint_number)?;
+        wb.write()?;
+
+        checkpoint_store.delete_checkpoint_contents(checkpoint_contents)?;
+        metrics
+            .num_pruned_transactions
+            .inc_by(transactions.len() as u64);
+        metrics
+            .last_pruned_transactions_checkpoint
+            .set(checkpoint_number as i64);
+        Ok(())
+    }
+
+    /// Prunes transactions, effects, events and checkpoint contents, along with their secondary
+    /// indexes, from all checkpoints of epochs eligible for pruning. The genesis checkpoint is
+    /// never pruned. Pruned checkpoints can no longer be served to peers through state sync, so
+    /// nodes catching up from this node must start from a later checkpoint or a snapshot.
+    pub async fn prune_transactions_for_eligible_epochs(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        indexes: Option<&Arc<IndexStore>>,
+        config: AuthorityStorePruningConfig,
+        metrics: Arc<AuthorityStorePruningMetrics>,
+    ) -> anyhow::Result<()> {
+        let Some(num_epochs_to_retain) = config.num_epochs_to_retain_for_transactions else {
+            return Ok(());
+        };
+        // Objects are pruned from effects, so transactions are retained at least as long
+        let num_epochs_to_retain = if config.num_epochs_to_retain == u64::MAX {
+            num_epochs_to_retain
+        } else {
+            num_epochs_to_retain.max(config.num_epochs_to_retain)
+        };
+        let mut checkpoint_number = perpetual_db
+            .get_highest_pruned_transactions_checkpoint()?
+            .unwrap_or_default();
+        let (highest_executed_checkpoint, current_epoch) = checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .map(|c| (c.sequence_number, c.epoch()))
+            .unwrap_or_default();
+        // The objects pruner reads effects, so transactions must not be pruned ahead of it
+        let highest_pruned_objects_checkpoint = if config.num_epochs_to_retain == u64::MAX {
+            CheckpointSequenceNumber::MAX
+        } else {
+            perpetual_db.get_highest_pruned_checkpoint()?
+        };
+        let mut batch_checkpoints = vec![];
+        let mut batch_transactions = vec![];
+
+        debug!(
+            "Starting transactions pruning. Current epoch: {}. Latest pruned checkpoint: {}",
+            current_epoch, checkpoint_number
+        );
+
+        loop {
+            let Some(ckpt) = checkpoint_store.certified_checkpoints.get(&(checkpoint_number + 1))? else {break;};
+            let checkpoint = ckpt.into_inner();
+            if (current_epoch < checkpoint.epoch().saturating_add(num_epochs_to_retain))
+                || (*checkpoint.sequence_number() > highest_executed_checkpoint)
+                || (*checkpoint.sequence_number() > highest_pruned_objects_checkpoint)
+            {
+                break;
+            }
+            checkpoint_number = *checkpoint.sequence_number();
+
+            let content = checkpoint_store
+                .get_checkpoint_contents(&checkpoint.content_digest)?
+                .ok_or_else(|| anyhow::anyhow!("checkpoint content data is missing"))?;
+            batch_transactions.extend(content.iter().copied());
+            batch_checkpoints.push(checkpoint.content_digest);
+
+            if batch_transactions.len() >= config.max_transactions_in_batch
+                || batch_checkpoints.len() >= config.max_checkpoints_in_batch
+            {
+                Self::prune_transactions(
+                    std::mem::take(&mut batch_transactions),
+                    std::mem::take(&mut batch_checkpoints),
+                    perpetual_db,
+                    checkpoint_store,
+                    indexes,
+                    checkpoint_number,
+                    metrics.clone(),
+                )?;
+            }
+        }
+        if !batch_checkpoints.is_empty() {
+            Self::prune_transactions(
+                batch_transactions,
+                batch_checkpoints,
+                perpetual_db,
+                checkpoint_store,
+                indexes,
+                checkpoint_number,
+                metrics,
+            )?;
+        }
+        Ok(())
+    }
+
     fn compact_next_sst_file(
         perpetual_db: Arc<AuthorityPerpetualTables>,
         delay_days: usize,
@@ -277,6 +479,7 @@ impl AuthorityStorePruner {
         epoch_duration_ms: u64,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
+        indexes: Option<Arc<IndexStore>>,
         objects_lock_table: Arc<RwLockTable<ObjectContentDigest>>,
         metrics: Arc<AuthorityStorePruningMetrics>,
         indirect_objects_threshold: usize,
@@ -317,9 +520,14 @@ impl AuthorityStorePruner {
         tokio::task::spawn(async move {
             loop {
                 tokio::select! {
-                    _ = prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX => {
-                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config, metrics.clone(), indirect_objects_threshold).await {
-                            error!("Failed to prune objects: {:?}", err);
+                    _ = prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX || config.num_epochs_to_retain_for_transactions.is_some() => {
+                        if config.num_epochs_to_retain != u64::MAX {
+                            if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config, metrics.clone(), indirect_objects_threshold).await {
+                                error!("Failed to prune objects: {:?}", err);
+                            }
+                        }
+                        if let Err(err) = Self::prune_transactions_for_eligible_epochs(&perpetual_db, &checkpoint_store, indexes.as_ref(), config, metrics.clone()).await {
+                            error!("Failed to prune transactions: {:?}", err);
                         }
                     },
                     _ = &mut recv => break,
@@ -331,6 +539,7 @@ impl AuthorityStorePruner {
     pub fn new(
         perpetual_db: Arc<AuthorityPerpetualTables>,
         checkpoint_store: Arc<CheckpointStore>,
+        indexes: Option<Arc<IndexStore>>,
         objects_lock_table: Arc<RwLockTable<ObjectContentDigest>>,
         pruning_config: AuthorityStorePruningConfig,
         epoch_duration_ms: u64,
@@ -343,6 +552,7 @@ impl AuthorityStorePruner {
                 epoch_duration_ms,
                 perpetual_db,
                 checkpoint_store,
+                indexes,
                 objects_lock_table,
                 AuthorityStorePruningMetrics::new(registry),
                 indirect_objects_threshold,
@@ -372,13 +582,26 @@ mod tests {
         get_store_object_pair, ObjectContentDigest, StoreData, StoreObject, StoreObjectPair,
         StoreObjectWrapper,
     };
+    use crate::checkpoints::CheckpointStore;
+    use crate::test_utils::make_dummy_tx;
+    use fastcrypto::traits::KeyPair;
     #[cfg(not(target_env = "msvc"))]
     use pprof::Symbol;
     use prometheus::Registry;
+    use sui_config::node::AuthorityStorePruningConfig;
     use sui_storage::mutex_table::RwLockTable;
-    use sui_types::base_types::{ObjectDigest, VersionNumber};
+    use sui_types::base_types::{ExecutionDigests, ObjectDigest, VersionNumber};
+    use sui_types::committee::Committee;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::digests::CheckpointContentsDigest;
     use sui_types::effects::TransactionEffects;
     use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::message_envelope::Message;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+        VerifiedCheckpoint,
+    };
     use sui_types::{
         base_types::{ObjectID, SequenceNumber},
         object::Object,
@@ -603,6 +826,191 @@ mod tests {
         }
     }
 
+    /// Creates two checkpoints per epoch for `num_epochs` epochs, each with a single transaction,
+    /// and marks all of them as executed. Returns the transactions of each checkpoint.
+    fn insert_checkpoints_with_transactions(
+        perpetual_db: &AuthorityPerpetualTables,
+        checkpoint_store: &CheckpointStore,
+        num_epochs: u64,
+    ) -> Vec<(ExecutionDigests, CheckpointContentsDigest)> {
+        let (committee, key_pairs) = Committee::new_simple_test_committee_of_size(1);
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let mut previous_digest = None;
+        let mut checkpoints = vec![];
+        for sequence_number in 0..num_epochs * 2 {
+            let epoch = sequence_number / 2;
+            let transaction = make_dummy_tx(sender, sender, &sender_key);
+            let effects = TransactionEffects::new_with_tx(&transaction);
+            let digests = ExecutionDigests::new(*transaction.digest(), effects.digest());
+            perpetual_db
+                .transactions
+                .insert(transaction.digest(), transaction.serializable_ref())
+                .unwrap();
+            perpetual_db
+                .executed_effects
+                .insert(transaction.digest(), &digests.effects)
+                .unwrap();
+            perpetual_db
+                .effects
+                .insert(&digests.effects, &effects)
+                .unwrap();
+
+            let contents = CheckpointContents::new_with_causally_ordered_transactions([digests]);
+            let summary = CheckpointSummary::new(
+                epoch,
+                sequence_number,
+                sequence_number + 1,
+                &contents,
+                previous_digest,
+                GasCostSummary::default(),
+                None,
+                0,
+            );
+            let committee = Committee::new_for_testing_with_normalized_voting_power(
+                epoch,
+                committee.voting_rights.iter().cloned().collect(),
+            );
+            let signature = SignedCheckpointSummary::new(
+                epoch,
+                summary.clone(),
+                &key_pairs[0],
+                key_pairs[0].public().into(),
+            )
+            .auth_sig()
+            .clone();
+            let checkpoint = VerifiedCheckpoint::new_unchecked(
+                CertifiedCheckpointSummary::new(summary, vec![signature], &committee).unwrap(),
+            );
+            previous_digest = Some(*checkpoint.digest());
+            checkpoints.push((digests, *contents.digest()));
+            checkpoint_store
+                .insert_checkpoint_contents(contents)
+                .unwrap();
+            checkpoint_store
+                .insert_verified_checkpoint(checkpoint.clone())
+                .unwrap();
+            checkpoint_store
+                .update_highest_executed_checkpoint(&checkpoint)
+                .unwrap();
+        }
+        checkpoints
+    }
+
+    fn is_pruned(
+        perpetual_db: &AuthorityPerpetualTables,
+        checkpoint_store: &CheckpointStore,
+        (digests, contents_digest): &(ExecutionDigests, CheckpointContentsDigest),
+    ) -> bool {
+        let transaction = perpetual_db.transactions.get(&digests.transaction).unwrap();
+        let executed_effects = perpetual_db
+            .executed_effects
+            .get(&digests.transaction)
+            .unwrap();
+        let effects = perpetual_db.effects.get(&digests.effects).unwrap();
+        let contents = checkpoint_store
+            .get_checkpoint_contents(contents_digest)
+            .unwrap();
+        // Either all data of the checkpoint is pruned, or none of it is
+        assert_eq!(transaction.is_none(), effects.is_none());
+        assert_eq!(transaction.is_none(), executed_effects.is_none());
+        assert_eq!(transaction.is_none(), contents.is_none());
+        transaction.is_none()
+    }
+
+    #[tokio::test]
+    async fn test_prune_transactions_for_eligible_epochs() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+        let checkpoints = insert_checkpoints_with_transactions(&perpetual_db, &checkpoint_store, 3);
+        let metrics = AuthorityStorePruningMetrics::new(&Registry::default());
+
+        // Transaction data is never pruned unless configured
+        let mut config = AuthorityStorePruningConfig {
+            num_epochs_to_retain: u64::MAX,
+            ..Default::default()
+        };
+        AuthorityStorePruner::prune_transactions_for_eligible_epochs(
+            &perpetual_db,
+            &checkpoint_store,
+            None,
+            config,
+            metrics.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(checkpoints
+            .iter()
+            .all(|c| !is_pruned(&perpetual_db, &checkpoint_store, c)));
+
+        // The current epoch is 2, so with one epoch retained all checkpoints of epochs 0 and 1
+        // are pruned, except for the genesis checkpoint
+        config.num_epochs_to_retain_for_transactions = Some(1);
+        AuthorityStorePruner::prune_transactions_for_eligible_epochs(
+            &perpetual_db,
+            &checkpoint_store,
+            None,
+            config,
+            metrics,
+        )
+        .await
+        .unwrap();
+        let pruned = checkpoints
+            .iter()
+            .map(|c| is_pruned(&perpetual_db, &checkpoint_store, c))
+            .collect::<Vec<_>>();
+        assert_eq!(pruned, vec![false, true, true, true, false, false]);
+        assert_eq!(
+            perpetual_db
+                .get_highest_pruned_transactions_checkpoint()
+                .unwrap(),
+            Some(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_transactions_retains_objects_pruning_epochs() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+        let checkpoints = insert_checkpoints_with_transactions(&perpetual_db, &checkpoint_store, 3);
+        let metrics = AuthorityStorePruningMetrics::new(&Registry::default());
+        let config = AuthorityStorePruningConfig {
+            num_epochs_to_retain: 2,
+            num_epochs_to_retain_for_transactions: Some(0),
+            ..Default::default()
+        };
+        let prune = || {
+            AuthorityStorePruner::prune_transactions_for_eligible_epochs(
+                &perpetual_db,
+                &checkpoint_store,
+                None,
+                config,
+                metrics.clone(),
+            )
+        };
+
+        // Transactions are not pruned ahead of the objects pruner, which reads their effects
+        prune().await.unwrap();
+        assert!(checkpoints
+            .iter()
+            .all(|c| !is_pruned(&perpetual_db, &checkpoint_store, c)));
+
+        // Once objects are pruned, transactions are still retained for `num_epochs_to_retain`
+        // epochs, so only epoch 0 is pruned
+        let mut wb = perpetual_db.pruned_checkpoint.batch();
+        perpetual_db
+            .set_highest_pruned_checkpoint(&mut wb, 5)
+            .unwrap();
+        wb.write().unwrap();
+        prune().await.unwrap();
+        let pruned = checkpoints
+            .iter()
+            .map(|c| is_pruned(&perpetual_db, &checkpoint_store, c))
+            .collect::<Vec<_>>();
+        assert_eq!(pruned, vec![false, true, false, false, false, false]);
+    }
+
     #[cfg(not(target_env = "msvc"))]
     #[tokio::test]
     async fn test_db_size_after_compaction() -> Result<(), anyhow::Error> {
@@ -9,12 +9,13 @@ use std::path::Path;
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::digests::TransactionEventsDigest;
-use sui_types::effects::TransactionEffects;
+use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::storage::ObjectStore;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::util::{empty_compaction_filter, reference_count_merge_operator};
 use typed_store::rocks::{
-    default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf, ReadWriteOptions,
+    default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf,
+    ReadWriteOptions, TypedStoreError,
 };
 use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 
@@ -88,7 +89,7 @@ pub struct AuthorityPerpetualTables {
     // Currently this is needed in the validator for returning events during process certificates.
     // We could potentially remove this if we decided not to provide events in the execution path.
     // TODO: Figure out what to do with this table in the long run.
-    // Also we need a pruning policy for this table. We can prune this table along with tx/effects.
+    // This table is pruned along with tx/effects, see `num_epochs_to_retain_for_transactions`.
     #[default_options_override_fn = "events_table_default_config"]
     pub(crate) events: DBMap<(TransactionEventsDigest, usize), Event>,
 
@@ -108,6 +109,11 @@ pub struct AuthorityPerpetualTables {
     /// A singleton table that stores latest pruned checkpoint. Used to keep objects pruner progress
     pub(crate) pruned_checkpoint: DBMap<(), CheckpointSequenceNumber>,
 
+    /// A singleton table that stores the latest checkpoint whose transactions, effects, events and
+    /// contents have been pruned. Used to keep transactions pruner progress, and to tell pruned
+    /// data apart from missing data on reads
+    pub(crate) pruned_transactions_checkpoint: DBMap<(), CheckpointSequenceNumber>,
+
     /// Expected total amount of SUI in the network. This is expected to remain constant
     /// throughout the lifetime of the network. We check it at the end of each epoch if
     /// expensive checks are enabled. We cannot use 10B today because in tests we often
@@ -257,6 +263,25 @@ impl AuthorityPerpetualTables {
         Ok(())
     }
 
+    /// Returns the highest checkpoint whose transaction data has been pruned, if any
+    pub fn get_highest_pruned_transactions_checkpoint(
+        &self,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.pruned_transactions_checkpoint.get(&())?)
+    }
+
+    pub fn set_highest_pruned_transactions_checkpoint(
+        &self,
+        wb: &mut DBBatch,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> SuiResult {
+        wb.insert_batch(
+            &self.pruned_transactions_checkpoint,
+            [((), checkpoint_number)],
+        )?;
+        Ok(())
+    }
+
     pub fn get_transaction(
         &self,
         digest: &TransactionDigest,
@@ -274,6 +299,20 @@ impl AuthorityPerpetualTables {
         Ok(self.effects.get(&effect_digest)?)
     }
 
+    pub fn get_events(
+        &self,
+        event_digest: &TransactionEventsDigest,
+    ) -> Result<Option<TransactionEvents>, TypedStoreError> {
+        let data = self
+            .events
+            .iter()
+            .skip_to(&(*event_digest, 0))?
+            .take_while(|((digest, _), _)| digest == event_digest)
+            .map(|(_, e)| e)
+            .collect::<Vec<_>>();
+        Ok((!data.is_empty()).then_some(TransactionEvents { data }))
+    }
+
     pub fn get_checkpoint_sequence_number(
         &self,
         digest: &TransactionDigest,
@@ -389,6 +428,7 @@ impl AuthorityPerpetualTables {
         self.root_state_hash_by_epoch.clear()?;
         self.epoch_start_configuration.clear()?;
         self.pruned_checkpoint.clear()?;
+        self.pruned_transactions_checkpoint.clear()?;
         self.expected_network_sui_amount.clear()?;
         self.expected_storage_fund_imbalance.clear()?;
         self.objects
@@ -409,6 +449,75 @@ impl AuthorityPerpetualTables {
         wb.write()?;
         Ok(())
     }
+
+    pub fn insert_executed_transaction_test_only(
+        &self,
+        transaction: &VerifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> SuiResult {
+        let transaction_digest = transaction.digest();
+        let effects_digest = effects.digest();
+        let mut wb = self.transactions.batch();
+        wb.insert_batch(
+            &self.transactions,
+            std::iter::once((transaction_digest, transaction.serializable_ref())),
+        )?
+        .insert_batch(&self.effects, [(effects_digest, effects)])?
+        .insert_batch(
+            &self.executed_effects,
+            [(transaction_digest, effects_digest)],
+        )?;
+        wb.write()?;
+        Ok(())
+    }
+
+    /// Inserts live objects restored from a state snapshot, and initializes the locks of the
+    /// address owned ones. Meant to be used on fresh tables only.
+    pub fn insert_live_objects_for_restore(
+        &self,
+        live_objects: &[LiveObject],
+        indirect_objects_threshold: usize,
+    ) -> SuiResult {
+        let mut wb = self.objects.batch();
+        for live_object in live_objects {
+            match live_object {
+                LiveObject::Normal(object) => {
+                    let object_ref = object.compute_object_reference();
+                    let StoreObjectPair(store_object, indirect_object) =
+                        get_store_object_pair(object.clone(), indirect_objects_threshold);
+                    wb.insert_batch(
+                        &self.objects,
+                        std::iter::once((ObjectKey::from(object_ref), store_object)),
+                    )?;
+                    // Several objects can share the same contents, so go through the merge
+                    // operator to keep the reference counts right
+                    if let Some(indirect_object) = indirect_object {
+                        wb.merge_batch(
+                            &self.indirect_move_objects,
+                            std::iter::once((indirect_object.inner().digest(), indirect_object)),
+                        )?;
+                    }
+                    if object.get_single_owner().is_some() && !object.is_child_object() {
+                        wb.insert_batch(
+                            &self.owned_object_transaction_locks,
+                            std::iter::once((object_ref, None)),
+                        )?;
+                    }
+                }
+                LiveObject::Wrapped(object_key) => {
+                    wb.insert_batch(
+                        &self.objects,
+                        std::iter::once((
+                            *object_key,
+                            StoreObjectWrapper::from(StoreObject::Wrapped),
+                        )),
+                    )?;
+                }
+            }
+        }
+        wb.write()?;
+        Ok(())
+    }
 }
 
 impl ObjectStore for AuthorityPerpetualTables {
@@ -262,6 +262,7 @@ pub async fn send_consensus(authority: &AuthorityState, cert: &VerifiedCertifica
             .epoch_store_for_testing()
             .process_consensus_transactions(
                 vec![transaction],
+                0,
                 &Arc::new(CheckpointServiceNoop {}),
                 authority.db(),
             )
@@ -292,6 +293,7 @@ pub async fn send_consensus_no_execution(authority: &AuthorityState, cert: &Veri
             .epoch_store_for_testing()
             .process_consensus_transactions(
                 vec![transaction],
+                0,
                 &Arc::new(CheckpointServiceNoop {}),
                 &authority.db(),
             )
@@ -464,7 +464,7 @@ impl ValidatorService {
         if !wait_for_effects {
             // It is useful to enqueue owned object transaction for execution locally,
             // even when we are not returning effects to user
-            if !certificate.contains_shared_object() {
+            if !certificate.requires_sequencing() {
                 state
                     .enqueue_certificates_for_execution(vec![certificate.clone()], &epoch_store)?;
             }
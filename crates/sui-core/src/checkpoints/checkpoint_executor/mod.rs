@@ -51,6 +51,7 @@ use typed_store::Map;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
+use crate::event_handler::SubscriptionHandler;
 use crate::state_accumulator::StateAccumulator;
 use crate::transaction_manager::TransactionManager;
 use crate::{authority::EffectsNotifyRead, checkpoints::CheckpointStore};
@@ -72,6 +73,7 @@ pub struct CheckpointExecutor {
     authority_store: Arc<AuthorityStore>,
     tx_manager: Arc<TransactionManager>,
     accumulator: Arc<StateAccumulator>,
+    subscription_handler: Arc<SubscriptionHandler>,
     config: CheckpointExecutorConfig,
     metrics: Arc<CheckpointExecutorMetrics>,
 }
@@ -83,6 +85,7 @@ impl CheckpointExecutor {
         authority_store: Arc<AuthorityStore>,
         tx_manager: Arc<TransactionManager>,
         accumulator: Arc<StateAccumulator>,
+        subscription_handler: Arc<SubscriptionHandler>,
         config: CheckpointExecutorConfig,
         prometheus_registry: &Registry,
     ) -> Self {
@@ -92,6 +95,7 @@ impl CheckpointExecutor {
             authority_store,
             tx_manager,
             accumulator,
+            subscription_handler,
             config,
             metrics: CheckpointExecutorMetrics::new(prometheus_registry),
         }
@@ -110,6 +114,7 @@ impl CheckpointExecutor {
             authority_store,
             tx_manager,
             accumulator,
+            subscription_handler: Arc::new(SubscriptionHandler::default()),
             config: Default::default(),
             metrics: CheckpointExecutorMetrics::new_for_tests(),
         }
@@ -269,6 +274,7 @@ impl CheckpointExecutor {
             .update_highest_executed_checkpoint(checkpoint)
             .unwrap();
         self.metrics.last_executed_checkpoint.set(seq as i64);
+        self.subscription_handler.process_checkpoint(seq);
 
         checkpoint.report_checkpoint_age_ms(&self.metrics.last_executed_checkpoint_age_ms);
     }
@@ -424,6 +424,15 @@ impl CheckpointStore {
         self.full_checkpoint_content.remove(&seq)
     }
 
+    pub fn delete_checkpoint_contents(
+        &self,
+        digests: impl IntoIterator<Item = CheckpointContentsDigest>,
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.checkpoint_content.batch();
+        batch.delete_batch(&self.checkpoint_content, digests)?;
+        batch.write()
+    }
+
     pub fn get_epoch_last_checkpoint(
         &self,
         epoch_id: EpochId,
@@ -300,6 +300,7 @@ impl<T: ParentSync + Send + Sync> ExecutionState for ConsensusHandler<T> {
             .epoch_store
             .process_consensus_transactions(
                 verified_transactions,
+                timestamp,
                 &self.checkpoint_service,
                 &self.parent_sync_store,
             )
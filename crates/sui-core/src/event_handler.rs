@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use tokio::sync::watch;
 use tokio_stream::Stream;
 use tracing::{error, instrument, trace};
 
@@ -11,6 +12,7 @@ use sui_json_rpc_types::{
 };
 use sui_json_rpc_types::{SuiEvent, SuiTransactionBlockEffectsAPI};
 use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::transaction::TransactionData;
 
 #[cfg(test)]
@@ -22,6 +24,9 @@ pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 pub struct SubscriptionHandler {
     event_streamer: Streamer<SuiEvent, SuiEvent, EventFilter>,
     transaction_streamer: Streamer<EffectsWithInput, SuiTransactionBlockEffects, TransactionFilter>,
+    /// Sequence number of the highest checkpoint whose execution has finished. Checkpoint
+    /// subscribers read the checkpoints themselves, so they only need to be woken up.
+    checkpoint_sender: watch::Sender<Option<CheckpointSequenceNumber>>,
 }
 
 impl Default for SubscriptionHandler {
@@ -29,6 +34,7 @@ impl Default for SubscriptionHandler {
         Self {
             event_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE),
             transaction_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE),
+            checkpoint_sender: watch::channel(None).0,
         }
     }
 }
@@ -77,4 +83,14 @@ impl SubscriptionHandler {
     ) -> impl Stream<Item = SuiTransactionBlockEffects> {
         self.transaction_streamer.subscribe(filter)
     }
+
+    pub fn process_checkpoint(&self, sequence_number: CheckpointSequenceNumber) {
+        trace!(sequence_number, "Finished executing checkpoint");
+        self.checkpoint_sender.send_replace(Some(sequence_number));
+    }
+
+    /// Returns a receiver that is notified every time a checkpoint finishes executing.
+    pub fn subscribe_checkpoints(&self) -> watch::Receiver<Option<CheckpointSequenceNumber>> {
+        self.checkpoint_sender.subscribe()
+    }
 }
@@ -0,0 +1,466 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority::AuthorityState;
+use anyhow::{anyhow, Context, Result};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::{EventSinkConfig, EventSinkDestination};
+use sui_json_rpc_types::{
+    EventFilter, Filter, SuiEvent, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+};
+use sui_types::base_types::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::error::SuiError;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::sync::oneshot;
+use tokio::sync::oneshot::Sender;
+use tokio::sync::watch;
+use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tracing::{error, info, warn};
+
+pub struct EventSinkMetrics {
+    pub event_sink_last_delivered_checkpoint: IntGauge,
+    pub event_sink_delivered_events: IntCounter,
+    pub event_sink_delivery_errors: IntCounter,
+    pub event_sink_skipped_checkpoints: IntCounter,
+}
+
+impl EventSinkMetrics {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        let this = Self {
+            event_sink_last_delivered_checkpoint: register_int_gauge_with_registry!(
+                "event_sink_last_delivered_checkpoint",
+                "Last checkpoint whose events were delivered by the event sink",
+                registry
+            )
+            .unwrap(),
+            event_sink_delivered_events: register_int_counter_with_registry!(
+                "event_sink_delivered_events",
+                "Number of events delivered by the event sink, including redeliveries",
+                registry
+            )
+            .unwrap(),
+            event_sink_delivery_errors: register_int_counter_with_registry!(
+                "event_sink_delivery_errors",
+                "Number of failed attempts to deliver a checkpoint to the event sink destination",
+                registry
+            )
+            .unwrap(),
+            event_sink_skipped_checkpoints: register_int_counter_with_registry!(
+                "event_sink_skipped_checkpoints",
+                "Number of checkpoints skipped by the event sink because their contents were pruned",
+                registry
+            )
+            .unwrap(),
+        };
+        Arc::new(this)
+    }
+}
+
+/// The forwarded data of one transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSinkRecord {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub digest: TransactionDigest,
+    pub events: Vec<SuiEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects: Option<SuiTransactionBlockEffects>,
+}
+
+/// The body posted to a webhook for each checkpoint with forwarded data.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSinkBatch {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub records: Vec<EventSinkRecord>,
+}
+
+enum Destination {
+    Webhook {
+        client: reqwest::Client,
+        url: String,
+    },
+    File {
+        directory: PathBuf,
+    },
+}
+
+impl Destination {
+    async fn deliver(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        records: &[EventSinkRecord],
+    ) -> Result<()> {
+        match self {
+            Destination::Webhook { client, url } => {
+                let batch = EventSinkBatch {
+                    checkpoint,
+                    records: records.to_vec(),
+                };
+                client
+                    .post(url)
+                    .json(&batch)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+            Destination::File { directory } => {
+                let mut contents = Vec::new();
+                for record in records {
+                    serde_json::to_writer(&mut contents, record)?;
+                    contents.push(b'\n');
+                }
+                write_atomically(&directory.join(format!("{checkpoint}.jsonl")), &contents)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Forwards the events of executed checkpoints matching a filter to a webhook or to local files,
+/// one checkpoint at a time. A checkpoint is only recorded in the cursor file once it has been
+/// delivered, so a checkpoint may be delivered again after a restart but is never skipped.
+pub struct EventSink {
+    state: Arc<AuthorityState>,
+    filter: Option<EventFilter>,
+    include_effects: bool,
+    destination: Destination,
+    cursor_path: PathBuf,
+    start_checkpoint: Option<CheckpointSequenceNumber>,
+    skip_pruned_checkpoints: bool,
+    metrics: Arc<EventSinkMetrics>,
+}
+
+impl EventSink {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        config: &EventSinkConfig,
+        default_cursor_path: &Path,
+        registry: &Registry,
+    ) -> Result<Self> {
+        let destination = match &config.destination {
+            EventSinkDestination::Webhook { url } => Destination::Webhook {
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(30))
+                    .build()?,
+                url: url.clone(),
+            },
+            EventSinkDestination::File { directory } => {
+                fs::create_dir_all(directory)?;
+                Destination::File {
+                    directory: directory.clone(),
+                }
+            }
+        };
+        let filter = config
+            .filter
+            .clone()
+            .map(serde_json::from_value::<EventFilter>)
+            .transpose()
+            .context("Invalid event sink filter")?;
+        Ok(Self {
+            state,
+            filter,
+            include_effects: config.include_effects,
+            destination,
+            cursor_path: config
+                .cursor_path
+                .clone()
+                .unwrap_or_else(|| default_cursor_path.to_path_buf()),
+            start_checkpoint: config.start_checkpoint,
+            skip_pruned_checkpoints: config.skip_pruned_checkpoints,
+            metrics: EventSinkMetrics::new(registry),
+        })
+    }
+
+    pub fn start(self) -> Result<Sender<()>> {
+        let mut next = match read_cursor(&self.cursor_path)? {
+            Some(cursor) => cursor + 1,
+            None => self.start_checkpoint.unwrap_or_else(|| {
+                self.state
+                    .get_latest_checkpoint_sequence_number()
+                    .map_or(0, |seq| seq + 1)
+            }),
+        };
+        let mut receiver = self.state.subscription_handler.subscribe_checkpoints();
+        let (sender, mut recv) = oneshot::channel::<()>();
+        tokio::task::spawn(async move {
+            info!(next, "Event sink started");
+            loop {
+                tokio::select! {
+                    result = async {
+                        self.wait_for_checkpoint(&mut receiver, next).await;
+                        self.process_checkpoint(next).await
+                    } => {
+                        if let Err(e) = result {
+                            error!(
+                                error = ?e,
+                                next,
+                                "Event sink stopped, set skip-pruned-checkpoints to skip checkpoints \
+                                 whose contents were pruned"
+                            );
+                            break;
+                        }
+                        next += 1;
+                    }
+                    _ = &mut recv => break,
+                }
+            }
+        });
+        Ok(sender)
+    }
+
+    async fn wait_for_checkpoint(
+        &self,
+        receiver: &mut watch::Receiver<Option<CheckpointSequenceNumber>>,
+        sequence_number: CheckpointSequenceNumber,
+    ) {
+        loop {
+            // Mark the current value as seen before reading the watermark, so that a
+            // checkpoint executed in between still wakes us up.
+            receiver.borrow_and_update();
+            let executed = self.state.get_latest_checkpoint_sequence_number().ok();
+            if executed.map_or(false, |seq| seq >= sequence_number) {
+                return;
+            }
+            if receiver.changed().await.is_err() {
+                // The node is shutting down, wait for the stop signal.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Delivers a checkpoint and records it in the cursor, retrying until both succeed. Fails
+    /// without retrying if the contents of the checkpoint were pruned and are not to be skipped,
+    /// as they will never be available again.
+    async fn process_checkpoint(&self, sequence_number: CheckpointSequenceNumber) -> Result<()> {
+        let mut retry_strategy = ExponentialBackoff::from_millis(100)
+            .max_delay(Duration::from_secs(30))
+            .map(jitter);
+        loop {
+            match self.try_process_checkpoint(sequence_number).await {
+                Ok(()) => return Ok(()),
+                Err(e) if is_pruned(&e) => return Err(e),
+                Err(e) => {
+                    self.metrics.event_sink_delivery_errors.inc();
+                    warn!(error = ?e, sequence_number, "Failed to deliver checkpoint to event sink");
+                    // The strategy never runs out since it is not bounded by `take`.
+                    tokio::time::sleep(retry_strategy.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    async fn try_process_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let records = match self.read_records(sequence_number) {
+            Err(e) if self.skip_pruned_checkpoints && is_pruned(&e) => {
+                warn!(
+                    sequence_number,
+                    "Skipping event sink checkpoint whose contents were pruned"
+                );
+                self.metrics.event_sink_skipped_checkpoints.inc();
+                vec![]
+            }
+            records => records?,
+        };
+        if !records.is_empty() {
+            self.destination.deliver(sequence_number, &records).await?;
+        }
+        write_cursor(&self.cursor_path, sequence_number)?;
+        let events: usize = records.iter().map(|record| record.events.len()).sum();
+        self.metrics
+            .event_sink_delivered_events
+            .inc_by(events as u64);
+        self.metrics
+            .event_sink_last_delivered_checkpoint
+            .set(sequence_number as i64);
+        Ok(())
+    }
+
+    fn read_records(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<EventSinkRecord>> {
+        let summary = self
+            .state
+            .get_verified_checkpoint_by_sequence_number(sequence_number)?;
+        let contents = self
+            .state
+            .get_checkpoint_contents_by_sequence_number(sequence_number)?;
+        let digests: Vec<_> = contents.iter().map(|digests| digests.transaction).collect();
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+
+        let mut records = vec![];
+        for (effects, digest) in self
+            .state
+            .multi_get_executed_effects(&digests)?
+            .into_iter()
+            .zip(digests)
+        {
+            let effects =
+                effects.ok_or_else(|| anyhow!("Effects for transaction {digest} not found"))?;
+            let Some(events_digest) = effects.events_digest() else {
+                continue;
+            };
+            let events = self.state.get_transaction_events(events_digest)?;
+            let events = SuiTransactionBlockEvents::try_from(
+                events,
+                digest,
+                Some(summary.timestamp_ms),
+                epoch_store.module_cache(),
+            )?
+            .data
+            .into_iter()
+            .filter(|event| self.filter.as_ref().map_or(true, |f| f.matches(event)))
+            .collect::<Vec<_>>();
+            if events.is_empty() {
+                continue;
+            }
+            let effects = if self.include_effects {
+                Some(SuiTransactionBlockEffects::try_from(effects)?)
+            } else {
+                None
+            };
+            records.push(EventSinkRecord {
+                checkpoint: sequence_number,
+                digest,
+                events,
+                effects,
+            });
+        }
+        Ok(records)
+    }
+}
+
+/// Whether `error` is caused by the data of a checkpoint having been pruned.
+fn is_pruned(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<SuiError>(),
+        Some(SuiError::CheckpointContentsPruned { .. } | SuiError::TransactionPruned { .. })
+    )
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it into place, so that
+/// readers never observe a partially written file.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)
+        .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path).with_context(|| {
+        format!(
+            "Failed to rename {} to {}",
+            tmp_path.display(),
+            path.display()
+        )
+    })?;
+    Ok(())
+}
+
+fn read_cursor(path: &Path) -> Result<Option<CheckpointSequenceNumber>> {
+    match fs::read_to_string(path) {
+        Ok(cursor) => Ok(Some(cursor.trim().parse().with_context(|| {
+            format!("Invalid event sink cursor in {}", path.display())
+        })?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_cursor(path: &Path, sequence_number: CheckpointSequenceNumber) -> Result<()> {
+    write_atomically(path, sequence_number.to_string().as_bytes()).map_err(|e| {
+        error!(error = ?e, "Failed to write event sink cursor");
+        e
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_sink::{is_pruned, read_cursor, write_cursor, Destination, EventSinkRecord};
+    use std::fs;
+    use sui_types::base_types::TransactionDigest;
+    use sui_types::digests::CheckpointContentsDigest;
+    use sui_types::error::SuiError;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_file_destination() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let destination = Destination::File {
+            directory: dir.path().to_path_buf(),
+        };
+        let records: Vec<_> = (0..2)
+            .map(|_| EventSinkRecord {
+                checkpoint: 7,
+                digest: TransactionDigest::random(),
+                events: vec![],
+                effects: None,
+            })
+            .collect();
+        destination.deliver(7, &records).await?;
+        // Redelivering a checkpoint replaces its file instead of appending to it.
+        destination.deliver(7, &records).await?;
+
+        let contents = fs::read_to_string(dir.path().join("7.jsonl"))?;
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, record) in lines.into_iter().zip(&records) {
+            let read: EventSinkRecord = serde_json::from_str(line)?;
+            assert_eq!(read.checkpoint, 7);
+            assert_eq!(read.digest, record.digest);
+        }
+        assert!(!dir.path().join("7.tmp").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_pruned() {
+        let read = |error: SuiError| -> anyhow::Result<()> { Err(error)? };
+        assert!(is_pruned(
+            &read(SuiError::CheckpointContentsPruned {
+                digest: CheckpointContentsDigest::random(),
+                checkpoint: 1,
+            })
+            .unwrap_err()
+        ));
+        assert!(is_pruned(
+            &read(SuiError::TransactionPruned {
+                digest: TransactionDigest::random(),
+                checkpoint: 1,
+            })
+            .unwrap_err()
+        ));
+        assert!(!is_pruned(
+            &read(SuiError::IndexStoreNotAvailable).unwrap_err()
+        ));
+    }
+
+    #[test]
+    fn test_cursor() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("cursor");
+        assert_eq!(read_cursor(&path)?, None);
+        write_cursor(&path, 3)?;
+        assert_eq!(read_cursor(&path)?, Some(3));
+        write_cursor(&path, 4)?;
+        assert_eq!(read_cursor(&path)?, Some(4));
+
+        fs::write(&path, "not a checkpoint")?;
+        assert!(read_cursor(&path).is_err());
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@ pub mod consensus_validator;
 pub mod db_checkpoint_handler;
 pub mod epoch;
 pub mod event_handler;
+pub mod event_sink;
 mod execution_driver;
 mod math;
 pub mod metrics;
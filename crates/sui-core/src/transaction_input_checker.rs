@@ -14,14 +14,15 @@ use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::ObjectRef;
 use sui_types::error::{UserInputError, UserInputResult};
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::metrics::BytecodeVerifierMetrics;
 use sui_types::transaction::{
-    InputObjectKind, InputObjects, TransactionData, TransactionDataAPI, TransactionKind,
-    VersionedProtocolMessage,
+    InputObjectKind, InputObjects, TransactionData, TransactionDataAPI, TransactionExpiration,
+    TransactionKind, VersionedProtocolMessage,
 };
 use sui_types::{
-    base_types::{SequenceNumber, SuiAddress},
-    error::SuiResult,
+    base_types::{EpochId, SequenceNumber, SuiAddress},
+    error::{SuiError, SuiResult},
     fp_ensure,
     gas::{SuiCostTable, SuiGasStatus},
     object::{Object, Owner},
@@ -177,6 +178,28 @@ pub async fn check_certificate_input(
     Ok((gas_status, input_objects))
 }
 
+/// Checks that the transaction has not expired. Before signing this uses the local clock and the
+/// highest executed checkpoint. Certificates which expire at a timestamp or checkpoint are checked
+/// again when they are sequenced, against the consensus commit timestamp and the highest
+/// checkpoint sequenced by consensus, so that all validators agree on whether they execute.
+pub fn check_transaction_expiration(
+    transaction: &TransactionData,
+    epoch: EpochId,
+    highest_executed_checkpoint: Option<CheckpointSequenceNumber>,
+    now_ms: u64,
+) -> SuiResult {
+    let expired = match transaction.expiration() {
+        TransactionExpiration::None => false,
+        TransactionExpiration::Epoch(expiration) => *expiration < epoch,
+        TransactionExpiration::Timestamp(expiration_ms) => *expiration_ms < now_ms,
+        TransactionExpiration::Checkpoint(expiration) => {
+            highest_executed_checkpoint.map_or(false, |checkpoint| *expiration < checkpoint)
+        }
+    };
+    fp_ensure!(!expired, SuiError::TransactionExpired);
+    Ok(())
+}
+
 /// Check transaction gas data/info and gas coins consistency.
 /// Return the gas status to be used for the lifecycle of the transaction.
 #[instrument(level = "trace", skip_all)]
@@ -31,14 +31,16 @@ use sui_json_rpc_types::{
 };
 use sui_macros::sim_test;
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
+use sui_test_transaction_builder::TestTransactionBuilder;
 use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::effects::TransactionEffects;
 use sui_types::epoch_data::EpochData;
 use sui_types::error::UserInputError;
 use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
+use sui_types::execution_trace::ObjectAccessKind;
 use sui_types::gas::SuiCostTable;
 use sui_types::gas_coin::GasCoin;
-use sui_types::messages_consensus::ConsensusCommitPrologue;
+use sui_types::messages_consensus::{ConsensusCommitPrologue, ConsensusTransaction};
 use sui_types::object::Data;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SuiSystemStateWrapper;
@@ -57,6 +59,8 @@ use sui_types::{
 use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::authority::move_integration_tests::build_and_publish_test_package_with_upgrade_cap;
 use crate::authority::test_authority_builder::TestAuthorityBuilder;
+use crate::checkpoints::CheckpointServiceNoop;
+use crate::consensus_handler::SequencedConsensusTransaction;
 use crate::{
     authority_client::{AuthorityAPI, NetworkAuthorityClient},
     authority_server::AuthorityServer,
@@ -573,7 +577,7 @@ async fn test_dev_inspect_dynamic_field() {
     };
     let kind = TransactionKind::programmable(pt);
     let DevInspectResults { error, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
     // produces an error
@@ -618,6 +622,155 @@ async fn test_dev_inspect_dynamic_field() {
     assert!(return_values.is_empty());
 }
 
+#[tokio::test]
+async fn test_dev_inspect_trace() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (validator, fullnode, object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![(sender, gas_object_id)]).await;
+
+    let mut created = vec![];
+    for _ in 0..2 {
+        let effects = call_move_(
+            &validator,
+            Some(&fullnode),
+            &gas_object_id,
+            &sender,
+            &sender_key,
+            &object_basics.0,
+            "object_basics",
+            "create",
+            vec![],
+            vec![
+                TestCallArg::Pure(bcs::to_bytes(&(16_u64)).unwrap()),
+                TestCallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+            ],
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(effects.status().is_ok(), "{:#?}", effects.status());
+        created.push(effects.created()[0].0 .0);
+    }
+    let parent_id = created[0];
+    let child_bytes = validator
+        .get_object(&created[1])
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+        .try_as_move()
+        .unwrap()
+        .contents()
+        .to_vec();
+
+    // add a dynamic field to an existing object, with tracing enabled
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let arguments = vec![
+        TestCallArg::Object(parent_id)
+            .to_call_arg(&mut builder, &fullnode)
+            .await,
+        TestCallArg::Pure(child_bytes)
+            .to_call_arg(&mut builder, &fullnode)
+            .await,
+    ];
+    builder.command(Command::move_call(
+        object_basics.0,
+        Identifier::new("object_basics").unwrap(),
+        Identifier::new("add_field").unwrap(),
+        vec![],
+        arguments,
+    ));
+    let kind = TransactionKind::programmable(builder.finish());
+    let DevInspectResults { error, trace, .. } = fullnode
+        .dev_inspect_transaction_block(sender, kind, Some(1), true)
+        .await
+        .unwrap();
+    assert!(error.is_none(), "{:?}", error);
+    let trace = trace.unwrap();
+
+    // the entry function is the only top-level call, and it calls into the framework
+    assert_eq!(trace.commands.len(), 1);
+    let calls = &trace.commands[0].calls;
+    assert_eq!(calls.len(), 1);
+    let call = &calls[0];
+    assert_eq!(call.frame.package, object_basics.0);
+    assert_eq!(call.frame.module, "object_basics");
+    assert_eq!(call.frame.function, "add_field");
+    assert!(!call.native);
+    assert!(call.gas_used > 0);
+    assert!(call.instructions > 0);
+    let add = call
+        .calls
+        .iter()
+        .find(|c| c.frame.module == "dynamic_field" && c.frame.function == "add")
+        .unwrap();
+    assert!(add.gas_used <= call.gas_used);
+    assert!(add.calls.iter().any(|c| c.native));
+    assert!(trace.abort_stack.is_empty());
+
+    // the parent is read and mutated, and the field is looked up through it and created
+    assert!(trace
+        .objects
+        .iter()
+        .any(|o| o.object_id == parent_id && o.kind == ObjectAccessKind::Read));
+    assert!(trace
+        .objects
+        .iter()
+        .any(|o| o.object_id == parent_id && o.kind == ObjectAccessKind::Mutated));
+    assert!(trace
+        .objects
+        .iter()
+        .any(|o| o.kind == ObjectAccessKind::Created));
+    assert!(trace
+        .dynamic_fields
+        .iter()
+        .any(|f| f.parent == parent_id && f.version.is_none()));
+    assert!(trace
+        .objects
+        .iter()
+        .filter(|o| o.kind == ObjectAccessKind::Created)
+        .all(|o| o.storage_cost.unwrap() > 0));
+
+    // the gas profile attributes computation to the call stack, and storage to the call stack
+    // that wrote each object, if any
+    let profile = trace.folded_gas_profile(1000);
+    let lines: Vec<_> = profile.lines().collect();
+    assert!(lines.iter().any(|l| l.starts_with(&format!(
+        "[command 0];{}::object_basics::add_field ",
+        object_basics.0.to_hex_literal()
+    ))));
+    assert!(lines
+        .iter()
+        .any(|l| l.contains("::object_basics::add_field;0x2::dynamic_field::add")));
+    // the field is added by `dynamic_field::add`, the parent is only mutated by reference
+    assert!(lines.iter().any(|l| l
+        .contains("::object_basics::add_field;0x2::dynamic_field::add;")
+        && l.contains(";[storage];")
+        && l.contains("::dynamic_field::Field<")));
+    assert!(lines.iter().any(|l| l.starts_with("[storage];")));
+    assert!(lines
+        .iter()
+        .all(|l| l.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap() > 0));
+
+    // no trace unless requested
+    let DevInspectResults { trace, .. } = call_dev_inspect(
+        &fullnode,
+        &sender,
+        &object_basics.0,
+        "object_basics",
+        "create",
+        vec![],
+        vec![
+            TestCallArg::Pure(bcs::to_bytes(&(16_u64)).unwrap()),
+            TestCallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(trace.is_none());
+}
+
 #[tokio::test]
 async fn test_dev_inspect_return_values() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -815,7 +968,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     };
     let kind = TransactionKind::programmable(pt);
     let results = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap()
         .results
@@ -877,7 +1030,7 @@ async fn test_dev_inspect_uses_unbound_object() {
     let kind = TransactionKind::programmable(pt);
 
     let result = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await;
     let Err(err) = result else { panic!() };
     assert!(err.to_string().contains("ObjectNotFound"));
@@ -1017,7 +1170,7 @@ async fn test_dry_run_dev_inspect_dynamic_field_too_new() {
     let kind = TransactionKind::programmable(pt.clone());
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
     assert_eq!(effects.deleted().len(), 1);
@@ -1076,7 +1229,7 @@ async fn test_dry_run_dev_inspect_max_gas_version() {
     let kind = TransactionKind::programmable(pt.clone());
     // dev inspect
     let DevInspectResults { effects, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
     assert_eq!(effects.status(), &SuiExecutionStatus::Success);
@@ -1238,6 +1391,126 @@ async fn test_handle_shared_object_with_max_sequence_number() {
     );
 }
 
+#[tokio::test]
+async fn test_handle_transaction_expiration() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let rgp = authority_state.reference_gas_price_for_testing().unwrap();
+    let epoch_store = authority_state.load_epoch_store_one_call_per_task();
+    let object_ref = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    let gas_ref = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    let transfer_data = |expiration| {
+        TransactionData::new_transfer(
+            recipient,
+            object_ref,
+            sender,
+            gas_ref,
+            rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            rgp,
+        )
+        .with_expiration(expiration)
+    };
+
+    // Expired transactions are rejected before any object is locked
+    let expired = to_sender_signed_transaction(
+        transfer_data(TransactionExpiration::Timestamp(0)),
+        &sender_key,
+    );
+    assert!(matches!(
+        authority_state
+            .handle_transaction(&epoch_store, expired)
+            .await
+            .unwrap_err(),
+        SuiError::TransactionExpired
+    ));
+
+    let data = transfer_data(TransactionExpiration::Checkpoint(10));
+    assert!(transaction_input_checker::check_transaction_expiration(&data, 0, None, 0).is_ok());
+    assert!(transaction_input_checker::check_transaction_expiration(&data, 0, Some(10), 0).is_ok());
+    assert!(matches!(
+        transaction_input_checker::check_transaction_expiration(&data, 0, Some(11), 0).unwrap_err(),
+        SuiError::TransactionExpired
+    ));
+
+    let valid = to_sender_signed_transaction(
+        transfer_data(TransactionExpiration::Timestamp(u64::MAX)),
+        &sender_key,
+    );
+    authority_state
+        .handle_transaction(&epoch_store, valid)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_drop_certificate_expired_at_sequencing() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let authority_state = init_state_with_ids(vec![(sender, gas_object_id)]).await;
+    let rgp = authority_state.reference_gas_price_for_testing().unwrap();
+    let epoch_store = authority_state.epoch_store_for_testing();
+    let gas_ref = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+
+    // Sequence a certificate in a consensus commit with timestamp 1_000, and return the
+    // certificates that were scheduled for execution.
+    let sequence = |expire_at_timestamp_ms| {
+        let transaction = to_sender_signed_transaction(
+            TestTransactionBuilder::new(sender, gas_ref, rgp)
+                .transfer_sui(Some(1), dbg_addr(2))
+                .expire_at_timestamp(expire_at_timestamp_ms)
+                .build(),
+            &sender_key,
+        );
+        let certificate = init_certified_transaction(transaction, &authority_state);
+        let transaction = epoch_store
+            .verify_consensus_transaction(
+                SequencedConsensusTransaction::new_test(
+                    ConsensusTransaction::new_certificate_message(
+                        &authority_state.name,
+                        certificate.into_inner(),
+                    ),
+                ),
+                &authority_state.metrics.skipped_consensus_txns,
+            )
+            .unwrap();
+        let epoch_store = epoch_store.clone();
+        let authority_state = &authority_state;
+        async move {
+            epoch_store
+                .process_consensus_transactions(
+                    vec![transaction],
+                    1_000,
+                    &Arc::new(CheckpointServiceNoop {}),
+                    authority_state.db(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    assert!(sequence(500).await.is_empty());
+    assert_eq!(sequence(2_000).await.len(), 1);
+}
+
 #[tokio::test]
 async fn test_handle_transfer_transaction_unknown_sender() {
     let sender = dbg_addr(1);
@@ -4303,7 +4576,7 @@ pub async fn call_dev_inspect(
     ));
     let kind = TransactionKind::programmable(builder.finish());
     authority
-        .dev_inspect_transaction_block(*sender, kind, Some(1))
+        .dev_inspect_transaction_block(*sender, kind, Some(1), false)
         .await
 }
 
@@ -4967,7 +5240,7 @@ async fn test_for_inc_201_dev_inspect() {
     ));
     let kind = TransactionKind::programmable(builder.finish());
     let DevInspectResults { events, .. } = fullnode
-        .dev_inspect_transaction_block(sender, kind, Some(1))
+        .dev_inspect_transaction_block(sender, kind, Some(1), false)
         .await
         .unwrap();
 
@@ -125,6 +125,7 @@ async fn submit_transaction_to_consensus_adapter() {
                     vec![VerifiedSequencedConsensusTransaction::new_test(
                         transaction.clone(),
                     )],
+                    0,
                     &Arc::new(CheckpointServiceNoop {}),
                     self.0.db(),
                 )
@@ -129,5 +129,8 @@ async fn _merge_coins(gas_coin: &str, mut wallet: WalletContext) -> Result<(), a
 pub async fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
     info!("Initialize wallet from config path: {:?}", wallet_conf);
-    WalletContext::new(&wallet_conf, Some(Duration::from_secs(timeout_secs)), None).await
+    let mut context =
+        WalletContext::new(&wallet_conf, Some(Duration::from_secs(timeout_secs)), None).await?;
+    context.unlock_keystore()?;
+    Ok(context)
 }
@@ -31,6 +31,18 @@ pub enum FaucetError {
     #[error("Coin Transfer Failed `{0}`")]
     Transfer(String),
 
+    #[error("Request quota exceeded for {0}, retry after {1} seconds")]
+    QuotaExceeded(String, u64),
+
+    #[error("Missing or invalid client IP in header `{0}`")]
+    InvalidClientIp(String),
+
+    #[error("Too many requests are queued, try again later")]
+    BatchQueueFull,
+
+    #[error("Unknown batch request `{0}`")]
+    BatchTaskNotFound(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
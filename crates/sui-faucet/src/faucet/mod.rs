@@ -6,8 +6,10 @@ use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use uuid::Uuid;
 
+mod quota;
 mod simple_faucet;
 mod write_ahead_log;
+pub use self::quota::{Quota, QuotaKey, QuotaReservation, RequestQuotas};
 pub use self::simple_faucet::SimpleFaucet;
 use clap::Parser;
 use std::{net::Ipv4Addr, path::PathBuf};
@@ -25,6 +27,22 @@ pub struct CoinInfo {
     pub transfer_tx_digest: TransactionDigest,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFaucetReceipt {
+    pub task: String,
+}
+
+/// Progress of a request queued with `Faucet::batch_send`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchSendStatus {
+    /// Waiting to be sent, or being sent as part of a batch
+    InProgress,
+    Succeeded(FaucetReceipt),
+    Failed(String),
+}
+
 #[async_trait]
 pub trait Faucet {
     /// Send `Coin<SUI>` of the specified amount to the recipient
@@ -34,6 +52,19 @@ pub trait Faucet {
         recipient: SuiAddress,
         amounts: &[u64],
     ) -> Result<FaucetReceipt, FaucetError>;
+
+    /// Queue a request to send `Coin<SUI>` of the specified amount to the recipient, to be sent
+    /// together with other queued requests. Returns without waiting for the transfer, whose
+    /// progress can be followed with `get_batch_send_status`.
+    async fn batch_send(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: &[u64],
+    ) -> Result<BatchFaucetReceipt, FaucetError>;
+
+    /// Status of a request queued with `batch_send`
+    async fn get_batch_send_status(&self, task_id: Uuid) -> Result<BatchSendStatus, FaucetError>;
 }
 
 pub const DEFAULT_AMOUNT: u64 = 1_000_000_000;
@@ -72,6 +103,42 @@ pub struct FaucetConfig {
 
     #[clap(long, default_value_t = 300)]
     pub wal_retry_interval: u64,
+
+    /// Maximum number of requests served to the same recipient address within
+    /// `address-quota-window-secs`. Unlimited if not set
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_requests_per_address: Option<u64>,
+
+    #[clap(long, default_value_t = 24 * 60 * 60)]
+    pub address_quota_window_secs: u64,
+
+    /// Maximum number of requests served to the same client IP within `ip-quota-window-secs`.
+    /// Unlimited if not set
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_requests_per_ip: Option<u64>,
+
+    #[clap(long, default_value_t = 24 * 60 * 60)]
+    pub ip_quota_window_secs: u64,
+
+    /// Header holding the client IP, e.g. `x-forwarded-for` when running behind a proxy. The
+    /// address of the connection is used if not set
+    #[clap(long)]
+    pub client_ip_header: Option<String>,
+
+    /// Serve requests to `/v1/gas` by queueing them and sending up to `batch-request-size` of them
+    /// in a single transaction. Clients poll `/v1/status/:task_id` for the result
+    #[clap(long)]
+    pub batch_enabled: bool,
+
+    #[clap(long, default_value_t = 100)]
+    pub batch_request_size: usize,
+
+    /// How long to wait for more requests to fill a batch, before sending it anyway
+    #[clap(long, default_value_t = 500)]
+    pub batch_request_timeout_ms: u64,
+
+    #[clap(long, default_value_t = 10_000)]
+    pub max_queued_batch_requests: usize,
 }
 
 impl Default for FaucetConfig {
@@ -86,6 +153,15 @@ impl Default for FaucetConfig {
             wallet_client_timeout_secs: 60,
             write_ahead_log: Default::default(),
             wal_retry_interval: 300,
+            max_requests_per_address: None,
+            address_quota_window_secs: 24 * 60 * 60,
+            max_requests_per_ip: None,
+            ip_quota_window_secs: 24 * 60 * 60,
+            client_ip_header: None,
+            batch_enabled: false,
+            batch_request_size: 100,
+            batch_request_timeout_ms: 500,
+            max_queued_batch_requests: 10_000,
         }
     }
 }
@@ -0,0 +1,354 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use tokio::sync::Mutex;
+use typed_store::rocks::{DBMap, TypedStoreError};
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::Map;
+use typed_store_derive::DBMapUtils;
+use uuid::Uuid;
+
+use crate::{FaucetConfig, FaucetError};
+
+/// Who a faucet request is accounted to
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum QuotaKey {
+    Address(SuiAddress),
+    Ip(IpAddr),
+}
+
+impl std::fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaKey::Address(address) => write!(f, "address {address}"),
+            QuotaKey::Ip(ip) => write!(f, "IP {ip}"),
+        }
+    }
+}
+
+/// Maximum number of requests accepted within any rolling window of `window`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub max_requests: u64,
+    pub window: Duration,
+}
+
+type RequestKey = (QuotaKey, u64, uuid::Bytes);
+
+/// Persistent record of the requests accepted for every recipient address and client IP, keyed by
+/// the time the request was accepted (in milliseconds since the unix epoch). Entries older than
+/// the quota window are removed as new requests come in.
+#[derive(DBMapUtils)]
+pub struct QuotaStore {
+    pub requests: DBMap<RequestKey, ()>,
+}
+
+impl QuotaStore {
+    pub(crate) fn open(path: &Path) -> Self {
+        Self::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::default(),
+            None,
+            None,
+        )
+    }
+}
+
+/// Requests accounted against the quotas, which can be given back if the request could not be
+/// served.
+#[derive(Debug)]
+pub struct QuotaReservation {
+    keys: Vec<RequestKey>,
+}
+
+/// Enforces per recipient address and per client IP quotas on faucet requests, over rolling
+/// windows. The accounting is persisted next to the `WriteAheadLog`, so that restarting the
+/// faucet does not reset the quotas.
+pub struct RequestQuotas {
+    store: QuotaStore,
+    address_quota: Option<Quota>,
+    ip_quota: Option<Quota>,
+    // Serializes checking the quotas and recording the request.
+    lock: Mutex<()>,
+}
+
+impl RequestQuotas {
+    pub fn new(path: &Path, address_quota: Option<Quota>, ip_quota: Option<Quota>) -> Self {
+        Self {
+            store: QuotaStore::open(path),
+            address_quota,
+            ip_quota,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Opens the quota store next to the write ahead log configured in `config`
+    pub fn from_config(config: &FaucetConfig) -> Self {
+        let quota = |max_requests: Option<u64>, window_secs: u64| {
+            max_requests.map(|max_requests| Quota {
+                max_requests,
+                window: Duration::from_secs(window_secs),
+            })
+        };
+        Self::new(
+            &config.write_ahead_log.with_extension("quota"),
+            quota(
+                config.max_requests_per_address,
+                config.address_quota_window_secs,
+            ),
+            quota(config.max_requests_per_ip, config.ip_quota_window_secs),
+        )
+    }
+
+    /// Accounts a request for `recipient` from `ip` against the quotas. Fails with
+    /// `FaucetError::QuotaExceeded` if either of them has used up its quota, in which case nothing
+    /// is recorded.
+    pub async fn acquire(
+        &self,
+        uuid: Uuid,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<QuotaReservation, FaucetError> {
+        self.acquire_at(uuid, recipient, ip, now_ms()).await
+    }
+
+    async fn acquire_at(
+        &self,
+        uuid: Uuid,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+        now_ms: u64,
+    ) -> Result<QuotaReservation, FaucetError> {
+        let quotas = [
+            self.address_quota
+                .map(|quota| (QuotaKey::Address(recipient), quota)),
+            self.ip_quota
+                .zip(ip)
+                .map(|(quota, ip)| (QuotaKey::Ip(ip), quota)),
+        ];
+
+        let _guard = self.lock.lock().await;
+        let mut batch = self.store.requests.batch();
+        let mut keys = vec![];
+        for (key, quota) in quotas.into_iter().flatten() {
+            // Requests count against the quota for `window`, up to and excluding its end
+            let window_start = (now_ms + 1).saturating_sub(quota.window.as_millis() as u64);
+            // Forget requests which are out of the window
+            batch
+                .delete_range(
+                    &self.store.requests,
+                    &(key, 0, [0; 16]),
+                    &(key, window_start, [0; 16]),
+                )
+                .map_err(FaucetError::internal)?;
+
+            let requests = self
+                .requests_since(key, window_start)
+                .map_err(FaucetError::internal)?;
+            if requests.len() as u64 >= quota.max_requests {
+                // The request can be accepted once enough of the earlier ones left the window. A
+                // quota of zero requests never accepts any, so clients are told to retry after a
+                // full window.
+                let expires_at = requests
+                    .get(requests.len() - quota.max_requests as usize)
+                    .copied()
+                    .unwrap_or(now_ms)
+                    + quota.window.as_millis() as u64;
+                let retry_after_secs = (expires_at.saturating_sub(now_ms) + 999) / 1000;
+                return Err(FaucetError::QuotaExceeded(
+                    key.to_string(),
+                    retry_after_secs,
+                ));
+            }
+            keys.push((key, now_ms, *uuid.as_bytes()));
+        }
+
+        batch
+            .insert_batch(&self.store.requests, keys.iter().map(|key| (*key, ())))
+            .map_err(FaucetError::internal)?;
+        batch.write().map_err(FaucetError::internal)?;
+        Ok(QuotaReservation { keys })
+    }
+
+    /// Gives back the quota taken by a request that could not be served
+    pub async fn release(&self, reservation: QuotaReservation) -> Result<(), FaucetError> {
+        let _guard = self.lock.lock().await;
+        self.store
+            .requests
+            .multi_remove(reservation.keys)
+            .map_err(FaucetError::internal)
+    }
+
+    /// Times of the requests accounted to `key` since `start_ms`, in ascending order
+    fn requests_since(&self, key: QuotaKey, start_ms: u64) -> Result<Vec<u64>, TypedStoreError> {
+        Ok(self
+            .store
+            .requests
+            .iter()
+            .skip_to(&(key, start_ms, [0; 16]))?
+            .take_while(|((k, _, _), _)| *k == key)
+            .map(|((_, timestamp_ms, _), _)| timestamp_ms)
+            .collect())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn quota(max_requests: u64) -> Option<Quota> {
+        Some(Quota {
+            max_requests,
+            window: WINDOW,
+        })
+    }
+
+    #[tokio::test]
+    async fn address_quota_over_rolling_window() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::new(&tmp.path().join("quota"), quota(2), None);
+        let recipient = SuiAddress::random_for_testing_only();
+        let other = SuiAddress::random_for_testing_only();
+
+        quotas
+            .acquire_at(Uuid::new_v4(), recipient, None, 1_000)
+            .await
+            .unwrap();
+        quotas
+            .acquire_at(Uuid::new_v4(), recipient, None, 31_000)
+            .await
+            .unwrap();
+
+        // The quota is used up until the first request leaves the window
+        assert_eq!(
+            quotas
+                .acquire_at(Uuid::new_v4(), recipient, None, 40_500)
+                .await
+                .unwrap_err(),
+            FaucetError::QuotaExceeded(QuotaKey::Address(recipient).to_string(), 21)
+        );
+        quotas
+            .acquire_at(Uuid::new_v4(), other, None, 40_500)
+            .await
+            .unwrap();
+        quotas
+            .acquire_at(Uuid::new_v4(), recipient, None, 61_000)
+            .await
+            .unwrap();
+        assert_eq!(
+            quotas
+                .acquire_at(Uuid::new_v4(), recipient, None, 61_000)
+                .await
+                .unwrap_err(),
+            FaucetError::QuotaExceeded(QuotaKey::Address(recipient).to_string(), 30)
+        );
+    }
+
+    #[tokio::test]
+    async fn ip_quota_is_shared_by_recipients() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::new(&tmp.path().join("quota"), quota(10), quota(1));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        quotas
+            .acquire_at(
+                Uuid::new_v4(),
+                SuiAddress::random_for_testing_only(),
+                Some(ip),
+                1_000,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            quotas
+                .acquire_at(
+                    Uuid::new_v4(),
+                    SuiAddress::random_for_testing_only(),
+                    Some(ip),
+                    2_000,
+                )
+                .await,
+            Err(FaucetError::QuotaExceeded(_, 59))
+        ));
+        // Requests without a known client IP are only subject to the address quota
+        quotas
+            .acquire_at(
+                Uuid::new_v4(),
+                SuiAddress::random_for_testing_only(),
+                None,
+                2_000,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn zero_quota_refuses_all_requests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = RequestQuotas::new(&tmp.path().join("quota"), quota(0), None);
+        let recipient = SuiAddress::random_for_testing_only();
+        assert_eq!(
+            quotas
+                .acquire_at(Uuid::new_v4(), recipient, None, 1_000)
+                .await
+                .unwrap_err(),
+            FaucetError::QuotaExceeded(QuotaKey::Address(recipient).to_string(), 60)
+        );
+    }
+
+    #[test]
+    fn zero_quota_is_rejected_in_config() {
+        let config = |args: &[&str]| {
+            FaucetConfig::try_parse_from(
+                ["sui-faucet", "--write-ahead-log", "wal"]
+                    .iter()
+                    .chain(args),
+            )
+        };
+        assert!(config(&["--max-requests-per-address", "1"]).is_ok());
+        assert!(config(&["--max-requests-per-address", "0"]).is_err());
+        assert!(config(&["--max-requests-per-ip", "0"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn released_requests_and_restarts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("quota");
+        let recipient = SuiAddress::random_for_testing_only();
+        {
+            let quotas = RequestQuotas::new(&path, quota(1), None);
+            let reservation = quotas
+                .acquire_at(Uuid::new_v4(), recipient, None, 1_000)
+                .await
+                .unwrap();
+            quotas.release(reservation).await.unwrap();
+            quotas
+                .acquire_at(Uuid::new_v4(), recipient, None, 2_000)
+                .await
+                .unwrap();
+        }
+
+        // The accounting survives a restart
+        let quotas = RequestQuotas::new(&path, quota(1), None);
+        assert!(quotas
+            .acquire_at(Uuid::new_v4(), recipient, None, 3_000)
+            .await
+            .is_err());
+    }
+}
@@ -10,6 +10,7 @@ use tap::tap::TapFallible;
 use shared_crypto::intent::Intent;
 #[cfg(test)]
 use std::collections::HashSet;
+use std::collections::{HashMap, VecDeque};
 use std::path::Path;
 use typed_store::Map;
 
@@ -28,14 +29,17 @@ use sui_types::{
 };
 use tokio::sync::{
     mpsc::{self, Receiver, Sender},
-    Mutex,
+    Mutex, Notify,
 };
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use super::quota::{QuotaReservation, RequestQuotas};
 use super::write_ahead_log::WriteAheadLog;
-use crate::{CoinInfo, Faucet, FaucetConfig, FaucetError, FaucetReceipt};
+use crate::{
+    BatchFaucetReceipt, BatchSendStatus, CoinInfo, Faucet, FaucetConfig, FaucetError, FaucetReceipt,
+};
 
 pub struct SimpleFaucet {
     wallet: WalletContext,
@@ -44,6 +48,21 @@ pub struct SimpleFaucet {
     consumer: Mutex<Receiver<ObjectID>>,
     pub metrics: FaucetMetrics,
     wal: Mutex<WriteAheadLog>,
+    batch_request_size: usize,
+    batch_request_timeout: Duration,
+    batch_producer: Sender<BatchRequest>,
+    batch_consumer: Mutex<Receiver<BatchRequest>>,
+    // Status of the requests queued with `batch_send`, and when it last changed.
+    batch_statuses: std::sync::Mutex<HashMap<Uuid, (Instant, BatchSendStatus)>>,
+    // Woken up whenever a batch request finishes.
+    batch_status_changed: Notify,
+}
+
+/// A request queued with `batch_send`
+struct BatchRequest {
+    id: Uuid,
+    recipient: SuiAddress,
+    amounts: Vec<u64>,
 }
 
 enum GasCoinResponse {
@@ -58,6 +77,10 @@ enum GasCoinResponse {
 const DEFAULT_GAS_COMPUTATION_BUCKET: u64 = 10_000_000;
 const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
 const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+// How long the status of a finished batch request can be polled for.
+const BATCH_STATUS_RETENTION: Duration = Duration::from_secs(60 * 60);
+// Maximum number of objects fullnode returns from a single multi-get.
+const MAX_OBJECTS_PER_READ: usize = 50;
 
 impl SimpleFaucet {
     pub async fn new(
@@ -111,6 +134,7 @@ impl SimpleFaucet {
             }
         }
 
+        let (batch_producer, batch_consumer) = mpsc::channel(config.max_queued_batch_requests);
         let faucet = Self {
             wallet,
             active_address,
@@ -118,6 +142,12 @@ impl SimpleFaucet {
             consumer: Mutex::new(consumer),
             metrics,
             wal: Mutex::new(wal),
+            batch_request_size: config.batch_request_size,
+            batch_request_timeout: Duration::from_millis(config.batch_request_timeout_ms),
+            batch_producer,
+            batch_consumer: Mutex::new(batch_consumer),
+            batch_statuses: std::sync::Mutex::new(HashMap::new()),
+            batch_status_changed: Notify::new(),
         };
 
         // Retrying all the pending transactions from the WAL, before continuing.  Ignore return
@@ -378,6 +408,301 @@ impl SimpleFaucet {
         }
     }
 
+    /// Serves the requests queued with `batch_send`, sending up to `batch_request_size` of them in
+    /// every transaction. Runs for as long as the faucet is alive.
+    pub async fn run_batch_requests(&self) {
+        let mut consumer = self.batch_consumer.lock().await;
+        let mut pending = VecDeque::new();
+        loop {
+            if pending.is_empty() {
+                let Some(request) = consumer.recv().await else {
+                    return;
+                };
+                pending.push_back(request);
+            }
+
+            // Give more requests the chance to join the batch before sending it.
+            let deadline = Instant::now() + self.batch_request_timeout;
+            while pending.len() < self.batch_request_size {
+                match tokio::time::timeout_at(deadline, consumer.recv()).await {
+                    Ok(Some(request)) => pending.push_back(request),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            let uuid = Uuid::new_v4();
+            let (batch, result) = match self.prepare_batch(uuid, &mut pending).await {
+                Ok((coin_id, gas_cost, batch)) => {
+                    let result = self
+                        .batch_transfer_gases(uuid, coin_id, gas_cost, &batch)
+                        .await;
+                    (batch, result)
+                }
+                Err(e) => {
+                    // Fail the requests which would have made up this batch, so that clients
+                    // don't wait on a faucet that is out of coins.
+                    let len = pending.len().min(self.batch_request_size);
+                    (pending.drain(..len).collect(), Err(e))
+                }
+            };
+            self.metrics
+                .current_queued_batch_requests
+                .sub(batch.len() as i64);
+
+            match result {
+                Ok(receipts) => {
+                    info!(?uuid, requests = batch.len(), "Batch PaySui txn succeeded");
+                    for (request, receipt) in batch.iter().zip(receipts) {
+                        self.set_batch_status(request.id, BatchSendStatus::Succeeded(receipt));
+                    }
+                }
+                Err(e) => {
+                    warn!(?uuid, requests = batch.len(), "Failed to send batch: {e:?}");
+                    for request in &batch {
+                        self.set_batch_status(request.id, BatchSendStatus::Failed(e.to_string()));
+                    }
+                }
+            }
+            self.prune_batch_statuses();
+        }
+    }
+
+    /// Pulls a gas coin from the queue, and takes the requests at the front of `pending` that it
+    /// can pay for, up to `batch_request_size` of them. Coins which cannot even pay for the first
+    /// request are removed from the pool, like in `transfer_gases`.
+    async fn prepare_batch(
+        &self,
+        uuid: Uuid,
+        pending: &mut VecDeque<BatchRequest>,
+    ) -> Result<(ObjectID, u64, Vec<BatchRequest>), FaucetError> {
+        let gas_cost = self.get_gas_cost().await?;
+        let min_amount = pending
+            .front()
+            .map_or(0, |request| request.amounts.iter().sum::<u64>())
+            + gas_cost;
+
+        let (coin_id, balance) = loop {
+            let Some(coin_id) = self.pop_gas_coin(uuid).await else {
+                warn!("Failed getting gas coin, try later!");
+                return Err(FaucetError::NoGasCoinAvailable);
+            };
+            match self.get_gas_coin_and_check_faucet_owner(coin_id).await {
+                Ok(Some(gas_coin)) if gas_coin.value() >= min_amount => {
+                    info!(?uuid, ?coin_id, "balance: {}", gas_coin.value());
+                    break (coin_id, gas_coin.value());
+                }
+                Ok(Some(_)) => {
+                    warn!(?uuid, ?coin_id, "Insufficient balance, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+                Ok(None) => {
+                    warn!(?uuid, ?coin_id, "Invalid, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+                Err(e) => {
+                    error!(?uuid, ?coin_id, "Fullnode read error: {e:?}");
+                    self.recycle_gas_coin(coin_id, uuid).await;
+                    return Err(FaucetError::FullnodeReadingError(format!(
+                        "unknown gas coin {coin_id:?}"
+                    )));
+                }
+            }
+        };
+
+        let mut total_amount = gas_cost;
+        let mut batch = vec![];
+        while batch.len() < self.batch_request_size {
+            let Some(request) = pending.front() else {
+                break;
+            };
+            let amount: u64 = request.amounts.iter().sum();
+            if total_amount + amount > balance {
+                break;
+            }
+            total_amount += amount;
+            batch.extend(pending.pop_front());
+        }
+        Ok((coin_id, gas_cost, batch))
+    }
+
+    /// Sends all requests in `batch` in one PaySui transaction paid from `coin_id`, and returns
+    /// the receipt of each request, in order.
+    async fn batch_transfer_gases(
+        &self,
+        uuid: Uuid,
+        coin_id: ObjectID,
+        gas_cost: u64,
+        batch: &[BatchRequest],
+    ) -> Result<Vec<FaucetReceipt>, FaucetError> {
+        let (recipients, amounts): (Vec<_>, Vec<_>) = batch
+            .iter()
+            .flat_map(|request| {
+                request
+                    .amounts
+                    .iter()
+                    .map(move |amount| (request.recipient, *amount))
+            })
+            .unzip();
+        let number_of_coins = amounts.len();
+
+        let tx_data = match self
+            .build_batch_pay_sui_txn(coin_id, self.active_address, recipients, amounts, gas_cost)
+            .await
+        {
+            Ok(tx_data) => tx_data,
+            Err(e) => {
+                self.recycle_gas_coin(coin_id, uuid).await;
+                return Err(FaucetError::internal(e));
+            }
+        };
+
+        {
+            // The transaction has many recipients, so it is logged as sent to the faucet itself.
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, coin_id, self.active_address, tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+        let response = self
+            .sign_and_execute_txn(uuid, self.active_address, coin_id, tx_data)
+            .await?;
+        let digest = response.digest;
+        let created = response
+            .effects
+            .ok_or_else(|| {
+                FaucetError::ParseTransactionResponseError(format!(
+                    "effects field missing for txn {digest}"
+                ))
+            })?
+            .created()
+            .to_vec();
+        if created.len() != number_of_coins {
+            panic!(
+                "PaySui Transaction should create exact {:?} new coins, but got {:?}",
+                number_of_coins, created
+            );
+        }
+
+        let coin_ids: Vec<ObjectID> = created
+            .iter()
+            .map(|created_coin_owner_ref| created_coin_owner_ref.reference.object_id)
+            .collect();
+        let values = self.get_coin_values(&coin_ids).await;
+        let mut coins_by_owner: HashMap<SuiAddress, Vec<(ObjectID, u64)>> = HashMap::new();
+        for created_coin_owner_ref in &created {
+            if let Owner::AddressOwner(owner) = created_coin_owner_ref.owner {
+                let coin_id = created_coin_owner_ref.reference.object_id;
+                coins_by_owner
+                    .entry(owner)
+                    .or_default()
+                    .push((coin_id, values.get(&coin_id).copied().unwrap_or(0)));
+            }
+        }
+
+        // A recipient can have several requests in the batch, so hand out its coins by amount.
+        Ok(batch
+            .iter()
+            .map(|request| {
+                let coins = coins_by_owner.entry(request.recipient).or_default();
+                let sent = request
+                    .amounts
+                    .iter()
+                    .filter_map(|amount| {
+                        let position = coins
+                            .iter()
+                            .position(|(_, value)| value == amount)
+                            .or((!coins.is_empty()).then_some(0))?;
+                        let (id, amount) = coins.swap_remove(position);
+                        Some(CoinInfo {
+                            amount,
+                            id,
+                            transfer_tx_digest: digest,
+                        })
+                    })
+                    .collect();
+                FaucetReceipt { sent }
+            })
+            .collect())
+    }
+
+    /// Values of the given coins. Coins which could not be read are left out.
+    async fn get_coin_values(&self, coin_ids: &[ObjectID]) -> HashMap<ObjectID, u64> {
+        let client = match self.wallet.get_client().await {
+            Ok(client) => client,
+            Err(e) => {
+                info!("Could not read coins after successful transaction, error: {e:?}");
+                return HashMap::new();
+            }
+        };
+        let reads = futures::future::join_all(coin_ids.chunks(MAX_OBJECTS_PER_READ).map(|ids| {
+            client.read_api().multi_get_object_with_options(
+                ids.to_vec(),
+                SuiObjectDataOptions::new().with_type().with_content(),
+            )
+        }))
+        .await;
+
+        let mut values = HashMap::new();
+        for read in reads {
+            match read {
+                Ok(objects) => values.extend(objects.iter().filter_map(|object| {
+                    let data = object.data.as_ref()?;
+                    let coin = GasCoin::try_from(data).ok()?;
+                    Some((data.object_id, coin.value()))
+                })),
+                Err(e) => {
+                    info!("Could not read coins after successful transaction, error: {e:?}")
+                }
+            }
+        }
+        values
+    }
+
+    fn set_batch_status(&self, id: Uuid, status: BatchSendStatus) {
+        self.batch_statuses
+            .lock()
+            .unwrap()
+            .insert(id, (Instant::now(), status));
+        self.batch_status_changed.notify_waiters();
+    }
+
+    /// Waits for the batch request `id` to be served, and returns its final status.
+    pub async fn wait_for_batch_send(&self, id: Uuid) -> Result<BatchSendStatus, FaucetError> {
+        loop {
+            // Register for the wake up before checking the status, so that it is not missed.
+            let changed = self.batch_status_changed.notified();
+            match self.get_batch_send_status(id).await? {
+                BatchSendStatus::InProgress => changed.await,
+                status => return Ok(status),
+            }
+        }
+    }
+
+    /// Waits for the batch request `id` to be served, and gives back the quota it took if it
+    /// could not be sent, like a failed `send` does.
+    pub async fn release_quota_on_failure(
+        &self,
+        id: Uuid,
+        quotas: &RequestQuotas,
+        reservation: QuotaReservation,
+    ) -> Result<(), FaucetError> {
+        if let BatchSendStatus::Failed(e) = self.wait_for_batch_send(id).await? {
+            info!(uuid = ?id, "Releasing the quota of failed batch request: {e}");
+            quotas.release(reservation).await?;
+        }
+        Ok(())
+    }
+
+    /// Forgets finished batch requests which have not been updated in `BATCH_STATUS_RETENTION`.
+    fn prune_batch_statuses(&self) {
+        self.batch_statuses
+            .lock()
+            .unwrap()
+            .retain(|_, (updated_at, status)| {
+                matches!(status, BatchSendStatus::InProgress)
+                    || updated_at.elapsed() < BATCH_STATUS_RETENTION
+            });
+    }
+
     async fn recycle_gas_coin(&self, coin_id: ObjectID, uuid: Uuid) {
         // Once transactions are done, in despite of success or failure,
         // we put back the coins. The producer should never wait indefinitely,
@@ -483,10 +808,24 @@ impl SimpleFaucet {
     ) -> Result<TransactionData, anyhow::Error> {
         let recipients: Vec<SuiAddress> =
             std::iter::repeat(recipient).take(amounts.len()).collect();
+        self.build_batch_pay_sui_txn(coin_id, signer, recipients, amounts.to_vec(), budget)
+            .await
+    }
+
+    /// Builds a programmable transaction which splits `amounts` off the gas coin, and transfers
+    /// them to the corresponding `recipients`.
+    async fn build_batch_pay_sui_txn(
+        &self,
+        coin_id: ObjectID,
+        signer: SuiAddress,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        budget: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
         let client = self.wallet.get_client().await?;
         client
             .transaction_builder()
-            .pay_sui(signer, vec![coin_id], recipients, amounts.to_vec(), budget)
+            .pay_sui(signer, vec![coin_id], recipients, amounts, budget)
             .await
             .map_err(|e| {
                 anyhow::anyhow!(
@@ -592,10 +931,45 @@ impl Faucet for SimpleFaucet {
         }
         Ok(FaucetReceipt { sent })
     }
+
+    async fn batch_send(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: &[u64],
+    ) -> Result<BatchFaucetReceipt, FaucetError> {
+        info!(?recipient, uuid = ?id, "Queueing faucet request");
+
+        // Record the status first, as the request may be served before `try_send` returns.
+        self.set_batch_status(id, BatchSendStatus::InProgress);
+        let request = BatchRequest {
+            id,
+            recipient,
+            amounts: amounts.to_vec(),
+        };
+        if self.batch_producer.try_send(request).is_err() {
+            self.batch_statuses.lock().unwrap().remove(&id);
+            return Err(FaucetError::BatchQueueFull);
+        }
+        self.metrics.current_queued_batch_requests.inc();
+        Ok(BatchFaucetReceipt {
+            task: id.to_string(),
+        })
+    }
+
+    async fn get_batch_send_status(&self, task_id: Uuid) -> Result<BatchSendStatus, FaucetError> {
+        self.batch_statuses
+            .lock()
+            .unwrap()
+            .get(&task_id)
+            .map(|(_, status)| status.clone())
+            .ok_or_else(|| FaucetError::BatchTaskNotFound(task_id.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::Quota;
     use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
     use sui_json_rpc_types::SuiExecutionStatus;
     use sui_sdk::wallet_context::WalletContext;
@@ -914,6 +1288,138 @@ mod tests {
         assert!(candidates.get(&tiny_coin_id).is_none());
     }
 
+    #[tokio::test]
+    async fn test_batch_send() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            batch_request_size: 3,
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            test_cluster.wallet,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+        let available = faucet.metrics.total_available_coins.get();
+
+        // One recipient is sent to twice, in the same batch
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut requests = vec![(recipient, vec![1, 2]), (recipient, vec![3])];
+        requests.extend((0..5).map(|_| (SuiAddress::random_for_testing_only(), vec![4, 5])));
+        let mut tasks = vec![];
+        for (recipient, amounts) in &requests {
+            let BatchFaucetReceipt { task } = faucet
+                .batch_send(Uuid::new_v4(), *recipient, amounts)
+                .await
+                .unwrap();
+            tasks.push(Uuid::parse_str(&task).unwrap());
+        }
+        assert!(matches!(
+            faucet.get_batch_send_status(tasks[0]).await.unwrap(),
+            BatchSendStatus::InProgress
+        ));
+
+        let poll = async {
+            loop {
+                let statuses = futures::future::join_all(
+                    tasks.iter().map(|task| faucet.get_batch_send_status(*task)),
+                )
+                .await
+                .into_iter()
+                .map(|status| status.unwrap())
+                .collect::<Vec<_>>();
+                if statuses
+                    .iter()
+                    .all(|status| !matches!(status, BatchSendStatus::InProgress))
+                {
+                    return statuses;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        };
+        let statuses = tokio::select! {
+            _ = faucet.run_batch_requests() => unreachable!(),
+            statuses = poll => statuses,
+        };
+
+        let mut digests = HashSet::new();
+        for ((_, amounts), status) in requests.iter().zip(statuses) {
+            let BatchSendStatus::Succeeded(FaucetReceipt { sent }) = status else {
+                panic!("Expected the request to succeed, got {status:?}");
+            };
+            let mut actual_amounts: Vec<u64> = sent.iter().map(|c| c.amount).collect();
+            actual_amounts.sort_unstable();
+            assert_eq!(&actual_amounts, amounts);
+            digests.extend(sent.iter().map(|c| c.transfer_tx_digest));
+        }
+        // Seven requests are sent in batches of up to three
+        assert_eq!(digests.len(), 3);
+        assert_eq!(available, faucet.metrics.total_available_coins.get());
+        assert_eq!(faucet.metrics.current_queued_batch_requests.get(), 0);
+        assert!(matches!(
+            faucet.get_batch_send_status(Uuid::new_v4()).await,
+            Err(FaucetError::BatchTaskNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_send_failure_releases_quota() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let faucet = SimpleFaucet::new(
+            test_cluster.wallet,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            FaucetConfig::default(),
+        )
+        .await
+        .unwrap();
+        let quotas = RequestQuotas::new(
+            &tmp.path().join("faucet.quota"),
+            Some(Quota {
+                max_requests: 1,
+                window: Duration::from_secs(60),
+            }),
+            None,
+        );
+
+        // No coin can pay for the request, so its batch fails
+        let recipient = SuiAddress::random_for_testing_only();
+        let id = Uuid::new_v4();
+        let reservation = quotas.acquire(id, recipient, None).await.unwrap();
+        faucet
+            .batch_send(id, recipient, &[u64::MAX / 2])
+            .await
+            .unwrap();
+        assert!(matches!(
+            quotas.acquire(Uuid::new_v4(), recipient, None).await,
+            Err(FaucetError::QuotaExceeded(..))
+        ));
+
+        tokio::select! {
+            _ = faucet.run_batch_requests() => unreachable!(),
+            result = faucet.release_quota_on_failure(id, &quotas, reservation) => result.unwrap(),
+        };
+        assert!(matches!(
+            faucet.get_batch_send_status(id).await.unwrap(),
+            BatchSendStatus::Failed(_)
+        ));
+        quotas
+            .acquire(Uuid::new_v4(), recipient, None)
+            .await
+            .unwrap();
+    }
+
     async fn test_basic_interface(faucet: &impl Faucet) {
         let recipient = SuiAddress::random_for_testing_only();
         let amounts = vec![1, 2, 3];
@@ -3,13 +3,14 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
+    extract::{ConnectInfo, Path},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use clap::Parser;
-use http::Method;
+use http::{header, HeaderMap, Method};
 use mysten_metrics::spawn_monitored_task;
 use std::env;
 use std::{
@@ -20,7 +21,8 @@ use std::{
 };
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
 use sui_faucet::{
-    Faucet, FaucetConfig, FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet,
+    BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetConfig, FaucetError,
+    FaucetRequest, FaucetResponse, RequestMetricsLayer, RequestQuotas, SimpleFaucet,
 };
 use sui_sdk::wallet_context::WalletContext;
 use tower::{limit::RateLimitLayer, ServiceBuilder};
@@ -33,6 +35,7 @@ const CONCURRENCY_LIMIT: usize = 30;
 struct AppState<F = SimpleFaucet> {
     faucet: F,
     config: FaucetConfig,
+    quotas: RequestQuotas,
     // TODO: add counter
 }
 
@@ -59,6 +62,7 @@ async fn main() -> Result<(), anyhow::Error> {
         max_request_per_second,
         wallet_client_timeout_secs,
         ref write_ahead_log,
+        batch_enabled,
         ..
     } = config;
 
@@ -78,6 +82,7 @@ async fn main() -> Result<(), anyhow::Error> {
         )
         .await
         .unwrap(),
+        quotas: RequestQuotas::from_config(&config),
         config,
     });
 
@@ -87,24 +92,35 @@ async fn main() -> Result<(), anyhow::Error> {
         .allow_headers(Any)
         .allow_origin(Any);
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/", get(health))
-        .route("/gas", post(request_gas))
-        .layer(
-            ServiceBuilder::new()
-                .layer(HandleErrorLayer::new(handle_error))
-                .layer(RequestMetricsLayer::new(&prometheus_registry))
-                .layer(cors)
-                .load_shed()
-                .buffer(request_buffer_size)
-                .layer(RateLimitLayer::new(
-                    max_request_per_second,
-                    Duration::from_secs(1),
-                ))
-                .concurrency_limit(max_concurrency)
-                .layer(Extension(app_state.clone()))
-                .into_inner(),
-        );
+        .route("/gas", post(request_gas));
+    if batch_enabled {
+        app = app
+            .route("/v1/gas", post(batch_request_gas))
+            .route("/v1/status/:task_id", get(request_status));
+
+        let batch_state = app_state.clone();
+        spawn_monitored_task!(async move {
+            info!("Starting task to serve batch requests.");
+            batch_state.faucet.run_batch_requests().await
+        });
+    }
+    let app = app.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(handle_error))
+            .layer(RequestMetricsLayer::new(&prometheus_registry))
+            .layer(cors)
+            .load_shed()
+            .buffer(request_buffer_size)
+            .layer(RateLimitLayer::new(
+                max_request_per_second,
+                Duration::from_secs(1),
+            ))
+            .concurrency_limit(max_concurrency)
+            .layer(Extension(app_state.clone()))
+            .into_inner(),
+    );
 
     // TODO (jian):Investigate this issue later.
     // spawn_monitored_task!(async move {
@@ -119,7 +135,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -132,33 +148,58 @@ async fn health() -> &'static str {
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     // ID for traceability
     let id = Uuid::new_v4();
     info!(uuid = ?id, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let ip = match client_ip(&state.config, addr, &headers) {
+                Ok(ip) => ip,
+                Err(e) => {
+                    warn!(uuid = ?id, "Rejected gas request: {e}");
+                    return (StatusCode::BAD_REQUEST, Json(FaucetResponse::from(e)))
+                        .into_response();
+                }
+            };
+            let reservation = match state.quotas.acquire(id, requests.recipient, Some(ip)).await {
+                Ok(reservation) => reservation,
+                Err(e) => {
+                    warn!(uuid = ?id, recipient = ?requests.recipient, ?ip, "Rejected gas request: {e}");
+                    return quota_error(e);
+                }
+            };
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the reclycing of coins
-            spawn_monitored_task!(async move {
-                state
+            let task_state = state.clone();
+            let result = spawn_monitored_task!(async move {
+                task_state
                     .faucet
                     .send(
                         id,
                         requests.recipient,
-                        &vec![state.config.amount; state.config.num_coins],
+                        &vec![task_state.config.amount; task_state.config.num_coins],
                     )
                     .await
             })
             .await
-            .unwrap()
+            .unwrap();
+            // Requests which were not served don't count against the quotas
+            if result.is_err() {
+                if let Err(e) = state.quotas.release(reservation).await {
+                    warn!(uuid = ?id, "Failed to release request quota: {e}");
+                }
+            }
+            result
         }
     };
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -166,19 +207,143 @@ async fn request_gas(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(FaucetResponse::from(v)),
             )
+                .into_response()
+        }
+    }
+}
+
+/// handler for batch gas requests, which are queued and served together with other requests.
+/// Responds with the task id to poll `/v1/status/:task_id` with.
+async fn batch_request_gas(
+    Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<FaucetRequest>,
+) -> Response {
+    let id = Uuid::new_v4();
+    info!(uuid = ?id, "Got new batch gas request.");
+    let FaucetRequest::FixedAmountRequest(request) = payload;
+    let ip = match client_ip(&state.config, addr, &headers) {
+        Ok(ip) => ip,
+        Err(e) => {
+            warn!(uuid = ?id, "Rejected gas request: {e}");
+            return (StatusCode::BAD_REQUEST, Json(BatchFaucetResponse::from(e))).into_response();
+        }
+    };
+    let reservation = match state.quotas.acquire(id, request.recipient, Some(ip)).await {
+        Ok(reservation) => reservation,
+        Err(e) => {
+            warn!(uuid = ?id, recipient = ?request.recipient, ?ip, "Rejected gas request: {e}");
+            return quota_error(e);
+        }
+    };
+    let result = state
+        .faucet
+        .batch_send(
+            id,
+            request.recipient,
+            &vec![state.config.amount; state.config.num_coins],
+        )
+        .await;
+    match result {
+        Ok(v) => {
+            info!(uuid = ?id, "Request is successfully queued");
+            // Give back the quota if the batch the request ends up in fails to be sent.
+            let batch_state = state.clone();
+            spawn_monitored_task!(async move {
+                if let Err(e) = batch_state
+                    .faucet
+                    .release_quota_on_failure(id, &batch_state.quotas, reservation)
+                    .await
+                {
+                    warn!(uuid = ?id, "Failed to release request quota: {e}");
+                }
+            });
+            (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(v))).into_response()
+        }
+        Err(v) => {
+            warn!(uuid = ?id, "Failed to queue gas request: {:?}", v);
+            if let Err(e) = state.quotas.release(reservation).await {
+                warn!(uuid = ?id, "Failed to release request quota: {e}");
+            }
+            let status = match v {
+                FaucetError::BatchQueueFull => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(BatchFaucetResponse::from(v))).into_response()
         }
     }
 }
 
+/// handler for polling the status of a batch gas request
+async fn request_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Response {
+    let result = match Uuid::parse_str(&task_id) {
+        Ok(task_id) => state.faucet.get_batch_send_status(task_id).await,
+        Err(_) => Err(FaucetError::BatchTaskNotFound(task_id)),
+    };
+    match result {
+        Ok(v) => (StatusCode::OK, Json(BatchStatusFaucetResponse::from(v))).into_response(),
+        Err(v) => (
+            StatusCode::NOT_FOUND,
+            Json(BatchStatusFaucetResponse::from(v)),
+        )
+            .into_response(),
+    }
+}
+
+/// The IP of the client, read from `client_ip_header` if configured. The last address in the
+/// header is used, since that is the one appended by the proxy in front of the faucet. Requests
+/// without a valid address in the header are rejected rather than let past the IP quota.
+fn client_ip(
+    config: &FaucetConfig,
+    addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<IpAddr, FaucetError> {
+    let Some(name) = &config.client_ip_header else {
+        return Ok(addr.ip());
+    };
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse().ok())
+        .ok_or_else(|| FaucetError::InvalidClientIp(name.clone()))
+}
+
+fn quota_error(error: FaucetError) -> Response {
+    let retry_after_secs = match &error {
+        FaucetError::QuotaExceeded(_, retry_after_secs) => *retry_after_secs,
+        _ => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(FaucetResponse::from(error)),
+            )
+                .into_response()
+        }
+    };
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        Json(FaucetResponse::from(error)),
+    )
+        .into_response()
+}
+
 async fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
     info!("Initialize wallet from config path: {:?}", wallet_conf);
-    WalletContext::new(
+    let mut context = WalletContext::new(
         &wallet_conf,
         Some(Duration::from_secs(timeout_secs)),
         Some(1000),
     )
-    .await
+    .await?;
+    // The faucet signs every request, so unlock the keystore up front
+    context.unlock_keystore()?;
+    Ok(context)
 }
 
 async fn handle_error(error: BoxError) -> impl IntoResponse {
@@ -194,3 +359,33 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
         Cow::from(format!("Unhandled internal error: {}", error)),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_ip_from_header() {
+        let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        let mut config = FaucetConfig::default();
+        assert_eq!(client_ip(&config, addr, &headers).unwrap(), addr.ip());
+
+        // With a header configured, requests must carry a valid address in it
+        config.client_ip_header = Some("x-forwarded-for".to_string());
+        assert!(matches!(
+            client_ip(&config, addr, &headers),
+            Err(FaucetError::InvalidClientIp(_))
+        ));
+        headers.insert("x-forwarded-for", "not an ip".parse().unwrap());
+        assert!(matches!(
+            client_ip(&config, addr, &headers),
+            Err(FaucetError::InvalidClientIp(_))
+        ));
+        headers.insert("x-forwarded-for", "1.1.1.1, 2.2.2.2".parse().unwrap());
+        assert_eq!(
+            client_ip(&config, addr, &headers).unwrap(),
+            "2.2.2.2".parse::<IpAddr>().unwrap()
+        );
+    }
+}
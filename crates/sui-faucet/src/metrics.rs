@@ -27,6 +27,7 @@ pub struct FaucetMetrics {
     pub(crate) current_executions_in_flight: IntGauge,
     pub(crate) total_available_coins: IntGauge,
     pub(crate) total_discarded_coins: IntGauge,
+    pub(crate) current_queued_batch_requests: IntGauge,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -105,6 +106,12 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            current_queued_batch_requests: register_int_gauge_with_registry!(
+                "current_queued_batch_requests",
+                "Current number of batch requests waiting to be sent",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -0,0 +1,7 @@
+DROP FUNCTION IF EXISTS render_display(TEXT, JSONB);
+DROP TABLE IF EXISTS display;
+DROP INDEX IF EXISTS objects_object_json;
+ALTER TABLE objects_history
+    DROP COLUMN IF EXISTS object_json;
+ALTER TABLE objects
+    DROP COLUMN IF EXISTS object_json;
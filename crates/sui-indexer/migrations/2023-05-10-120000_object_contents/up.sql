@@ -0,0 +1,39 @@
+-- Move struct fields of objects as JSON, for filtering objects on their contents.
+ALTER TABLE objects
+    ADD COLUMN object_json JSONB;
+ALTER TABLE objects_history
+    ADD COLUMN object_json JSONB;
+CREATE INDEX objects_object_json ON objects USING GIN (object_json jsonb_path_ops);
+
+-- Latest Display template fields of each object type.
+CREATE TABLE display
+(
+    object_type VARCHAR  PRIMARY KEY,
+    id          address  NOT NULL,
+    version     INT      NOT NULL,
+    fields      JSONB    NOT NULL
+);
+
+-- Renders a Display template against the JSON fields of an object, NULL if a field is missing.
+CREATE OR REPLACE FUNCTION render_display(template TEXT, content JSONB) RETURNS TEXT AS
+$body$
+DECLARE
+    rendered TEXT := template;
+    field    TEXT[];
+    value    TEXT;
+BEGIN
+    IF template IS NULL OR content IS NULL THEN
+        RETURN NULL;
+    END IF;
+    FOR field IN SELECT regexp_matches(template, '\{([A-Za-z0-9_.]+)\}', 'g')
+        LOOP
+            value := content #>> string_to_array(field[1], '.');
+            IF value IS NULL THEN
+                RETURN NULL;
+            END IF;
+            rendered := replace(rendered, '{' || field[1] || '}', value);
+        END LOOP;
+    RETURN replace(rendered, '\', '');
+END;
+$body$
+    LANGUAGE plpgsql IMMUTABLE;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS objects_history_start;
+DROP TRIGGER IF EXISTS objects_history ON objects;
+DROP FUNCTION IF EXISTS objects_modified_func();
@@ -0,0 +1,63 @@
+-- Record each version of objects written from checkpoints, to read objects and balances as of a
+-- past checkpoint. Objects that have not changed since this migration only have the version
+-- they are at now in their history.
+CREATE OR REPLACE FUNCTION objects_modified_func() RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF (TG_OP = 'INSERT') THEN
+        INSERT INTO objects_history (epoch, checkpoint, object_id, version, object_digest, owner_type,
+                                     owner_address, old_owner_type, old_owner_address,
+                                     initial_shared_version, previous_transaction, object_type,
+                                     object_status, has_public_transfer, storage_rebate, bcs, object_json)
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address, NULL, NULL,
+                NEW.initial_shared_version, NEW.previous_transaction, NEW.object_type,
+                NEW.object_status, NEW.has_public_transfer, NEW.storage_rebate, NEW.bcs, NEW.object_json)
+        ON CONFLICT DO NOTHING;
+    ELSIF (TG_OP = 'UPDATE') THEN
+        INSERT INTO objects_history (epoch, checkpoint, object_id, version, object_digest, owner_type,
+                                     owner_address, old_owner_type, old_owner_address,
+                                     initial_shared_version, previous_transaction, object_type,
+                                     object_status, has_public_transfer, storage_rebate, bcs, object_json)
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address, OLD.owner_type, OLD.owner_address,
+                NEW.initial_shared_version, NEW.previous_transaction, NEW.object_type,
+                NEW.object_status, NEW.has_public_transfer, NEW.storage_rebate, NEW.bcs, NEW.object_json)
+        -- retried checkpoints write the same versions again
+        ON CONFLICT DO NOTHING;
+    END IF;
+    RETURN NEW;
+END;
+$body$
+    LANGUAGE plpgsql;
+
+-- Fast-path writes, with a checkpoint of -1, are rewritten once their checkpoint is indexed.
+CREATE TRIGGER objects_history
+    AFTER INSERT OR UPDATE
+    ON objects
+    FOR EACH ROW
+    WHEN (NEW.checkpoint >= 0)
+EXECUTE PROCEDURE objects_modified_func();
+
+INSERT INTO objects_history (epoch, checkpoint, object_id, version, object_digest, owner_type,
+                             owner_address, old_owner_type, old_owner_address,
+                             initial_shared_version, previous_transaction, object_type,
+                             object_status, has_public_transfer, storage_rebate, bcs, object_json)
+SELECT epoch, checkpoint, object_id, version, object_digest, owner_type,
+       owner_address, NULL, NULL,
+       initial_shared_version, previous_transaction, object_type,
+       object_status, has_public_transfer, storage_rebate, bcs, object_json
+FROM objects
+WHERE checkpoint >= 0
+ON CONFLICT DO NOTHING;
+
+-- The first checkpoint whose state can be read back from objects_history. Before it, only the
+-- objects which have not changed since are known.
+CREATE TABLE objects_history_start
+(
+    checkpoint BIGINT PRIMARY KEY
+);
+INSERT INTO objects_history_start (checkpoint)
+SELECT COALESCE(MAX(checkpoint), 0)
+FROM objects
+WHERE checkpoint >= 0;
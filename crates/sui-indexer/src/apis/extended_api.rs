@@ -5,16 +5,20 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 
+use move_core_types::language_storage::TypeTag;
 use sui_json_rpc::api::{
     validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
 };
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics, Page,
+    Balance, CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics, Page,
     QueryObjectsPage, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery,
 };
 use sui_open_rpc::Module;
-use sui_types::sui_serde::BigInt;
+use sui_types::base_types::SuiAddress;
+use sui_types::gas_coin::GAS;
+use sui_types::parse_sui_struct_tag;
+use sui_types::sui_serde::{to_sui_type_tag_string, BigInt};
 
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
@@ -81,6 +85,39 @@ impl<S: IndexerStore> ExtendedApi<S> {
             has_next_page,
         })
     }
+
+    async fn get_balance_at_checkpoint_internal(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoint: u64,
+    ) -> Result<Balance, IndexerError> {
+        let latest_checkpoint = self
+            .state
+            .get_latest_object_checkpoint_sequence_number()
+            .await?;
+        if checkpoint as i64 > latest_checkpoint {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Objects are only indexed up to checkpoint {latest_checkpoint}, got {checkpoint}"
+            )));
+        }
+        let history_start = self.state.get_objects_history_start_checkpoint().await?;
+        if (checkpoint as i64) < history_start {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Object history is only available from checkpoint {history_start}, got {checkpoint}"
+            )));
+        }
+        let coin_type = TypeTag::Struct(Box::new(match coin_type {
+            Some(c) => parse_sui_struct_tag(&c)?,
+            None => GAS::type_(),
+        }));
+        let coin_type = to_sui_type_tag_string(&coin_type).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!("Invalid coin type {coin_type}: {e}"))
+        })?;
+        self.state
+            .get_balance_at_checkpoint(owner, coin_type, checkpoint)
+            .await
+    }
 }
 
 #[async_trait]
@@ -120,6 +157,17 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
         Ok(self.query_objects_internal(query, cursor, limit).await?)
     }
 
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoint: BigInt<u64>,
+    ) -> RpcResult<Balance> {
+        Ok(self
+            .get_balance_at_checkpoint_internal(owner, coin_type, *checkpoint)
+            .await?)
+    }
+
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics> {
         Ok(self.state.get_network_metrics().await?)
     }
@@ -3,10 +3,9 @@
 
 use std::sync::Arc;
 
-use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::future::join_all;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::core::{Error as RpcError, RpcResult};
 use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
@@ -19,15 +18,16 @@ use sui_json_rpc::api::{
 use sui_json_rpc::indexer_api::spawn_subscription;
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataFilter,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponseQuery,
-    TransactionBlocksPage, TransactionFilter,
+    CheckpointSubscriptionOptions, DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page,
+    SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::sui_serde::BigInt;
 
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
@@ -224,6 +224,17 @@ impl<S: IndexerStore> IndexerApi<S> {
                     )
                     .await
             }
+            Some(
+                filter @ (TransactionFilter::CheckpointRange { .. }
+                | TransactionFilter::TimeRange { .. }
+                | TransactionFilter::All(_)
+                | TransactionFilter::Any(_)
+                | TransactionFilter::And(_, _)
+                | TransactionFilter::Or(_, _)),
+            ) => Err(IndexerError::NotSupportedError(format!(
+                "Transaction filter {:?} is not supported by the indexer",
+                filter
+            ))),
         }?;
 
         let has_next_page = tx_vec_from_db.len() > limit;
@@ -268,20 +279,14 @@ impl<S: IndexerStore> IndexerApi<S> {
         limit: Option<usize>,
     ) -> RpcResult<ObjectsPage> {
         let address = SuiObjectDataFilter::AddressOwner(address);
-        // MUSTFIX(gegaowp): implement other filters beside address owner filter.
         let (filter, options) = match query {
             Some(SuiObjectResponseQuery {
                 filter: Some(filter),
                 options,
-            }) => match filter {
-                SuiObjectDataFilter::AddressOwner(_) => Ok((address, options)),
-                _ => Err(anyhow!(
-                    "Only address filter is supported on indexer for now."
-                )),
-            },
-            Some(SuiObjectResponseQuery { filter: _, options }) => Ok((address, options)),
-            None => Ok((address, None)),
-        }?;
+            }) => (address.and(filter), options),
+            Some(SuiObjectResponseQuery { filter: _, options }) => (address, options),
+            None => (address, None),
+        };
         let options = options.unwrap_or_default();
         let limit = validate_limit(limit, *QUERY_MAX_RESULT_LIMIT)?;
 
@@ -322,9 +327,15 @@ where
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<ObjectsPage> {
-        if !self
-            .migrated_methods
-            .contains(&"get_owned_objects".to_string())
+        // Only the indexer can evaluate filters on object contents
+        let has_content_filter = query
+            .as_ref()
+            .and_then(|q| q.filter.as_ref())
+            .map_or(false, |f| f.has_content_filter());
+        if !has_content_filter
+            && !self
+                .migrated_methods
+                .contains(&"get_owned_objects".to_string())
         {
             let owned_obj_guard = self
                 .state
@@ -395,6 +406,26 @@ where
             .await?)
     }
 
+    async fn get_child_objects(
+        &self,
+        parent_object_id: ObjectID,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let child_objects_guard = self
+            .state
+            .indexer_metrics()
+            .get_child_objects_latency
+            .start_timer();
+        let child_objects_resp = self
+            .fullnode
+            .get_child_objects(parent_object_id, query, cursor, limit)
+            .await;
+        child_objects_guard.stop_and_record();
+        child_objects_resp
+    }
+
     async fn get_dynamic_fields(
         &self,
         parent_object_id: ObjectID,
@@ -449,6 +480,18 @@ where
         Ok(())
     }
 
+    fn subscribe_checkpoint(
+        &self,
+        mut sink: SubscriptionSink,
+        _cursor: Option<BigInt<u64>>,
+        _options: Option<CheckpointSubscriptionOptions>,
+    ) -> SubscriptionResult {
+        sink.reject(RpcError::from(IndexerError::NotSupportedError(
+            "Checkpoint subscription is only supported by fullnodes".to_string(),
+        )))?;
+        Ok(())
+    }
+
     async fn resolve_name_service_address(&self, _name: String) -> RpcResult<Option<SuiAddress>> {
         // TODO(gegaowp): implement name service resolver in indexer
         todo!()
@@ -35,6 +35,7 @@ use crate::errors::IndexerError;
 use crate::metrics::IndexerMetrics;
 use crate::models::addresses::{dedup_from_addresses, dedup_from_and_to_addresses};
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
 use crate::models::epoch::{DBEpochInfo, SystemEpochInfoEvent};
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
@@ -434,6 +435,7 @@ where
                     addresses,
                     active_addresses,
                     packages: _,
+                    displays: _,
                     input_objects: _,
                     move_calls: _,
                     recipients: _,
@@ -538,6 +540,7 @@ where
                     addresses: _,
                     active_addresses: _,
                     packages,
+                    displays,
                     input_objects,
                     move_calls,
                     recipients,
@@ -590,6 +593,20 @@ where
                     }
                 });
 
+                // NOTE: commit displays in the current task so that later versions overwrite earlier ones.
+                let mut display_commit_res = self.state.persist_displays(&displays).await;
+                while let Err(e) = display_commit_res {
+                    warn!(
+                        "Indexer display commit failed with error: {:?}, retrying after {:?} milli-secs...",
+                        e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+                    );
+                    tokio::time::sleep(std::time::Duration::from_millis(
+                        DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+                    ))
+                    .await;
+                    display_commit_res = self.state.persist_displays(&displays).await;
+                }
+
                 // NOTE: commit object changes in the current task to stick to the original order.
                 let object_db_guard = self.metrics.object_db_commit_latency.start_timer();
                 let mut object_changes_commit_res = self
@@ -829,6 +846,15 @@ where
         // Index packages
         let packages = Self::index_packages(transactions, changed_objects)?;
 
+        // Index displays, keeping the latest version of each object type's display in the checkpoint
+        let mut displays = BTreeMap::new();
+        for event in transactions.iter().flat_map(|tx| tx.events.data.iter()) {
+            if let Some(display) = StoredDisplay::try_from_event(event)? {
+                displays.insert(display.object_type.clone(), display);
+            }
+        }
+        let displays = displays.into_values().collect();
+
         // Store input objects, move calls and recipients separately for transaction query indexing.
         let input_objects = transactions
             .iter()
@@ -1020,6 +1046,7 @@ where
                 addresses,
                 active_addresses,
                 packages,
+                displays,
                 input_objects,
                 move_calls,
                 recipients,
@@ -1107,7 +1134,8 @@ pub async fn fetch_changed_objects(
         http_client
             .try_multi_get_past_objects(
                 wanted_past_object_request,
-                Some(SuiObjectDataOptions::bcs_lossless()),
+                // NOTE: content is needed for filtering objects on their fields.
+                Some(SuiObjectDataOptions::bcs_lossless().with_content()),
             )
             .map(move |resp| (resp, wanted_past_object_statuses))
     }))
@@ -28,7 +28,7 @@ use regex::Regex;
 use rustls::client::{ServerCertVerified, ServerCertVerifier};
 use rustls::{Certificate, Error, ServerName};
 use tokio::runtime::Handle;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 use url::Url;
 
 use apis::{
@@ -194,6 +194,17 @@ impl Indexer {
         );
         let event_handler = Arc::new(SubscriptionHandler::default());
 
+        if config.fullnode_sync_worker {
+            // Objects indexed before their contents were stored can't be filtered on them until
+            // this is done, run it in the background so that indexing is not held up.
+            let backfill_store = store.clone();
+            spawn_monitored_task!(async move {
+                if let Err(e) = backfill_store.backfill_object_json().await {
+                    error!("Failed to backfill object_json: {:?}", e);
+                }
+            });
+        }
+
         if config.rpc_server_worker && config.fullnode_sync_worker {
             info!("Starting indexer with both fullnode sync and RPC server");
             let handle = build_json_rpc_server(
@@ -58,6 +58,7 @@ pub struct IndexerMetrics {
     pub query_transaction_blocks_latency: Histogram,
     pub query_events_latency: Histogram,
     pub get_dynamic_fields_latency: Histogram,
+    pub get_child_objects_latency: Histogram,
     pub get_dynamic_field_object_latency: Histogram,
     pub get_protocol_config_latency: Histogram,
 }
@@ -308,6 +309,13 @@ impl IndexerMetrics {
                 registry
             )
             .unwrap(),
+            get_child_objects_latency: register_histogram_with_registry!(
+                "get_child_objects_latency",
+                "Time spent in get_child_objects on the fullnode behind.",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
             get_dynamic_field_object_latency: register_histogram_with_registry!(
                 "get_dynamic_field_object_latency",
                 "Time spent in get_dynamic_field_object on the fullnode behind.",
@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use serde_json::{Map, Value};
+
+use sui_json_rpc_types::SuiEvent;
+use sui_types::display::{
+    DisplayVersionUpdatedEvent, DISPLAY_MODULE_NAME, DISPLAY_VERSION_UPDATED_EVENT_NAME,
+};
+use sui_types::sui_serde::to_sui_type_tag_string;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::errors::IndexerError;
+use crate::schema::display;
+
+/// The latest `Display` template fields of an object type, used to filter objects on the
+/// rendered values of their `Display`.
+#[derive(Queryable, Insertable, Debug, Identifiable, Clone)]
+#[diesel(table_name = display, primary_key(object_type))]
+pub struct StoredDisplay {
+    pub object_type: String,
+    pub id: String,
+    pub version: i32,
+    pub fields: Value,
+}
+
+impl StoredDisplay {
+    /// Returns the display of a `0x2::display::VersionUpdated<T>` event, `None` for other events.
+    pub fn try_from_event(event: &SuiEvent) -> Result<Option<Self>, IndexerError> {
+        let type_ = &event.type_;
+        if type_.address != SUI_FRAMEWORK_ADDRESS
+            || type_.module.as_ident_str() != DISPLAY_MODULE_NAME
+            || type_.name.as_ident_str() != DISPLAY_VERSION_UPDATED_EVENT_NAME
+        {
+            return Ok(None);
+        }
+        let [object_type] = &type_.type_params[..] else {
+            return Ok(None);
+        };
+        let display: DisplayVersionUpdatedEvent = bcs::from_bytes(&event.bcs).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to deserialize display version updated event: {:?}",
+                e
+            ))
+        })?;
+        let object_type = to_sui_type_tag_string(object_type).map_err(|e| {
+            IndexerError::SerdeError(format!("Failed to format display object type: {:?}", e))
+        })?;
+        let fields = display
+            .fields
+            .contents
+            .into_iter()
+            .map(|entry| (entry.key, Value::String(entry.value)))
+            .collect::<Map<_, _>>();
+
+        Ok(Some(Self {
+            object_type,
+            id: display.id.object_id().to_string(),
+            version: display.version.into(),
+            fields: Value::Object(fields),
+        }))
+    }
+}
@@ -3,6 +3,7 @@
 
 pub mod addresses;
 pub mod checkpoints;
+pub mod display;
 pub mod epoch;
 pub mod events;
 pub mod network_metrics;
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 
 use move_bytecode_utils::module_cache::GetModule;
-use sui_json_rpc_types::{SuiObjectData, SuiObjectRef, SuiRawData};
+use sui_json_rpc_types::{SuiObjectData, SuiObjectRef, SuiParsedData, SuiRawData};
 use sui_types::base_types::{ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::move_package::MovePackage;
@@ -52,6 +52,8 @@ pub struct Object {
     pub has_public_transfer: bool,
     pub storage_rebate: i64,
     pub bcs: Vec<NamedBcsBytes>,
+    // Move struct fields of the object as JSON, for filtering on object contents.
+    pub object_json: Option<serde_json::Value>,
 }
 #[derive(SqlType, Debug, Clone)]
 #[diesel(sql_type = crate::schema::sql_types::BcsBytes)]
@@ -104,6 +106,7 @@ impl From<DeletedObject> for Object {
             has_public_transfer: o.has_public_transfer,
             storage_rebate: 0,
             bcs: vec![],
+            object_json: None,
         }
     }
 }
@@ -145,6 +148,11 @@ impl Object {
                 ),
             };
 
+        let object_json = match o.content.clone() {
+            Some(SuiParsedData::MoveObject(o)) => Some(o.fields.to_json_value()),
+            _ => None,
+        };
+
         Object {
             epoch: epoch as i64,
             // NOTE: -1 as temp checkpoint for object updates from fast path,
@@ -168,6 +176,7 @@ impl Object {
             has_public_transfer,
             storage_rebate: o.storage_rebate.unwrap_or_default() as i64,
             bcs,
+            object_json,
         }
     }
 
@@ -188,6 +197,22 @@ impl Object {
         })
     }
 
+    /// Decodes the Move struct fields of the object from its BCS, for rows indexed before
+    /// `object_json` was populated. `None` for packages.
+    pub fn decode_object_json(
+        self,
+        module_cache: &impl GetModule,
+    ) -> Result<Option<serde_json::Value>, IndexerError> {
+        let object_read = self.try_into_object_read(module_cache)?;
+        let content = SuiParsedData::try_from_object_read(object_read).map_err(|e| {
+            IndexerError::SerdeError(format!("Failed to decode object contents: {:?}", e))
+        })?;
+        Ok(match content {
+            SuiParsedData::MoveObject(o) => Some(o.fields.to_json_value()),
+            SuiParsedData::Package(_) => None,
+        })
+    }
+
     pub fn get_object_ref(&self) -> Result<ObjectRef, IndexerError> {
         let object_id = self.object_id.parse()?;
         let digest = self.object_digest.parse().map_err(|e| {
@@ -342,7 +367,8 @@ pub fn compose_object_bulk_insert_update_query(objects: &[Object]) -> String {
             object_status = EXCLUDED.object_status,
             has_public_transfer = EXCLUDED.has_public_transfer,
             storage_rebate = EXCLUDED.storage_rebate,
-            bcs = EXCLUDED.bcs;",
+            bcs = EXCLUDED.bcs,
+            object_json = EXCLUDED.object_json;",
         insert_query
     );
     insert_update_query
@@ -381,6 +407,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
                 obj.has_public_transfer,
                 obj.storage_rebate,
                 bcs_rows,
+                obj.object_json.as_ref().map(|json| json.to_string()),
             )
         })
         .collect::<Vec<_>>();
@@ -388,7 +415,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
     let rows_query = rows
         .iter()
         .map(|row| {
-            let (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs_rows) = row;
+            let (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs_rows, object_json) = row;
 
             let bcs_rows_query = bcs_rows
                 .iter()
@@ -406,7 +433,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
             format!(
                 "ROW({}::BIGINT, {}::BIGINT, '{}'::address, {}::BIGINT, '{}'::base58digest, '{}'::owner_type, 
                      '{}'::address, {}::BIGINT, '{}'::base58digest, '{}'::VARCHAR, '{}'::object_status,
-                     {}::BOOLEAN, {}::BIGINT, ARRAY[{}]::bcs_bytes[])",
+                     {}::BOOLEAN, {}::BIGINT, ARRAY[{}]::bcs_bytes[], {}::JSONB)",
                 epoch,
                 checkpoint,
                 object_id,
@@ -427,6 +454,10 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
                 has_public_transfer,
                 storage_rebate,
                 bcs_rows_query,
+                object_json
+                    .as_ref()
+                    .map(|json| format!("'{}'", json.replace('\'', "''")))
+                    .unwrap_or_else(|| "NULL".to_string()),
             )
         })
         .collect::<Vec<_>>()
@@ -435,10 +466,10 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
     // Construct a prepared statement with placeholders for each row element
     let bulk_insert_query = format!(
         "INSERT INTO objects
-            (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs)
+            (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs, object_json)
         SELECT (unnest_arr).*
         FROM unnest(ARRAY[{}]::record[]) 
-        AS unnest_arr(epoch BIGINT, checkpoint BIGINT, object_id address, version BIGINT, object_digest base58digest, owner_type owner_type, owner_address address, initial_shared_version BIGINT, previous_transaction base58digest, object_type VARCHAR, object_status object_status, has_public_transfer BOOLEAN, storage_rebate BIGINT, bcs bcs_bytes[]);",
+        AS unnest_arr(epoch BIGINT, checkpoint BIGINT, object_id address, version BIGINT, object_digest base58digest, owner_type owner_type, owner_address address, initial_shared_version BIGINT, previous_transaction base58digest, object_type VARCHAR, object_status object_status, has_public_transfer BOOLEAN, storage_rebate BIGINT, bcs bcs_bytes[], object_json JSONB);",
         rows_query
     );
     bulk_insert_query
@@ -93,6 +93,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    display (object_type) {
+        object_type -> Varchar,
+        id -> Varchar,
+        version -> Int4,
+        fields -> Jsonb,
+    }
+}
+
 diesel::table! {
     events (id) {
         id -> Int8,
@@ -152,6 +161,7 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        object_json -> Nullable<Jsonb>,
     }
 }
 
@@ -178,6 +188,13 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        object_json -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    objects_history_start (checkpoint) {
+        checkpoint -> Int8,
     }
 }
 
@@ -315,12 +332,14 @@ diesel::allow_tables_to_appear_in_same_query!(
     addresses,
     at_risk_validators,
     checkpoints,
+    display,
     epochs,
     events,
     input_objects,
     move_calls,
     objects,
     objects_history,
+    objects_history_start,
     packages,
     recipients,
     system_states,
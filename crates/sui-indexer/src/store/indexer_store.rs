@@ -6,9 +6,9 @@ use prometheus::Histogram;
 
 use move_core_types::identifier::Identifier;
 use sui_json_rpc_types::{
-    Checkpoint as RpcCheckpoint, CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics,
-    NetworkMetrics, SuiObjectData, SuiObjectDataFilter, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    Balance, Checkpoint as RpcCheckpoint, CheckpointId, EpochInfo, EventFilter, EventPage,
+    MoveCallMetrics, NetworkMetrics, SuiObjectData, SuiObjectDataFilter,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress, VersionNumber};
 use sui_types::digests::CheckpointDigest;
@@ -22,6 +22,7 @@ use crate::errors::IndexerError;
 use crate::metrics::IndexerMetrics;
 use crate::models::addresses::{ActiveAddress, Address};
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
@@ -73,6 +74,18 @@ pub trait IndexerStore {
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError>;
 
+    /// Returns the first checkpoint which the state of objects can be read at from their history.
+    /// Objects indexed before their history was recorded only have the version they were at then.
+    async fn get_objects_history_start_checkpoint(&self) -> Result<i64, IndexerError>;
+
+    /// Returns the balance of coins of `coin_type` owned by `owner` at the end of `checkpoint`.
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: String,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Balance, IndexerError>;
+
     async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError>;
 
     // TODO: combine all get_transaction* methods
@@ -213,6 +226,10 @@ pub trait IndexerStore {
         active_addresses: &[ActiveAddress],
     ) -> Result<(), IndexerError>;
     async fn persist_packages(&self, packages: &[Package]) -> Result<(), IndexerError>;
+    async fn persist_displays(&self, displays: &[StoredDisplay]) -> Result<(), IndexerError>;
+    // NOTE: fills in `object_json` of objects indexed before it was populated, can be resumed
+    // after an interruption since only rows without it are read.
+    async fn backfill_object_json(&self) -> Result<(), IndexerError>;
     // NOTE: these tables are for tx query performance optimization
     async fn persist_transaction_index_tables(
         &self,
@@ -288,6 +305,7 @@ pub struct TemporaryCheckpointStore {
     pub addresses: Vec<Address>,
     pub active_addresses: Vec<ActiveAddress>,
     pub packages: Vec<Package>,
+    pub displays: Vec<StoredDisplay>,
     pub input_objects: Vec<InputObject>,
     pub move_calls: Vec<MoveCall>,
     pub recipients: Vec<Recipient>,
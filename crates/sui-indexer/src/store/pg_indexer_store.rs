@@ -11,7 +11,7 @@ use cached::proc_macro::once;
 use diesel::dsl::max;
 use diesel::pg::PgConnection;
 use diesel::query_builder::AsQuery;
-use diesel::sql_types::{BigInt, VarChar};
+use diesel::sql_types::{BigInt, Jsonb, VarChar};
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgArrayExpressionMethods};
 use diesel::{OptionalExtension, QueryableByName};
@@ -21,11 +21,11 @@ use fastcrypto::traits::ToFromBytes;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_core_types::identifier::Identifier;
 use prometheus::Histogram;
-use tracing::info;
+use tracing::{info, warn};
 
 use sui_json_rpc::{ObjectProvider, ObjectProviderCache};
 use sui_json_rpc_types::{
-    CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics, MoveFunctionName,
+    Balance, CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics, MoveFunctionName,
     NetworkMetrics, SuiEvent, SuiObjectDataFilter,
 };
 use sui_json_rpc_types::{
@@ -47,6 +47,7 @@ use crate::errors::{Context, IndexerError};
 use crate::metrics::IndexerMetrics;
 use crate::models::addresses::{ActiveAddress, Address};
 use crate::models::checkpoints::Checkpoint;
+use crate::models::display::StoredDisplay;
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
 use crate::models::network_metrics::{DBMoveCallMetrics, DBNetworkMetrics};
@@ -58,11 +59,12 @@ use crate::models::system_state::DBValidatorSummary;
 use crate::models::transaction_index::{InputObject, MoveCall, Recipient};
 use crate::models::transactions::Transaction;
 use crate::schema::{
-    active_addresses, addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, epochs,
+    active_addresses, addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, display, epochs,
     epochs::dsl as epochs_dsl, events, input_objects, input_objects::dsl as input_objects_dsl,
     move_calls, move_calls::dsl as move_calls_dsl, objects, objects::dsl as objects_dsl,
-    objects_history, packages, recipients, recipients::dsl as recipients_dsl, system_states,
-    transactions, transactions::dsl as transactions_dsl, validators,
+    objects_history, objects_history_start, packages, recipients,
+    recipients::dsl as recipients_dsl, system_states, transactions,
+    transactions::dsl as transactions_dsl, validators,
 };
 use crate::store::diesel_marco::{read_only_blocking, transactional_blocking};
 use crate::store::indexer_store::TemporaryCheckpointStore;
@@ -75,6 +77,8 @@ use crate::{AsyncPgConnectionPool, PgConnectionPool};
 
 const MAX_EVENT_PAGE_SIZE: usize = 1000;
 const PG_COMMIT_CHUNK_SIZE: usize = 1000;
+const OBJECT_JSON_BACKFILL_BATCH_SIZE: usize = 1000;
+const OBJECT_COLUMNS: &str = "epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs, object_json";
 
 const GET_PARTITION_SQL: &str = r#"
 SELECT parent.relname                           AS table_name,
@@ -146,6 +150,7 @@ impl PgIndexerStore {
                     objects_history::has_public_transfer,
                     objects_history::storage_rebate,
                     objects_history::bcs,
+                    objects_history::object_json,
                 ))
                 .filter(objects_history::object_id.eq(object_id.to_string()))
                 .filter(objects_history::version.eq(version.value() as i64))
@@ -185,6 +190,7 @@ impl PgIndexerStore {
                     objects_history::has_public_transfer,
                     objects_history::storage_rebate,
                     objects_history::bcs,
+                    objects_history::object_json,
                 ))
                 .filter(objects_history::object_id.eq(id.to_string()))
                 .filter(objects_history::version.le(version.value() as i64))
@@ -603,6 +609,7 @@ impl IndexerStore for PgIndexerStore {
                         objects_history::has_public_transfer,
                         objects_history::storage_rebate,
                         objects_history::bcs,
+                        objects_history::object_json,
                     ))
                     .filter(objects_history::object_id.eq(object_id.to_string()))
                     .filter(objects_history::version.eq(version.value() as i64))
@@ -646,6 +653,7 @@ impl IndexerStore for PgIndexerStore {
                 "has_public_transfer",
                 "storage_rebate",
                 "bcs",
+                "object_json",
             ];
             diesel::sql_query(filter.to_objects_history_sql(cursor, limit, columns))
                 .bind::<BigInt, _>(at_checkpoint as i64)
@@ -658,6 +666,70 @@ impl IndexerStore for PgIndexerStore {
             .collect()
     }
 
+    async fn get_objects_history_start_checkpoint(&self) -> Result<i64, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            objects_history_start::table
+                .select(objects_history_start::checkpoint)
+                .first::<i64>(conn)
+        })
+        .context("Failed reading the first checkpoint of objects history from PostgresDB")
+    }
+
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: String,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Balance, IndexerError> {
+        // The latest version at the checkpoint of every coin the owner ever had until then.
+        let sql = format!(
+            "SELECT {OBJECT_COLUMNS}
+FROM (SELECT DISTINCT ON (o.object_id) o.*
+      FROM objects_history o
+      WHERE o.checkpoint BETWEEN 0 AND $1
+      AND o.object_id IN (SELECT h.object_id
+                          FROM objects_history h
+                          WHERE h.checkpoint BETWEEN 0 AND $1
+                          AND h.owner_type = 'address_owner'
+                          AND h.owner_address = $2
+                          AND h.object_type = $3)
+      ORDER BY o.object_id, o.version DESC, o.checkpoint DESC) AS t1
+WHERE t1.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted')
+AND t1.owner_type = 'address_owner'
+AND t1.owner_address = $2;"
+        );
+        let object_type = format!("0x2::coin::Coin<{coin_type}>");
+        let coins: Vec<Object> =
+            read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(&sql)
+                .bind::<BigInt, _>(checkpoint as i64)
+                .bind::<VarChar, _>(owner.to_string())
+                .bind::<VarChar, _>(object_type)
+                .load(conn))
+            .context(&format!(
+                "Failed reading balance of {owner} for {coin_type} at checkpoint {checkpoint}"
+            ))?;
+
+        // Balances are read from the BCS of the coins, `object_json` is not populated for objects
+        // indexed before it was added.
+        let coin_object_count = coins.len();
+        let mut total_balance = 0u128;
+        for coin in coins {
+            let object_id = coin.object_id.clone();
+            let object: sui_types::object::Object = coin.try_into()?;
+            let coin = object.as_coin_maybe().ok_or_else(|| {
+                IndexerError::SerdeError(format!("Failed to decode coin {object_id}"))
+            })?;
+            total_balance += coin.value() as u128;
+        }
+
+        Ok(Balance {
+            coin_type,
+            coin_object_count,
+            total_balance,
+            locked_balance: Default::default(),
+        })
+    }
+
     // NOTE(gegaowp): now only supports query by address owner
     async fn query_latest_objects(
         &self,
@@ -680,12 +752,12 @@ impl IndexerStore for PgIndexerStore {
             "has_public_transfer",
             "storage_rebate",
             "bcs",
+            "object_json",
         ];
 
-        let objects = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(
-            filter.to_latest_objects_sql(cursor, limit, columns)
-        )
-        .get_results::<Object>(conn))?;
+        let query = filter.to_latest_objects_sql(cursor, limit, columns)?;
+        let objects = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(query)
+            .get_results::<Object>(conn))?;
 
         objects
             .into_iter()
@@ -1151,6 +1223,7 @@ impl IndexerStore for PgIndexerStore {
             addresses,
             active_addresses,
             packages,
+            displays,
             input_objects,
             move_calls,
             recipients,
@@ -1254,6 +1327,9 @@ impl IndexerStore for PgIndexerStore {
                     .context("Failed writing packages to PostgresDB")?;
             }
 
+            // Commit indexed displays
+            persist_displays(conn, displays)?;
+
             // Commit indexed move calls
             for move_calls_chunk in move_calls.chunks(PG_COMMIT_CHUNK_SIZE) {
                 diesel::insert_into(move_calls::table)
@@ -1464,6 +1540,79 @@ WHERE e1.epoch = e2.epoch
         Ok(())
     }
 
+    async fn persist_displays(&self, displays: &[StoredDisplay]) -> Result<(), IndexerError> {
+        transactional_blocking!(&self.blocking_cp, |conn| persist_displays(conn, displays))?;
+        Ok(())
+    }
+
+    async fn backfill_object_json(&self) -> Result<(), IndexerError> {
+        for table in ["objects", "objects_history"] {
+            let mut cursor = None;
+            let mut backfilled = 0;
+            loop {
+                let cursor_clause = match &cursor {
+                    Some((object_id, version)) => {
+                        format!("\nAND (object_id, version) > ('{object_id}', {version})")
+                    }
+                    None => "".to_string(),
+                };
+                let sql = format!(
+                    "SELECT {OBJECT_COLUMNS}
+FROM {table}
+WHERE object_json IS NULL
+AND object_type <> 'package'
+AND object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted'){cursor_clause}
+ORDER BY object_id, version
+LIMIT {OBJECT_JSON_BACKFILL_BATCH_SIZE};"
+                );
+                let objects: Vec<Object> = read_only_blocking!(&self.blocking_cp, |conn| {
+                    diesel::sql_query(&sql).load(conn)
+                })
+                .context(&format!(
+                    "Failed reading {table} to backfill from PostgresDB"
+                ))?;
+                let Some(last) = objects.last() else {
+                    break;
+                };
+                cursor = Some((last.object_id.clone(), last.version));
+
+                let mut updates = vec![];
+                for object in objects {
+                    let (object_id, version) = (object.object_id.clone(), object.version);
+                    // Rows which can't be decoded are left empty, and only skipped by this run
+                    match object.decode_object_json(&self.module_cache) {
+                        Ok(Some(object_json)) => updates.push((object_id, version, object_json)),
+                        Ok(None) => {}
+                        Err(e) => warn!(
+                            "Failed to backfill object_json of object {} version {} in {}: {:?}",
+                            object_id, version, table, e
+                        ),
+                    }
+                }
+                transactional_blocking!(&self.blocking_cp, |conn| {
+                    for (object_id, version, object_json) in &updates {
+                        diesel::sql_query(format!(
+                            "UPDATE {table} SET object_json = $1
+WHERE object_id = '{object_id}' AND version = {version} AND object_json IS NULL;"
+                        ))
+                        .bind::<Jsonb, _>(object_json)
+                        .execute(conn)?;
+                    }
+                    Ok::<(), diesel::result::Error>(())
+                })
+                .context(&format!(
+                    "Failed backfilling object_json of {table} to PostgresDB"
+                ))?;
+                backfilled += updates.len();
+                info!(
+                    "Backfilled object_json of {} rows of {}, up to object {:?}",
+                    backfilled, table, cursor
+                );
+            }
+        }
+        Ok(())
+    }
+
     async fn persist_transaction_index_tables(
         &self,
         input_objects: &[InputObject],
@@ -1766,6 +1915,27 @@ fn persist_transaction_object_changes(
     Ok(0)
 }
 
+fn persist_displays(
+    conn: &mut PgConnection,
+    displays: &[StoredDisplay],
+) -> Result<(), IndexerError> {
+    for displays_chunk in displays.chunks(PG_COMMIT_CHUNK_SIZE) {
+        diesel::insert_into(display::table)
+            .values(displays_chunk)
+            .on_conflict(display::object_type)
+            .do_update()
+            .set((
+                display::id.eq(excluded(display::id)),
+                display::version.eq(excluded(display::version)),
+                display::fields.eq(excluded(display::fields)),
+            ))
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing displays to PostgresDB")?;
+    }
+    Ok(())
+}
+
 #[derive(Clone)]
 struct PartitionManager {
     cp: PgConnectionPool,
@@ -1,13 +1,23 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use serde_json::{Map, Value};
+
 use sui_json_rpc_types::SuiObjectDataFilter;
 use sui_types::base_types::ObjectID;
+use sui_types::sui_serde::to_sui_type_tag_string;
+
+use crate::errors::IndexerError;
 
 pub trait DBFilter<C> {
     fn to_objects_history_sql(&self, cursor: Option<C>, limit: usize, columns: Vec<&str>)
         -> String;
-    fn to_latest_objects_sql(&self, cursor: Option<C>, limit: usize, columns: Vec<&str>) -> String;
+    fn to_latest_objects_sql(
+        &self,
+        cursor: Option<C>,
+        limit: usize,
+        columns: Vec<&str>,
+    ) -> Result<String, IndexerError>;
 }
 
 impl DBFilter<ObjectID> for SuiObjectDataFilter {
@@ -58,7 +68,7 @@ LIMIT {limit};"
         cursor: Option<ObjectID>,
         limit: usize,
         columns: Vec<&str>,
-    ) -> String {
+    ) -> Result<String, IndexerError> {
         let columns = columns
             .iter()
             .map(|c| format!("o.{c}"))
@@ -71,27 +81,86 @@ LIMIT {limit};"
             "".to_string()
         };
 
-        let inner_clauses = to_latest_objects_clauses(self);
-        let inner_clauses = if let Some(inner_clauses) = inner_clauses {
-            format!(" AND {inner_clauses}")
-        } else {
+        let inner_clauses = to_latest_objects_clauses(self).ok_or_else(|| {
+            IndexerError::NotSupportedError(format!(
+                "Object filter {self:?} is not supported on latest objects"
+            ))
+        })?;
+        let inner_clauses = if inner_clauses == "TRUE" {
             "".to_string()
+        } else {
+            format!(" AND {inner_clauses}")
         };
 
-        format!(
+        Ok(format!(
             "SELECT {columns}
 FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted'){cursor}{inner_clauses}
 LIMIT {limit};"
-        )
+        ))
     }
 }
 
+/// `None` if the filter, or any of its sub-filters, cannot be expressed on the objects table.
 fn to_latest_objects_clauses(filter: &SuiObjectDataFilter) -> Option<String> {
     match filter {
+        SuiObjectDataFilter::MatchAll(sub_filters) => {
+            let sub_filters = sub_filters
+                .iter()
+                .map(to_latest_objects_clauses)
+                .collect::<Option<Vec<_>>>()?;
+            if sub_filters.is_empty() {
+                Some("TRUE".to_string())
+            } else if sub_filters.len() == 1 {
+                Some(sub_filters[0].to_string())
+            } else {
+                Some(format!("({})", sub_filters.join(" AND ")))
+            }
+        }
+        SuiObjectDataFilter::MatchAny(sub_filters) => {
+            let sub_filters = sub_filters
+                .iter()
+                .map(to_latest_objects_clauses)
+                .collect::<Option<Vec<_>>>()?;
+            if sub_filters.is_empty() {
+                // Any default to false
+                Some("FALSE".to_string())
+            } else if sub_filters.len() == 1 {
+                Some(sub_filters[0].to_string())
+            } else {
+                Some(format!("({})", sub_filters.join(" OR ")))
+            }
+        }
+        SuiObjectDataFilter::MatchNone(sub_filters) => {
+            let sub_filters = sub_filters
+                .iter()
+                .map(to_latest_objects_clauses)
+                .collect::<Option<Vec<_>>>()?;
+            if sub_filters.is_empty() {
+                Some("TRUE".to_string())
+            } else {
+                Some(format!("NOT ({})", sub_filters.join(" OR ")))
+            }
+        }
+        SuiObjectDataFilter::ObjectIds(ids) => {
+            if ids.is_empty() {
+                Some("FALSE".to_string())
+            } else {
+                let ids = ids
+                    .iter()
+                    .map(|o| format!("'{o}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("o.object_id IN ({ids})"))
+            }
+        }
         SuiObjectDataFilter::AddressOwner(a) => Some(format!(
             "(o.owner_type = 'address_owner' AND o.owner_address = '{a}')"
         )),
-        _ => None,
+        SuiObjectDataFilter::ObjectOwner(o) => Some(format!(
+            "(o.owner_type = 'object_owner' AND o.owner_address = '{o}')"
+        )),
+        // the objects table has no previous owner, other filters read the same on both tables
+        _ => to_clauses(filter),
     }
 }
 
@@ -163,9 +232,91 @@ fn to_clauses(filter: &SuiObjectDataFilter) -> Option<String> {
             }
         }
         SuiObjectDataFilter::Version(v) => Some(format!("o.version = {v}")),
+        SuiObjectDataFilter::MoveFieldEquals { path, value } => {
+            let Some(path) = parse_field_path(path) else {
+                return Some("FALSE".to_string());
+            };
+            // Integers up to u32 are rendered as JSON numbers and larger ones as JSON strings,
+            // so an integer value matches either form.
+            let integer = match value {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.parse::<u64>().ok(),
+                _ => None,
+            };
+            let contains = |value: Value| {
+                let json = path.iter().rev().fold(value, |json, field| {
+                    Value::Object(Map::from_iter([(field.to_string(), json)]))
+                });
+                format!("o.object_json @> {}::jsonb", quote(&json.to_string()))
+            };
+            Some(match integer {
+                Some(n) => format!(
+                    "({} OR {})",
+                    contains(Value::from(n)),
+                    contains(Value::from(n.to_string()))
+                ),
+                None => contains(value.clone()),
+            })
+        }
+        SuiObjectDataFilter::MoveFieldRange { path, min, max } => {
+            let Some(path) = parse_field_path(path) else {
+                return Some("FALSE".to_string());
+            };
+            let field = format!("o.object_json #>> '{{{}}}'", path.join(","));
+            let value = format!("(CASE WHEN {field} ~ '^[0-9]+$' THEN ({field})::NUMERIC END)");
+            let mut bounds = vec![];
+            if let Some(min) = min {
+                bounds.push(format!("{value} >= {min}"));
+            }
+            if let Some(max) = max {
+                bounds.push(format!("{value} <= {max}"));
+            }
+            if bounds.is_empty() {
+                bounds.push(format!("{value} IS NOT NULL"));
+            }
+            Some(format!("({})", bounds.join(" AND ")))
+        }
+        SuiObjectDataFilter::DisplayField { name, value } => Some(format!(
+            "EXISTS (SELECT 1 FROM display d WHERE d.object_type = o.object_type AND render_display(d.fields ->> {}, o.object_json) = {})",
+            quote(name),
+            quote(value)
+        )),
+        SuiObjectDataFilter::HasDynamicField(type_) => {
+            let Ok(type_) = to_sui_type_tag_string(type_) else {
+                return Some("FALSE".to_string());
+            };
+            // Dynamic fields are `Field<Name, Value>` objects owned by their parent, dynamic object
+            // fields are owned by a `Field<Wrapper<Name>, ID>` object owned by their parent.
+            Some(format!(
+                "EXISTS (SELECT 1 FROM objects f WHERE f.owner_type = 'object_owner' AND f.owner_address = o.object_id AND f.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND (f.object_type LIKE {} OR EXISTS (SELECT 1 FROM objects v WHERE v.owner_type = 'object_owner' AND v.owner_address = f.object_id AND v.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND v.object_type = {})))",
+                quote(&format!("0x2::dynamic\\_field::Field<%, {}>", escape_like(&type_))),
+                quote(&type_)
+            ))
+        }
     }
 }
 
+/// Splits a `.` separated path of Move fields, `None` if it is not made of plain identifiers
+/// and indices, which are safe to inline in a Postgres text array.
+fn parse_field_path(path: &str) -> Option<Vec<&str>> {
+    let path = path.split('.').collect::<Vec<_>>();
+    path.iter()
+        .all(|field| {
+            !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .then_some(path)
+}
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 fn to_outer_clauses(filter: &SuiObjectDataFilter) -> Option<String> {
     match filter {
         SuiObjectDataFilter::MatchNone(sub_filters) => {
@@ -215,6 +366,7 @@ mod test {
     use std::str::FromStr;
 
     use move_core_types::ident_str;
+    use serde_json::json;
 
     use sui_json_rpc_types::SuiObjectDataFilter;
     use sui_types::base_types::{ObjectID, SuiAddress};
@@ -326,4 +478,95 @@ LIMIT 100;";
             filter.to_objects_history_sql(None, 100, vec!["*"])
         );
     }
+
+    #[test]
+    fn test_owned_coin_balance_range_filter() {
+        let address = SuiAddress::from_str(
+            "0x92dd4d9b0150c251661d821583ef078024ae9e9ee11063e216500861eec7f381",
+        )
+        .unwrap();
+        let filter =
+            SuiObjectDataFilter::AddressOwner(address).and(SuiObjectDataFilter::MatchAll(vec![
+                SuiObjectDataFilter::StructType(parse_sui_struct_tag("0x2::coin::Coin").unwrap()),
+                SuiObjectDataFilter::MoveFieldRange {
+                    path: "balance".to_string(),
+                    min: Some(1000),
+                    max: None,
+                },
+            ]));
+        let expected_sql = "SELECT o.object_id, o.version
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND ((o.owner_type = 'address_owner' AND o.owner_address = '0x92dd4d9b0150c251661d821583ef078024ae9e9ee11063e216500861eec7f381') AND (o.object_type LIKE '0x2::coin::Coin%' AND ((CASE WHEN o.object_json #>> '{balance}' ~ '^[0-9]+$' THEN (o.object_json #>> '{balance}')::NUMERIC END) >= 1000)))
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            filter
+                .to_latest_objects_sql(None, 100, vec!["object_id", "version"])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_move_field_equals_filter() {
+        let filter = SuiObjectDataFilter::MatchAll(vec![
+            SuiObjectDataFilter::MoveFieldEquals {
+                path: "attributes.rarity".to_string(),
+                value: json!("legend'ary"),
+            },
+            SuiObjectDataFilter::MoveFieldEquals {
+                path: "level".to_string(),
+                value: json!(3),
+            },
+            SuiObjectDataFilter::MoveFieldEquals {
+                path: "level'; DROP TABLE objects; --".to_string(),
+                value: json!(3),
+            },
+        ]);
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND (o.object_json @> '{\"attributes\":{\"rarity\":\"legend''ary\"}}'::jsonb AND (o.object_json @> '{\"level\":3}'::jsonb OR o.object_json @> '{\"level\":\"3\"}'::jsonb) AND FALSE)
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            filter.to_latest_objects_sql(None, 100, vec!["*"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_display_field_filter() {
+        let filter = SuiObjectDataFilter::DisplayField {
+            name: "rarity".to_string(),
+            value: "legendary".to_string(),
+        };
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND EXISTS (SELECT 1 FROM display d WHERE d.object_type = o.object_type AND render_display(d.fields ->> 'rarity', o.object_json) = 'legendary')
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            filter.to_latest_objects_sql(None, 100, vec!["*"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_latest_objects_nested_filters() {
+        // An empty id set matches nothing, it must not be dropped from the enclosing filter.
+        let filter = SuiObjectDataFilter::MatchAll(vec![
+            SuiObjectDataFilter::ObjectIds(vec![]),
+            SuiObjectDataFilter::StructType(parse_sui_struct_tag("0x2::test::Test").unwrap()),
+        ]);
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND (FALSE AND o.object_type LIKE '0x2::test::Test%')
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            filter.to_latest_objects_sql(None, 100, vec!["*"]).unwrap()
+        );
+
+        let filter = SuiObjectDataFilter::MatchNone(vec![SuiObjectDataFilter::MatchAll(vec![])]);
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND NOT (TRUE)
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            filter.to_latest_objects_sql(None, 100, vec!["*"]).unwrap()
+        );
+    }
 }
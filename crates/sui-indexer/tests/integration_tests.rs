@@ -12,6 +12,7 @@ pub mod pg_integration_test {
     use move_core_types::language_storage::StructTag;
     use move_core_types::parser::parse_struct_tag;
     use ntest::timeout;
+    use serde_json::json;
     use std::env;
     use std::str::FromStr;
     use tokio::task::JoinHandle;
@@ -27,6 +28,7 @@ pub mod pg_integration_test {
     use sui_indexer::store::{IndexerStore, PgIndexerStore};
     use sui_indexer::test_utils::{start_test_indexer, SuiTransactionBlockResponseBuilder};
     use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, IndexerConfig};
+    use sui_json_rpc::api::CoinReadApiClient;
     use sui_json_rpc::api::ExtendedApiClient;
     use sui_json_rpc::api::IndexerApiClient;
     use sui_json_rpc::api::{ReadApiClient, TransactionBuilderClient, WriteApiClient};
@@ -950,6 +952,244 @@ pub mod pg_integration_test {
         Ok(())
     }
 
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_query_objects_by_move_fields() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+        wait_until_objects_synced_in_checkpoint(&store, 0).await;
+        let sender = *test_cluster.accounts.first().unwrap();
+        let coins = test_cluster
+            .rpc_client()
+            .get_coins(sender, None, None, None)
+            .await?
+            .data;
+        let coin = coins.first().unwrap();
+
+        let count_coins = |filter: SuiObjectDataFilter| {
+            let query = SuiObjectResponseQuery::new_with_filter(
+                SuiObjectDataFilter::StructType(
+                    parse_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap(),
+                )
+                .and(filter),
+            );
+            let client = indexer_rpc_client.clone();
+            async move {
+                client
+                    .get_owned_objects(sender, Some(query), None, None)
+                    .await
+                    .map(|page| page.data.len())
+            }
+        };
+
+        // u64 balances are rendered as JSON strings, they match either form
+        let same_balance = coins.iter().filter(|c| c.balance == coin.balance).count();
+        for value in [json!(coin.balance), json!(coin.balance.to_string())] {
+            let filter = SuiObjectDataFilter::MoveFieldEquals {
+                path: "balance".to_string(),
+                value,
+            };
+            assert_eq!(count_coins(filter).await?, same_balance);
+        }
+        let filter = SuiObjectDataFilter::MoveFieldEquals {
+            path: "id.id".to_string(),
+            value: json!(coin.coin_object_id.to_string()),
+        };
+        assert_eq!(count_coins(filter).await?, 1);
+        // Paths which are not plain fields match nothing
+        let filter = SuiObjectDataFilter::MoveFieldEquals {
+            path: "balance' OR TRUE --".to_string(),
+            value: json!(coin.balance),
+        };
+        assert_eq!(count_coins(filter).await?, 0);
+
+        let filter = SuiObjectDataFilter::MoveFieldRange {
+            path: "balance".to_string(),
+            min: Some(coin.balance as u128 + 1),
+            max: None,
+        };
+        let above = coins.iter().filter(|c| c.balance > coin.balance).count();
+        assert_eq!(count_coins(filter).await?, above);
+        let filter = SuiObjectDataFilter::MoveFieldRange {
+            path: "balance".to_string(),
+            min: None,
+            max: Some(coin.balance as u128),
+        };
+        assert_eq!(count_coins(filter).await?, coins.len() - above);
+        // Non numeric fields are never in range
+        let filter = SuiObjectDataFilter::MoveFieldRange {
+            path: "id.id".to_string(),
+            min: None,
+            max: None,
+        };
+        assert_eq!(count_coins(filter).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_backfill_object_json() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+        wait_until_objects_synced_in_checkpoint(&store, 0).await;
+        let sender = *test_cluster.accounts.first().unwrap();
+        let coins = test_cluster
+            .rpc_client()
+            .get_coins(sender, None, None, None)
+            .await?
+            .data;
+        let query = SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::MoveFieldRange {
+            path: "balance".to_string(),
+            min: Some(0),
+            max: None,
+        });
+        let count_coins = || async {
+            indexer_rpc_client
+                .get_owned_objects(sender, Some(query.clone()), None, None)
+                .await
+                .map(|page| page.data.len())
+        };
+        assert_eq!(count_coins().await?, coins.len());
+
+        // Rows indexed before object_json was populated
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let (pg_connection_pool, _) = new_pg_connection_pool(&db_url).await.unwrap();
+        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+        for table in ["objects", "objects_history"] {
+            diesel::sql_query(format!(
+                "UPDATE {table} SET object_json = NULL WHERE owner_address = '{sender}'"
+            ))
+            .execute(&mut pg_pool_conn)?;
+        }
+        assert_eq!(count_coins().await?, 0);
+
+        store.backfill_object_json().await?;
+        assert_eq!(count_coins().await?, coins.len());
+        let missing: Vec<String> = diesel::dsl::sql::<diesel::sql_types::Text>(&format!(
+            "SELECT object_id FROM objects_history WHERE object_json IS NULL AND owner_address = '{sender}'"
+        ))
+        .load(&mut pg_pool_conn)?;
+        assert!(missing.is_empty());
+        // Nothing left to backfill
+        store.backfill_object_json().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_render_display() -> Result<(), anyhow::Error> {
+        // Helps clear/build the database
+        start_test_cluster(None).await;
+
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let (pg_connection_pool, _) = new_pg_connection_pool(&db_url).await.unwrap();
+        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+
+        let content = r#"{"name": "Sword", "level": 3, "owner": {"name": "alice"}}"#;
+        let mut render = |template: &str| {
+            diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(&format!(
+                "SELECT render_display('{template}', '{content}'::jsonb)"
+            ))
+            .get_result::<Option<String>>(&mut pg_pool_conn)
+        };
+        assert_eq!(render("{name} #{level}")?, Some("Sword #3".to_string()));
+        assert_eq!(
+            render("{name} of {owner.name}")?,
+            Some("Sword of alice".to_string())
+        );
+        assert_eq!(render(r"\{name\}")?, Some("{name}".to_string()));
+        // Templates referring to missing fields are not rendered
+        assert_eq!(render("{name} {rarity}")?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_balance_at_checkpoint() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+        wait_until_objects_synced_in_checkpoint(&store, 0).await;
+        let sender = *test_cluster.accounts.first().unwrap();
+        let balance_before = test_cluster.rpc_client().get_balance(sender, None).await?;
+
+        let (tx_response, _, _, _) =
+            execute_simple_transfer(&mut test_cluster, &indexer_rpc_client).await?;
+        wait_until_transaction_synced_in_checkpoint(
+            &store,
+            tx_response.digest.base58_encode().as_str(),
+        )
+        .await;
+        let tx_checkpoint = store
+            .get_transaction_by_digest(tx_response.digest.base58_encode().as_str())
+            .await?
+            .checkpoint_sequence_number
+            .unwrap() as u64;
+        wait_until_objects_synced_in_checkpoint(&store, tx_checkpoint).await;
+        let balance_after = test_cluster.rpc_client().get_balance(sender, None).await?;
+        assert!(balance_after.total_balance < balance_before.total_balance);
+
+        // The balance before the transfer is still available once it has happened
+        let balance = indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, (tx_checkpoint - 1).into())
+            .await?;
+        assert_eq!(balance.total_balance, balance_before.total_balance);
+        assert_eq!(balance.coin_object_count, balance_before.coin_object_count);
+        let balance = indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, tx_checkpoint.into())
+            .await?;
+        assert_eq!(balance.total_balance, balance_after.total_balance);
+        assert_eq!(balance.coin_object_count, balance_after.coin_object_count);
+
+        // Checkpoints whose objects are not indexed yet are rejected
+        assert!(indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, (tx_checkpoint + 1000).into())
+            .await
+            .is_err());
+
+        // Coins indexed before their contents were stored as JSON are counted from their BCS
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let (pg_connection_pool, _) = new_pg_connection_pool(&db_url).await.unwrap();
+        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+        diesel::sql_query(format!(
+            "UPDATE objects_history SET object_json = NULL WHERE owner_address = '{sender}'"
+        ))
+        .execute(&mut pg_pool_conn)?;
+        let balance = indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, (tx_checkpoint - 1).into())
+            .await?;
+        assert_eq!(balance.total_balance, balance_before.total_balance);
+        assert_eq!(balance.coin_object_count, balance_before.coin_object_count);
+
+        // Checkpoints before objects history was recorded are rejected, as in databases which
+        // indexed objects before it was added
+        assert_eq!(store.get_objects_history_start_checkpoint().await?, 0);
+        diesel::sql_query(format!(
+            "UPDATE objects_history_start SET checkpoint = {tx_checkpoint}"
+        ))
+        .execute(&mut pg_pool_conn)?;
+        assert!(indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, (tx_checkpoint - 1).into())
+            .await
+            .is_err());
+        let balance = indexer_rpc_client
+            .get_balance_at_checkpoint(sender, None, tx_checkpoint.into())
+            .await?;
+        assert_eq!(balance.total_balance, balance_after.total_balance);
+        Ok(())
+    }
+
     #[tokio::test]
     #[timeout(60000)]
     async fn pg_parameter_limit_test() {
@@ -979,6 +1219,7 @@ pub mod pg_integration_test {
                 has_public_transfer: false,
                 storage_rebate: 0,
                 bcs: vec![],
+                object_json: None,
             })
             .collect::<Vec<_>>();
 
@@ -1040,6 +1281,7 @@ pub mod pg_integration_test {
                 has_public_transfer: false,
                 storage_rebate: 0,
                 bcs: vec![NamedBcsBytes("object".to_string(), vec![1u8, 2u8, 3u8])],
+                object_json: None,
             })
             .collect::<Vec<_>>();
 
@@ -1385,6 +1627,21 @@ pub mod pg_integration_test {
         }
     }
 
+    async fn wait_until_objects_synced_in_checkpoint(store: &PgIndexerStore, checkpoint: u64) {
+        let since = std::time::Instant::now();
+        loop {
+            let cp = store.get_latest_object_checkpoint_sequence_number().await;
+            if matches!(cp, Ok(cp) if cp >= checkpoint as i64) {
+                break;
+            }
+            let now = std::time::Instant::now();
+            if now.duration_since(since).as_secs() > WAIT_UNTIL_TIME_LIMIT {
+                panic!("wait_until_objects_synced_in_checkpoint timed out!");
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
     fn get_filter_on_event_type(event_type: &str) -> EventFilter {
         EventFilter::MoveEventType(StructTag::from_str(event_type).unwrap())
     }
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::Page;
+use crate::{Page, SuiTransactionBlockEffects};
 use fastcrypto::encoding::Base64;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -109,6 +109,31 @@ impl
     }
 }
 
+/// Checkpoint pushed to `subscribeCheckpoint` subscribers, with the effects of its transactions
+/// if they were requested.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointWithEffects {
+    #[serde(flatten)]
+    pub checkpoint: Checkpoint,
+    /// Effects of the checkpoint's transactions, in the order they were executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects: Option<Vec<SuiTransactionBlockEffects>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
+#[serde(
+    rename_all = "camelCase",
+    rename = "CheckpointSubscriptionOptions",
+    default
+)]
+pub struct CheckpointSubscriptionOptions {
+    /// Whether to show the digests of the checkpoint's transactions. Default to be False
+    pub show_transactions: bool,
+    /// Whether to show the effects of the checkpoint's transactions. Default to be False
+    pub show_effects: bool,
+}
+
 #[serde_as]
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -12,7 +12,7 @@ use colored::Colorize;
 use fastcrypto::encoding::Base64;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveStructLayout};
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -34,6 +34,7 @@ use sui_types::object::{Data, MoveObject, Object, ObjectFormatOptions, ObjectRea
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
 use sui_types::sui_serde::SuiStructTag;
+use sui_types::sui_serde::SuiTypeTag;
 
 use crate::{Page, SuiMoveStruct, SuiMoveValue};
 
@@ -1134,6 +1135,37 @@ pub enum SuiObjectDataFilter {
         #[serde_as(as = "BigInt<u64>")]
         u64,
     ),
+    /// Query by the value of a field of the object's Move struct, `path` is a `.` separated list
+    /// of field names, e.g. `balance` or `attributes.rarity`.
+    MoveFieldEquals {
+        path: String,
+        value: Value,
+    },
+    /// Query by a numeric field of the object's Move struct being in the [min, max] interval,
+    /// either bound can be omitted.
+    MoveFieldRange {
+        path: String,
+        #[schemars(with = "Option<BigInt<u128>>")]
+        #[serde_as(as = "Option<BigInt<u128>>")]
+        #[serde(default)]
+        min: Option<u128>,
+        #[schemars(with = "Option<BigInt<u128>>")]
+        #[serde_as(as = "Option<BigInt<u128>>")]
+        #[serde(default)]
+        max: Option<u128>,
+    },
+    /// Query by the value of a field of the object's `Display`, as rendered from its template.
+    DisplayField {
+        name: String,
+        value: String,
+    },
+    /// Query by objects having a dynamic field, or a dynamic object field, holding a value of
+    /// this type.
+    HasDynamicField(
+        #[schemars(with = "String")]
+        #[serde_as(as = "SuiTypeTag")]
+        TypeTag,
+    ),
 }
 
 impl SuiObjectDataFilter {
@@ -1151,6 +1183,23 @@ impl SuiObjectDataFilter {
         Self::MatchNone(vec![self, other])
     }
 
+    /// Whether the filter looks into the contents of objects, rather than only into their
+    /// metadata. Such filters cannot be checked against an `ObjectInfo`.
+    pub fn has_content_filter(&self) -> bool {
+        match self {
+            SuiObjectDataFilter::MatchAll(filters)
+            | SuiObjectDataFilter::MatchAny(filters)
+            | SuiObjectDataFilter::MatchNone(filters) => {
+                filters.iter().any(|f| f.has_content_filter())
+            }
+            SuiObjectDataFilter::MoveFieldEquals { .. }
+            | SuiObjectDataFilter::MoveFieldRange { .. }
+            | SuiObjectDataFilter::DisplayField { .. }
+            | SuiObjectDataFilter::HasDynamicField(_) => true,
+            _ => false,
+        }
+    }
+
     pub fn matches(&self, object: &ObjectInfo) -> bool {
         match self {
             SuiObjectDataFilter::MatchAll(filters) => !filters.iter().any(|f| !f.matches(object)),
@@ -1187,6 +1236,10 @@ impl SuiObjectDataFilter {
             SuiObjectDataFilter::ObjectId(id) => &object.object_id == id,
             SuiObjectDataFilter::ObjectIds(ids) => ids.contains(&object.object_id),
             SuiObjectDataFilter::Version(v) => object.version.value() == *v,
+            SuiObjectDataFilter::MoveFieldEquals { .. }
+            | SuiObjectDataFilter::MoveFieldRange { .. }
+            | SuiObjectDataFilter::DisplayField { .. }
+            | SuiObjectDataFilter::HasDynamicField(_) => false,
         }
     }
 }
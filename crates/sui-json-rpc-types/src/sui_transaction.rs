@@ -26,6 +26,7 @@ use sui_types::digests::{ObjectDigest, TransactionEventsDigest};
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
 use sui_types::error::{ExecutionError, SuiError, SuiResult};
 use sui_types::execution_status::ExecutionStatus;
+use sui_types::execution_trace::ExecutionTrace;
 use sui_types::gas::GasCostSummary;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
@@ -78,6 +79,21 @@ impl SuiTransactionBlockResponseQuery {
 
 pub type TransactionBlocksPage = Page<SuiTransactionBlockResponse, TransactionDigest>;
 
+/// Additional arguments supported by dev-inspect
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase", rename = "DevInspectArgs", default)]
+pub struct DevInspectArgs {
+    /// Whether to return a trace of the Move calls made, gas charged, and objects accessed
+    /// during execution, default to be False
+    pub show_trace: bool,
+}
+
+impl DevInspectArgs {
+    pub fn with_trace() -> Self {
+        Self { show_trace: true }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
 #[serde(
     rename_all = "camelCase",
@@ -736,6 +752,10 @@ pub struct DevInspectResults {
     /// Execution error from executing the transactions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Trace of the Move calls made, gas charged, and objects accessed while executing the
+    /// transaction. Only present if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<ExecutionTrace>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -760,6 +780,7 @@ impl DevInspectResults {
         effects: TransactionEffects,
         events: TransactionEvents,
         return_values: Result<Vec<ExecutionResult>, ExecutionError>,
+        trace: Option<ExecutionTrace>,
         resolver: &impl GetModule,
     ) -> SuiResult<Self> {
         let tx_digest = *effects.transaction_digest();
@@ -794,6 +815,7 @@ impl DevInspectResults {
             events: SuiTransactionBlockEvents::try_from(events, tx_digest, None, resolver)?,
             results,
             error,
+            trace,
         })
     }
 }
@@ -1669,6 +1691,47 @@ pub enum TransactionFilter {
     FromAndToAddress { from: SuiAddress, to: SuiAddress },
     /// Query by transaction kind
     TransactionKind(String),
+    /// Query by checkpoints in [start_checkpoint, end_checkpoint) interval
+    #[serde(rename_all = "camelCase")]
+    CheckpointRange {
+        /// left endpoint of checkpoint interval, inclusive
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        start_checkpoint: CheckpointSequenceNumber,
+        /// right endpoint of checkpoint interval, exclusive
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        end_checkpoint: CheckpointSequenceNumber,
+    },
+    /// Query by transactions executed in [start_time, end_time) interval
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        /// left endpoint of time interval, milliseconds since epoch, inclusive
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        start_time: u64,
+        /// right endpoint of time interval, milliseconds since epoch, exclusive
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        end_time: u64,
+    },
+    /// Query by transactions matching all the given filters.
+    All(Vec<TransactionFilter>),
+    /// Query by transactions matching any of the given filters.
+    Any(Vec<TransactionFilter>),
+    /// Query by transactions matching both filters.
+    And(Box<TransactionFilter>, Box<TransactionFilter>),
+    /// Query by transactions matching either filter.
+    Or(Box<TransactionFilter>, Box<TransactionFilter>),
+}
+
+impl TransactionFilter {
+    pub fn and(self, other_filter: TransactionFilter) -> Self {
+        Self::All(vec![self, other_filter])
+    }
+    pub fn or(self, other_filter: TransactionFilter) -> Self {
+        Self::Any(vec![self, other_filter])
+    }
 }
 
 impl Filter<EffectsWithInput> for TransactionFilter {
@@ -1705,8 +1768,14 @@ impl Filter<EffectsWithInput> for TransactionFilter {
                     && (function.is_none() || matches!(function, Some(f2) if f2 == &f.to_string()))
             }),
             TransactionFilter::TransactionKind(kind) => item.input.kind().to_string() == *kind,
+            TransactionFilter::All(filters) => filters.iter().all(|f| f.matches(item)),
+            TransactionFilter::Any(filters) => filters.iter().any(|f| f.matches(item)),
+            TransactionFilter::And(f1, f2) => f1.matches(item) && f2.matches(item),
+            TransactionFilter::Or(f1, f2) => f1.matches(item) || f2.matches(item),
             // these filters are not supported, rpc will reject these filters on subscription
-            TransactionFilter::Checkpoint(_) => false,
+            TransactionFilter::Checkpoint(_)
+            | TransactionFilter::CheckpointRange { .. }
+            | TransactionFilter::TimeRange { .. } => false,
         }
     }
 }
@@ -5,10 +5,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics, QueryObjectsPage,
-    SuiObjectResponseQuery,
+    Balance, CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics,
+    QueryObjectsPage, SuiObjectResponseQuery,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::SuiAddress;
 use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
@@ -42,6 +43,19 @@ pub trait ExtendedApi {
         limit: Option<usize>,
     ) -> RpcResult<QueryObjectsPage>;
 
+    /// Return the total coin balance for one coin type, owned by the address owner at the end of
+    /// a checkpoint. Note that this is an enhanced full node only api.
+    #[method(name = "getBalanceAtCheckpoint")]
+    async fn get_balance_at_checkpoint(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// the checkpoint sequence number to read the balance at
+        checkpoint: BigInt<u64>,
+    ) -> RpcResult<Balance>;
+
     /// Return Network metrics
     #[method(name = "getNetworkMetrics")]
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics>;
@@ -6,15 +6,16 @@ use jsonrpsee_proc_macros::rpc;
 
 use sui_json_rpc_types::SuiTransactionBlockEffects;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    CheckpointSubscriptionOptions, CheckpointWithEffects, DynamicFieldPage, EventFilter, EventPage,
+    ObjectsPage, Page, SuiEvent, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
 #[rpc(server, client, namespace = "suix")]
@@ -77,6 +78,16 @@ pub trait IndexerApi {
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
     fn subscribe_transaction(&self, filter: TransactionFilter);
 
+    /// Subscribe to a stream of checkpoints, pushed as soon as the node finishes executing them
+    #[subscription(name = "subscribeCheckpoint", item = CheckpointWithEffects)]
+    fn subscribe_checkpoint(
+        &self,
+        /// An optional sequence number to resume from. If provided, the stream will start from the checkpoint after it, which may already have been executed. Default to start from the next checkpoint to be executed if not specified.
+        cursor: Option<BigInt<u64>>,
+        /// Options for specifying the content to be returned
+        options: Option<CheckpointSubscriptionOptions>,
+    );
+
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
     async fn get_dynamic_fields(
@@ -89,6 +100,21 @@ pub trait IndexerApi {
         limit: Option<usize>,
     ) -> RpcResult<DynamicFieldPage>;
 
+    /// Return the list of objects owned by an object, including objects transferred to the
+    /// object's ID that are not dynamic fields.
+    #[method(name = "getChildObjects")]
+    async fn get_child_objects(
+        &self,
+        /// The ID of the parent object
+        parent_object_id: ObjectID,
+        /// the objects query criteria.
+        query: Option<SuiObjectResponseQuery>,
+        /// An optional paging cursor. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<ObjectID>,
+        /// Max number of items returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
     /// Return the dynamic field object information for a specified object
     #[method(name = "getDynamicFieldObject")]
     async fn get_dynamic_field_object(
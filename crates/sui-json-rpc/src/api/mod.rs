@@ -89,6 +89,9 @@ pub struct JsonRpcMetrics {
     pub get_dynamic_fields_limit: Histogram,
     pub get_dynamic_fields_result_size: Histogram,
     pub get_dynamic_fields_result_size_total: IntCounter,
+    pub get_child_objects_limit: Histogram,
+    pub get_child_objects_result_size: Histogram,
+    pub get_child_objects_result_size_total: IntCounter,
     pub query_tx_blocks_limit: Histogram,
     pub query_tx_blocks_result_size: Histogram,
     pub query_tx_blocks_result_size_total: IntCounter,
@@ -205,6 +208,22 @@ impl JsonRpcMetrics {
                 registry
             )
             .unwrap(),
+            get_child_objects_limit: Histogram::new_in_registry(
+                "json_rpc_get_child_objects_limit",
+                "The input limit for get_child_objects, after applying the cap",
+                registry,
+            ),
+            get_child_objects_result_size: Histogram::new_in_registry(
+                "json_rpc_get_child_objects_result_size",
+                "The return size for get_child_objects",
+                registry,
+            ),
+            get_child_objects_result_size_total: register_int_counter_with_registry!(
+                "json_rpc_get_child_objects_result_size_total",
+                "The total return size for get_child_objects",
+                registry
+            )
+            .unwrap(),
             query_tx_blocks_limit: Histogram::new_in_registry(
                 "json_rpc_query_tx_blocks_limit",
                 "The input limit for query_tx_blocks, after applying the cap",
@@ -11,6 +11,10 @@ use sui_types::quorum_driver_types::QuorumDriverError;
 use thiserror::Error;
 use tokio::task::JoinError;
 
+/// Error code returned for reads of transactions, effects, events or checkpoint contents which
+/// have been pruned from the node
+pub const PRUNED_DATA_ERROR_CODE: i32 = -32050;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error(transparent)]
@@ -66,6 +70,14 @@ impl Error {
                 SuiError::TransactionNotFound { .. } | SuiError::TransactionsNotFound { .. } => {
                     RpcError::Call(CallError::InvalidParams(sui_error.into()))
                 }
+                SuiError::TransactionPruned { .. } | SuiError::CheckpointContentsPruned { .. } => {
+                    let error_object = ErrorObject::owned::<()>(
+                        PRUNED_DATA_ERROR_CODE,
+                        sui_error.to_string(),
+                        None,
+                    );
+                    RpcError::Call(CallError::Custom(error_object))
+                }
                 _ => RpcError::Call(CallError::Failed(sui_error.into())),
             },
             Error::QuorumDriverError(err) => match err {
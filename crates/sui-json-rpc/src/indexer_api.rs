@@ -21,16 +21,22 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiMoveValue,
-    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedMoveObject,
+    Checkpoint, CheckpointSubscriptionOptions, CheckpointWithEffects, DynamicFieldPage,
+    EventFilter, EventPage, ObjectsPage, Page, SuiMoveValue, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiParsedMoveObject, SuiTransactionBlockEffects,
     SuiTransactionBlockResponse, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
     TransactionFilter,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::{ObjectID, SuiAddress, STD_UTF8_MODULE_NAME, STD_UTF8_STRUCT_NAME};
+use sui_types::base_types::{
+    ObjectID, ObjectInfo, SuiAddress, STD_UTF8_MODULE_NAME, STD_UTF8_STRUCT_NAME,
+};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::sui_serde::BigInt;
+use tokio::sync::watch;
 
 use crate::api::{
     cap_page_limit, validate_limit, IndexerApiServer, JsonRpcMetrics, ReadApiServer,
@@ -64,6 +70,77 @@ where
         };
     });
 }
+
+/// Streams checkpoints in order starting from `next`, waiting for each one to be executed.
+fn checkpoint_stream(
+    state: Arc<AuthorityState>,
+    receiver: watch::Receiver<Option<CheckpointSequenceNumber>>,
+    next: CheckpointSequenceNumber,
+    options: CheckpointSubscriptionOptions,
+) -> impl Stream<Item = CheckpointWithEffects> {
+    futures::stream::unfold((receiver, next), move |(mut receiver, next)| {
+        let state = state.clone();
+        let options = options.clone();
+        async move {
+            loop {
+                // Mark the current value as seen before reading the watermark, so that a
+                // checkpoint executed in between still wakes us up.
+                receiver.borrow_and_update();
+                let executed = state.get_latest_checkpoint_sequence_number().ok();
+                if executed.map_or(false, |seq| seq >= next) {
+                    break;
+                }
+                receiver.changed().await.ok()?;
+            }
+            match read_checkpoint(&state, next, &options) {
+                Ok(checkpoint) => Some((checkpoint, (receiver, next + 1))),
+                Err(e) => {
+                    warn!(error = ?e, sequence_number = next, "Checkpoint subscription closed.");
+                    None
+                }
+            }
+        }
+    })
+}
+
+fn read_checkpoint(
+    state: &AuthorityState,
+    sequence_number: CheckpointSequenceNumber,
+    options: &CheckpointSubscriptionOptions,
+) -> Result<CheckpointWithEffects, anyhow::Error> {
+    let verified_summary = state.get_verified_checkpoint_by_sequence_number(sequence_number)?;
+    let contents = state.get_checkpoint_contents_by_sequence_number(sequence_number)?;
+    let signature = verified_summary.auth_sig().signature.clone();
+    let mut checkpoint: Checkpoint = (
+        verified_summary.into_inner().into_data(),
+        contents,
+        signature,
+    )
+        .into();
+    let effects = if options.show_effects {
+        let effects = state
+            .multi_get_executed_effects(&checkpoint.transactions)?
+            .into_iter()
+            .zip(&checkpoint.transactions)
+            .map(|(effects, digest)| {
+                let effects =
+                    effects.ok_or_else(|| anyhow!("Effects for transaction {digest} not found"))?;
+                Ok(SuiTransactionBlockEffects::try_from(effects)?)
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        Some(effects)
+    } else {
+        None
+    };
+    if !options.show_transactions {
+        checkpoint.transactions.clear();
+    }
+    Ok(CheckpointWithEffects {
+        checkpoint,
+        effects,
+    })
+}
+
 pub struct IndexerApi<R> {
     state: Arc<AuthorityState>,
     read_api: R,
@@ -85,6 +162,26 @@ impl<R: ReadApiServer> IndexerApi<R> {
             metrics,
         }
     }
+
+    /// Reads the requested data of indexed objects, from the index itself if it has all of it.
+    async fn get_objects_from_info(
+        &self,
+        objects: Vec<ObjectInfo>,
+        options: SuiObjectDataOptions,
+    ) -> anyhow::Result<Vec<SuiObjectResponse>> {
+        Ok(match options.is_not_in_object_info() {
+            true => {
+                let object_ids = objects.iter().map(|obj| obj.object_id).collect();
+                self.read_api
+                    .multi_get_objects(object_ids, Some(options))
+                    .await?
+            }
+            false => objects
+                .into_iter()
+                .map(|o_info| SuiObjectResponse::try_from((o_info, options.clone())))
+                .collect::<Result<Vec<SuiObjectResponse>, _>>()?,
+        })
+    }
 }
 
 #[async_trait]
@@ -101,6 +198,11 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             let limit = validate_limit(limit, *QUERY_MAX_RESULT_LIMIT)?;
             self.metrics.get_owned_objects_limit.report(limit as u64);
             let SuiObjectResponseQuery { filter, options } = query.unwrap_or_default();
+            if filter.as_ref().map_or(false, |f| f.has_content_filter()) {
+                return Err(anyhow!(
+                    "Filters on object contents are only supported by the indexer"
+                ))?;
+            }
             let options = options.unwrap_or_default();
             let mut objects = self
                 .state
@@ -115,18 +217,7 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
                 .cloned()
                 .map_or(cursor, |o_info| Some(o_info.object_id));
 
-            let data = match options.is_not_in_object_info() {
-                true => {
-                    let object_ids = objects.iter().map(|obj| obj.object_id).collect();
-                    self.read_api
-                        .multi_get_objects(object_ids, Some(options))
-                        .await?
-                }
-                false => objects
-                    .into_iter()
-                    .map(|o_info| SuiObjectResponse::try_from((o_info, options.clone())))
-                    .collect::<Result<Vec<SuiObjectResponse>, _>>()?,
-            };
+            let data = self.get_objects_from_info(objects, options).await?;
 
             self.metrics
                 .get_owned_objects_result_size
@@ -250,6 +341,33 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         Ok(())
     }
 
+    fn subscribe_checkpoint(
+        &self,
+        sink: SubscriptionSink,
+        cursor: Option<BigInt<u64>>,
+        options: Option<CheckpointSubscriptionOptions>,
+    ) -> SubscriptionResult {
+        // Subscribe before reading the watermark, so that no checkpoint is missed in between.
+        let receiver = self.state.subscription_handler.subscribe_checkpoints();
+        let next = match cursor {
+            Some(cursor) => *cursor + 1,
+            None => self
+                .state
+                .get_latest_checkpoint_sequence_number()
+                .map_or(0, |seq| seq + 1),
+        };
+        spawn_subscription(
+            sink,
+            Box::pin(checkpoint_stream(
+                self.state.clone(),
+                receiver,
+                next,
+                options.unwrap_or_default(),
+            )),
+        );
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn get_dynamic_fields(
         &self,
@@ -282,6 +400,51 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn get_child_objects(
+        &self,
+        parent_object_id: ObjectID,
+        query: Option<SuiObjectResponseQuery>,
+        // If `Some`, the query will start from the next item after the specified cursor
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        with_tracing!("get_child_objects", async move {
+            let limit = validate_limit(limit, *QUERY_MAX_RESULT_LIMIT)?;
+            self.metrics.get_child_objects_limit.report(limit as u64);
+            let SuiObjectResponseQuery { filter, options } = query.unwrap_or_default();
+            if filter.as_ref().map_or(false, |f| f.has_content_filter()) {
+                return Err(anyhow!(
+                    "Filters on object contents are only supported by the indexer"
+                ))?;
+            }
+            let options = options.unwrap_or_default();
+            let mut objects = self
+                .state
+                .get_child_objects(parent_object_id, cursor, limit + 1, filter)
+                .map_err(|e| anyhow!("{e}"))?;
+
+            let has_next_page = objects.len() > limit;
+            objects.truncate(limit);
+            let next_cursor = objects
+                .last()
+                .map_or(cursor, |o_info| Some(o_info.object_id));
+            let data = self.get_objects_from_info(objects, options).await?;
+
+            self.metrics
+                .get_child_objects_result_size
+                .report(data.len() as u64);
+            self.metrics
+                .get_child_objects_result_size_total
+                .inc_by(data.len() as u64);
+            Ok(Page {
+                data,
+                next_cursor,
+                has_next_page,
+            })
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_dynamic_field_object(
         &self,
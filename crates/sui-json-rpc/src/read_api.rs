@@ -38,7 +38,7 @@ use sui_types::crypto::default_hash;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::display::DisplayVersionUpdatedEvent;
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
-use sui_types::error::{SuiObjectResponseError, UserInputError};
+use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
 use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointTimestamp};
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, Object, ObjectRead, PastObjectRead};
@@ -104,9 +104,7 @@ impl ReadApi {
             CheckpointId::SequenceNumber(seq) => {
                 let verified_summary =
                     self.state.get_verified_checkpoint_by_sequence_number(seq)?;
-                let content = self
-                    .state
-                    .get_checkpoint_contents(verified_summary.content_digest)?;
+                let content = self.state.get_checkpoint_contents_by_sequence_number(seq)?;
                 let signature = verified_summary.auth_sig().signature.clone();
                 (
                     verified_summary.into_inner().into_data(),
@@ -119,9 +117,9 @@ impl ReadApi {
                 let verified_summary = self
                     .state
                     .get_verified_checkpoint_summary_by_digest(digest)?;
-                let content = self
-                    .state
-                    .get_checkpoint_contents(verified_summary.content_digest)?;
+                let content = self.state.get_checkpoint_contents_by_sequence_number(
+                    *verified_summary.sequence_number(),
+                )?;
                 let signature = verified_summary.auth_sig().signature.clone();
                 (
                     verified_summary.into_inner().into_data(),
@@ -205,6 +203,19 @@ impl ReadApi {
             cache_entry.checkpoint_seq = seq.map(|(_, seq)| seq);
         }
 
+        // Report transactions whose data has been pruned, rather than leaving them empty
+        for (digest, cache_entry) in temp_response.iter_mut() {
+            if cache_entry.checkpoint_seq.is_some()
+                && ((opts.require_input() && cache_entry.transaction.is_none())
+                    || (opts.require_effects() && cache_entry.effects.is_none()))
+            {
+                let err = self.state.transaction_not_found_error(**digest)?;
+                if matches!(err, SuiError::TransactionPruned { .. }) {
+                    cache_entry.errors.push(err.to_string());
+                }
+            }
+        }
+
         let unique_checkpoint_numbers = temp_response
             .values()
             .filter_map(|cache_entry| cache_entry.checkpoint_seq.map(<u64>::from))
@@ -16,7 +16,7 @@ use sui_core::authority::AuthorityState;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
     SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
@@ -229,12 +229,19 @@ impl WriteApiServer for TransactionExecutionApi {
         tx_bytes: Base64,
         gas_price: Option<BigInt<u64>>,
         _epoch: Option<BigInt<u64>>,
+        additional_args: Option<DevInspectArgs>,
     ) -> RpcResult<DevInspectResults> {
         let tx_kind: TransactionKind =
             bcs::from_bytes(&tx_bytes.to_vec().map_err(|e| anyhow!(e))?).map_err(|e| anyhow!(e))?;
+        let DevInspectArgs { show_trace } = additional_args.unwrap_or_default();
         Ok(self
             .state
-            .dev_inspect_transaction_block(sender_address, tx_kind, gas_price.map(|i| *i))
+            .dev_inspect_transaction_block(
+                sender_address,
+                tx_kind,
+                gas_price.map(|i| *i),
+                show_trace,
+            )
             .instrument(error_span!("dev_inspect_transaction_block"))
             .await
             .map_err(Error::from)?)
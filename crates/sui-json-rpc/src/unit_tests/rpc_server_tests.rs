@@ -5,6 +5,9 @@ use crate::api::{
     CoinReadApiClient, GovernanceReadApiClient, IndexerApiClient, ReadApiClient,
     TransactionBuilderClient, WriteApiClient,
 };
+use crate::error::PRUNED_DATA_ERROR_CODE;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::CallError;
 use std::collections::BTreeMap;
 use std::path::Path;
 #[cfg(not(msim))]
@@ -32,7 +35,9 @@ use sui_types::gas_coin::GAS;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::utils::to_sender_signed_transaction;
 use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
-use test_utils::network::TestClusterBuilder;
+use test_utils::network::{
+    start_fullnode_from_config, wait_for_node_transition_to_epoch, TestClusterBuilder,
+};
 use tokio::time::sleep;
 
 #[sim_test]
@@ -953,3 +958,63 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_get_pruned_transaction_block() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let mut config = cluster.fullnode_config_builder().build()?;
+    config.authority_store_pruning_config.num_epochs_to_retain = 0;
+    config
+        .authority_store_pruning_config
+        .num_epochs_to_retain_for_transactions = Some(0);
+    config
+        .authority_store_pruning_config
+        .pruning_run_delay_seconds = Some(1);
+    let fullnode = start_fullnode_from_config(config).await?;
+
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let objects = http_client
+        .get_owned_objects(*address, None, None, None)
+        .await?
+        .data;
+    let obj = objects.first().unwrap().object().unwrap().object_id;
+    let gas = objects.last().unwrap().object().unwrap().object_id;
+    let transaction_bytes: TransactionBlockBytes = http_client
+        .transfer_object(*address, obj, Some(gas), 10_000.into(), *address)
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(transaction_bytes.to_data()?, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let digest = http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            None,
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?
+        .digest;
+
+    cluster.trigger_reconfiguration().await;
+    wait_for_node_transition_to_epoch(&fullnode.sui_node, 1).await;
+
+    // The checkpoint of the transaction is pruned on one of the next pruner runs
+    let mut result = Ok(());
+    for _ in 0..30 {
+        match fullnode
+            .rpc_client
+            .get_transaction_block(digest, None)
+            .await
+        {
+            Err(RpcError::Call(CallError::Custom(error))) => {
+                assert_eq!(error.code(), PRUNED_DATA_ERROR_CODE);
+                return Ok(());
+            }
+            other => result = other.map(|_| ()),
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+    panic!("Transaction {digest} was not pruned: {result:?}");
+}
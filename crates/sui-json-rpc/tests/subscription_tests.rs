@@ -9,8 +9,10 @@ use tokio::time::timeout;
 
 use sui_core::test_utils::wait_for_tx;
 use sui_json_rpc_types::{
-    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, TransactionFilter,
+    CheckpointSubscriptionOptions, CheckpointWithEffects, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, TransactionFilter,
 };
+use sui_types::sui_serde::BigInt;
 use test_utils::network::TestClusterBuilder;
 
 #[tokio::test]
@@ -45,3 +47,69 @@ async fn test_subscribe_transaction() -> Result<(), anyhow::Error> {
     assert_eq!(&digest, effects.transaction_digest());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_subscribe_checkpoint() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await.unwrap();
+    let wallet = cluster.wallet;
+    let ws_client = cluster.fullnode_handle.ws_client;
+
+    let options = CheckpointSubscriptionOptions {
+        show_transactions: true,
+        show_effects: true,
+    };
+    let mut sub: Subscription<CheckpointWithEffects> = ws_client
+        .subscribe(
+            "suix_subscribeCheckpoint",
+            rpc_params![None::<BigInt<u64>>, options.clone()],
+            "suix_unsubscribeCheckpoint",
+        )
+        .await
+        .unwrap();
+
+    let package_id = wallet.publish_nfts_package().await.0;
+    let (_, _, digest) = wallet.create_devnet_nft(package_id).await;
+
+    // Checkpoints are streamed in order until the one including the transaction
+    let mut previous: Option<u64> = None;
+    let checkpoint = loop {
+        let checkpoint = match timeout(Duration::from_secs(30), sub.next()).await {
+            Ok(Some(Ok(checkpoint))) => checkpoint,
+            _ => panic!("Failed to get checkpoint"),
+        };
+        let sequence_number = checkpoint.checkpoint.sequence_number;
+        if let Some(previous) = previous {
+            assert_eq!(previous + 1, sequence_number);
+        }
+        previous = Some(sequence_number);
+        if checkpoint.checkpoint.transactions.contains(&digest) {
+            break checkpoint;
+        }
+    };
+    let effects = checkpoint.effects.as_ref().unwrap();
+    assert_eq!(effects.len(), checkpoint.checkpoint.transactions.len());
+    assert!(effects.iter().any(|e| e.transaction_digest() == &digest));
+
+    // Resuming after the previous checkpoint replays the same checkpoint
+    let sequence_number = checkpoint.checkpoint.sequence_number;
+    let mut sub: Subscription<CheckpointWithEffects> = ws_client
+        .subscribe(
+            "suix_subscribeCheckpoint",
+            rpc_params![
+                Some(BigInt::from(sequence_number - 1)),
+                CheckpointSubscriptionOptions::default()
+            ],
+            "suix_unsubscribeCheckpoint",
+        )
+        .await
+        .unwrap();
+    let resumed = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(checkpoint))) => checkpoint,
+        _ => panic!("Failed to get checkpoint"),
+    };
+    assert_eq!(resumed.checkpoint.sequence_number, sequence_number);
+    assert_eq!(resumed.checkpoint.digest, checkpoint.checkpoint.digest);
+    assert!(resumed.checkpoint.transactions.is_empty());
+    assert!(resumed.effects.is_none());
+    Ok(())
+}
@@ -821,14 +821,18 @@ fn resolve_call_arg(
 fn resolve_call_args(
     view: &BinaryIndexedView,
     type_args: &[TypeTag],
-    json_args: &[SuiJsonValue],
+    json_args: &[Option<SuiJsonValue>],
     parameter_types: &[SignatureToken],
-) -> Result<Vec<ResolvedCallArg>, anyhow::Error> {
+) -> Result<Vec<Option<ResolvedCallArg>>, anyhow::Error> {
     json_args
         .iter()
         .zip(parameter_types)
         .enumerate()
-        .map(|(idx, (arg, param))| resolve_call_arg(view, type_args, idx, arg, param))
+        .map(|(idx, (arg, param))| {
+            arg.as_ref()
+                .map(|arg| resolve_call_arg(view, type_args, idx, arg, param))
+                .transpose()
+        })
         .collect()
 }
 
@@ -841,6 +845,35 @@ pub fn resolve_move_function_args(
     type_args: &[TypeTag],
     combined_args_json: Vec<SuiJsonValue>,
 ) -> Result<Vec<(ResolvedCallArg, SignatureToken)>, anyhow::Error> {
+    let args = resolve_partial_move_function_args(
+        package,
+        module_ident,
+        function,
+        type_args,
+        combined_args_json.into_iter().map(Some).collect(),
+    )?;
+    Ok(args
+        .into_iter()
+        .map(|(arg, expected_type)| {
+            (
+                arg.expect("All arguments are provided as JSON and must be resolved"),
+                expected_type,
+            )
+        })
+        .collect())
+}
+
+/// Like `resolve_move_function_args`, but arguments passed as `None` are skipped during
+/// resolution and only their expected type is returned. This is used when some of the arguments
+/// are already known to the caller, e.g. results of earlier commands in a programmable
+/// transaction.
+pub fn resolve_partial_move_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    type_args: &[TypeTag],
+    combined_args_json: Vec<Option<SuiJsonValue>>,
+) -> Result<Vec<(Option<ResolvedCallArg>, SignatureToken)>, anyhow::Error> {
     // Extract the expected function signature
     let module = package.deserialize_module(&module_ident, VERSION_MAX, true)?;
     let function_str = function.as_ident_str();
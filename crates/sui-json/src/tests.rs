@@ -28,7 +28,7 @@ use sui_types::{parse_sui_type_tag, MOVE_STDLIB_ADDRESS};
 use crate::ResolvedCallArg;
 
 use super::{check_valid_homogeneous, HEX_PREFIX};
-use super::{resolve_move_function_args, SuiJsonValue};
+use super::{resolve_move_function_args, resolve_partial_move_function_args, SuiJsonValue};
 
 // Negative test cases
 #[test]
@@ -617,6 +617,25 @@ fn test_basic_args_linter_top_level() {
         ResolvedCallArg::Pure(bcs::to_bytes(&AccountAddress::from(address)).unwrap())
     );
 
+    // Test with an argument that is already resolved by the caller
+    let module = Identifier::new("object_basics").unwrap();
+    let function = Identifier::new("transfer").unwrap();
+    let args = vec![
+        None,
+        Some(SuiJsonValue::new(json!(format!("{address}"))).unwrap()),
+    ];
+
+    let args =
+        resolve_partial_move_function_args(framework_pkg, module, function, &[], args).unwrap();
+
+    assert_eq!(args[0].0, None);
+    assert_eq!(
+        args[1].0,
+        Some(ResolvedCallArg::Pure(
+            bcs::to_bytes(&AccountAddress::from(address)).unwrap()
+        ))
+    );
+
     // Test with object vector  args
     let path = Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("../sui-core/src/unit_tests/data/entry_point_vector");
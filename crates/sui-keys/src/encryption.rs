@@ -0,0 +1,245 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encrypted keystore file format.
+//!
+//! The encryption key is derived from a passphrase with Argon2id, and every keypair is encrypted
+//! separately with AES-256-GCM, using its public key as associated data. Public keys are stored in
+//! the clear so that addresses can be listed without the passphrase. A known check value is
+//! encrypted alongside the keys, so that a wrong passphrase is rejected even when there are none.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use argon2::{Algorithm, Argon2, Params, Version};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, SuiKeyPair};
+
+/// Environment variable the passphrase of an encrypted keystore is read from
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+/// Environment variable the new passphrase is read from when (re-)encrypting a keystore
+pub const KEYSTORE_NEW_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_NEW_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+/// Plaintext of the passphrase check, which is also its associated data
+const PASSPHRASE_CHECK: &[u8] = b"sui keystore passphrase check";
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EncryptedKeystoreFile {
+    version: u8,
+    kdf: KdfParams,
+    check: Sealed,
+    keys: Vec<EncryptedKey>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    salt: String,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+}
+
+/// A message encrypted under the derived key with a random nonce
+#[derive(Clone, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct EncryptedKey {
+    public_key: PublicKey,
+    #[serde(flatten)]
+    sealed: Sealed,
+}
+
+/// Encryption state of a `FileBasedKeystore`. The derived key is only known once the keystore has
+/// been unlocked with its passphrase.
+pub(crate) struct KeystoreEncryption {
+    kdf: KdfParams,
+    check: Sealed,
+    cipher: Option<Aes256Gcm>,
+    keys: BTreeMap<SuiAddress, EncryptedKey>,
+}
+
+impl KdfParams {
+    fn generate() -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self {
+            algorithm: "argon2id".to_string(),
+            salt: Base64::encode(salt),
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+
+    fn derive_cipher(&self, passphrase: &str) -> Result<Aes256Gcm, anyhow::Error> {
+        if self.algorithm != "argon2id" {
+            return Err(anyhow!("Unsupported key derivation {}", self.algorithm));
+        }
+        let params = Params::new(
+            self.memory_cost,
+            self.time_cost,
+            self.parallelism,
+            Some(KEY_LENGTH),
+        )
+        .map_err(|e| anyhow!("Invalid key derivation parameters: {e}"))?;
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid salt: {e}"))?;
+        let mut key = [0u8; KEY_LENGTH];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Cannot derive keystore key: {e}"))?;
+        Ok(Aes256Gcm::new(&key.into()))
+    }
+}
+
+impl Sealed {
+    fn seal(cipher: &Aes256Gcm, msg: &[u8], aad: &[u8]) -> Result<Self, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg, aad })
+            .map_err(|_| anyhow!("Cannot encrypt keystore data"))?;
+        Ok(Self {
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// Fails if the data was sealed under a different key, or has been tampered with
+    fn open(&self, cipher: &Aes256Gcm, aad: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce = Base64::decode(&self.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!("Invalid nonce length"));
+        }
+        let ciphertext =
+            Base64::decode(&self.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("Incorrect passphrase"))
+    }
+}
+
+impl EncryptedKey {
+    fn encrypt(cipher: &Aes256Gcm, keypair: &SuiKeyPair) -> Result<Self, anyhow::Error> {
+        let public_key = keypair.public();
+        let sealed = Sealed::seal(
+            cipher,
+            keypair.encode_base64().as_bytes(),
+            public_key.as_ref(),
+        )?;
+        Ok(Self { public_key, sealed })
+    }
+
+    fn decrypt(&self, cipher: &Aes256Gcm) -> Result<SuiKeyPair, anyhow::Error> {
+        let address = SuiAddress::from(&self.public_key);
+        let plaintext = self
+            .sealed
+            .open(cipher, self.public_key.as_ref())
+            .map_err(|e| anyhow!("{e}, cannot decrypt key [{address}]"))?;
+        let keypair = SuiKeyPair::decode_base64(&String::from_utf8(plaintext)?)
+            .map_err(|e| anyhow!("Invalid keypair for [{address}]: {e}"))?;
+        if keypair.public() != self.public_key {
+            return Err(anyhow!("Keypair does not match public key [{address}]"));
+        }
+        Ok(keypair)
+    }
+}
+
+impl KeystoreEncryption {
+    /// Derives a new key from the passphrase, with a fresh salt
+    pub fn new(passphrase: &str) -> Result<Self, anyhow::Error> {
+        let kdf = KdfParams::generate();
+        let cipher = kdf.derive_cipher(passphrase)?;
+        Ok(Self {
+            kdf,
+            check: Sealed::seal(&cipher, PASSPHRASE_CHECK, PASSPHRASE_CHECK)?,
+            cipher: Some(cipher),
+            keys: BTreeMap::new(),
+        })
+    }
+
+    pub fn from_file(file: EncryptedKeystoreFile) -> Result<Self, anyhow::Error> {
+        if file.version != ENCRYPTED_KEYSTORE_VERSION {
+            return Err(anyhow!(
+                "Unsupported encrypted keystore version {}",
+                file.version
+            ));
+        }
+        Ok(Self {
+            kdf: file.kdf,
+            check: file.check,
+            cipher: None,
+            keys: file
+                .keys
+                .into_iter()
+                .map(|key| ((&key.public_key).into(), key))
+                .collect(),
+        })
+    }
+
+    pub fn to_file(&self) -> EncryptedKeystoreFile {
+        EncryptedKeystoreFile {
+            version: ENCRYPTED_KEYSTORE_VERSION,
+            kdf: self.kdf.clone(),
+            check: self.check.clone(),
+            keys: self.keys.values().cloned().collect(),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.cipher.is_none()
+    }
+
+    pub fn public_keys(&self) -> Vec<PublicKey> {
+        self.keys
+            .values()
+            .map(|key| key.public_key.clone())
+            .collect()
+    }
+
+    /// Derives the key from the passphrase and decrypts all keypairs
+    pub fn unlock(
+        &mut self,
+        passphrase: &str,
+    ) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+        let cipher = self.kdf.derive_cipher(passphrase)?;
+        if self.check.open(&cipher, PASSPHRASE_CHECK)? != PASSPHRASE_CHECK {
+            return Err(anyhow!("Incorrect passphrase"));
+        }
+        let keys = self
+            .keys
+            .iter()
+            .map(|(address, key)| Ok((*address, key.decrypt(&cipher)?)))
+            .collect::<Result<_, anyhow::Error>>()?;
+        self.cipher = Some(cipher);
+        Ok(keys)
+    }
+
+    pub fn insert(&mut self, keypair: &SuiKeyPair) -> Result<(), anyhow::Error> {
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| anyhow!("Keystore is locked"))?;
+        let key = EncryptedKey::encrypt(cipher, keypair)?;
+        self.keys.insert((&key.public_key).into(), key);
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protocol used by `ExternalKeystore` to talk to a signer process which holds the private keys.
+//!
+//! Every exchange is a single request and a single response, each one line of JSON:
+//!
+//! - `{"method":"keys"}` is answered with `{"keys":["<base64 flag || public key>", ...]}`
+//! - `{"method":"sign","public_key":"<base64 flag || public key>","message":"<base64>"}` is
+//!   answered with `{"signature":"<base64 signature>"}`. The message is the 32 byte digest that a
+//!   `SuiKeyPair` would sign, and the signature is the raw 64 byte signature of the key scheme, as
+//!   produced by signing the message with fastcrypto (Secp256k1 and Secp256r1 hash it with sha256
+//!   first, and the signature must be normalized to a low s).
+//! - Any request can be answered with `{"error":"<reason>"}`.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    Keys,
+    Sign { public_key: String, message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    Keys(Vec<String>),
+    Signature(String),
+    Error(String),
+}
+
+/// How to reach the external signer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExternalSigner {
+    /// Runs the program for every request, writing the request to its stdin and reading the
+    /// response from its stdout
+    Command {
+        program: PathBuf,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// Connects to a signer listening on a Unix socket, with one connection per request
+    UnixSocket(PathBuf),
+}
+
+impl ExternalSigner {
+    pub fn request(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        let response = match self {
+            ExternalSigner::Command { program, args } => {
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .map_err(|e| anyhow!("Cannot start external signer {:?}: {e}", program))?;
+                child
+                    .stdin
+                    .take()
+                    .ok_or_else(|| anyhow!("Cannot write to external signer {:?}", program))?
+                    .write_all(line.as_bytes())?;
+                let output = child.wait_with_output()?;
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "External signer {:?} failed with {}: {}",
+                        program,
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                String::from_utf8(output.stdout)?
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            }
+            #[cfg(unix)]
+            ExternalSigner::UnixSocket(path) => {
+                let mut stream = std::os::unix::net::UnixStream::connect(path)
+                    .map_err(|e| anyhow!("Cannot connect to external signer at {:?}: {e}", path))?;
+                stream.write_all(line.as_bytes())?;
+                stream.shutdown(std::net::Shutdown::Write)?;
+                let mut response = String::new();
+                BufReader::new(stream).read_line(&mut response)?;
+                response
+            }
+            #[cfg(not(unix))]
+            ExternalSigner::UnixSocket(_) => {
+                return Err(anyhow!("Unix sockets are not supported on this platform"))
+            }
+        };
+        serde_json::from_str(&response)
+            .map_err(|e| anyhow!("Invalid response from external signer: {e}"))
+    }
+}
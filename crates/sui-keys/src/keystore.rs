@@ -1,10 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{EncryptedKeystoreFile, KeystoreEncryption};
+use crate::external_signer::{ExternalSigner, SignerRequest, SignerResponse};
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use fastcrypto::traits::ToFromBytes;
+use once_cell::sync::OnceCell;
 use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
@@ -18,7 +24,8 @@ use std::path::{Path, PathBuf};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
-    enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    enum_dispatch, DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme,
+    SuiKeyPair, SuiSignature,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -26,6 +33,7 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -84,14 +92,34 @@ impl Display for Keystore {
         match self {
             Keystore::File(file) => {
                 writeln!(writer, "Keystore Type : File")?;
-                write!(writer, "Keystore Path : {:?}", file.path)?;
+                writeln!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(writer, "Keystore Encrypted : {}", file.is_encrypted())?;
                 write!(f, "{}", writer)
             }
             Keystore::InMem(_) => {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Keystore Signer : {:?}", external.signer)?;
+                write!(f, "{}", writer)
+            }
+        }
+    }
+}
+
+impl Keystore {
+    /// Whether this is an encrypted file keystore which has not been unlocked yet
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Keystore::File(file) if file.is_locked())
+    }
+
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        if let Keystore::File(file) = self {
+            file.unlock(passphrase)?;
         }
+        Ok(())
     }
 }
 
@@ -99,6 +127,16 @@ impl Display for Keystore {
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     path: Option<PathBuf>,
+    encryption: Option<KeystoreEncryption>,
+}
+
+/// On disk format of a `FileBasedKeystore`, either a list of Base64 encoded `SuiKeyPair`s or an
+/// encrypted keystore.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Plain(Vec<String>),
+    Encrypted(EncryptedKeystoreFile),
 }
 
 impl Serialize for FileBasedKeystore {
@@ -131,9 +169,8 @@ impl AccountKeystore for FileBasedKeystore {
     fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
         Ok(Signature::new_hashed(
             msg,
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
     fn sign_secure<T>(
@@ -147,13 +184,15 @@ impl AccountKeystore for FileBasedKeystore {
     {
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
 
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        if let Some(encryption) = &mut self.encryption {
+            encryption.insert(&keypair)?;
+        }
         let address: SuiAddress = (&keypair.public()).into();
         self.keys.insert(address, keypair);
         self.save()?;
@@ -161,12 +200,18 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        match &self.encryption {
+            Some(encryption) => encryption.public_keys(),
+            None => self.keys.values().map(|key| key.public()).collect(),
+        }
     }
 
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
         match self.keys.get(address) {
             Some(key) => Ok(key),
+            None if self.is_locked() => Err(anyhow!(
+                "Keystore is locked, unlock it to use the key for address: [{address}]"
+            )),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
     }
@@ -174,45 +219,97 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
+        let file = if path.exists() {
             let reader = BufReader::new(
                 File::open(path)
                     .map_err(|e| anyhow!("Can't open FileBasedKeystore from {:?}: {e}", path))?,
             );
-            let kp_strings: Vec<String> = serde_json::from_reader(reader)
-                .map_err(|e| anyhow!("Can't deserialize FileBasedKeystore from {:?}: {e}", path))?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow::anyhow!("Invalid Keypair file {:#?} {:?}", e, path))?
+            serde_json::from_reader(reader)
+                .map_err(|e| anyhow!("Can't deserialize FileBasedKeystore from {:?}: {e}", path))?
         } else {
-            BTreeMap::new()
+            KeystoreFile::Plain(vec![])
+        };
+
+        let (keys, encryption) = match file {
+            KeystoreFile::Plain(kp_strings) => {
+                let keys = kp_strings
+                    .iter()
+                    .map(|kpstr| {
+                        let key = SuiKeyPair::decode_base64(kpstr);
+                        key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+                    .map_err(|e| anyhow::anyhow!("Invalid Keypair file {:#?} {:?}", e, path))?;
+                (keys, None)
+            }
+            // Encrypted keystores start out locked, see [FileBasedKeystore::unlock]
+            KeystoreFile::Encrypted(file) => (
+                BTreeMap::new(),
+                Some(
+                    KeystoreEncryption::from_file(file)
+                        .map_err(|e| anyhow!("Invalid encrypted keystore {:?}: {e}", path))?,
+                ),
+            ),
         };
 
         Ok(Self {
             keys,
             path: Some(path.to_path_buf()),
+            encryption,
         })
     }
 
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Whether the keystore is encrypted and has not been unlocked yet
+    pub fn is_locked(&self) -> bool {
+        self.encryption
+            .as_ref()
+            .map_or(false, KeystoreEncryption::is_locked)
+    }
+
+    /// Decrypts the keys of an encrypted keystore, fails if the passphrase is incorrect.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        if let Some(encryption) = &mut self.encryption {
+            self.keys = encryption.unlock(passphrase)?;
+        }
+        Ok(())
+    }
+
+    /// Encrypts all keys with a key derived from the new passphrase and saves the keystore. This
+    /// both migrates a plaintext keystore and re-encrypts an encrypted one, which must be unlocked.
+    pub fn encrypt(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        if self.is_locked() {
+            return Err(anyhow!(
+                "Keystore must be unlocked before it is re-encrypted"
+            ));
+        }
+        let mut encryption = KeystoreEncryption::new(passphrase)?;
+        for keypair in self.keys.values() {
+            encryption.insert(keypair)?;
+        }
+        self.encryption = Some(encryption);
+        self.save()
+    }
+
     pub fn set_path(&mut self, path: &Path) {
         self.path = Some(path.to_path_buf());
     }
 
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(EncodeDecodeBase64::encode_base64)
-                    .collect::<Vec<_>>(),
-            )
-            .unwrap();
+            let file = match &self.encryption {
+                Some(encryption) => KeystoreFile::Encrypted(encryption.to_file()),
+                None => KeystoreFile::Plain(
+                    self.keys
+                        .values()
+                        .map(EncodeDecodeBase64::encode_base64)
+                        .collect(),
+                ),
+            };
+            let store = serde_json::to_string_pretty(&file).unwrap();
             fs::write(path, store)?
         }
         Ok(())
@@ -283,3 +380,151 @@ impl InMemKeystore {
         Self { keys }
     }
 }
+
+/// A keystore which holds no private keys, and hands all signing to an external signer process.
+/// See `external_signer` for the protocol. The signer is only contacted once keys are listed or
+/// used, so loading a config that holds this keystore never spawns or connects to it.
+pub struct ExternalKeystore {
+    signer: ExternalSigner,
+    keys: OnceCell<BTreeMap<SuiAddress, PublicKey>>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.signer.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ExternalKeystore::new(ExternalSigner::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.sign(address, msg)
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let mut hasher = DefaultHash::default();
+        hasher.update(&bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?);
+        let signature = self.sign(address, &hasher.finalize().digest)?;
+        // Do not hand out a signature which would be rejected later on
+        signature
+            .verify_secure(&intent_msg, *address)
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "External signer returned an invalid signature for address [{address}]: {e}"
+                ))
+            })?;
+        Ok(signature)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        Err(anyhow!(
+            "Cannot add keys to an external keystore, add them to the external signer instead"
+        ))
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        // Listing cannot fail, an unreachable signer shows up as an empty keystore and the error
+        // is reported when signing
+        self.loaded_keys()
+            .map(|keys| keys.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        Err(anyhow!(
+            "The private key for address [{address}] is held by the external signer"
+        ))
+    }
+}
+
+impl ExternalKeystore {
+    pub fn new(signer: ExternalSigner) -> Self {
+        Self {
+            signer,
+            keys: OnceCell::new(),
+        }
+    }
+
+    /// Reloads the list of public keys from the external signer
+    pub fn refresh_keys(&mut self) -> Result<(), anyhow::Error> {
+        self.keys = OnceCell::with_value(self.fetch_keys()?);
+        Ok(())
+    }
+
+    /// Public keys of the external signer, fetched on first use
+    fn loaded_keys(&self) -> Result<&BTreeMap<SuiAddress, PublicKey>, anyhow::Error> {
+        self.keys.get_or_try_init(|| self.fetch_keys())
+    }
+
+    fn fetch_keys(&self) -> Result<BTreeMap<SuiAddress, PublicKey>, anyhow::Error> {
+        let keys = match self.signer.request(&SignerRequest::Keys)? {
+            SignerResponse::Keys(keys) => keys,
+            SignerResponse::Error(e) => return Err(anyhow!("External signer error: {e}")),
+            response => return Err(anyhow!("Unexpected external signer response: {response:?}")),
+        };
+        keys.iter()
+            .map(|key| {
+                let key = PublicKey::decode_base64(key)
+                    .map_err(|e| anyhow!("Invalid public key {key} from external signer: {e}"))?;
+                Ok(((&key).into(), key))
+            })
+            .collect()
+    }
+
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let public_key = self
+            .loaded_keys()
+            .map_err(|e| signature::Error::from_source(e.to_string()))?
+            .get(address)
+            .ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?;
+        let request = SignerRequest::Sign {
+            public_key: public_key.encode_base64(),
+            message: Base64::encode(msg),
+        };
+        let signature = match self
+            .signer
+            .request(&request)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?
+        {
+            SignerResponse::Signature(signature) => Base64::decode(&signature)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+            SignerResponse::Error(e) => {
+                return Err(signature::Error::from_source(format!(
+                    "External signer error: {e}"
+                )))
+            }
+            response => {
+                return Err(signature::Error::from_source(format!(
+                    "Unexpected external signer response: {response:?}"
+                )))
+            }
+        };
+        let mut bytes = vec![public_key.flag()];
+        bytes.extend_from_slice(&signature);
+        bytes.extend_from_slice(public_key.as_ref());
+        Signature::from_bytes(&bytes).map_err(signature::Error::from_source)
+    }
+}
@@ -2,11 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::str::FromStr;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use sui_keys::external_signer::{ExternalSigner, SignerRequest, SignerResponse};
+use sui_keys::keystore::{
+    AccountKeystore, ExternalKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+};
+use sui_types::crypto::{
+    DefaultHash, EncodeDecodeBase64, PublicKey, SignatureScheme, SuiSignature, SuiSignatureInner,
+};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +76,152 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn encrypted_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, _, _) =
+        keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+    let private_key = keystore.get_key(&address)?.encode_base64();
+    assert!(std::fs::read_to_string(&keystore_path)?.contains(&private_key));
+    keystore.encrypt("correct horse")?;
+    assert!(keystore.is_encrypted());
+    assert!(!std::fs::read_to_string(&keystore_path)?.contains(&private_key));
+
+    // Public keys are readable without the passphrase, private keys are not
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    assert!(keystore.is_locked());
+    assert_eq!(keystore.addresses(), vec![address]);
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore.unlock("wrong horse").is_err());
+    assert!(keystore.is_locked());
+    keystore.unlock("correct horse")?;
+    assert!(keystore.get_key(&address).is_ok());
+
+    // Keys added to an unlocked keystore are encrypted too
+    let (address2, _, _) =
+        keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None, None)?;
+    keystore.encrypt("battery staple")?;
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    assert!(keystore.unlock("correct horse").is_err());
+    keystore.unlock("battery staple")?;
+    assert!(keystore.get_key(&address).is_ok());
+    assert!(keystore.get_key(&address2).is_ok());
+
+    // The passphrase is checked even when there are no keys to decrypt
+    let empty_path = temp_dir.path().join("empty.keystore");
+    FileBasedKeystore::new(&empty_path)?.encrypt("correct horse")?;
+    let mut keystore = FileBasedKeystore::new(&empty_path)?;
+    assert!(keystore.unlock("wrong horse").is_err());
+    assert!(keystore.is_locked());
+    keystore.unlock("correct horse")?;
+    Ok(())
+}
+
+/// Serves the keys of an in memory keystore over a Unix socket, following the external signer
+/// protocol.
+#[cfg(unix)]
+fn spawn_external_signer(keystore: InMemKeystore, path: &std::path::Path) {
+    use std::io::{BufRead, BufReader, Write};
+    let listener = std::os::unix::net::UnixListener::bind(path).unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let response = match serde_json::from_str(&line).unwrap() {
+                SignerRequest::Keys => SignerResponse::Keys(
+                    keystore.keys().iter().map(|k| k.encode_base64()).collect(),
+                ),
+                SignerRequest::Sign {
+                    public_key,
+                    message,
+                } => {
+                    let public_key = PublicKey::decode_base64(&public_key).unwrap();
+                    let message = Base64::decode(&message).unwrap();
+                    match keystore.sign_hashed(&(&public_key).into(), &message) {
+                        Ok(signature) => {
+                            SignerResponse::Signature(Base64::encode(signature.signature_bytes()))
+                        }
+                        Err(e) => SignerResponse::Error(e.to_string()),
+                    }
+                }
+            };
+            let mut response = serde_json::to_string(&response).unwrap();
+            response.push('\n');
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_test() -> Result<(), anyhow::Error> {
+    let mut signer_keys = InMemKeystore::default();
+    let mut addresses = vec![];
+    for scheme in [
+        SignatureScheme::ED25519,
+        SignatureScheme::Secp256k1,
+        SignatureScheme::Secp256r1,
+    ] {
+        let (address, _, _) = signer_keys.generate_and_add_new_key(scheme, None, None)?;
+        addresses.push(address);
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    spawn_external_signer(signer_keys, &socket_path);
+
+    let keystore = Keystore::from(ExternalKeystore::new(ExternalSigner::UnixSocket(
+        socket_path,
+    )));
+    let mut listed = keystore.addresses();
+    listed.sort();
+    addresses.sort();
+    assert_eq!(listed, addresses);
+
+    let msg = PersonalMessage {
+        message: b"hello".to_vec(),
+    };
+    for address in &addresses {
+        let signature = keystore.sign_secure(address, &msg, Intent::personal_message())?;
+        signature.verify_secure(
+            &IntentMessage::new(Intent::personal_message(), msg.clone()),
+            *address,
+        )?;
+    }
+
+    assert!(keystore.get_key(&addresses[0]).is_err());
+    assert!(keystore
+        .sign_secure(&SuiAddress::ZERO, &msg, Intent::personal_message())
+        .is_err());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_connects_lazily() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    let config = serde_json::to_string(&ExternalSigner::UnixSocket(socket_path.clone()))?;
+
+    // Loading the keystore must not need the signer to be up
+    let keystore: ExternalKeystore = serde_json::from_str(&config)?;
+    let keystore = Keystore::from(keystore);
+    let msg = PersonalMessage {
+        message: b"hello".to_vec(),
+    };
+    assert!(keystore
+        .sign_secure(&SuiAddress::ZERO, &msg, Intent::personal_message())
+        .is_err());
+
+    let mut signer_keys = InMemKeystore::default();
+    let (address, _, _) =
+        signer_keys.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+    spawn_external_signer(signer_keys, &socket_path);
+    assert_eq!(keystore.addresses(), vec![address]);
+    keystore.sign_secure(&address, &msg, Intent::personal_message())?;
+    Ok(())
+}
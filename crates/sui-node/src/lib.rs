@@ -63,6 +63,7 @@ use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::epoch::data_removal::EpochDataRemover;
 use sui_core::epoch::epoch_metrics::EpochMetrics;
 use sui_core::epoch::reconfiguration::ReconfigurationInitiator;
+use sui_core::event_sink::EventSink;
 use sui_core::module_cache_metrics::ResolverMetrics;
 use sui_core::narwhal_manager::{NarwhalConfiguration, NarwhalManager, NarwhalManagerMetrics};
 use sui_core::signature_verifier::SignatureVerifierMetrics;
@@ -141,6 +142,8 @@ pub struct SuiNode {
 
     _db_checkpoint_handle: Option<oneshot::Sender<()>>,
 
+    _event_sink_handle: Option<oneshot::Sender<()>>,
+
     #[cfg(msim)]
     sim_node: sui_simulator::runtime::NodeHandle,
 
@@ -390,6 +393,19 @@ impl SuiNode {
                 .unwrap();
         }
 
+        let event_sink_handle = match &config.event_sink_config {
+            Some(event_sink_config) => Some(
+                EventSink::new(
+                    state.clone(),
+                    event_sink_config,
+                    &config.db_path().join("event_sink_cursor"),
+                    &prometheus_registry,
+                )?
+                .start()?,
+            ),
+            None => None,
+        };
+
         let (end_of_epoch_channel, end_of_epoch_receiver) =
             broadcast::channel(config.end_of_epoch_broadcast_channel_capacity);
 
@@ -485,6 +501,7 @@ impl SuiNode {
             trusted_peer_change_tx,
 
             _db_checkpoint_handle: db_checkpoint_handle,
+            _event_sink_handle: event_sink_handle,
             #[cfg(msim)]
             sim_node: sui_simulator::runtime::NodeHandle::current(),
             #[cfg(msim)]
@@ -949,6 +966,7 @@ impl SuiNode {
             self.state.database.clone(),
             self.state.transaction_manager().clone(),
             self.accumulator.clone(),
+            self.state.subscription_handler.clone(),
             self.config.checkpoint_executor_config.clone(),
             &self.registry_service.default_registry(),
         );
@@ -283,6 +283,7 @@ impl RpcExampleProvider {
             events: SuiTransactionBlockEvents { data: vec![] },
             results: None,
             error: None,
+            trace: None,
         };
 
         Examples::new(
@@ -10,7 +10,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 10;
+const MAX_PROTOCOL_VERSION: u64 = 11;
 
 // Record history of protocol version allocations here:
 //
@@ -36,6 +36,7 @@ const MAX_PROTOCOL_VERSION: u64 = 10;
 // Version 10:increase bytecode verifier `max_verifier_meter_ticks_per_function` and
 //            `max_meter_ticks_per_module` limits each from 6_000_000 to 16_000_000. sui-system
 //            framework changes.
+// Version 11: Allow transactions to expire at a timestamp or at a checkpoint.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -178,6 +179,9 @@ struct FeatureFlags {
     // If true, checks no extra bytes in a compiled module
     #[serde(skip_serializing_if = "is_false")]
     no_extraneous_module_bytes: bool,
+    // If true, transactions can expire at a timestamp or checkpoint, not only at an epoch
+    #[serde(skip_serializing_if = "is_false")]
+    transaction_expiration_bounds: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -701,6 +705,10 @@ impl ProtocolConfig {
     pub fn no_extraneous_module_bytes(&self) -> bool {
         self.feature_flags.no_extraneous_module_bytes
     }
+
+    pub fn transaction_expiration_bounds(&self) -> bool {
+        self.feature_flags.transaction_expiration_bounds
+    }
 }
 
 // Special getters
@@ -1150,6 +1158,11 @@ impl ProtocolConfig {
                 cfg.max_meter_ticks_per_module = Some(16_000_000);
                 cfg
             }
+            11 => {
+                let mut cfg = Self::get_for_version_impl(version - 1);
+                cfg.feature_flags.transaction_expiration_bounds = true;
+                cfg
+            }
             // Use this template when making changes:
             //
             //     // modify an existing constant.
@@ -1,14 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::db_rider::DBRider;
 use crate::types::LocalExecError;
 use crate::types::EPOCH_CHANGE_STRUCT_TAG;
 use async_trait::async_trait;
 use futures::future::join_all;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::ModuleId;
 use move_core_types::parser::parse_struct_tag;
 use rand::Rng;
 use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use sui_core::authority::NodeStateDump;
 use sui_json_rpc::api::QUERY_MAX_RESULT_LIMIT;
 use sui_json_rpc_types::EventFilter;
@@ -18,16 +23,25 @@ use sui_json_rpc_types::SuiObjectData;
 use sui_json_rpc_types::SuiObjectDataOptions;
 use sui_json_rpc_types::SuiObjectResponse;
 use sui_json_rpc_types::SuiPastObjectResponse;
+use sui_json_rpc_types::SuiTransactionBlock;
+use sui_json_rpc_types::SuiTransactionBlockEffects;
+use sui_json_rpc_types::SuiTransactionBlockEvents;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 use sui_sdk::SuiClient;
 use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
 use sui_types::digests::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::event::Event;
+use sui_types::messages_checkpoint::{CheckpointContents, VerifiedCheckpoint};
 use sui_types::object::Object;
+use sui_types::storage::{get_module_by_id, BackingPackageStore, ObjectStore};
 use sui_types::transaction::SenderSignedData;
 use sui_types::transaction::TransactionDataAPI;
 use sui_types::transaction::TransactionKind;
 use tracing::error;
+use typed_store::traits::Map;
 /// This trait defines the interfaces for fetching data from some local or remote store
 #[async_trait]
 pub(crate) trait DataFetcher {
@@ -77,19 +91,20 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    NodeDb(NodeDbFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::NodeDb(_) => panic!("not a remote fetcher"),
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
+            Fetchers::Remote(_) | Fetchers::NodeDb(_) => panic!("not a node state dump fetcher"),
             Fetchers::NodeStateDump(q) => q,
         }
     }
@@ -105,6 +120,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::NodeDb(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -112,6 +128,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::NodeDb(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -119,6 +136,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::NodeDb(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -129,6 +147,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::NodeDb(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -139,6 +158,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::NodeDb(q) => q.get_loaded_child_objects(tx_digest).await,
         }
     }
 
@@ -146,6 +166,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::NodeDb(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -163,6 +184,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_tx(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::NodeDb(q) => {
+                q.fetch_random_tx(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -173,6 +198,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::NodeDb(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -183,6 +209,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::NodeDb(q) => q.get_epoch_change_events(reverse).await,
         }
     }
 }
@@ -326,36 +353,7 @@ impl DataFetcher for RemoteFetcher {
         &self,
         epoch_id: u64,
     ) -> Result<(u64, u64), LocalExecError> {
-        let event = self
-            .get_epoch_change_events(true)
-            .await?
-            .into_iter()
-            .find(|ev| match extract_epoch_and_version(ev.clone()) {
-                Ok((epoch, _)) => epoch == epoch_id,
-                Err(_) => false,
-            })
-            .ok_or(LocalExecError::EventNotFound { epoch: epoch_id })?;
-
-        let reference_gas_price = if let serde_json::Value::Object(w) = event.parsed_json {
-            u64::from_str(&w["reference_gas_price"].to_string().replace('\"', "")).unwrap()
-        } else {
-            return Err(LocalExecError::UnexpectedEventFormat {
-                event: event.clone(),
-            });
-        };
-
-        let epoch_change_tx = event.id.tx_digest;
-
-        // Fetch full transaction content
-        let tx_info = self.get_transaction(&epoch_change_tx).await?;
-
-        let orig_tx: SenderSignedData = bcs::from_bytes(&tx_info.raw_transaction).unwrap();
-        let tx_kind_orig = orig_tx.transaction_data().kind();
-
-        if let TransactionKind::ChangeEpoch(change) = tx_kind_orig {
-            return Ok((change.epoch_start_timestamp_ms, reference_gas_price));
-        }
-        Err(LocalExecError::InvalidEpochChangeTx { epoch: epoch_id })
+        epoch_start_timestamp_and_rgp_from_events(self, epoch_id).await
     }
 
     async fn get_epoch_change_events(
@@ -380,6 +378,44 @@ impl DataFetcher for RemoteFetcher {
     }
 }
 
+/// Finds the epoch change event for the given epoch, and extracts the epoch start timestamp and the
+/// reference gas price from it and its transaction
+async fn epoch_start_timestamp_and_rgp_from_events<F: DataFetcher + Sync>(
+    fetcher: &F,
+    epoch_id: u64,
+) -> Result<(u64, u64), LocalExecError> {
+    let event = fetcher
+        .get_epoch_change_events(true)
+        .await?
+        .into_iter()
+        .find(|ev| match extract_epoch_and_version(ev.clone()) {
+            Ok((epoch, _)) => epoch == epoch_id,
+            Err(_) => false,
+        })
+        .ok_or(LocalExecError::EventNotFound { epoch: epoch_id })?;
+
+    let reference_gas_price = if let serde_json::Value::Object(w) = event.parsed_json {
+        u64::from_str(&w["reference_gas_price"].to_string().replace('\"', "")).unwrap()
+    } else {
+        return Err(LocalExecError::UnexpectedEventFormat {
+            event: event.clone(),
+        });
+    };
+
+    let epoch_change_tx = event.id.tx_digest;
+
+    // Fetch full transaction content
+    let tx_info = fetcher.get_transaction(&epoch_change_tx).await?;
+
+    let orig_tx: SenderSignedData = bcs::from_bytes(&tx_info.raw_transaction).unwrap();
+    let tx_kind_orig = orig_tx.transaction_data().kind();
+
+    if let TransactionKind::ChangeEpoch(change) = tx_kind_orig {
+        return Ok((change.epoch_start_timestamp_ms, reference_gas_price));
+    }
+    Err(LocalExecError::InvalidEpochChangeTx { epoch: epoch_id })
+}
+
 fn convert_past_obj_response(resp: SuiPastObjectResponse) -> Result<Object, LocalExecError> {
     match resp {
         SuiPastObjectResponse::VersionFound(o) => obj_from_sui_obj_data(&o),
@@ -550,3 +586,347 @@ impl DataFetcher for NodeStateDumpFetcher {
         unimplemented!("get_epoch_change_events for state dump is not implemented")
     }
 }
+
+/// Fetches data directly from the tables of a node database on disk, without going through RPC
+#[derive(Clone)]
+pub struct NodeDbFetcher {
+    pub db: Arc<DBRider>,
+    /// Epoch change events of the epochs scanned so far, in epoch order, along with the next epoch
+    /// to scan
+    epoch_change_events: Arc<Mutex<(u64, Vec<SuiEvent>)>>,
+}
+
+impl NodeDbFetcher {
+    pub fn new(db: Arc<DBRider>) -> Self {
+        Self {
+            db,
+            epoch_change_events: Arc::new(Mutex::new((0, vec![]))),
+        }
+    }
+
+    fn get_checkpoint(&self, id: u64) -> Result<VerifiedCheckpoint, LocalExecError> {
+        self.db
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(id)?
+            .ok_or(LocalExecError::CheckpointNotFound { id })
+    }
+
+    fn get_checkpoint_contents(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<CheckpointContents, LocalExecError> {
+        self.db
+            .checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or(LocalExecError::CheckpointNotFound {
+                id: *checkpoint.sequence_number(),
+            })
+    }
+
+    fn get_effects(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<TransactionEffects, LocalExecError> {
+        self.db
+            .perpetual_store
+            .get_effects(tx_digest)?
+            .ok_or(LocalExecError::TransactionNotFound { digest: *tx_digest })
+    }
+
+    /// Loads the events emitted by a transaction, keeping only those which match `filter`
+    fn get_events(
+        &self,
+        tx_digest: &TransactionDigest,
+        effects: &TransactionEffects,
+        timestamp_ms: Option<u64>,
+        filter: impl Fn(&Event) -> bool,
+    ) -> Result<Vec<SuiEvent>, LocalExecError> {
+        let Some(events_digest) = effects.events_digest() else {
+            return Ok(vec![]);
+        };
+        let events = self
+            .db
+            .perpetual_store
+            .get_events(events_digest)?
+            .map(|events| events.data)
+            .unwrap_or_default();
+        events
+            .into_iter()
+            .enumerate()
+            .filter(|(_, event)| filter(event))
+            .map(|(seq, event)| {
+                SuiEvent::try_from(event, *tx_digest, seq as u64, timestamp_ms, self)
+                    .map_err(LocalExecError::from)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DataFetcher for NodeDbFetcher {
+    #![allow(implied_bounds_entailment)]
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, LocalExecError> {
+        objects
+            .iter()
+            .map(|(id, version)| {
+                self.db
+                    .perpetual_store
+                    .get_object_by_key(id, *version)?
+                    .ok_or(LocalExecError::ObjectVersionNotFound {
+                        id: *id,
+                        version: *version,
+                    })
+            })
+            .collect()
+    }
+
+    async fn multi_get_latest(&self, objects: &[ObjectID]) -> Result<Vec<Object>, LocalExecError> {
+        objects
+            .iter()
+            .map(|id| {
+                let (id, version, digest) = self
+                    .db
+                    .perpetual_store
+                    .get_object_or_tombstone(*id)?
+                    .ok_or(LocalExecError::ObjectNotExist { id: *id })?;
+                if !digest.is_alive() {
+                    return Err(LocalExecError::ObjectDeleted {
+                        id,
+                        version,
+                        digest,
+                    });
+                }
+                self.db
+                    .perpetual_store
+                    .get_object_by_key(&id, version)?
+                    .ok_or(LocalExecError::ObjectVersionNotFound { id, version })
+            })
+            .collect()
+    }
+
+    async fn get_checkpoint_txs(&self, id: u64) -> Result<Vec<TransactionDigest>, LocalExecError> {
+        let checkpoint = self.get_checkpoint(id)?;
+        Ok(self
+            .get_checkpoint_contents(&checkpoint)?
+            .iter()
+            .map(|digests| digests.transaction)
+            .collect())
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, LocalExecError> {
+        let transaction = self
+            .db
+            .perpetual_store
+            .get_transaction(tx_digest)?
+            .ok_or(LocalExecError::TransactionNotFound { digest: *tx_digest })?;
+        let sender_signed_data: SenderSignedData = transaction.into_inner().into_data();
+        let raw_transaction = bcs::to_bytes(&sender_signed_data)
+            .map_err(|e| LocalExecError::GeneralError { err: e.to_string() })?;
+        let effects = self.get_effects(tx_digest)?;
+        let checkpoint = self
+            .db
+            .perpetual_store
+            .get_checkpoint_sequence_number(tx_digest)?
+            .map(|(_, seq)| seq);
+        let timestamp_ms = match checkpoint {
+            Some(seq) => Some(self.get_checkpoint(seq)?.timestamp_ms),
+            None => None,
+        };
+        let events = self.get_events(tx_digest, &effects, timestamp_ms, |_| true)?;
+
+        Ok(SuiTransactionBlockResponse {
+            digest: *tx_digest,
+            transaction: Some(SuiTransactionBlock::try_from(sender_signed_data, self)?),
+            raw_transaction,
+            effects: Some(SuiTransactionBlockEffects::try_from(effects)?),
+            events: Some(SuiTransactionBlockEvents { data: events }),
+            timestamp_ms,
+            checkpoint,
+            ..Default::default()
+        })
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, LocalExecError> {
+        // The index is only populated on fullnodes
+        Ok(self
+            .db
+            .index_store
+            .loaded_child_object_versions
+            .get(tx_digest)?
+            .unwrap_or_default())
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, LocalExecError> {
+        self.db
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?
+            .ok_or(LocalExecError::GeneralError {
+                err: "No executed checkpoints in node db".to_string(),
+            })
+    }
+
+    async fn fetch_random_tx(
+        &self,
+        // TODO: add more params
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, LocalExecError> {
+        let checkpoint_id_end =
+            checkpoint_id_end.unwrap_or(self.get_latest_checkpoint_sequence_number().await?);
+        let checkpoint_id_start = checkpoint_id_start.unwrap_or(1);
+        let checkpoint_id = rand::thread_rng().gen_range(checkpoint_id_start..=checkpoint_id_end);
+
+        let txs = self.get_checkpoint_txs(checkpoint_id).await?;
+        let tx_idx = rand::thread_rng().gen_range(0..txs.len());
+
+        Ok(txs[tx_idx])
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), LocalExecError> {
+        epoch_start_timestamp_and_rgp_from_events(self, epoch_id).await
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        reverse: bool,
+    ) -> Result<Vec<SuiEvent>, LocalExecError> {
+        let struct_tag = parse_struct_tag(EPOCH_CHANGE_STRUCT_TAG)?;
+
+        // The epoch change TX is always the last TX of the last checkpoint of an epoch. Epochs
+        // scanned by earlier calls are not scanned again.
+        let mut cache = self.epoch_change_events.lock().unwrap();
+        let (next_epoch, cached_events) = &mut *cache;
+        while let Some(checkpoint) = self
+            .db
+            .checkpoint_store
+            .get_epoch_last_checkpoint(*next_epoch)?
+        {
+            let contents = self.get_checkpoint_contents(&checkpoint)?;
+            if let Some(digests) = contents.iter().last() {
+                let effects = self.get_effects(&digests.transaction)?;
+                cached_events.extend(self.get_events(
+                    &digests.transaction,
+                    &effects,
+                    Some(checkpoint.timestamp_ms),
+                    |event| event.type_ == struct_tag,
+                )?);
+            }
+            *next_epoch += 1;
+        }
+
+        let mut events = cached_events.clone();
+        if reverse {
+            events.reverse();
+        }
+        Ok(events)
+    }
+}
+
+impl BackingPackageStore for NodeDbFetcher {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.db.perpetual_store.get_object(package_id)
+    }
+}
+
+impl GetModule for NodeDbFetcher {
+    type Error = SuiError;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> anyhow::Result<Option<Self::Item>, Self::Error> {
+        get_module_by_id(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::Registry;
+    use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_storage::IndexStore;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::transaction::TransactionData;
+    use sui_types::utils::to_sender_signed_transaction;
+
+    #[tokio::test]
+    async fn test_node_db_fetcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let perpetual_store = Arc::new(AuthorityPerpetualTables::open(
+            &dir.path().join("store"),
+            None,
+        ));
+        let _index_store = IndexStore::new(dir.path().join("indexes"), &Registry::new(), None);
+        let _checkpoint_store = CheckpointStore::new(&dir.path().join("checkpoints"));
+
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+        perpetual_store
+            .insert_object_test_only(object.clone())
+            .unwrap();
+        let transaction = to_sender_signed_transaction(
+            TransactionData::new_transfer_sui(
+                SuiAddress::random_for_testing_only(),
+                sender,
+                None,
+                random_object_ref(),
+                1_000_000,
+                1_000,
+            ),
+            &key,
+        );
+        let effects = TransactionEffects::new_with_tx(&transaction);
+        perpetual_store
+            .insert_executed_transaction_test_only(&transaction, &effects)
+            .unwrap();
+
+        // The stores are still open in read-write mode, as they would be by a running node
+        let fetcher = NodeDbFetcher::new(Arc::new(DBRider::open(dir.path())));
+
+        let versioned = fetcher
+            .multi_get_versioned(&[(object.id(), object.version())])
+            .await
+            .unwrap();
+        assert_eq!(versioned, vec![object.clone()]);
+        let latest = fetcher.multi_get_latest(&[object.id()]).await.unwrap();
+        assert_eq!(latest, vec![object]);
+        assert!(matches!(
+            fetcher.multi_get_latest(&[ObjectID::random()]).await,
+            Err(LocalExecError::ObjectNotExist { .. })
+        ));
+
+        let response = fetcher.get_transaction(transaction.digest()).await.unwrap();
+        assert_eq!(response.digest, *transaction.digest());
+        assert_eq!(
+            response.raw_transaction,
+            bcs::to_bytes(transaction.data()).unwrap()
+        );
+        assert_eq!(
+            response.effects.unwrap(),
+            SuiTransactionBlockEffects::try_from(effects).unwrap()
+        );
+        assert_eq!(response.checkpoint, None);
+        assert!(matches!(
+            fetcher.get_transaction(&TransactionDigest::random()).await,
+            Err(LocalExecError::TransactionNotFound { .. })
+        ));
+        // No epoch has ended yet
+        assert!(fetcher
+            .get_epoch_change_events(false)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
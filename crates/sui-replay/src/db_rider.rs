@@ -1,39 +1,49 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::PathBuf;
-use sui_core::authority::authority_store_tables::{
-    AuthorityPerpetualTables, AuthorityPerpetualTablesReadOnly,
-};
+use std::path::Path;
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::checkpoints::CheckpointStore;
 use sui_storage::indexes::IndexStoreTablesReadOnly;
 use sui_storage::IndexStoreTables;
 use typed_store::rocks::MetricConf;
 
-pub struct _DBRider {
+/// Handles to the tables of a node database which are needed for replay.
+/// The path is expected to point to the node's live db dir, e.g. `/opt/sui/db/authorities_db/live`,
+/// or to a db checkpoint of it.
+/// All the stores are opened in secondary mode, so they are never written to and the node can keep
+/// running. Only the data written by the node before they are opened is visible.
+pub struct DBRider {
     pub index_store: IndexStoreTablesReadOnly,
-    pub perpatual_store: AuthorityPerpetualTablesReadOnly,
+    pub perpetual_store: Arc<AuthorityPerpetualTables>,
+    pub checkpoint_store: Arc<CheckpointStore>,
 }
 
-impl _DBRider {
-    pub fn _open(path: PathBuf) -> Self {
-        let mut index_path = path.clone();
-        index_path.push("indexes");
-
-        let index_store =
-            IndexStoreTables::get_read_only_handle(index_path, None, None, MetricConf::default());
-        let mut perpetual_path = path;
-        perpetual_path.push("store");
-        perpetual_path.push("perpetual");
-
-        let perpatual_store = AuthorityPerpetualTables::get_read_only_handle(
-            perpetual_path,
+impl DBRider {
+    pub fn open(path: &Path) -> Self {
+        let index_store = IndexStoreTables::get_read_only_handle(
+            path.join("indexes"),
             None,
             None,
             MetricConf::default(),
         );
+        let perpetual_store = Arc::new(AuthorityPerpetualTables::open_tables_secondary(
+            AuthorityPerpetualTables::path(&path.join("store")),
+            None,
+            MetricConf::default(),
+            None,
+        ));
+        let checkpoint_store = Arc::new(CheckpointStore::open_tables_secondary(
+            path.join("checkpoints"),
+            None,
+            MetricConf::default(),
+            None,
+        ));
         Self {
             index_store,
-            perpatual_store,
+            perpetual_store,
+            checkpoint_store,
         }
     }
 }
@@ -0,0 +1,232 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use similar::{ChangeTag, TextDiff};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
+use sui_types::gas::GasCostSummary;
+use sui_types::storage::{DeleteKind, WriteKind};
+
+/// How an object was changed by a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectChangeKind {
+    Write(WriteKind),
+    Delete(DeleteKind),
+}
+
+/// The state of an object after a transaction, as recorded in the effects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectChange {
+    pub kind: ObjectChangeKind,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+}
+
+/// An object which the on-chain and local effects do not agree on
+#[derive(Debug, Clone)]
+pub struct ObjectDiff {
+    pub id: ObjectID,
+    pub on_chain: Option<ObjectChange>,
+    pub local: Option<ObjectChange>,
+    /// Line diff of the object contents, if both sides wrote the object and it could be fetched
+    pub contents_diff: Option<String>,
+}
+
+/// An event which the on-chain and local executions do not agree on.
+/// Events are compared by position, type and BCS contents.
+#[derive(Debug, Clone)]
+pub struct EventDiff {
+    pub seq: usize,
+    pub on_chain: Option<StructTag>,
+    pub local: Option<StructTag>,
+}
+
+/// Structured diff between the on-chain effects of a transaction and the effects of executing it
+/// locally, possibly under a different protocol config or framework
+#[derive(Debug, Clone)]
+pub struct EffectsDiff {
+    pub tx_digest: TransactionDigest,
+    pub status: Option<(SuiExecutionStatus, SuiExecutionStatus)>,
+    pub gas_cost: Option<(GasCostSummary, GasCostSummary)>,
+    pub objects: Vec<ObjectDiff>,
+    pub events: Vec<EventDiff>,
+}
+
+impl EffectsDiff {
+    pub fn new(
+        tx_digest: TransactionDigest,
+        on_chain: &SuiTransactionBlockEffects,
+        local: &SuiTransactionBlockEffects,
+    ) -> Self {
+        let status = (on_chain.status() != local.status())
+            .then(|| (on_chain.status().clone(), local.status().clone()));
+        let gas_cost = (on_chain.gas_cost_summary() != local.gas_cost_summary()).then(|| {
+            (
+                on_chain.gas_cost_summary().clone(),
+                local.gas_cost_summary().clone(),
+            )
+        });
+
+        let on_chain_changes = object_changes(on_chain);
+        let local_changes = object_changes(local);
+        let objects = on_chain_changes
+            .keys()
+            .chain(local_changes.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|id| {
+                let on_chain = on_chain_changes.get(id).cloned();
+                let local = local_changes.get(id).cloned();
+                (on_chain != local).then_some(ObjectDiff {
+                    id: *id,
+                    on_chain,
+                    local,
+                    contents_diff: None,
+                })
+            })
+            .collect();
+
+        Self {
+            tx_digest,
+            status,
+            gas_cost,
+            objects,
+            events: vec![],
+        }
+    }
+
+    /// Compares the events emitted on-chain and locally, given as (type, BCS contents)
+    pub fn with_events(
+        mut self,
+        on_chain: &[(StructTag, Vec<u8>)],
+        local: &[(StructTag, Vec<u8>)],
+    ) -> Self {
+        self.events = (0..on_chain.len().max(local.len()))
+            .filter(|seq| on_chain.get(*seq) != local.get(*seq))
+            .map(|seq| EventDiff {
+                seq,
+                on_chain: on_chain.get(seq).map(|(type_, _)| type_.clone()),
+                local: local.get(seq).map(|(type_, _)| type_.clone()),
+            })
+            .collect();
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.gas_cost.is_none()
+            && self.objects.is_empty()
+            && self.events.is_empty()
+    }
+}
+
+fn object_changes(effects: &SuiTransactionBlockEffects) -> BTreeMap<ObjectID, ObjectChange> {
+    let written = effects.all_changed_objects().into_iter().map(|(o, kind)| {
+        (
+            o.reference.object_id,
+            ObjectChange {
+                kind: ObjectChangeKind::Write(kind),
+                version: o.reference.version,
+                digest: o.reference.digest,
+            },
+        )
+    });
+    let deleted = effects.all_deleted_objects().into_iter().map(|(o, kind)| {
+        (
+            o.object_id,
+            ObjectChange {
+                kind: ObjectChangeKind::Delete(kind),
+                version: o.version,
+                digest: o.digest,
+            },
+        )
+    });
+    written.chain(deleted).collect()
+}
+
+/// Line by line diff of two strings, marking removed lines with `---` and added lines with `+++`
+pub fn diff_lines(old: &str, new: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| {
+            let sign = match change.tag() {
+                ChangeTag::Delete => "---",
+                ChangeTag::Insert => "+++",
+                ChangeTag::Equal => "   ",
+            };
+            format!("{}{}", sign, change)
+        })
+        .collect()
+}
+
+impl Display for ObjectChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} at version {} digest {}",
+            self.kind, self.version, self.digest
+        )
+    }
+}
+
+impl Display for EffectsDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(
+                f,
+                "{}: local effects match on-chain effects",
+                self.tx_digest
+            );
+        }
+        writeln!(f, "{}: local effects differ from on-chain", self.tx_digest)?;
+        if let Some((on_chain, local)) = &self.status {
+            writeln!(f, "  Status")?;
+            writeln!(f, "    on-chain: {:?}", on_chain)?;
+            writeln!(f, "    local:    {:?}", local)?;
+        }
+        if let Some((on_chain, local)) = &self.gas_cost {
+            writeln!(f, "  Gas cost")?;
+            writeln!(f, "    on-chain: {}", on_chain)?;
+            writeln!(f, "    local:    {}", local)?;
+            writeln!(
+                f,
+                "    gas used: {} -> {} ({:+})",
+                on_chain.gas_used(),
+                local.gas_used(),
+                local.gas_used() as i128 - on_chain.gas_used() as i128
+            )?;
+        }
+        if !self.objects.is_empty() {
+            writeln!(f, "  Objects")?;
+        }
+        for object in &self.objects {
+            writeln!(f, "    {}", object.id)?;
+            let on_chain = object.on_chain.as_ref().map(|c| c.to_string());
+            let local = object.local.as_ref().map(|c| c.to_string());
+            writeln!(f, "      on-chain: {}", on_chain.as_deref().unwrap_or("-"))?;
+            writeln!(f, "      local:    {}", local.as_deref().unwrap_or("-"))?;
+            if let Some(contents_diff) = &object.contents_diff {
+                for line in contents_diff.lines() {
+                    writeln!(f, "      {}", line)?;
+                }
+            }
+        }
+        if !self.events.is_empty() {
+            writeln!(f, "  Events")?;
+        }
+        for event in &self.events {
+            let on_chain = event.on_chain.as_ref().map(|t| t.to_string());
+            let local = event.local.as_ref().map(|t| t.to_string());
+            writeln!(f, "    #{}", event.seq)?;
+            writeln!(f, "      on-chain: {}", on_chain.as_deref().unwrap_or("-"))?;
+            writeln!(f, "      local:    {}", local.as_deref().unwrap_or("-"))?;
+        }
+        Ok(())
+    }
+}
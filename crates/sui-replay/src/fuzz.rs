@@ -1,26 +1,36 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use futures::FutureExt;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_types::{
-    digests::TransactionDigest, execution_status::ExecutionFailureStatus,
-    transaction::TransactionKind,
+    base_types::{ObjectID, ObjectRef},
+    digests::TransactionDigest,
+    execution_status::ExecutionFailureStatus,
+    object::Object,
+    transaction::{CallArg, InputObjectKind, ObjectArg, ProgrammableTransaction, TransactionKind},
 };
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
     data_fetcher::DataFetcher,
     replay::{ExecutionSandboxState, LocalExec},
-    types::LocalExecError,
+    types::{LocalExecError, OnChainTransactionInfo},
 };
-use rand::{rngs::ThreadRng, seq::SliceRandom};
+use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
 
 // Step 1: Get a transaction T from the network
 // Step 2: Create the sandbox and verify the TX does not fork locally
 // Step 3: Create desired mutations of T in set S
 // Step 4: For each mutation in S, replay the transaction with the sandbox state from T
 //         and verify no panic or invariant violation
+// Step 5: If a mutation fails, minimize it and save it as a reproducer
 
 pub struct ReplayFuzzerConfig {
     pub checkpoint_id_start: Option<u64>,
@@ -28,6 +38,8 @@ pub struct ReplayFuzzerConfig {
     pub num_mutations_per_base: u64,
 
     pub mutator: Box<dyn TransactionKindMutator>,
+    /// Directory to save minimized reproducers of failures to
+    pub reproducer_dir: Option<PathBuf>,
 }
 
 /// Provides the starting transaction for a fuzz session
@@ -38,10 +50,31 @@ pub struct ReplayFuzzer {
     pub config: ReplayFuzzerConfig,
 }
 
+/// The parts of a transaction which mutators can change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzTransaction {
+    pub kind: TransactionKind,
+    pub gas_budget: u64,
+    pub gas_price: u64,
+}
+
+impl From<&OnChainTransactionInfo> for FuzzTransaction {
+    fn from(tx_info: &OnChainTransactionInfo) -> Self {
+        Self {
+            kind: tx_info.kind.clone(),
+            gas_budget: tx_info.gas_budget,
+            gas_price: tx_info.gas_price,
+        }
+    }
+}
+
 pub trait TransactionKindMutator {
-    fn mutate(&mut self, transaction_kind: &TransactionKind) -> Option<TransactionKind>;
+    fn mutate(&mut self, transaction: &FuzzTransaction) -> Option<FuzzTransaction>;
 
     fn reset(&mut self, mutations_per_base: u64);
+
+    /// Called with the sandbox state of every base transaction, before it is mutated
+    fn observe(&mut self, _sandbox_state: &ExecutionSandboxState) {}
 }
 
 impl ReplayFuzzer {
@@ -55,20 +88,23 @@ impl ReplayFuzzer {
             .init_for_execution()
             .await?;
 
-        Self::new_with_local_executor(local_exec, base_transaction, config).await
+        Ok(Self::new_with_local_executor(local_exec, base_transaction, config).await?)
     }
 
     pub async fn new_with_local_executor(
         mut local_exec: LocalExec,
         base_transaction: Option<TransactionDigest>,
-        config: ReplayFuzzerConfig,
-    ) -> Result<Self, anyhow::Error> {
-        let base_transaction = base_transaction.unwrap_or(
-            local_exec
-                .fetcher
-                .fetch_random_tx(config.checkpoint_id_start, config.checkpoint_id_end)
-                .await?,
-        );
+        mut config: ReplayFuzzerConfig,
+    ) -> Result<Self, LocalExecError> {
+        let base_transaction = match base_transaction {
+            Some(base_transaction) => base_transaction,
+            None => {
+                local_exec
+                    .fetcher
+                    .fetch_random_tx(config.checkpoint_id_start, config.checkpoint_id_end)
+                    .await?
+            }
+        };
 
         let sandbox_state = local_exec
             .execute_transaction(
@@ -77,6 +113,7 @@ impl ReplayFuzzer {
                 false,
             )
             .await?;
+        config.mutator.observe(&sandbox_state);
 
         Ok(Self {
             base_transaction,
@@ -89,7 +126,7 @@ impl ReplayFuzzer {
     pub async fn re_init(
         mut self,
         base_transaction: Option<TransactionDigest>,
-    ) -> Result<Self, anyhow::Error> {
+    ) -> Result<Self, LocalExecError> {
         let local_executor = self
             .local_exec
             .reset_for_new_execution_with_client()
@@ -100,14 +137,35 @@ impl ReplayFuzzer {
         Self::new_with_local_executor(local_executor, base_transaction, self.config).await
     }
 
+    /// The transaction info of the base transaction, with the mutation applied
+    fn mutated_transaction_info(&self, transaction: &FuzzTransaction) -> OnChainTransactionInfo {
+        let mut tx_info = self.sandbox_state.transaction_info.clone();
+        // Inputs may have been swapped, so the input objects to download must be recomputed
+        if transaction.kind != tx_info.kind {
+            if let Ok(mut input_objects) = transaction.kind.input_objects() {
+                input_objects.extend(
+                    tx_info
+                        .gas
+                        .iter()
+                        .map(|gas| InputObjectKind::ImmOrOwnedMoveObject(*gas)),
+                );
+                tx_info.input_objects = input_objects;
+            }
+        }
+        tx_info.gas_budget = transaction.gas_budget;
+        tx_info.gas_price = transaction.gas_price;
+        tx_info
+    }
+
     pub async fn execute_tx(
         &mut self,
-        transaction_kind: &TransactionKind,
+        transaction: &FuzzTransaction,
     ) -> Result<ExecutionSandboxState, LocalExecError> {
+        let tx_info = self.mutated_transaction_info(transaction);
         self.local_exec
             .execution_engine_execute_with_tx_info_impl(
-                &self.sandbox_state.transaction_info,
-                Some(transaction_kind.clone()),
+                &tx_info,
+                Some(transaction.kind.clone()),
                 ExpensiveSafetyCheckConfig::new_enable_all(),
             )
             .await
@@ -115,9 +173,16 @@ impl ReplayFuzzer {
 
     pub async fn execute_tx_and_check_status(
         &mut self,
-        transaction_kind: &TransactionKind,
+        transaction: &FuzzTransaction,
     ) -> Result<ExecutionSandboxState, ReplayFuzzError> {
-        let sandbox_state = self.execute_tx(transaction_kind).await?;
+        let sandbox_state = AssertUnwindSafe(self.execute_tx(transaction))
+            .catch_unwind()
+            .await
+            .map_err(|panic| ReplayFuzzError::Panic {
+                tx_digest: self.base_transaction,
+                kind: transaction.kind.clone(),
+                message: panic_message(panic),
+            })??;
         if let Err(e) = &sandbox_state.local_exec_status {
             let stat = e.to_execution_status().0;
             match &stat {
@@ -125,7 +190,7 @@ impl ReplayFuzzer {
                 | ExecutionFailureStatus::VMInvariantViolation => {
                     return Err(ReplayFuzzError::InvariantViolation {
                         tx_digest: self.base_transaction,
-                        kind: transaction_kind.clone(),
+                        kind: transaction.kind.clone(),
                         exec_status: stat,
                     });
                 }
@@ -135,38 +200,68 @@ impl ReplayFuzzer {
         Ok(sandbox_state)
     }
 
-    // Simple command and arg shuffle mutation
-    // TODO: do more complicated mutations
-    pub fn next_mutation(&mut self, transaction_kind: &TransactionKind) -> Option<TransactionKind> {
-        self.config.mutator.mutate(transaction_kind)
+    pub fn next_mutation(&mut self, transaction: &FuzzTransaction) -> Option<FuzzTransaction> {
+        self.config.mutator.mutate(transaction)
     }
 
-    pub async fn run(mut self, mut num_base_tx: u64) -> Result<(), ReplayFuzzError> {
-        let mut tx_kind = self.sandbox_state.transaction_info.kind.clone();
+    /// Shrinks a failing mutation while it keeps failing the same way
+    pub async fn minimize(
+        &mut self,
+        transaction: FuzzTransaction,
+        failure: &ReplayFuzzError,
+    ) -> FuzzTransaction {
+        let base = FuzzTransaction::from(&self.sandbox_state.transaction_info);
+        let mut minimizer = Minimizer::new(&base, transaction);
+        while let Some(candidate) = minimizer.next_candidate() {
+            let result = self.execute_tx_and_check_status(&candidate).await;
+            minimizer.report(fails_same_way(&result, failure));
+        }
+        minimizer.minimized
+    }
+
+    /// Minimizes the failing mutation and saves it to the reproducer dir, if one is configured
+    async fn save_reproducer(&mut self, transaction: FuzzTransaction, failure: &ReplayFuzzError) {
+        let Some(dir) = self.config.reproducer_dir.clone() else {
+            return;
+        };
+        let transaction = self.minimize(transaction, failure).await;
+        let reproducer = FuzzReproducer {
+            base_transaction: self.base_transaction,
+            transaction,
+            error: failure.to_string(),
+        };
+        match reproducer.save(&dir) {
+            Ok(path) => info!("Saved fuzz reproducer to {}", path.display()),
+            Err(e) => error!("Failed to save fuzz reproducer to {}: {e}", dir.display()),
+        }
+    }
 
+    pub async fn run(mut self, mut num_base_tx: u64) -> Result<(), ReplayFuzzError> {
         while num_base_tx > 0 {
             info!(
                 "Starting fuzz with new base TX {}",
                 self.sandbox_state.transaction_info.tx_digest
             );
+            let base = FuzzTransaction::from(&self.sandbox_state.transaction_info);
 
-            while let Some(mutation) = self.next_mutation(&tx_kind) {
-                let status = self.execute_tx_and_check_status(&mutation).await;
-                if let Err(ReplayFuzzError::InvariantViolation {
-                    tx_digest,
-                    kind,
-                    exec_status,
-                }) = &status
-                {
-                    error!(
-                        "Invariant violation: tx digest: {:?}\n kind: {:#?}\nstatus{:?}",
-                        tx_digest, kind, exec_status
-                    );
-                    return Err(status.unwrap_err());
-                };
-                tx_kind = status.unwrap().transaction_info.kind.clone();
+            while let Some(mutation) = self.next_mutation(&base) {
+                match self.execute_tx_and_check_status(&mutation).await {
+                    Err(
+                        failure @ (ReplayFuzzError::InvariantViolation { .. }
+                        | ReplayFuzzError::Panic { .. }),
+                    ) => {
+                        error!(
+                            "Fuzz failure: {}\ngas budget: {}, gas price: {}\nkind: {:#?}",
+                            failure, mutation.gas_budget, mutation.gas_price, mutation.kind
+                        );
+                        self.save_reproducer(mutation, &failure).await;
+                        return Err(failure);
+                    }
+                    Err(e) => warn!("Mutation could not be executed: {e}"),
+                    Ok(_) => (),
+                }
             }
-            self = self.re_init(None).await.unwrap();
+            self = self.re_init(None).await?;
             num_base_tx -= 1;
         }
 
@@ -174,6 +269,131 @@ impl ReplayFuzzer {
     }
 }
 
+/// Whether the result of executing a mutation is the same failure as `expected`: an invariant
+/// violation with the same status, or a panic with the same message
+fn fails_same_way(
+    result: &Result<ExecutionSandboxState, ReplayFuzzError>,
+    expected: &ReplayFuzzError,
+) -> bool {
+    match (result, expected) {
+        (
+            Err(ReplayFuzzError::InvariantViolation { exec_status, .. }),
+            ReplayFuzzError::InvariantViolation {
+                exec_status: expected,
+                ..
+            },
+        ) => exec_status == expected,
+        (
+            Err(ReplayFuzzError::Panic { message, .. }),
+            ReplayFuzzError::Panic {
+                message: expected, ..
+            },
+        ) => message == expected,
+        _ => false,
+    }
+}
+
+/// Shrinks a failing mutation one candidate at a time: the gas values are reset to the base ones
+/// first, then commands are dropped one by one, starting from the last one. Each candidate is
+/// kept if it is reported to still fail the same way.
+struct Minimizer {
+    minimized: FuzzTransaction,
+    /// The candidate returned last, until its result is reported
+    candidate: Option<FuzzTransaction>,
+    /// Gas budget and price of the base transaction, until they have been tried
+    base_gas: Option<(u64, u64)>,
+    /// Dropping the commands before this index remains to be tried
+    next_command: usize,
+}
+
+impl Minimizer {
+    fn new(base: &FuzzTransaction, transaction: FuzzTransaction) -> Self {
+        let next_command = match &transaction.kind {
+            TransactionKind::ProgrammableTransaction(p) => p.commands.len(),
+            _ => 0,
+        };
+        Self {
+            minimized: transaction,
+            candidate: None,
+            base_gas: Some((base.gas_budget, base.gas_price)),
+            next_command,
+        }
+    }
+
+    /// The next smaller transaction to try, if any
+    fn next_candidate(&mut self) -> Option<FuzzTransaction> {
+        if let Some((gas_budget, gas_price)) = self.base_gas.take() {
+            let candidate = FuzzTransaction {
+                gas_budget,
+                gas_price,
+                ..self.minimized.clone()
+            };
+            if candidate != self.minimized {
+                self.candidate = Some(candidate.clone());
+                return Some(candidate);
+            }
+        }
+        let TransactionKind::ProgrammableTransaction(p) = &self.minimized.kind else {
+            return None;
+        };
+        if self.next_command == 0 {
+            return None;
+        }
+        self.next_command -= 1;
+        let mut smaller = p.clone();
+        smaller.commands.remove(self.next_command);
+        let candidate = FuzzTransaction {
+            kind: TransactionKind::ProgrammableTransaction(smaller),
+            ..self.minimized.clone()
+        };
+        self.candidate = Some(candidate.clone());
+        Some(candidate)
+    }
+
+    /// Records whether the last candidate still fails the same way
+    fn report(&mut self, fails_same_way: bool) {
+        if let Some(candidate) = self.candidate.take() {
+            if fails_same_way {
+                self.minimized = candidate;
+            }
+        }
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// A minimized failing mutation, which can be replayed on top of its base transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzReproducer {
+    pub base_transaction: TransactionDigest,
+    pub transaction: FuzzTransaction,
+    pub error: String,
+}
+
+impl FuzzReproducer {
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, anyhow::Error> {
+        std::fs::create_dir_all(dir)?;
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis();
+        let path = dir.join(format!("{}_{}.json", self.base_transaction, timestamp));
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Error, Clone)]
 pub enum ReplayFuzzError {
@@ -187,13 +407,18 @@ pub enum ReplayFuzzError {
         exec_status: ExecutionFailureStatus,
     },
 
+    #[error("Panic: digest: {tx_digest}, kind: {kind}, message: {message}")]
+    Panic {
+        tx_digest: TransactionDigest,
+        kind: TransactionKind,
+        message: String,
+    },
+
     #[error(
         "LocalExecError: exec system error which may/not be related to fuzzing: {:?}.",
         err
     )]
     LocalExecError { err: LocalExecError },
-    // TODO: how exactly do we catch this?
-    //Panic(TransactionDigest, TransactionKind),
 }
 
 impl From<LocalExecError> for ReplayFuzzError {
@@ -208,19 +433,21 @@ pub struct ShuffleMutator {
 }
 
 impl TransactionKindMutator for ShuffleMutator {
-    fn mutate(&mut self, transaction_kind: &TransactionKind) -> Option<TransactionKind> {
+    fn mutate(&mut self, transaction: &FuzzTransaction) -> Option<FuzzTransaction> {
         if self.num_mutations_per_base_left == 0 {
             // Nothing else to do
             return None;
         }
 
         self.num_mutations_per_base_left -= 1;
-        if let TransactionKind::ProgrammableTransaction(mut p) = transaction_kind.clone() {
+        if let TransactionKind::ProgrammableTransaction(mut p) = transaction.kind.clone() {
             // Simple command and arg shuffle mutation
-            // TODO: do more complicated mutations
             p.commands.shuffle(&mut self.rng);
             p.inputs.shuffle(&mut self.rng);
-            Some(TransactionKind::ProgrammableTransaction(p))
+            Some(FuzzTransaction {
+                kind: TransactionKind::ProgrammableTransaction(p),
+                ..transaction.clone()
+            })
         } else {
             // Other types not supported yet
             None
@@ -231,3 +458,419 @@ impl TransactionKindMutator for ShuffleMutator {
         self.num_mutations_per_base_left = mutations_per_base;
     }
 }
+
+/// The mutations that `MultiMutator` can apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutatorKind {
+    /// Shuffle the commands and inputs
+    Shuffle,
+    /// Remove a command
+    DropCommand,
+    /// Repeat a command right after itself
+    DuplicateCommand,
+    /// Change the bytes of a pure input, around integer width and value boundaries
+    PureBytes,
+    /// Replace an owned or immutable object input with a different object of the same type
+    SwapObject,
+    /// Change the gas budget or gas price
+    Gas,
+}
+
+impl MutatorKind {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Shuffle,
+            Self::DropCommand,
+            Self::DuplicateCommand,
+            Self::PureBytes,
+            Self::SwapObject,
+            Self::Gas,
+        ]
+    }
+}
+
+/// Applies one of the configured mutations at random for every mutation of a base transaction
+pub struct MultiMutator {
+    pub rng: ThreadRng,
+    pub kinds: Vec<MutatorKind>,
+    pub num_mutations_per_base_left: u64,
+    /// Owned and immutable objects seen in base transactions, by type, to swap inputs with
+    objects_by_type: BTreeMap<StructTag, BTreeSet<ObjectRef>>,
+    object_types: BTreeMap<ObjectID, StructTag>,
+}
+
+impl MultiMutator {
+    pub fn new(kinds: Vec<MutatorKind>, num_mutations_per_base: u64) -> Self {
+        Self {
+            rng: rand::thread_rng(),
+            kinds,
+            num_mutations_per_base_left: num_mutations_per_base,
+            objects_by_type: BTreeMap::new(),
+            object_types: BTreeMap::new(),
+        }
+    }
+
+    /// Remembers the owned and immutable objects, to swap inputs with
+    fn observe_objects(&mut self, objects: &[Object]) {
+        for object in objects {
+            if object.is_shared() {
+                continue;
+            }
+            let Some(tag) = object.struct_tag() else {
+                continue;
+            };
+            self.object_types.insert(object.id(), tag.clone());
+            self.objects_by_type
+                .entry(tag)
+                .or_default()
+                .insert(object.compute_object_reference());
+        }
+    }
+
+    fn apply(
+        &mut self,
+        kind: MutatorKind,
+        transaction: &FuzzTransaction,
+    ) -> Option<FuzzTransaction> {
+        if kind == MutatorKind::Gas {
+            return Some(self.mutate_gas(transaction));
+        }
+        let TransactionKind::ProgrammableTransaction(mut p) = transaction.kind.clone() else {
+            // Other types not supported yet
+            return None;
+        };
+        let mutated = match kind {
+            MutatorKind::Shuffle => {
+                p.commands.shuffle(&mut self.rng);
+                p.inputs.shuffle(&mut self.rng);
+                true
+            }
+            MutatorKind::DropCommand => {
+                if p.commands.is_empty() {
+                    false
+                } else {
+                    p.commands.remove(self.rng.gen_range(0..p.commands.len()));
+                    true
+                }
+            }
+            MutatorKind::DuplicateCommand => {
+                if p.commands.is_empty() {
+                    false
+                } else {
+                    let idx = self.rng.gen_range(0..p.commands.len());
+                    p.commands.insert(idx + 1, p.commands[idx].clone());
+                    true
+                }
+            }
+            MutatorKind::PureBytes => self.mutate_pure_bytes(&mut p),
+            MutatorKind::SwapObject => self.swap_object(&mut p),
+            MutatorKind::Gas => unreachable!("Gas mutation handled above"),
+        };
+        mutated.then(|| FuzzTransaction {
+            kind: TransactionKind::ProgrammableTransaction(p),
+            ..transaction.clone()
+        })
+    }
+
+    fn mutate_pure_bytes(&mut self, p: &mut ProgrammableTransaction) -> bool {
+        const WIDTHS: [usize; 6] = [1, 2, 4, 8, 16, 32];
+        let mut pure_inputs = p
+            .inputs
+            .iter_mut()
+            .filter_map(|arg| match arg {
+                CallArg::Pure(bytes) => Some(bytes),
+                CallArg::Object(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let Some(bytes) = pure_inputs.choose_mut(&mut self.rng) else {
+            return false;
+        };
+        let fill = *[0x00, 0xff].choose(&mut self.rng).unwrap();
+        match self.rng.gen_range(0..4) {
+            // Reinterpret as an integer of another width, at its min or max value
+            0 => {
+                let width = *WIDTHS.choose(&mut self.rng).unwrap();
+                **bytes = vec![fill; width];
+            }
+            // Keep the width, but move the value to a boundary: min, max or only the sign bit set
+            1 => {
+                bytes.iter_mut().for_each(|b| *b = fill);
+                if let Some(last) = bytes.last_mut() {
+                    if self.rng.gen_bool(0.5) {
+                        *last ^= 0x80;
+                    }
+                }
+            }
+            // Cut off the last byte
+            2 => {
+                bytes.pop();
+            }
+            // Add one byte past the end
+            _ => bytes.push(self.rng.gen()),
+        }
+        true
+    }
+
+    fn swap_object(&mut self, p: &mut ProgrammableTransaction) -> bool {
+        let used = p
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(o)) => Some(o.0),
+                CallArg::Object(ObjectArg::SharedObject { id, .. }) => Some(*id),
+                CallArg::Pure(_) => None,
+            })
+            .collect::<BTreeSet<_>>();
+        let mut swappable = p
+            .inputs
+            .iter_mut()
+            .filter_map(|arg| {
+                let CallArg::Object(ObjectArg::ImmOrOwnedObject(o)) = arg else {
+                    return None;
+                };
+                let candidates = self
+                    .objects_by_type
+                    .get(self.object_types.get(&o.0)?)?
+                    .iter()
+                    .filter(|c| !used.contains(&c.0))
+                    .copied()
+                    .collect::<Vec<_>>();
+                (!candidates.is_empty()).then_some((o, candidates))
+            })
+            .collect::<Vec<_>>();
+        let Some((o, candidates)) = swappable.choose_mut(&mut self.rng) else {
+            return false;
+        };
+        **o = *candidates.choose(&mut self.rng).unwrap();
+        true
+    }
+
+    fn mutate_gas(&mut self, transaction: &FuzzTransaction) -> FuzzTransaction {
+        let FuzzTransaction {
+            gas_budget,
+            gas_price,
+            ..
+        } = *transaction;
+        let (gas_budget, gas_price) = match self.rng.gen_range(0..6) {
+            0 => (0, gas_price),
+            1 => (gas_budget / 2, gas_price),
+            2 => (gas_budget.saturating_mul(2), gas_price),
+            3 => (u64::MAX, gas_price),
+            4 => (gas_budget, gas_price.saturating_sub(1)),
+            _ => (gas_budget, u64::MAX),
+        };
+        FuzzTransaction {
+            gas_budget,
+            gas_price,
+            ..transaction.clone()
+        }
+    }
+}
+
+impl TransactionKindMutator for MultiMutator {
+    fn mutate(&mut self, transaction: &FuzzTransaction) -> Option<FuzzTransaction> {
+        if self.num_mutations_per_base_left == 0 {
+            // Nothing else to do
+            return None;
+        }
+
+        self.num_mutations_per_base_left -= 1;
+        // Try the mutations in random order until one applies to this transaction
+        let mut kinds = self.kinds.clone();
+        kinds.shuffle(&mut self.rng);
+        kinds
+            .into_iter()
+            .find_map(|kind| self.apply(kind, transaction))
+    }
+
+    fn reset(&mut self, mutations_per_base: u64) {
+        self.num_mutations_per_base_left = mutations_per_base;
+    }
+
+    fn observe(&mut self, sandbox_state: &ExecutionSandboxState) {
+        self.observe_objects(&sandbox_state.required_objects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::{Argument, Command};
+
+    fn transaction(build: impl FnOnce(&mut ProgrammableTransactionBuilder)) -> FuzzTransaction {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        build(&mut builder);
+        FuzzTransaction {
+            kind: TransactionKind::ProgrammableTransaction(builder.finish()),
+            gas_budget: 1_000_000,
+            gas_price: 1_000,
+        }
+    }
+
+    fn commands(transaction: &FuzzTransaction) -> &[Command] {
+        let TransactionKind::ProgrammableTransaction(p) = &transaction.kind else {
+            panic!("Expected a programmable transaction");
+        };
+        &p.commands
+    }
+
+    fn panic_failure(message: &str) -> ReplayFuzzError {
+        ReplayFuzzError::Panic {
+            tx_digest: TransactionDigest::random(),
+            kind: transaction(|_| ()).kind,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fails_same_way() {
+        let failure = panic_failure("index out of bounds");
+        assert!(fails_same_way(
+            &Err(panic_failure("index out of bounds")),
+            &failure
+        ));
+        assert!(!fails_same_way(
+            &Err(panic_failure("attempt to subtract with overflow")),
+            &failure
+        ));
+        let invariant_violation = ReplayFuzzError::InvariantViolation {
+            tx_digest: TransactionDigest::random(),
+            kind: transaction(|_| ()).kind,
+            exec_status: ExecutionFailureStatus::InvariantViolation,
+        };
+        assert!(!fails_same_way(&Err(invariant_violation.clone()), &failure));
+        assert!(fails_same_way(
+            &Err(invariant_violation.clone()),
+            &invariant_violation
+        ));
+        assert!(!fails_same_way(
+            &Err(ReplayFuzzError::InvariantViolation {
+                tx_digest: TransactionDigest::random(),
+                kind: transaction(|_| ()).kind,
+                exec_status: ExecutionFailureStatus::VMInvariantViolation,
+            }),
+            &invariant_violation
+        ));
+    }
+
+    /// Runs the minimizer with `fails` deciding whether each candidate still fails
+    fn minimize(
+        base: &FuzzTransaction,
+        transaction: FuzzTransaction,
+        fails: impl Fn(&FuzzTransaction) -> bool,
+    ) -> FuzzTransaction {
+        let mut minimizer = Minimizer::new(base, transaction);
+        while let Some(candidate) = minimizer.next_candidate() {
+            minimizer.report(fails(&candidate));
+        }
+        minimizer.minimized
+    }
+
+    #[test]
+    fn test_minimizer() {
+        let recipient = SuiAddress::random_for_testing_only();
+        let base = transaction(|builder| {
+            builder.transfer_sui(recipient, Some(1));
+        });
+        let mutation = FuzzTransaction {
+            gas_budget: u64::MAX,
+            ..transaction(|builder| {
+                builder.transfer_sui(recipient, Some(1));
+                builder.command(Command::MergeCoins(
+                    Argument::GasCoin,
+                    vec![Argument::GasCoin],
+                ));
+                builder.transfer_sui(recipient, Some(2));
+            })
+        };
+        let merge_coins = |command: &Command| matches!(command, Command::MergeCoins(..));
+
+        // Only the commands which are needed to fail are kept, and the base gas values are
+        // restored since they do not matter
+        let minimized = minimize(&base, mutation.clone(), |candidate| {
+            commands(candidate).iter().any(merge_coins)
+        });
+        assert_eq!(minimized.gas_budget, base.gas_budget);
+        assert_eq!(minimized.gas_price, base.gas_price);
+        assert_eq!(commands(&minimized).len(), 1);
+        assert!(merge_coins(&commands(&minimized)[0]));
+
+        // The mutated gas values are kept when the failure needs them
+        let minimized = minimize(&base, mutation.clone(), |candidate| {
+            candidate.gas_budget == u64::MAX
+        });
+        assert_eq!(minimized.gas_budget, u64::MAX);
+        assert!(commands(&minimized).is_empty());
+
+        // Nothing is removed when no smaller transaction fails the same way
+        let minimized = minimize(&base, mutation.clone(), |_| false);
+        assert_eq!(minimized, mutation);
+    }
+
+    #[test]
+    fn test_multi_mutator() {
+        let recipient = SuiAddress::random_for_testing_only();
+        let base = transaction(|builder| {
+            builder.transfer_sui(recipient, Some(1));
+        });
+        let mut mutator = MultiMutator::new(MutatorKind::all(), 1);
+
+        let dropped = mutator.apply(MutatorKind::DropCommand, &base).unwrap();
+        assert_eq!(commands(&dropped).len(), commands(&base).len() - 1);
+
+        let duplicated = mutator.apply(MutatorKind::DuplicateCommand, &base).unwrap();
+        assert_eq!(commands(&duplicated).len(), commands(&base).len() + 1);
+
+        let pure_bytes = mutator.apply(MutatorKind::PureBytes, &base).unwrap();
+        assert_ne!(pure_bytes.kind, base.kind);
+        assert_eq!(commands(&pure_bytes), commands(&base));
+
+        let gas = mutator.apply(MutatorKind::Gas, &base).unwrap();
+        assert_eq!(gas.kind, base.kind);
+        assert_ne!(
+            (gas.gas_budget, gas.gas_price),
+            (base.gas_budget, base.gas_price)
+        );
+
+        // Nothing to drop, nor any object to swap
+        let empty = transaction(|_| ());
+        assert!(mutator.apply(MutatorKind::DropCommand, &empty).is_none());
+        assert!(mutator.apply(MutatorKind::SwapObject, &base).is_none());
+
+        // Only one mutation per base transaction until reset
+        assert!(mutator.mutate(&base).is_some());
+        assert!(mutator.mutate(&base).is_none());
+        mutator.reset(1);
+        assert!(mutator.mutate(&base).is_some());
+    }
+
+    #[test]
+    fn test_multi_mutator_swap_object() {
+        let owner = SuiAddress::random_for_testing_only();
+        let coin = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+        let other_coin = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+        let base = transaction(|builder| {
+            builder
+                .transfer_object(owner, coin.compute_object_reference())
+                .unwrap();
+        });
+
+        let mut mutator = MultiMutator::new(vec![MutatorKind::SwapObject], 1);
+        mutator.observe_objects(&[coin.clone(), other_coin.clone()]);
+        let swapped = mutator.apply(MutatorKind::SwapObject, &base).unwrap();
+        let TransactionKind::ProgrammableTransaction(p) = &swapped.kind else {
+            panic!("Expected a programmable transaction");
+        };
+        assert_eq!(
+            p.inputs,
+            vec![
+                CallArg::Pure(bcs::to_bytes(&owner).unwrap()),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                    other_coin.compute_object_reference()
+                )),
+            ]
+        );
+    }
+}
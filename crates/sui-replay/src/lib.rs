@@ -5,14 +5,19 @@ use async_recursion::async_recursion;
 use clap::Parser;
 use sui_types::message_envelope::Message;
 
+use crate::db_rider::DBRider;
+use crate::replay::ExecutionOverrides;
 use crate::replay::LocalExec;
 use crate::replay::ProtocolVersionSummary;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_types::digests::TransactionDigest;
 use tracing::{error, info};
 mod data_fetcher;
 mod db_rider;
+mod effects_diff;
 pub mod fuzz;
 mod replay;
 pub mod types;
@@ -26,6 +31,15 @@ pub enum ReplayToolCommand {
         tx_digest: String,
         #[clap(long, short)]
         show_effects: bool,
+        /// Read data from the node DB at this path instead of RPC
+        #[clap(long)]
+        db_path: Option<PathBuf>,
+        /// Write a profile of the gas charged by the transaction to this path, in the folded
+        /// stack format read by speedscope and flamegraph tools. Computation and the storage of
+        /// the objects written are attributed to Move call stacks. Always replays through the
+        /// execution engine
+        #[clap(long)]
+        gas_profile: Option<PathBuf>,
     },
 
     #[clap(name = "rd")]
@@ -46,6 +60,9 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[clap(long, short, default_value = "16")]
         max_tasks: u64,
+        /// Read data from the node DB at this path instead of RPC
+        #[clap(long)]
+        db_path: Option<PathBuf>,
     },
 
     #[clap(name = "ep")]
@@ -56,15 +73,57 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[clap(long, short, default_value = "16")]
         max_tasks: u64,
+        /// Read data from the node DB at this path instead of RPC
+        #[clap(long)]
+        db_path: Option<PathBuf>,
+    },
+
+    /// Re-execute transactions under a different protocol config or framework, and report how
+    /// their effects differ from on-chain
+    #[clap(name = "diff")]
+    ReplayDiff {
+        #[clap(long, short)]
+        tx_digest: Option<String>,
+        #[clap(long, short, requires = "end", conflicts_with = "tx_digest")]
+        start: Option<u64>,
+        #[clap(long, short, requires = "start")]
+        end: Option<u64>,
+        /// Execute with the config of this protocol version
+        #[clap(long)]
+        protocol_version: Option<u64>,
+        /// Execute against the system packages built into this binary
+        #[clap(long)]
+        local_framework: bool,
+        /// Read data from the node DB at this path instead of RPC
+        #[clap(long)]
+        db_path: Option<PathBuf>,
     },
 
     #[clap(name = "report")]
     Report,
 }
 
+/// Opens the node DB if a path is given. The DB is shared by all tasks of a replay, so that its
+/// tables are only opened once.
+fn open_node_db(db_path: Option<PathBuf>) -> Option<Arc<DBRider>> {
+    db_path.map(|path| Arc::new(DBRider::open(&path)))
+}
+
+/// Reads data from the node DB if one is provided, otherwise from the RPC endpoint
+async fn new_local_exec(
+    rpc_url: &Option<String>,
+    node_db: &Option<Arc<DBRider>>,
+) -> anyhow::Result<LocalExec> {
+    Ok(match (node_db, rpc_url) {
+        (Some(db), _) => LocalExec::new_for_node_db(db.clone()).await?,
+        (None, Some(rpc_url)) => LocalExec::new_from_fn_url(rpc_url).await?,
+        (None, None) => anyhow::bail!("Either an RPC URL or a node DB path must be provided"),
+    })
+}
+
 #[async_recursion]
 pub async fn execute_replay_command(
-    rpc_url: String,
+    rpc_url: Option<String>,
     safety_checks: bool,
     use_authority: bool,
     cmd: ReplayToolCommand,
@@ -100,16 +159,33 @@ pub async fn execute_replay_command(
         ReplayToolCommand::ReplayTransaction {
             tx_digest,
             show_effects,
+            db_path,
+            gas_profile,
         } => {
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
-            let sandbox_state = LocalExec::new_from_fn_url(&rpc_url)
+            let mut lx = new_local_exec(&rpc_url, &open_node_db(db_path))
                 .await?
                 .init_for_execution()
-                .await?
-                .execute_transaction(&tx_digest, safety, use_authority)
+                .await?;
+            lx.execution_overrides.trace_execution = gas_profile.is_some();
+            let sandbox_state = lx
+                .execute_transaction(&tx_digest, safety, use_authority && gas_profile.is_none())
                 .await?;
 
+            if let Some(path) = gas_profile {
+                let trace = sandbox_state
+                    .local_exec_temporary_store
+                    .as_ref()
+                    .and_then(|store| store.execution_trace.as_ref())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("No execution trace collected for {tx_digest}")
+                    })?;
+                let gas_price = sandbox_state.transaction_info.gas_price;
+                std::fs::write(&path, trace.folded_gas_profile(gas_price))?;
+                info!("Wrote gas profile to {}", path.display());
+            }
+
             let effects = sandbox_state.local_exec_effects.clone();
             if show_effects {
                 println!("{:#?}", effects)
@@ -121,8 +197,66 @@ pub async fn execute_replay_command(
             (1u64, 1u64)
         }
 
+        ReplayToolCommand::ReplayDiff {
+            tx_digest,
+            start,
+            end,
+            protocol_version,
+            local_framework,
+            db_path,
+        } => {
+            let mut lx = new_local_exec(&rpc_url, &open_node_db(db_path))
+                .await?
+                .init_for_execution()
+                .await?;
+            lx.execution_overrides = ExecutionOverrides {
+                protocol_version,
+                local_framework,
+                trace_execution: false,
+            };
+
+            let tx_digests = match (tx_digest, start, end) {
+                (Some(tx_digest), _, _) => vec![TransactionDigest::from_str(&tx_digest)?],
+                (None, Some(start), Some(end)) => {
+                    let mut txs = vec![];
+                    for checkpoint in start..=end {
+                        txs.extend(lx.get_checkpoint_txs(checkpoint).await?);
+                    }
+                    txs
+                }
+                _ => anyhow::bail!("Either a TX digest or a checkpoint range must be provided"),
+            };
+
+            let total = tx_digests.len() as u64;
+            let mut matched = 0;
+            let mut failed = 0;
+            for tx_digest in tx_digests {
+                // Always use the execution engine, as the authority does not support overrides
+                let diff = match lx
+                    .execute_transaction(&tx_digest, safety.clone(), false)
+                    .await
+                {
+                    Ok(sandbox_state) => lx.effects_diff(&sandbox_state).await,
+                    Err(e) => Err(e),
+                };
+                match diff {
+                    Ok(diff) if diff.is_empty() => matched += 1,
+                    Ok(diff) => println!("{}", diff),
+                    Err(e) => {
+                        error!("Error diffing tx: {},  {:#?}", tx_digest, e);
+                        failed += 1;
+                    }
+                }
+            }
+            info!(
+                "{} out of {} TXs have the same effects as on-chain, {} could not be diffed",
+                matched, total, failed
+            );
+            (matched, total)
+        }
+
         ReplayToolCommand::Report => {
-            let mut lx = LocalExec::new_from_fn_url(&rpc_url).await?;
+            let mut lx = new_local_exec(&rpc_url, &None).await?;
             let epoch_table = lx.protocol_ver_to_epoch_map().await?;
 
             // We need this for other activities in this session
@@ -169,6 +303,7 @@ pub async fn execute_replay_command(
             end,
             terminate_early,
             max_tasks,
+            db_path,
         } => {
             assert!(start <= end, "Start checkpoint must be <= end checkpoint");
             assert!(max_tasks > 0, "Max tasks must be > 0");
@@ -179,15 +314,17 @@ pub async fn execute_replay_command(
                 start, end, max_tasks, checkpoints_per_task
             );
 
+            let node_db = open_node_db(db_path);
             let range: Vec<_> = (start..=end).collect();
             for (task_count, checkpoints) in range.chunks(checkpoints_per_task).enumerate() {
                 let checkpoints = checkpoints.to_vec();
                 let rpc_url = rpc_url.clone();
+                let node_db = node_db.clone();
                 let safety = safety.clone();
                 handles.push(tokio::spawn(async move {
                     info!("Spawning task {task_count} for checkpoints {checkpoints:?}");
                     let time = std::time::Instant::now();
-                    let (succeeded, total) = LocalExec::new_from_fn_url(&rpc_url)
+                    let (succeeded, total) = new_local_exec(&rpc_url, &node_db)
                         .await
                         .unwrap()
                         .init_for_execution()
@@ -235,10 +372,13 @@ pub async fn execute_replay_command(
             epoch,
             terminate_early,
             max_tasks,
+            db_path,
         } => {
-            let lx = LocalExec::new_from_fn_url(&rpc_url).await?;
+            let lx = new_local_exec(&rpc_url, &open_node_db(db_path.clone())).await?;
 
             let (start, end) = lx.checkpoints_for_epoch(epoch).await?;
+            // Release the node DB before the checkpoints replay reopens it
+            drop(lx);
 
             info!(
                 "Executing epoch {} (checkpoint range {}-{}) with at most {} tasks",
@@ -253,6 +393,7 @@ pub async fn execute_replay_command(
                     end,
                     terminate_early,
                     max_tasks,
+                    db_path,
                 },
             )
             .await;
@@ -273,3 +414,72 @@ pub async fn execute_replay_command(
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use sui_macros::sim_test;
+    use test_utils::network::TestClusterBuilder;
+
+    #[test]
+    fn test_replay_tool_arguments() {
+        ReplayToolCommand::command().debug_assert();
+
+        let diff = |args: &[&str]| {
+            ReplayToolCommand::try_parse_from(["sui-replay", "diff"].iter().chain(args))
+        };
+        assert!(diff(&["--tx-digest", "digest"]).is_ok());
+        assert!(diff(&["--start", "1", "--end", "2"]).is_ok());
+        assert!(diff(&["--start", "1"]).is_err());
+        assert!(diff(&["--tx-digest", "digest", "--start", "1", "--end", "2"]).is_err());
+
+        let tx = |args: &[&str]| {
+            ReplayToolCommand::try_parse_from(["sui-replay", "tx"].iter().chain(args))
+        };
+        assert!(tx(&["--tx-digest", "digest", "--gas-profile", "gas.folded"]).is_ok());
+        assert!(tx(&["--gas-profile", "gas.folded"]).is_err());
+    }
+
+    #[sim_test]
+    async fn test_replay_gas_profile() -> anyhow::Result<()> {
+        let test_cluster = TestClusterBuilder::new().build().await?;
+        let (package_id, _, _) = test_cluster.wallet.publish_nfts_package().await;
+        let (_, _, tx_digest) = test_cluster.wallet.create_devnet_nft(package_id).await;
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("gas.folded");
+        execute_replay_command(
+            Some(test_cluster.rpc_url().to_string()),
+            false,
+            false,
+            ReplayToolCommand::ReplayTransaction {
+                tx_digest: tx_digest.to_string(),
+                show_effects: false,
+                db_path: None,
+                gas_profile: Some(path.clone()),
+            },
+        )
+        .await?;
+
+        let profile = std::fs::read_to_string(&path)?;
+        let lines: Vec<_> = profile.lines().collect();
+        assert!(lines.iter().any(|l| l.starts_with(&format!(
+            "[command 0];{}::devnet_nft::mint ",
+            package_id.to_hex_literal()
+        ))));
+        // The minted NFT's storage is attributed to the call that transferred it, the gas coin's
+        // to no call
+        let mint_stack = format!(
+            "[command 0];{}::devnet_nft::mint;",
+            package_id.to_hex_literal()
+        );
+        assert!(lines.iter().any(|l| l.starts_with(&mint_stack)
+            && l.contains(";[storage];")
+            && l.contains("::devnet_nft::DevNetNFT ")));
+        assert!(lines
+            .iter()
+            .any(|l| l.starts_with("[storage];") && l.contains("::coin::Coin<")));
+        Ok(())
+    }
+}
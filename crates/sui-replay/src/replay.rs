@@ -4,8 +4,11 @@
 use crate::data_fetcher::extract_epoch_and_version;
 use crate::data_fetcher::DataFetcher;
 use crate::data_fetcher::Fetchers;
+use crate::data_fetcher::NodeDbFetcher;
 use crate::data_fetcher::NodeStateDumpFetcher;
 use crate::data_fetcher::RemoteFetcher;
+use crate::db_rider::DBRider;
+use crate::effects_diff::{diff_lines, EffectsDiff, ObjectChangeKind};
 use crate::types::*;
 use futures::executor::block_on;
 use move_binary_format::CompiledModule;
@@ -14,7 +17,6 @@ use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::{ModuleId, StructTag};
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
 use prometheus::Registry;
-use similar::{ChangeTag, TextDiff};
 use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -94,23 +96,25 @@ impl ExecutionSandboxState {
         let eff2 = &self.local_exec_effects;
         let on_chain_str = format!("{:#?}", eff1);
         let local_chain_str = format!("{:#?}", eff2);
-        let mut res = vec![];
 
-        let diff = TextDiff::from_lines(&on_chain_str, &local_chain_str);
         println!("On-chain vs local diff");
-        for change in diff.iter_all_changes() {
-            let sign = match change.tag() {
-                ChangeTag::Delete => "---",
-                ChangeTag::Insert => "+++",
-                ChangeTag::Equal => "   ",
-            };
-            res.push(format!("{}{}", sign, change));
-        }
-
-        res.join("")
+        diff_lines(&on_chain_str, &local_chain_str)
     }
 }
 
+/// Changes to the execution environment of a replayed transaction, used to check the impact of
+/// protocol upgrades and framework changes on existing transactions
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionOverrides {
+    /// Execute with the config of this protocol version instead of the one the TX executed with
+    pub protocol_version: Option<u64>,
+    /// Execute against the system packages built into this binary instead of the on-chain ones
+    pub local_framework: bool,
+    /// Collect an execution trace of the transaction, for example to profile its gas usage. Only
+    /// applies when executing through the execution engine
+    pub trace_execution: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProtocolVersionSummary {
     /// Protocol version at this point
@@ -214,6 +218,8 @@ pub struct LocalExec {
     pub metrics: Arc<LimitsMetrics>,
     // Used for fetching data from the network or remote store
     pub fetcher: Fetchers,
+    // Changes to the execution environment, if any
+    pub execution_overrides: ExecutionOverrides,
 
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
@@ -322,6 +328,7 @@ impl LocalExec {
             exec_store_events: Arc::new(Mutex::new(Vec::new())),
             metrics,
             storage: Storage::default(),
+            execution_overrides: ExecutionOverrides::default(),
             fetcher: Fetchers::Remote(fetcher),
             // TODO: make these configurable
             num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
@@ -346,6 +353,7 @@ impl LocalExec {
             exec_store_events: Arc::new(Mutex::new(Vec::new())),
             metrics,
             storage: Storage::default(),
+            execution_overrides: ExecutionOverrides::default(),
             fetcher: Fetchers::NodeStateDump(fetcher),
             // TODO: make these configurable
             num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
@@ -353,6 +361,29 @@ impl LocalExec {
         })
     }
 
+    pub async fn new_for_node_db(db: Arc<DBRider>) -> Result<Self, LocalExecError> {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        let fetcher = NodeDbFetcher::new(db);
+
+        Ok(Self {
+            client: None,
+            protocol_version_epoch_table: BTreeMap::new(),
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version: 0,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            execution_overrides: ExecutionOverrides::default(),
+            fetcher: Fetchers::NodeDb(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+        })
+    }
+
     #[allow(clippy::wrong_self_convention)]
     pub fn to_temporary_store(
         &mut self,
@@ -582,12 +613,19 @@ impl LocalExec {
 
         // Initialize the state necessary for execution
         // Get the input objects
-        let input_objects = self.initialize_execution_env_state(tx_info).await?;
+        let mut input_objects = self.initialize_execution_env_state(tx_info).await?;
+        if self.execution_overrides.local_framework {
+            self.install_local_framework(&mut input_objects)?;
+        }
 
         // At this point we have all the objects needed for replay
 
         // This assumes we already initialized the protocol version table `protocol_version_epoch_table`
-        let protocol_config = &tx_info.protocol_config;
+        let protocol_config = &match self.execution_overrides.protocol_version {
+            Some(version) => ProtocolConfig::get_for_version_if_supported(version.into())
+                .ok_or(LocalExecError::ProtocolVersionNotSupported { version })?,
+            None => tx_info.protocol_config.clone(),
+        };
 
         let metrics = self.metrics.clone();
 
@@ -608,23 +646,44 @@ impl LocalExec {
         let move_vm = get_vm(protocol_config, expensive_safety_check_config)?;
 
         // All prep done
-        let res = execute_transaction_to_effects_impl::<execution_mode::Normal, _>(
-            tx_info.shared_object_refs.clone(),
-            temporary_store,
-            override_transaction_kind.unwrap_or(tx_info.kind.clone()),
-            tx_info.sender,
-            &tx_info.gas.clone(),
-            *tx_digest,
-            tx_info.dependencies.clone().into_iter().collect(),
-            &move_vm,
-            gas_status,
-            &tx_info.executed_epoch,
-            epoch_start_timestamp,
-            protocol_config,
-            metrics,
-            true,
-            &HashSet::new(),
-        );
+        let tx_kind = override_transaction_kind.unwrap_or(tx_info.kind.clone());
+        let res = if self.execution_overrides.trace_execution {
+            execute_transaction_to_effects_impl::<execution_mode::Trace<execution_mode::Normal>, _>(
+                tx_info.shared_object_refs.clone(),
+                temporary_store,
+                tx_kind,
+                tx_info.sender,
+                &tx_info.gas.clone(),
+                *tx_digest,
+                tx_info.dependencies.clone().into_iter().collect(),
+                &move_vm,
+                gas_status,
+                &tx_info.executed_epoch,
+                epoch_start_timestamp,
+                protocol_config,
+                metrics,
+                true,
+                &HashSet::new(),
+            )
+        } else {
+            execute_transaction_to_effects_impl::<execution_mode::Normal, _>(
+                tx_info.shared_object_refs.clone(),
+                temporary_store,
+                tx_kind,
+                tx_info.sender,
+                &tx_info.gas.clone(),
+                *tx_digest,
+                tx_info.dependencies.clone().into_iter().collect(),
+                &move_vm,
+                gas_status,
+                &tx_info.executed_epoch,
+                epoch_start_timestamp,
+                protocol_config,
+                metrics,
+                true,
+                &HashSet::new(),
+            )
+        };
 
         let all_required_objects = self.storage.all_objects();
         let effects = SuiTransactionBlockEffects::try_from(res.1).map_err(LocalExecError::from)?;
@@ -816,6 +875,120 @@ impl LocalExec {
         Ok(sandbox_state)
     }
 
+    /// Replaces the system packages used for execution with the ones built into this binary.
+    /// The on-chain package versions are kept so that the versions of the outputs are unaffected.
+    fn install_local_framework(
+        &mut self,
+        input_objects: &mut [(InputObjectKind, Object)],
+    ) -> Result<(), LocalExecError> {
+        let versions: BTreeMap<_, _> = self
+            .system_package_versions_for_epoch(self.current_protocol_version)?
+            .into_iter()
+            .collect();
+
+        let mut package_cache = self.storage.package_cache.lock().expect("Cannot lock");
+        let mut object_version_cache = self
+            .storage
+            .object_version_cache
+            .lock()
+            .expect("Cannot lock");
+        for package in BuiltInFramework::iter_system_packages() {
+            // Do not introduce packages which did not exist at this protocol version
+            let Some(version) = versions.get(package.id()) else {
+                continue;
+            };
+            let previous_transaction = package_cache
+                .get(package.id())
+                .map(|o| o.previous_transaction)
+                .unwrap_or_else(TransactionDigest::genesis);
+            let local = Object::new_system_package(
+                &package.modules(),
+                *version,
+                package.dependencies().to_vec(),
+                previous_transaction,
+            );
+            package_cache.insert(*package.id(), local.clone());
+            object_version_cache.insert((*package.id(), *version), local.clone());
+            for (_, input) in input_objects.iter_mut() {
+                if input.id() == *package.id() {
+                    *input = local.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes a structured diff between the on-chain effects of a transaction, and the effects of
+    /// executing it in `sandbox_state`. Contents of objects whose digests disagree are fetched
+    /// from the network or DB and compared as well.
+    pub async fn effects_diff(
+        &self,
+        sandbox_state: &ExecutionSandboxState,
+    ) -> Result<EffectsDiff, LocalExecError> {
+        let tx_info = &sandbox_state.transaction_info;
+        let mut diff = EffectsDiff::new(
+            tx_info.tx_digest,
+            &tx_info.effects,
+            &sandbox_state.local_exec_effects,
+        );
+        let Some(temporary_store) = &sandbox_state.local_exec_temporary_store else {
+            return Ok(diff);
+        };
+
+        for object in diff.objects.iter_mut() {
+            let (Some(on_chain), Some(local)) = (&object.on_chain, &object.local) else {
+                continue;
+            };
+            if !matches!(on_chain.kind, ObjectChangeKind::Write(_))
+                || !matches!(local.kind, ObjectChangeKind::Write(_))
+            {
+                continue;
+            }
+            let Some(local_obj) = temporary_store.get_written_object(&object.id) else {
+                continue;
+            };
+            // The on-chain output might have been pruned, in which case we only report the digests
+            match self.multi_download(&[(object.id, on_chain.version)]).await {
+                Ok(on_chain_objs) => {
+                    if let Some(on_chain_obj) = on_chain_objs.first() {
+                        object.contents_diff = Some(diff_lines(
+                            &format!("{:#?}", on_chain_obj),
+                            &format!("{:#?}", local_obj),
+                        ));
+                    }
+                }
+                Err(e) => warn!(
+                    "Unable to fetch on-chain object {} version {}: {}",
+                    object.id, on_chain.version, e
+                ),
+            }
+        }
+
+        // Events are only committed to by digest in the effects
+        if tx_info.effects.events_digest() != sandbox_state.local_exec_effects.events_digest()
+            && self.is_remote_replay()
+        {
+            let on_chain_events: Vec<_> = self
+                .fetcher
+                .get_transaction(&tx_info.tx_digest)
+                .await?
+                .events
+                .map(|events| events.data)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|event| (event.type_, event.bcs))
+                .collect();
+            let local_events: Vec<_> = temporary_store
+                .events
+                .data
+                .iter()
+                .map(|event| (event.type_.clone(), event.contents.clone()))
+                .collect();
+            diff = diff.with_events(&on_chain_events, &local_events);
+        }
+        Ok(diff)
+    }
+
     pub async fn execute_state_dump(
         &mut self,
         expensive_safety_check_config: ExpensiveSafetyCheckConfig,
@@ -906,8 +1079,10 @@ impl LocalExec {
         Ok(Some(o))
     }
 
+    /// Whether TX data is fetched on demand from the network or a node DB, as opposed to being
+    /// read from a state dump
     pub fn is_remote_replay(&self) -> bool {
-        matches!(self.fetcher, Fetchers::Remote(_))
+        matches!(self.fetcher, Fetchers::Remote(_) | Fetchers::NodeDb(_))
     }
 
     /// Must be called after `populate_protocol_version_tables`
@@ -916,7 +1091,7 @@ impl LocalExec {
         epoch: u64,
     ) -> Result<Vec<(ObjectID, SequenceNumber)>, LocalExecError> {
         match &self.fetcher {
-            Fetchers::Remote(_) => Ok(self
+            Fetchers::Remote(_) | Fetchers::NodeDb(_) => Ok(self
                 .protocol_version_system_package_table
                 .get(&epoch)
                 .ok_or(LocalExecError::FrameworkObjectVersionTableNotPopulated { epoch })?
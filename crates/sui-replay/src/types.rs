@@ -18,6 +18,7 @@ use sui_types::transaction::{InputObjectKind, SenderSignedData, TransactionKind}
 use thiserror::Error;
 use tokio::time::Duration;
 use tracing::error;
+use typed_store::rocks::TypedStoreError;
 
 // TODO: make these configurable
 pub(crate) const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(10_000);
@@ -127,6 +128,9 @@ pub enum LocalExecError {
     #[error("Protocol version not found for epoch {epoch}")]
     ProtocolVersionNotFound { epoch: u64 },
 
+    #[error("Protocol version {version} is not supported by this binary")]
+    ProtocolVersionNotSupported { version: u64 },
+
     #[error("Error querying system events for epoch {epoch}")]
     ErrorQueryingSystemEvents { epoch: u64 },
 
@@ -153,6 +157,12 @@ pub enum LocalExecError {
 
     #[error("Error getting dynamic fields loaded objects: {}", rpc_err)]
     UnableToGetDynamicFieldLoadedObjects { rpc_err: String },
+
+    #[error("Transaction not found: {digest}")]
+    TransactionNotFound { digest: TransactionDigest },
+
+    #[error("Checkpoint not found: {id}")]
+    CheckpointNotFound { id: u64 },
 }
 
 impl From<SuiObjectResponseError> for LocalExecError {
@@ -199,6 +209,12 @@ impl From<SuiRpcError> for LocalExecError {
     }
 }
 
+impl From<TypedStoreError> for LocalExecError {
+    fn from(err: TypedStoreError) -> Self {
+        LocalExecError::SuiError { err: err.into() }
+    }
+}
+
 impl From<UserInputError> for LocalExecError {
     fn from(err: UserInputError) -> Self {
         LocalExecError::UserInputError { err }
@@ -7,16 +7,17 @@ use axum_extra::extract::WithRejection;
 use futures::StreamExt;
 
 use sui_sdk::rpc_types::StakeStatus;
-use sui_sdk::{SuiClient, SUI_COIN_TYPE};
+use sui_sdk::SuiClient;
 use sui_types::base_types::SuiAddress;
+use sui_types::parse_sui_type_tag;
 use tracing::info;
 
 use crate::errors::Error;
 use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
-    Amount, Coin, SubAccount, SubAccountType, SubBalance,
+    Amount, Coin, Currency, SubAccount, SubAccountType, SubBalance,
 };
-use crate::{OnlineServerContext, SuiEnv};
+use crate::{OnlineServerContext, SuiEnv, SUI};
 use std::time::Duration;
 
 /// Get an array of all AccountBalances for an AccountIdentifier and the BlockIdentifier
@@ -70,12 +71,7 @@ pub async fn balance(
     } else {
         // Get current live balance
         while retry_attempts > 0 {
-            let balances_first = ctx
-                .client
-                .coin_read_api()
-                .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
-                .await?
-                .total_balance as i128;
+            let balances_first = get_balances(&ctx, address, &request.currencies).await?;
 
             // Get current latest checkpoint
             let checkpoint1 = ctx
@@ -101,12 +97,7 @@ pub async fn balance(
             }
 
             // Get live balance again
-            let balances_second = ctx
-                .client
-                .coin_read_api()
-                .get_balance(address, Some(SUI_COIN_TYPE.to_string()))
-                .await?
-                .total_balance as i128;
+            let balances_second = get_balances(&ctx, address, &request.currencies).await?;
 
             // if those two live balances are equal then that is the current balance for checkpoint2
             if balances_first.eq(&balances_second) {
@@ -116,7 +107,7 @@ pub async fn balance(
                 );
                 return Ok(AccountBalanceResponse {
                     block_identifier: ctx.blocks().create_block_identifier(checkpoint2).await?,
-                    balances: vec![Amount::new(balances_first)],
+                    balances: balances_first,
                 });
             } else {
                 // balances are different so we need to try again.
@@ -131,6 +122,57 @@ pub async fn balance(
     }
 }
 
+/// Balances of the account in `currencies`, or in all currencies if empty. The SUI balance is
+/// always returned first in the latter case.
+async fn get_balances(
+    ctx: &OnlineServerContext,
+    address: SuiAddress,
+    currencies: &[Currency],
+) -> Result<Vec<Amount>, Error> {
+    if currencies.is_empty() {
+        let mut balances = vec![Amount::new(0)];
+        for balance in ctx.client.coin_read_api().get_all_balances(address).await? {
+            let coin_type = parse_sui_type_tag(&balance.coin_type)?;
+            // Coins without `CoinMetadata` have no currency to report their balance in
+            let Some(currency) = ctx.coin_metadata_cache.get_currency(&coin_type).await? else {
+                continue;
+            };
+            let amount = Amount::new_with_currency(balance.total_balance as i128, currency);
+            if amount.currency == *SUI {
+                balances[0] = amount;
+            } else {
+                balances.push(amount);
+            }
+        }
+        return Ok(balances);
+    }
+
+    let mut balances = vec![];
+    for currency in currencies {
+        let currency = get_currency(ctx, currency).await?;
+        let balance = ctx
+            .client
+            .coin_read_api()
+            .get_balance(address, Some(currency.coin_type()?.to_string()))
+            .await?
+            .total_balance;
+        balances.push(Amount::new_with_currency(balance as i128, currency));
+    }
+    Ok(balances)
+}
+
+/// The currency of the coin type named by `currency`, as described by its `CoinMetadata`
+pub(crate) async fn get_currency(
+    ctx: &OnlineServerContext,
+    currency: &Currency,
+) -> Result<Currency, Error> {
+    let coin_type = currency.coin_type()?;
+    ctx.coin_metadata_cache
+        .get_currency(&coin_type)
+        .await?
+        .ok_or_else(|| Error::InvalidInput(format!("Unknown currency {}", currency.symbol)))
+}
+
 async fn get_sub_account_balances(
     account_type: SubAccountType,
     client: &SuiClient,
@@ -201,16 +243,43 @@ pub async fn coins(
     WithRejection(Json(request), _): WithRejection<Json<AccountCoinsRequest>, Error>,
 ) -> Result<AccountCoinsResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
-    let coins = context
-        .client
-        .coin_read_api()
-        .get_coins_stream(
-            request.account_identifier.address,
-            Some(SUI_COIN_TYPE.to_string()),
-        )
-        .map(Coin::from)
-        .collect()
-        .await;
+    let address = request.account_identifier.address;
+    let mut coins = vec![];
+    if request.currencies.is_empty() {
+        let mut cursor = None;
+        loop {
+            let page = context
+                .client
+                .coin_read_api()
+                .get_all_coins(address, cursor, None)
+                .await?;
+            for coin in page.data {
+                let coin_type = parse_sui_type_tag(&coin.coin_type)?;
+                if let Some(currency) = context.coin_metadata_cache.get_currency(&coin_type).await?
+                {
+                    coins.push(Coin::new(coin, currency));
+                }
+            }
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+    } else {
+        for currency in &request.currencies {
+            let currency = get_currency(&context, currency).await?;
+            let coin_type = currency.coin_type()?.to_string();
+            coins.extend(
+                context
+                    .client
+                    .coin_read_api()
+                    .get_coins_stream(address, Some(coin_type))
+                    .map(|coin| Coin::new(coin, currency.clone()))
+                    .collect::<Vec<_>>()
+                    .await,
+            );
+        }
+    }
 
     Ok(AccountCoinsResponse {
         block_identifier: context.blocks().current_block_identifier().await?,
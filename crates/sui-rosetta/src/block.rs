@@ -57,7 +57,7 @@ pub async fn transaction(
         .await?;
     let hash = response.digest;
 
-    let operations = response.try_into()?;
+    let operations = context.coin_metadata_cache.operations(response).await?;
 
     let transaction = Transaction {
         transaction_identifier: TransactionIdentifier { hash },
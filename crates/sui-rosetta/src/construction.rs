@@ -7,6 +7,9 @@ use axum_extra::extract::WithRejection;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::hash::HashFunction;
 use futures::StreamExt;
+use move_core_types::language_storage::TypeTag;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use shared_crypto::intent::{Intent, IntentMessage};
 use sui_json_rpc_types::{
@@ -20,15 +23,17 @@ use sui_types::error::SuiError;
 use sui_types::signature::GenericSignature;
 use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
 
+use crate::account::get_currency;
 use crate::errors::Error;
+use crate::operations::Operations;
 use crate::types::{
     Amount, ConstructionCombineRequest, ConstructionCombineResponse, ConstructionDeriveRequest,
     ConstructionDeriveResponse, ConstructionHashRequest, ConstructionMetadata,
     ConstructionMetadataRequest, ConstructionMetadataResponse, ConstructionParseRequest,
     ConstructionParseResponse, ConstructionPayloadsRequest, ConstructionPayloadsResponse,
     ConstructionPreprocessRequest, ConstructionPreprocessResponse, ConstructionSubmitRequest,
-    InternalOperation, MetadataOptions, SignatureType, SigningPayload, TransactionIdentifier,
-    TransactionIdentifierResponse,
+    Currency, InternalOperation, MetadataOptions, SignatureType, SigningPayload,
+    TransactionIdentifier, TransactionIdentifierResponse,
 };
 use crate::{OnlineServerContext, SuiEnv};
 
@@ -222,6 +227,34 @@ pub async fn metadata(
             let amount = amounts.iter().sum::<u64>();
             (Some(amount), vec![])
         }
+        InternalOperation::PayCoin {
+            amounts, currency, ..
+        } => {
+            let expected = get_currency(&context, currency).await?;
+            if (&expected.symbol, expected.decimals) != (&currency.symbol, currency.decimals) {
+                return Err(Error::InvalidInput(format!(
+                    "Currency {} does not match the coin metadata of {}",
+                    currency.symbol,
+                    currency.coin_type()?
+                )));
+            }
+            let amount = amounts.iter().sum::<u64>();
+            let coins = context
+                .client
+                .coin_read_api()
+                .select_coins(
+                    sender,
+                    Some(currency.coin_type()?.to_string()),
+                    amount.into(),
+                    vec![],
+                )
+                .await?;
+            // The payment is made from coins of the currency, only gas is paid in SUI
+            (
+                Some(0),
+                coins.into_iter().map(|coin| coin.object_ref()).collect(),
+            )
+        }
         InternalOperation::Stake { amount, .. } => (*amount, vec![]),
         InternalOperation::WithdrawStake { sender, stake_ids } => {
             let stake_ids = if stake_ids.is_empty() {
@@ -345,6 +378,7 @@ pub async fn metadata(
 /// [Rosetta API Spec](https://www.rosetta-api.org/docs/ConstructionApi.html#constructionparse)
 pub async fn parse(
     Extension(env): Extension<SuiEnv>,
+    Extension(currencies): Extension<Arc<HashMap<TypeTag, Currency>>>,
     WithRejection(Json(request), _): WithRejection<Json<ConstructionParseRequest>, Error>,
 ) -> Result<ConstructionParseResponse, Error> {
     env.check_network_identifier(&request.network_identifier)?;
@@ -362,7 +396,7 @@ pub async fn parse(
     } else {
         vec![]
     };
-    let operations = data.try_into()?;
+    let operations = Operations::try_from_data(data, &currencies)?;
     Ok(ConstructionParseResponse {
         operations,
         account_identifier_signers,
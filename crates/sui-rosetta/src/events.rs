@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use sui_types::sui_serde::BigInt;
+
+use crate::types::{
+    BlockEvent, BlockEventType, BlockIdentifier, EventsBlocksRequest, EventsBlocksResponse,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Events API](https://www.rosetta-api.org/docs/EventsApi.html)
+
+const MAX_EVENTS_LIMIT: u64 = 100;
+
+/// Get the events of blocks being added or removed, starting at `offset`, or the latest events
+/// if there is none. Checkpoints are final, so blocks are only ever added and the sequence of an
+/// event is the index of the block it adds.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/EventsApi.html#eventsblocks)
+pub async fn blocks(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<EventsBlocksRequest>, Error>,
+) -> Result<EventsBlocksResponse, Error> {
+    debug!(
+        "Called /events/blocks endpoint, offset: {:?}, limit: {:?}",
+        request.offset, request.limit
+    );
+    env.check_network_identifier(&request.network_identifier)?;
+    let max_sequence = context
+        .client
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await?;
+    let limit = request
+        .limit
+        .map_or(MAX_EVENTS_LIMIT, |limit| limit.min(MAX_EVENTS_LIMIT));
+    let start = request
+        .offset
+        .unwrap_or_else(|| (max_sequence + 1).saturating_sub(limit));
+    if start > max_sequence || limit == 0 {
+        return Ok(EventsBlocksResponse {
+            max_sequence,
+            events: vec![],
+        });
+    }
+
+    let checkpoints = context
+        .client
+        .read_api()
+        .get_checkpoints(
+            start.checked_sub(1).map(BigInt::from),
+            Some(limit.min(max_sequence - start + 1) as usize),
+            false,
+        )
+        .await?;
+    let events = checkpoints
+        .data
+        .into_iter()
+        .map(|checkpoint| BlockEvent {
+            sequence: checkpoint.sequence_number,
+            block_identifier: BlockIdentifier {
+                index: checkpoint.sequence_number,
+                hash: checkpoint.digest,
+            },
+            type_: BlockEventType::BlockAdded,
+        })
+        .collect();
+
+    Ok(EventsBlocksResponse {
+        max_sequence,
+        events,
+    })
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -10,11 +11,12 @@ use once_cell::sync::Lazy;
 use tokio::task::JoinHandle;
 use tracing::info;
 
+use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use sui_sdk::SuiClient;
 
 use crate::errors::Error;
-use crate::state::{CheckpointBlockProvider, OnlineServerContext};
+use crate::state::{CheckpointBlockProvider, CoinMetadataCache, OnlineServerContext};
 use crate::types::{Currency, SuiEnv};
 
 /// This lib implements the Rosetta online and offline server defined by the [Rosetta API Spec](https://www.rosetta-api.org/docs/Reference.html)
@@ -22,14 +24,18 @@ mod account;
 mod block;
 mod construction;
 mod errors;
+mod events;
+mod mempool;
 mod network;
 pub mod operations;
+mod search;
 mod state;
 pub mod types;
 
 pub static SUI: Lazy<Currency> = Lazy::new(|| Currency {
     symbol: "SUI".to_string(),
     decimals: 9,
+    metadata: None,
 });
 
 pub struct RosettaOnlineServer {
@@ -39,10 +45,14 @@ pub struct RosettaOnlineServer {
 
 impl RosettaOnlineServer {
     pub fn new(env: SuiEnv, client: SuiClient) -> Self {
-        let blocks = Arc::new(CheckpointBlockProvider::new(client.clone()));
+        let coin_metadata_cache = CoinMetadataCache::new(client.clone());
+        let blocks = Arc::new(CheckpointBlockProvider::new(
+            client.clone(),
+            coin_metadata_cache.clone(),
+        ));
         Self {
             env,
-            context: OnlineServerContext::new(client, blocks),
+            context: OnlineServerContext::new(client, coin_metadata_cache, blocks),
         }
     }
 
@@ -58,6 +68,9 @@ impl RosettaOnlineServer {
             .route("/network/status", post(network::status))
             .route("/network/list", post(network::list))
             .route("/network/options", post(network::options))
+            .route("/search/transactions", post(search::transactions))
+            .route("/events/blocks", post(events::blocks))
+            .route("/mempool", post(mempool::mempool))
             .layer(Extension(self.env))
             .with_state(self.context);
         let server = axum::Server::bind(&addr).serve(app.into_make_service());
@@ -71,11 +84,24 @@ impl RosettaOnlineServer {
 
 pub struct RosettaOfflineServer {
     env: SuiEnv,
+    currencies: HashMap<TypeTag, Currency>,
 }
 
 impl RosettaOfflineServer {
     pub fn new(env: SuiEnv) -> Self {
-        Self { env }
+        Self {
+            env,
+            currencies: HashMap::new(),
+        }
+    }
+
+    /// Currencies other than SUI that `/construction/parse` recognizes payments in. The offline
+    /// server cannot read `CoinMetadata`, so they have to be provided up front.
+    pub fn with_currencies(mut self, currencies: Vec<Currency>) -> Result<Self, Error> {
+        for currency in currencies {
+            self.currencies.insert(currency.coin_type()?, currency);
+        }
+        Ok(self)
     }
 
     pub fn serve(self, addr: SocketAddr) -> JoinHandle<hyper::Result<()>> {
@@ -89,7 +115,8 @@ impl RosettaOfflineServer {
             .route("/construction/parse", post(construction::parse))
             .route("/network/list", post(network::list))
             .route("/network/options", post(network::options))
-            .layer(Extension(self.env));
+            .layer(Extension(self.env))
+            .layer(Extension(Arc::new(self.currencies)));
         let server = axum::Server::bind(&addr).serve(app.into_make_service());
         info!(
             "Sui Rosetta offline server listening on {}",
@@ -18,7 +18,7 @@ use tracing::log::warn;
 
 use sui_config::{sui_config_dir, Config, NodeConfig, SUI_FULLNODE_CONFIG, SUI_KEYSTORE_FILENAME};
 use sui_node::{metrics, SuiNode};
-use sui_rosetta::types::{CurveType, PrefundedAccount, SuiEnv};
+use sui_rosetta::types::{Currency, CurveType, PrefundedAccount, SuiEnv};
 use sui_rosetta::{RosettaOfflineServer, RosettaOnlineServer, SUI};
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::base_types::SuiAddress;
@@ -62,6 +62,10 @@ pub enum RosettaServerCommand {
         env: SuiEnv,
         #[clap(long, default_value = "0.0.0.0:9003")]
         addr: SocketAddr,
+        /// JSON file with the list of currencies other than SUI to support, as returned by the
+        /// online server.
+        #[clap(long)]
+        currencies: Option<PathBuf>,
     },
 }
 
@@ -126,9 +130,17 @@ impl RosettaServerCommand {
                 )?;
                 info!("Rosetta DSL file is stored in {:?}", dsl_path);
             }
-            RosettaServerCommand::StartOfflineServer { env, addr } => {
+            RosettaServerCommand::StartOfflineServer {
+                env,
+                addr,
+                currencies,
+            } => {
                 info!("Starting Rosetta Offline Server.");
-                let server = RosettaOfflineServer::new(env);
+                let currencies: Vec<Currency> = match currencies {
+                    Some(path) => serde_json::from_reader(BufReader::new(File::open(path)?))?,
+                    None => vec![],
+                };
+                let server = RosettaOfflineServer::new(env).with_currencies(currencies)?;
                 server.serve(addr).await??;
             }
             RosettaServerCommand::StartOnlineRemoteServer {
@@ -1,14 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Not;
 use std::str::FromStr;
 use std::vec;
 
 use anyhow::anyhow;
 use move_core_types::ident_str;
-use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use move_core_types::resolver::ModuleResolver;
 use serde::Deserialize;
 use serde::Serialize;
@@ -27,13 +27,16 @@ use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::object::Owner;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::TransactionData;
-use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
+use sui_types::{
+    parse_sui_type_tag, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID,
+};
 
 use crate::types::{
-    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, InternalOperation,
-    OperationIdentifier, OperationStatus, OperationType,
+    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType, PAY_MODULE_NAME,
+    PAY_SPLIT_AND_TRANSFER_FUN_NAME,
 };
-use crate::Error;
+use crate::{Error, SUI};
 
 #[cfg(test)]
 #[path = "unit_tests/operations_tests.rs"]
@@ -99,8 +102,20 @@ impl Operations {
         let type_ = self
             .type_()
             .ok_or_else(|| Error::MissingInput("Operation type".into()))?;
+        let is_payment = |type_| matches!(type_, OperationType::PaySui | OperationType::PayCoin);
+        if is_payment(type_)
+            && self
+                .0
+                .iter()
+                .any(|op| is_payment(op.type_) && op.type_ != type_)
+        {
+            return Err(Error::MalformedOperationError(
+                "PaySui and PayCoin operations cannot be mixed in one transaction.".into(),
+            ));
+        }
         match type_ {
             OperationType::PaySui => self.pay_sui_ops_to_internal(),
+            OperationType::PayCoin => self.pay_coin_ops_to_internal(),
             OperationType::Stake => self.stake_ops_to_internal(),
             OperationType::WithdrawStake => self.withdraw_stake_ops_to_internal(),
             op => Err(Error::UnsupportedOperation(op)),
@@ -135,6 +150,48 @@ impl Operations {
         })
     }
 
+    fn pay_coin_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let mut recipients = vec![];
+        let mut amounts = vec![];
+        let mut sender = None;
+        let mut currency = None;
+        for op in self {
+            if let (Some(amount), Some(account)) = (op.amount, op.account) {
+                if currency.get_or_insert_with(|| amount.currency.clone()) != &amount.currency {
+                    return Err(Error::MalformedOperationError(
+                        "PayCoin operations should all be in the same currency.".into(),
+                    ));
+                }
+                if amount.value.is_negative() {
+                    sender = Some(account.address)
+                } else {
+                    recipients.push(account.address);
+                    let amount = amount.value.abs();
+                    if amount > u64::MAX as i128 {
+                        return Err(Error::InvalidInput(
+                            "Input amount exceed u64::MAX".to_string(),
+                        ));
+                    }
+                    amounts.push(amount as u64)
+                }
+            }
+        }
+        let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
+        let currency = currency.ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
+        if currency.metadata.is_none() {
+            return Err(Error::InvalidInput(
+                "PayCoin operations need the coin type of the currency, use PaySui for SUI."
+                    .to_string(),
+            ));
+        }
+        Ok(InternalOperation::PayCoin {
+            sender,
+            recipients,
+            amounts,
+            currency,
+        })
+    }
+
     fn stake_ops_to_internal(self) -> Result<InternalOperation, Error> {
         let mut ops = self
             .0
@@ -213,10 +270,11 @@ impl Operations {
         tx: SuiTransactionBlockKind,
         sender: SuiAddress,
         status: Option<OperationStatus>,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> Result<Vec<Operation>, Error> {
         Ok(match tx {
             SuiTransactionBlockKind::ProgrammableTransaction(pt) => {
-                Self::parse_programmable_transaction(sender, status, pt)?
+                Self::parse_programmable_transaction(sender, status, pt, currencies)?
             }
             _ => vec![Operation::generic_op(status, sender, tx)],
         })
@@ -226,6 +284,7 @@ impl Operations {
         sender: SuiAddress,
         status: Option<OperationStatus>,
         pt: SuiProgrammableTransactionBlock,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> Result<Vec<Operation>, Error> {
         #[derive(Debug)]
         enum KnownValue {
@@ -304,6 +363,49 @@ impl Operations {
             }
             Some(vec![])
         }
+        fn merge_coins(
+            inputs: &[SuiCallArg],
+            coin: SuiArgument,
+            coins: &[SuiArgument],
+        ) -> Option<Vec<KnownValue>> {
+            // Merging the input coins of a PayCoin transaction leaves the balances unchanged
+            std::iter::once(&coin)
+                .chain(coins)
+                .all(|coin| matches!(coin, SuiArgument::Input(i) if inputs[*i as usize].object().is_some()))
+                .then_some(vec![])
+        }
+        fn pay_coin_call(
+            inputs: &[SuiCallArg],
+            currencies: &HashMap<TypeTag, Currency>,
+            call: &SuiProgrammableMoveCall,
+        ) -> Option<(Currency, SuiAddress, u64)> {
+            let [coin_type] = &call.type_arguments[..] else {
+                return None;
+            };
+            let currency = currencies.get(&parse_sui_type_tag(coin_type).ok()?)?;
+            let [SuiArgument::Input(_), SuiArgument::Input(amount), SuiArgument::Input(recipient)] =
+                call.arguments[..]
+            else {
+                return None;
+            };
+            let amount = pure_u64(inputs[amount as usize].pure()?.to_json_value())?;
+            let recipient = inputs[recipient as usize].pure()?.to_sui_address().ok()?;
+            Some((currency.clone(), recipient, amount))
+        }
+        // Pure arguments of Move calls are left as BCS bytes when the module is not known
+        fn pure_u64(value: serde_json::Value) -> Option<u64> {
+            match value {
+                serde_json::Value::String(value) => u64::from_str(&value).ok(),
+                serde_json::Value::Array(bytes) => bcs::from_bytes(
+                    &bytes
+                        .iter()
+                        .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                        .collect::<Option<Vec<_>>>()?,
+                )
+                .ok(),
+                _ => None,
+            }
+        }
         fn stake_call(
             inputs: &[SuiCallArg],
             known_results: &[Vec<KnownValue>],
@@ -362,6 +464,8 @@ impl Operations {
         let mut needs_generic = false;
         let mut operations = vec![];
         let mut stake_ids = vec![];
+        let mut coin_payments: Vec<(SuiAddress, u64)> = vec![];
+        let mut pay_coin_currency: Option<Currency> = None;
         for command in commands {
             let result = match command {
                 SuiCommand::SplitCoins(coin, amounts) => {
@@ -374,6 +478,20 @@ impl Operations {
                     objs,
                     *addr,
                 ),
+                SuiCommand::MergeCoins(coin, coins) => merge_coins(inputs, *coin, coins),
+                SuiCommand::MoveCall(m) if Self::is_pay_coin_call(m) => pay_coin_call(
+                    inputs, currencies, m,
+                )
+                .and_then(|(currency, recipient, amount)| {
+                    if pay_coin_currency.get_or_insert_with(|| currency.clone()) != &currency {
+                        return None;
+                    }
+                    match coin_payments.iter_mut().find(|(r, _)| *r == recipient) {
+                        Some((_, total)) => *total += amount,
+                        None => coin_payments.push((recipient, amount)),
+                    }
+                    Some(vec![])
+                }),
                 SuiCommand::MoveCall(m) if Self::is_stake_call(m) => {
                     stake_call(inputs, &known_results, m)?.map(|(amount, validator)| {
                         let amount = amount.map(|amount| Amount::new(-(amount as i128)));
@@ -414,6 +532,17 @@ impl Operations {
                     }),
             );
             operations.push(Operation::pay_sui(status, sender, -(total_paid as i128)));
+        } else if let Some(currency) = pay_coin_currency.filter(|_| !needs_generic) {
+            let total_paid: u64 = coin_payments.iter().map(|(_, amount)| amount).sum();
+            operations.extend(coin_payments.into_iter().map(|(recipient, amount)| {
+                Operation::pay_coin(status, recipient, amount.into(), currency.clone())
+            }));
+            operations.push(Operation::pay_coin(
+                status,
+                sender,
+                -(total_paid as i128),
+                currency,
+            ));
         } else if !stake_ids.is_empty() {
             let stake_ids = stake_ids.into_iter().flatten().collect::<Vec<_>>();
             let metadata = stake_ids
@@ -439,6 +568,42 @@ impl Operations {
         Ok(operations)
     }
 
+    fn is_pay_coin_call(tx: &SuiProgrammableMoveCall) -> bool {
+        tx.package == SUI_FRAMEWORK_PACKAGE_ID
+            && tx.module == PAY_MODULE_NAME.as_str()
+            && tx.function == PAY_SPLIT_AND_TRANSFER_FUN_NAME.as_str()
+    }
+
+    /// Coin types other than SUI that the transaction pays with or changes balances of, whose
+    /// currencies are needed to describe it with `try_from_response`.
+    pub fn coin_types(response: &SuiTransactionBlockResponse) -> HashSet<TypeTag> {
+        let mut coin_types = HashSet::new();
+        if let Some(balance_changes) = &response.balance_changes {
+            coin_types.extend(
+                balance_changes
+                    .iter()
+                    .map(|change| change.coin_type.clone()),
+            );
+        }
+        if let Some(tx) = &response.transaction {
+            if let SuiTransactionBlockKind::ProgrammableTransaction(pt) = tx.data.transaction() {
+                for command in &pt.commands {
+                    if let SuiCommand::MoveCall(call) = command {
+                        if Self::is_pay_coin_call(call) {
+                            coin_types.extend(
+                                call.type_arguments
+                                    .iter()
+                                    .filter_map(|t| parse_sui_type_tag(t).ok()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        coin_types.remove(&GAS::type_tag());
+        coin_types
+    }
+
     fn is_stake_call(tx: &SuiProgrammableMoveCall) -> bool {
         tx.package == SUI_SYSTEM_PACKAGE_ID
             && tx.module == SUI_SYSTEM_MODULE_NAME.as_str()
@@ -456,26 +621,34 @@ impl Operations {
         gas_used: i128,
         balance_changes: &[BalanceChange],
         status: Option<OperationStatus>,
-        balances: HashMap<SuiAddress, i128>,
+        balances: HashMap<(SuiAddress, Currency), i128>,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> impl Iterator<Item = Operation> {
         let mut balances = balance_changes
             .iter()
             .fold(balances, |mut balances, balance_change| {
                 // Rosetta only care about address owner
                 if let Owner::AddressOwner(owner) = balance_change.owner {
-                    if balance_change.coin_type == GAS::type_tag() {
-                        *balances.entry(owner).or_default() += balance_change.amount;
+                    let currency = if balance_change.coin_type == GAS::type_tag() {
+                        Some(SUI.clone())
+                    } else {
+                        currencies.get(&balance_change.coin_type).cloned()
+                    };
+                    // Coins without a known currency are left out
+                    if let Some(currency) = currency {
+                        *balances.entry((owner, currency)).or_default() += balance_change.amount;
                     }
                 }
                 balances
             });
         // separate gas from balances
-        *balances.entry(gas_owner).or_default() -= gas_used;
+        *balances.entry((gas_owner, SUI.clone())).or_default() -= gas_used;
 
-        let balance_change = balances
-            .into_iter()
-            .filter(|(_, amount)| *amount != 0)
-            .map(move |(addr, amount)| Operation::balance_change(status, addr, amount));
+        let balance_change = balances.into_iter().filter(|(_, amount)| *amount != 0).map(
+            move |((addr, currency), amount)| {
+                Operation::balance_change(status, addr, amount, currency)
+            },
+        );
 
         let gas = if gas_used != 0 {
             vec![Operation::gas(gas_owner, gas_used)]
@@ -490,18 +663,38 @@ impl Operations {
 impl TryFrom<SuiTransactionBlockData> for Operations {
     type Error = Error;
     fn try_from(data: SuiTransactionBlockData) -> Result<Self, Self::Error> {
+        Self::try_from_block_data(data, &HashMap::new())
+    }
+}
+
+impl TryFrom<SuiTransactionBlockResponse> for Operations {
+    type Error = Error;
+    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        Self::try_from_response(response, &HashMap::new())
+    }
+}
+
+impl Operations {
+    fn try_from_block_data(
+        data: SuiTransactionBlockData,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let sender = *data.sender();
         Ok(Self::new(Self::from_transaction(
             data.transaction().clone(),
             sender,
             None,
+            currencies,
         )?))
     }
-}
 
-impl TryFrom<SuiTransactionBlockResponse> for Operations {
-    type Error = Error;
-    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+    /// Operations of an executed transaction. `currencies` has the currencies of the coin types
+    /// other than SUI, as listed by `coin_types`. Payments and balance changes of coins whose
+    /// currency is not known are not reported.
+    pub fn try_from_response(
+        response: SuiTransactionBlockResponse,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let tx = response
             .transaction
             .ok_or_else(|| anyhow!("Response input should not be empty"))?;
@@ -516,7 +709,7 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
             - gas_summary.computation_cost as i128;
 
         let status = Some(effect.into_status().into());
-        let ops: Operations = tx.data.try_into()?;
+        let ops = Self::try_from_block_data(tx.data, currencies)?;
         let ops = ops.set_status(status).into_iter();
 
         // We will need to subtract the operation amounts from the actual balance
@@ -528,7 +721,9 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
                     if let (Some(acc), Some(amount), Some(OperationStatus::Success)) =
                         (&op.account, &op.amount, &op.status)
                     {
-                        *balances.entry(acc.address).or_default() -= amount.value;
+                        *balances
+                            .entry((acc.address, amount.currency.clone()))
+                            .or_default() -= amount.value;
                     }
                     balances
                 });
@@ -560,8 +755,8 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
             }
         }
         let staking_balance = if principal_amounts != 0 {
-            *accounted_balances.entry(sender).or_default() -= principal_amounts;
-            *accounted_balances.entry(sender).or_default() -= reward_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= principal_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= reward_amounts;
             vec![
                 Operation::stake_principle(status, sender, principal_amounts),
                 Operation::stake_reward(status, sender, reward_amounts),
@@ -579,6 +774,7 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
                 .ok_or_else(|| anyhow!("Response balance changes should not be empty."))?,
             status,
             accounted_balances,
+            currencies,
         );
 
         Ok(ops
@@ -598,6 +794,16 @@ fn is_unstake_event(tag: &StructTag) -> bool {
 impl TryFrom<TransactionData> for Operations {
     type Error = Error;
     fn try_from(data: TransactionData) -> Result<Self, Self::Error> {
+        Self::try_from_data(data, &HashMap::new())
+    }
+}
+
+impl Operations {
+    /// Operations of a transaction to be executed, recognizing payments in `currencies`
+    pub fn try_from_data(
+        data: TransactionData,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         struct NoOpsModuleResolver;
         impl ModuleResolver for NoOpsModuleResolver {
             type Error = Error;
@@ -606,7 +812,10 @@ impl TryFrom<TransactionData> for Operations {
             }
         }
         // Rosetta don't need the call args to be parsed into readable format
-        SuiTransactionBlockData::try_from(data, &&mut NoOpsModuleResolver)?.try_into()
+        Self::try_from_block_data(
+            SuiTransactionBlockData::try_from(data, &&mut NoOpsModuleResolver)?,
+            currencies,
+        )
     }
 }
 
@@ -694,13 +903,40 @@ impl Operation {
         }
     }
 
-    fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: i128) -> Self {
+    fn pay_coin(
+        status: Option<OperationStatus>,
+        address: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::PayCoin,
+            status,
+            account: Some(address.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
+
+    fn balance_change(
+        status: Option<OperationStatus>,
+        addr: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        let type_ = if currency == *SUI {
+            OperationType::SuiBalanceChange
+        } else {
+            OperationType::CoinBalanceChange
+        };
         Self {
             operation_identifier: Default::default(),
-            type_: OperationType::SuiBalanceChange,
+            type_,
             status,
             account: Some(addr.into()),
-            amount: Some(Amount::new(amount)),
+            amount: Some(Amount::new_with_currency(amount, currency)),
             coin_change: None,
             metadata: None,
         }
@@ -0,0 +1,185 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use tracing::debug;
+
+use sui_json_rpc_types::{
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_types::base_types::SuiAddress;
+
+use crate::types::{
+    BlockTransaction, Operator, SearchTransactionsRequest, SearchTransactionsResponse, Transaction,
+    TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+/// This module implements the [Rosetta Search API](https://www.rosetta-api.org/docs/SearchApi.html)
+
+const MAX_SEARCH_LIMIT: u64 = 100;
+
+/// Search for transactions touching an account or a coin, most recent first.
+/// The matching transactions are not counted up front, as that would mean scanning all of them
+/// on every request, so `total_count` only counts them up to the end of the returned page.
+/// [Rosetta API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+pub async fn transactions(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<SearchTransactionsRequest>, Error>,
+) -> Result<SearchTransactionsResponse, Error> {
+    debug!("Called /search/transactions endpoint");
+    env.check_network_identifier(&request.network_identifier)?;
+    for (filter, is_set) in [
+        ("currency", request.currency.is_some()),
+        ("status", request.status.is_some()),
+        ("type", request.type_.is_some()),
+        ("success", request.success.is_some()),
+    ] {
+        if is_set {
+            return Err(Error::InvalidInput(format!(
+                "Searching transactions by {filter} is not supported"
+            )));
+        }
+    }
+
+    // Only transactions in a checkpoint are part of a block
+    let max_block = match request.max_block {
+        Some(max_block) => max_block,
+        None => {
+            context
+                .client
+                .read_api()
+                .get_latest_checkpoint_sequence_number()
+                .await?
+        }
+    };
+    let offset = request.offset.unwrap_or_default() as usize;
+    let limit = request
+        .limit
+        .map_or(MAX_SEARCH_LIMIT, |limit| limit.min(MAX_SEARCH_LIMIT)) as usize;
+    let end = offset + limit;
+
+    let mut digests = if let Some(TransactionIdentifier { hash }) = &request.transaction_identifier
+    {
+        if request.account_identifier.is_some()
+            || request.address.is_some()
+            || request.coin_identifier.is_some()
+        {
+            return Err(Error::InvalidInput(
+                "Searching by transaction_identifier cannot be combined with other filters"
+                    .to_string(),
+            ));
+        }
+        vec![*hash]
+    } else {
+        let filter = transaction_filter(&request)?.and(TransactionFilter::CheckpointRange {
+            start_checkpoint: 0,
+            end_checkpoint: max_block + 1,
+        });
+        let query = SuiTransactionBlockResponseQuery::new_with_filter(filter);
+        let mut digests = vec![];
+        let mut cursor = None;
+        let mut has_next_page = true;
+        while has_next_page && digests.len() <= end {
+            let page = context
+                .client
+                .read_api()
+                .query_transaction_blocks(query.clone(), cursor, None, true)
+                .await?;
+            digests.extend(page.data.into_iter().map(|tx| tx.digest));
+            cursor = page.next_cursor;
+            has_next_page = page.has_next_page;
+        }
+        digests
+    };
+
+    let next_offset = (digests.len() > end).then_some(end as u64);
+    digests.truncate(end);
+    let total_count = digests.len() as u64;
+    let digests = digests.get(offset..).unwrap_or_default();
+
+    let mut block_identifiers = HashMap::new();
+    let mut transactions = vec![];
+    for batch in digests.chunks(50) {
+        let responses = context
+            .client
+            .read_api()
+            .multi_get_transactions_with_options(
+                batch.to_vec(),
+                SuiTransactionBlockResponseOptions::new()
+                    .with_input()
+                    .with_effects()
+                    .with_balance_changes()
+                    .with_events(),
+            )
+            .await?;
+        for response in responses {
+            let Some(checkpoint) = response.checkpoint.filter(|c| *c <= max_block) else {
+                continue;
+            };
+            let block_identifier = match block_identifiers.get(&checkpoint) {
+                Some(block_identifier) => *block_identifier,
+                None => {
+                    let block_identifier =
+                        context.blocks().create_block_identifier(checkpoint).await?;
+                    block_identifiers.insert(checkpoint, block_identifier);
+                    block_identifier
+                }
+            };
+            let hash = response.digest;
+            transactions.push(BlockTransaction {
+                block_identifier,
+                transaction: Transaction {
+                    transaction_identifier: TransactionIdentifier { hash },
+                    operations: context.coin_metadata_cache.operations(response).await?,
+                    related_transactions: vec![],
+                    metadata: None,
+                },
+            });
+        }
+    }
+
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count,
+        next_offset,
+    })
+}
+
+fn transaction_filter(request: &SearchTransactionsRequest) -> Result<TransactionFilter, Error> {
+    fn address_filter(address: SuiAddress) -> TransactionFilter {
+        TransactionFilter::FromAddress(address).or(TransactionFilter::ToAddress(address))
+    }
+
+    let mut filters = vec![];
+    if let Some(account) = &request.account_identifier {
+        if account.sub_account.is_some() {
+            return Err(Error::InvalidInput(
+                "Searching transactions by sub-account is not supported".to_string(),
+            ));
+        }
+        filters.push(address_filter(account.address));
+    }
+    if let Some(address) = request.address {
+        filters.push(address_filter(address));
+    }
+    // Transactions that used or changed the coin, at any version
+    if let Some(coin) = &request.coin_identifier {
+        let id = coin.identifier.id;
+        filters.push(TransactionFilter::InputObject(id).or(TransactionFilter::ChangedObject(id)));
+    }
+
+    match (filters.len(), request.operator) {
+        (0, _) => Err(Error::MissingInput(
+            "account_identifier, address, coin_identifier or transaction_identifier".to_string(),
+        )),
+        (1, _) => Ok(filters.remove(0)),
+        (_, Operator::And) => Ok(TransactionFilter::All(filters)),
+        (_, Operator::Or) => Ok(TransactionFilter::Any(filters)),
+    }
+}
@@ -3,14 +3,17 @@
 
 use crate::operations::Operations;
 use crate::types::{
-    Block, BlockHash, BlockIdentifier, BlockResponse, Transaction, TransactionIdentifier,
+    Block, BlockHash, BlockIdentifier, BlockResponse, Currency, Transaction, TransactionIdentifier,
 };
-use crate::Error;
+use crate::{Error, SUI};
 use async_trait::async_trait;
-use std::sync::Arc;
-use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use move_core_types::language_storage::TypeTag;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use sui_json_rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
 use sui_sdk::rpc_types::Checkpoint;
 use sui_sdk::SuiClient;
+use sui_types::gas_coin::GAS;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 #[cfg(test)]
@@ -20,13 +23,19 @@ mod balance_changing_tx_tests;
 #[derive(Clone)]
 pub struct OnlineServerContext {
     pub client: SuiClient,
+    pub coin_metadata_cache: CoinMetadataCache,
     block_provider: Arc<dyn BlockProvider + Send + Sync>,
 }
 
 impl OnlineServerContext {
-    pub fn new(client: SuiClient, block_provider: Arc<dyn BlockProvider + Send + Sync>) -> Self {
+    pub fn new(
+        client: SuiClient,
+        coin_metadata_cache: CoinMetadataCache,
+        block_provider: Arc<dyn BlockProvider + Send + Sync>,
+    ) -> Self {
         Self {
             client,
+            coin_metadata_cache,
             block_provider,
         }
     }
@@ -36,6 +45,69 @@ impl OnlineServerContext {
     }
 }
 
+/// Currencies of coin types, read from their `CoinMetadata` the first time they are needed
+#[derive(Clone)]
+pub struct CoinMetadataCache {
+    client: SuiClient,
+    // `None` for coin types without `CoinMetadata`
+    currencies: Arc<Mutex<HashMap<TypeTag, Option<Currency>>>>,
+}
+
+impl CoinMetadataCache {
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            client,
+            currencies: Default::default(),
+        }
+    }
+
+    /// The currency of `Coin<coin_type>`, or `None` if the coin type has no `CoinMetadata`
+    pub async fn get_currency(&self, coin_type: &TypeTag) -> Result<Option<Currency>, Error> {
+        if coin_type == &GAS::type_tag() {
+            return Ok(Some(SUI.clone()));
+        }
+        if let Some(currency) = self.currencies.lock().unwrap().get(coin_type) {
+            return Ok(currency.clone());
+        }
+        let currency = self
+            .client
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_string())
+            .await?
+            .map(|metadata| Currency::new(coin_type, metadata));
+        self.currencies
+            .lock()
+            .unwrap()
+            .insert(coin_type.clone(), currency.clone());
+        Ok(currency)
+    }
+
+    /// Currencies of the coin types that have `CoinMetadata`
+    pub async fn get_currencies(
+        &self,
+        coin_types: impl IntoIterator<Item = TypeTag>,
+    ) -> Result<HashMap<TypeTag, Currency>, Error> {
+        let mut currencies = HashMap::new();
+        for coin_type in coin_types {
+            if let Some(currency) = self.get_currency(&coin_type).await? {
+                currencies.insert(coin_type, currency);
+            }
+        }
+        Ok(currencies)
+    }
+
+    /// Operations of an executed transaction, in the currencies of all the coins it handles
+    pub async fn operations(
+        &self,
+        response: SuiTransactionBlockResponse,
+    ) -> Result<Operations, Error> {
+        let currencies = self
+            .get_currencies(Operations::coin_types(&response))
+            .await?;
+        Operations::try_from_response(response, &currencies)
+    }
+}
+
 #[async_trait]
 pub trait BlockProvider {
     async fn get_block_by_index(&self, index: u64) -> Result<BlockResponse, Error>;
@@ -53,6 +125,7 @@ pub trait BlockProvider {
 #[derive(Clone)]
 pub struct CheckpointBlockProvider {
     client: SuiClient,
+    coin_metadata_cache: CoinMetadataCache,
 }
 
 #[async_trait]
@@ -103,8 +176,11 @@ impl BlockProvider for CheckpointBlockProvider {
 }
 
 impl CheckpointBlockProvider {
-    pub fn new(client: SuiClient) -> Self {
-        Self { client }
+    pub fn new(client: SuiClient, coin_metadata_cache: CoinMetadataCache) -> Self {
+        Self {
+            client,
+            coin_metadata_cache,
+        }
     }
 
     async fn create_block_response(&self, checkpoint: Checkpoint) -> Result<BlockResponse, Error> {
@@ -127,7 +203,7 @@ impl CheckpointBlockProvider {
             for tx in transaction_responses.into_iter() {
                 transactions.push(Transaction {
                     transaction_identifier: TransactionIdentifier { hash: tx.digest },
-                    operations: Operations::try_from(tx)?,
+                    operations: self.coin_metadata_cache.operations(tx).await?,
                     related_transactions: vec![],
                     metadata: None,
                 })
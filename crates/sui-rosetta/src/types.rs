@@ -14,16 +14,21 @@ use serde_json::Value;
 use strum_macros::EnumIter;
 use strum_macros::EnumString;
 
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::TypeTag;
+use sui_json_rpc_types::SuiCoinMetadata;
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockKind};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::crypto::PublicKey as SuiPublicKey;
 use sui_types::crypto::SignatureScheme;
+use sui_types::gas_coin::GAS;
 use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages_checkpoint::CheckpointDigest;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{Argument, CallArg, Command, ObjectArg, TransactionData};
-use sui_types::SUI_SYSTEM_PACKAGE_ID;
+use sui_types::{parse_sui_type_tag, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
 use crate::errors::{Error, ErrorType};
 use crate::operations::Operations;
@@ -31,6 +36,9 @@ use crate::SUI;
 
 pub type BlockHeight = u64;
 
+pub const PAY_MODULE_NAME: &IdentStr = ident_str!("pay");
+pub const PAY_SPLIT_AND_TRANSFER_FUN_NAME: &IdentStr = ident_str!("split_and_transfer");
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct NetworkIdentifier {
     pub blockchain: String,
@@ -95,17 +103,48 @@ impl From<SuiAddress> for AccountIdentifier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Currency {
     pub symbol: String,
     pub decimals: u64,
+    /// Identifies the coin type of currencies other than SUI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CurrencyMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CurrencyMetadata {
+    pub coin_type: String,
+}
+
+impl Currency {
+    /// The currency of `Coin<coin_type>`, as described by its `CoinMetadata`
+    pub fn new(coin_type: &TypeTag, metadata: SuiCoinMetadata) -> Self {
+        Self {
+            symbol: metadata.symbol,
+            decimals: metadata.decimals.into(),
+            metadata: Some(CurrencyMetadata {
+                coin_type: coin_type.to_string(),
+            }),
+        }
+    }
+
+    pub fn coin_type(&self) -> Result<TypeTag, Error> {
+        match &self.metadata {
+            None => Ok(GAS::type_tag()),
+            Some(CurrencyMetadata { coin_type }) => parse_sui_type_tag(coin_type)
+                .map_err(|e| Error::InvalidInput(format!("Invalid coin type {coin_type}: {e}"))),
+        }
+    }
 }
+
 #[derive(Serialize, Deserialize)]
 pub struct AccountBalanceRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     #[serde(default)]
     pub block_identifier: PartialBlockIdentifier,
+    /// Currencies to return the balances of, all of the account's balances if empty
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub currencies: Vec<Currency>,
 }
@@ -153,9 +192,12 @@ pub struct SubBalance {
 
 impl Amount {
     pub fn new(value: i128) -> Self {
+        Self::new_with_currency(value, SUI.clone())
+    }
+    pub fn new_with_currency(value: i128, currency: Currency) -> Self {
         Self {
             value,
-            currency: SUI.clone(),
+            currency,
             metadata: None,
         }
     }
@@ -197,6 +239,9 @@ pub struct AccountCoinsRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     pub include_mempool: bool,
+    /// Currencies to return the coins of, all of the account's coins if empty
+    #[serde(default)]
+    pub currencies: Vec<Currency>,
 }
 #[derive(Serialize)]
 pub struct AccountCoinsResponse {
@@ -216,6 +261,12 @@ pub struct Coin {
 
 impl From<sui_sdk::rpc_types::Coin> for Coin {
     fn from(coin: sui_sdk::rpc_types::Coin) -> Self {
+        Self::new(coin, SUI.clone())
+    }
+}
+
+impl Coin {
+    pub fn new(coin: sui_sdk::rpc_types::Coin, currency: Currency) -> Self {
         Self {
             coin_identifier: CoinIdentifier {
                 identifier: CoinID {
@@ -223,11 +274,7 @@ impl From<sui_sdk::rpc_types::Coin> for Coin {
                     version: coin.version,
                 },
             },
-            amount: Amount {
-                value: coin.balance as i128,
-                currency: SUI.clone(),
-                metadata: None,
-            },
+            amount: Amount::new_with_currency(coin.balance as i128, currency),
         }
     }
 }
@@ -395,10 +442,12 @@ pub enum OperationType {
     // Balance changing operations from TransactionEffect
     Gas,
     SuiBalanceChange,
+    CoinBalanceChange,
     StakeReward,
     StakePrinciple,
     // sui-rosetta supported operation type
     PaySui,
+    PayCoin,
     Stake,
     WithdrawStake,
     // All other Sui transaction types, readonly
@@ -826,6 +875,110 @@ impl IntoResponse for BlockTransactionResponse {
     }
 }
 
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    #[default]
+    And,
+    Or,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchTransactionsRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default)]
+    pub operator: Operator,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_identifier: Option<CoinIdentifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchTransactionsResponse {
+    pub transactions: Vec<BlockTransaction>,
+    pub total_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
+impl IntoResponse for SearchTransactionsResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockTransaction {
+    pub block_identifier: BlockIdentifier,
+    pub transaction: Transaction,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventsBlocksRequest {
+    pub network_identifier: NetworkIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventsBlocksResponse {
+    pub max_sequence: u64,
+    pub events: Vec<BlockEvent>,
+}
+
+impl IntoResponse for EventsBlocksResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockEvent {
+    pub sequence: u64,
+    pub block_identifier: BlockIdentifier,
+    #[serde(rename = "type")]
+    pub type_: BlockEventType,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEventType {
+    BlockAdded,
+    BlockRemoved,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MempoolResponse {
+    pub transaction_identifiers: Vec<TransactionIdentifier>,
+}
+
+impl IntoResponse for MempoolResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct PrefundedAccount {
     pub privkey: String,
@@ -841,6 +994,12 @@ pub enum InternalOperation {
         recipients: Vec<SuiAddress>,
         amounts: Vec<u64>,
     },
+    PayCoin {
+        sender: SuiAddress,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        currency: Currency,
+    },
     Stake {
         sender: SuiAddress,
         validator: SuiAddress,
@@ -857,6 +1016,7 @@ impl InternalOperation {
     pub fn sender(&self) -> SuiAddress {
         match self {
             InternalOperation::PaySui { sender, .. }
+            | InternalOperation::PayCoin { sender, .. }
             | InternalOperation::Stake { sender, .. }
             | InternalOperation::WithdrawStake { sender, .. } => *sender,
         }
@@ -873,6 +1033,42 @@ impl InternalOperation {
                 builder.pay_sui(recipients, amounts)?;
                 builder.finish()
             }
+            Self::PayCoin {
+                recipients,
+                amounts,
+                currency,
+                ..
+            } => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                let coin_type = currency.coin_type()?;
+                let mut coins = metadata
+                    .objects
+                    .into_iter()
+                    .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if coins.is_empty() {
+                    return Err(Error::InvalidInput(format!(
+                        "No {} coin to pay with",
+                        currency.symbol
+                    )));
+                }
+                let coin = coins.remove(0);
+                if !coins.is_empty() {
+                    builder.command(Command::MergeCoins(coin, coins));
+                }
+                for (recipient, amount) in recipients.into_iter().zip(amounts) {
+                    let amount = builder.pure(amount)?;
+                    let recipient = builder.pure(recipient)?;
+                    builder.command(Command::move_call(
+                        SUI_FRAMEWORK_PACKAGE_ID,
+                        PAY_MODULE_NAME.to_owned(),
+                        PAY_SPLIT_AND_TRANSFER_FUN_NAME.to_owned(),
+                        vec![coin_type.clone()],
+                        vec![coin, amount, recipient],
+                    ));
+                }
+                builder.finish()
+            }
             InternalOperation::Stake {
                 validator, amount, ..
             } => {
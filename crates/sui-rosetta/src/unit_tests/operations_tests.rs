@@ -1,14 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use move_core_types::value::MoveTypeLayout;
+use serde_json::json;
 use sui_json_rpc_types::SuiCallArg;
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::parse_sui_type_tag;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::transaction::{CallArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
 
+use crate::errors::Error;
 use crate::operations::Operations;
-use crate::types::ConstructionMetadata;
+use crate::types::{
+    ConstructionMetadata, Currency, CurrencyMetadata, InternalOperation, OperationType,
+};
 
 #[tokio::test]
 async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
@@ -50,6 +57,102 @@ async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+#[tokio::test]
+async fn test_pay_coin_data_parsing() -> Result<(), anyhow::Error> {
+    let object_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        )
+    };
+    let gas = object_ref();
+    let coins = vec![object_ref(), object_ref()];
+    let sender = SuiAddress::random_for_testing_only();
+    let coin_type = parse_sui_type_tag("0x42::usd::USD")?;
+    let currency = Currency {
+        symbol: "USD".to_string(),
+        decimals: 6,
+        metadata: Some(CurrencyMetadata {
+            coin_type: coin_type.to_string(),
+        }),
+    };
+    let gas_price = 10;
+    let metadata = || ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: coins.clone(),
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+    };
+
+    let data = InternalOperation::PayCoin {
+        sender,
+        recipients: vec![
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        ],
+        amounts: vec![10000, 20000],
+        currency: currency.clone(),
+    }
+    .try_into_data(metadata())?;
+
+    let currencies = HashMap::from([(coin_type, currency.clone())]);
+    let ops = Operations::try_from_data(data.clone(), &currencies)?;
+    assert_eq!(ops.type_(), Some(OperationType::PayCoin));
+    assert!(ops
+        .clone()
+        .into_iter()
+        .all(|op| op.amount.unwrap().currency == currency));
+    let parsed_data = ops.into_internal()?.try_into_data(metadata())?;
+    assert_eq!(data, parsed_data);
+
+    // Payments in unknown currencies are reported as generic transactions
+    let ops: Operations = data.try_into()?;
+    assert_eq!(ops.type_(), Some(OperationType::ProgrammableTransaction));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mixed_pay_operations_are_rejected() {
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let ops: Operations = serde_json::from_value(json!([
+        {
+            "operation_identifier": {"index": 0},
+            "type": "PaySui",
+            "account": {"address": sender.to_string()},
+            "amount": {"value": "-100", "currency": {"symbol": "SUI", "decimals": 9}}
+        },
+        {
+            "operation_identifier": {"index": 1},
+            "type": "PaySui",
+            "account": {"address": recipient.to_string()},
+            "amount": {"value": "100", "currency": {"symbol": "SUI", "decimals": 9}}
+        },
+        {
+            "operation_identifier": {"index": 2},
+            "type": "PayCoin",
+            "account": {"address": recipient.to_string()},
+            "amount": {
+                "value": "100",
+                "currency": {
+                    "symbol": "USD",
+                    "decimals": 6,
+                    "metadata": {"coin_type": "0x42::usd::USD"}
+                }
+            }
+        }
+    ]))
+    .unwrap();
+    assert!(matches!(
+        ops.into_internal(),
+        Err(Error::MalformedOperationError(_))
+    ));
+}
+
 #[tokio::test]
 async fn test_sui_json() {
     let arg1 = CallArg::Pure(bcs::to_bytes(&1000000u64).unwrap());
@@ -10,11 +10,13 @@ use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 use sui_keys::keystore::AccountKeystore;
 use sui_rosetta::operations::Operations;
 use sui_rosetta::types::{
-    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, NetworkIdentifier,
+    AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockEventType,
+    EventsBlocksResponse, MempoolResponse, NetworkIdentifier, SearchTransactionsResponse,
     SubAccount, SubAccountType, SuiEnv,
 };
 use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_swarm_config::genesis_config::{DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT};
+use sui_types::base_types::SuiAddress;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::utils::to_sender_signed_transaction;
 use test_utils::network::TestClusterBuilder;
@@ -427,6 +429,133 @@ async fn test_pay_sui() {
     );
 }
 
+#[tokio::test]
+async fn test_search_transactions() {
+    let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let sender = test_cluster.accounts[0];
+    let recipient = test_cluster.accounts[1];
+    let client = test_cluster.wallet.get_client().await.unwrap();
+    let keystore = &test_cluster.wallet.config.keystore;
+
+    let (rosetta_client, _handle) = start_rosetta_test_server(client.clone()).await;
+
+    let ops = serde_json::from_value(json!(
+        [{
+            "operation_identifier":{"index":0},
+            "type":"PaySui",
+            "account": { "address" : recipient.to_string() },
+            "amount" : { "value": "1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        },{
+            "operation_identifier":{"index":1},
+            "type":"PaySui",
+            "account": { "address" : sender.to_string() },
+            "amount" : { "value": "-1000000000" , "currency": { "symbol": "SUI", "decimals": 9}}
+        }]
+    ))
+    .unwrap();
+    let response = rosetta_client.rosetta_flow(&ops, keystore).await;
+    let digest = response.transaction_identifier.hash;
+    // Wait for the transaction to be included in a checkpoint
+    let checkpoint = loop {
+        let tx = client
+            .read_api()
+            .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new())
+            .await
+            .unwrap();
+        if let Some(checkpoint) = tx.checkpoint {
+            break checkpoint;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    while client
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await
+        .unwrap()
+        < checkpoint
+    {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let network_identifier = NetworkIdentifier {
+        blockchain: "sui".to_string(),
+        network: SuiEnv::LocalNet,
+    };
+    let search = |account: SuiAddress, limit: Option<u64>, offset: Option<u64>| {
+        json!({
+            "network_identifier": network_identifier,
+            "account_identifier": { "address": account.to_string() },
+            "limit": limit,
+            "offset": offset,
+        })
+    };
+
+    // The recipient received the payment
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(recipient, None, None),
+        )
+        .await;
+    assert_eq!(response.total_count, 1);
+    assert_eq!(response.next_offset, None);
+    let found = &response.transactions[0];
+    assert_eq!(found.transaction.transaction_identifier.hash, digest);
+    assert_eq!(found.block_identifier.index, checkpoint);
+    assert!(found.transaction.operations.contains(&ops));
+
+    // The sender's payment is its most recent transaction
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(sender, Some(1), None),
+        )
+        .await;
+    assert_eq!(response.transactions.len(), 1);
+    assert_eq!(
+        response.transactions[0]
+            .transaction
+            .transaction_identifier
+            .hash,
+        digest
+    );
+    let response: SearchTransactionsResponse = rosetta_client
+        .call(
+            RosettaEndpoint::SearchTransactions,
+            &search(sender, Some(1), response.next_offset),
+        )
+        .await;
+    assert!(response
+        .transactions
+        .iter()
+        .all(|tx| tx.transaction.transaction_identifier.hash != digest));
+
+    // The block of the payment was added
+    let response: EventsBlocksResponse = rosetta_client
+        .call(
+            RosettaEndpoint::EventsBlocks,
+            &json!({
+                "network_identifier": network_identifier,
+                "offset": checkpoint,
+                "limit": 1,
+            }),
+        )
+        .await;
+    assert!(response.max_sequence >= checkpoint);
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events[0].sequence, checkpoint);
+    assert_eq!(response.events[0].block_identifier.index, checkpoint);
+    assert_eq!(response.events[0].type_, BlockEventType::BlockAdded);
+
+    let response: MempoolResponse = rosetta_client
+        .call(
+            RosettaEndpoint::Mempool,
+            &json!({ "network_identifier": network_identifier }),
+        )
+        .await;
+    assert!(response.transaction_identifiers.is_empty());
+}
+
 #[tokio::test]
 async fn test_pay_sui_multiple_times() {
     let test_cluster = TestClusterBuilder::new()
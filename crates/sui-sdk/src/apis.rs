@@ -19,12 +19,12 @@ use sui_json_rpc::api::{
     CoinReadApiClient, IndexerApiClient, MoveUtilsClient, ReadApiClient, WriteApiClient,
 };
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake,
-    DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
-    ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
-    SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    Balance, Checkpoint, CheckpointId, CheckpointSubscriptionOptions, CheckpointWithEffects, Coin,
+    CoinPage, DelegatedStake, DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse,
+    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, ProtocolConfigResponse, SuiCoinMetadata,
+    SuiCommittee, SuiEvent, SuiGetPastObjectRequest, SuiMoveNormalizedModule, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiPastObjectResponse, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
 };
 use sui_json_rpc_types::{CheckpointPage, SuiLoadedChildObjectsResponse};
 use sui_types::balance::Supply;
@@ -34,7 +34,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
-use sui_types::transaction::{TransactionData, VerifiedTransaction};
+use sui_types::transaction::{TransactionData, TransactionKind, VerifiedTransaction};
 
 const WAIT_FOR_LOCAL_EXECUTION_RETRY_COUNT: u8 = 3;
 
@@ -75,6 +75,20 @@ impl ReadApi {
             .await?)
     }
 
+    pub async fn get_child_objects(
+        &self,
+        object_id: ObjectID,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<ObjectsPage> {
+        Ok(self
+            .api
+            .http
+            .get_child_objects(object_id, query, cursor, limit)
+            .await?)
+    }
+
     pub async fn try_get_parsed_past_object(
         &self,
         object_id: ObjectID,
@@ -188,6 +202,25 @@ impl ReadApi {
             .await?)
     }
 
+    /// Subscribe to checkpoints as the fullnode executes them, starting after `cursor` if given
+    pub async fn subscribe_checkpoint(
+        &self,
+        cursor: Option<CheckpointSequenceNumber>,
+        options: CheckpointSubscriptionOptions,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<CheckpointWithEffects>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<CheckpointWithEffects> = c
+                    .subscribe_checkpoint(cursor.map(BigInt::from), Some(options))
+                    .await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     /// Return the sequence number of the latest checkpoint that has been executed
     pub async fn get_latest_checkpoint_sequence_number(
         &self,
@@ -258,6 +291,27 @@ impl ReadApi {
             .await?)
     }
 
+    pub async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: SuiAddress,
+        tx: TransactionKind,
+        gas_price: Option<BigInt<u64>>,
+        epoch: Option<BigInt<u64>>,
+        additional_args: Option<DevInspectArgs>,
+    ) -> SuiRpcResult<DevInspectResults> {
+        Ok(self
+            .api
+            .http
+            .dev_inspect_transaction_block(
+                sender_address,
+                Base64::from_bytes(&bcs::to_bytes(&tx)?),
+                gas_price,
+                epoch,
+                additional_args,
+            )
+            .await?)
+    }
+
     pub async fn get_loaded_child_objects(
         &self,
         digest: TransactionDigest,
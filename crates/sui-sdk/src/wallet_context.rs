@@ -15,6 +15,7 @@ use sui_json_rpc_types::{
     SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI,
     SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
+use sui_keys::encryption::KEYSTORE_PASSPHRASE_ENV;
 use sui_keys::keystore::AccountKeystore;
 use sui_test_transaction_builder::TestTransactionBuilder;
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
@@ -58,6 +59,16 @@ impl WalletContext {
         Ok(context)
     }
 
+    /// Decrypts the keys of an encrypted keystore, reading the passphrase with
+    /// [read_keystore_passphrase]. Only commands which use the private keys need to call this, so
+    /// that read-only commands never ask for the passphrase.
+    pub fn unlock_keystore(&mut self) -> Result<(), anyhow::Error> {
+        if self.config.keystore.is_locked() {
+            self.config.keystore.unlock(&read_keystore_passphrase()?)?;
+        }
+        Ok(())
+    }
+
     pub fn get_addresses(&self) -> Vec<SuiAddress> {
         self.config.keystore.addresses()
     }
@@ -544,3 +555,17 @@ impl WalletContext {
         self.execute_transaction_block(txn).await.unwrap()
     }
 }
+
+/// Reads the passphrase of an encrypted keystore from the `SUI_KEYSTORE_PASSPHRASE` environment
+/// variable, or prompts for it.
+pub fn read_keystore_passphrase() -> Result<String, anyhow::Error> {
+    if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    inquire::Password::new("Keystore passphrase:")
+        .without_confirmation()
+        .prompt()
+        .map_err(|e| {
+            anyhow!("Cannot read the keystore passphrase, set {KEYSTORE_PASSPHRASE_ENV} to provide it: {e}")
+        })
+}
@@ -5,7 +5,8 @@
 #[cfg(test)]
 mod tests;
 
-mod writer;
+pub mod reader;
+pub mod writer;
 
 use anyhow::Result;
 use fastcrypto::hash::{HashFunction, Sha3_256};
@@ -218,6 +219,11 @@ impl Manifest {
             Self::V1(manifest) => &manifest.file_metadata,
         }
     }
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Self::V1(manifest) => manifest.epoch,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
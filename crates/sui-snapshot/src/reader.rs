@@ -0,0 +1,333 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compute_sha3_checksum, Blob, Encoding, FileMetadata, FileType, Manifest, MAGIC_BYTES,
+    MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC, OBJECT_ID_BYTES, OBJECT_REF_BYTES,
+    REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES, SHA3_BYTES,
+};
+use anyhow::{anyhow, Context, Result};
+use byteorder::{BigEndian, ByteOrder};
+use fastcrypto::hash::{HashFunction, MultisetHash, Sha3_256};
+use integer_encoding::VarIntReader;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::state_accumulator::WrappedObject;
+use sui_storage::object_store::util::{copy_file, copy_files, path_to_filesystem};
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use sui_types::digests::ObjectDigest;
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
+use tracing::info;
+
+/// Number of objects written to the db in one batch during restore
+const RESTORE_BATCH_SIZE: usize = 10_000;
+
+/// StateSnapshotReaderV1 downloads a state snapshot from a remote object store into a local
+/// staging dir, verifies it and loads the live object set into the perpetual tables
+pub struct StateSnapshotReaderV1 {
+    epoch: u64,
+    local_staging_dir_root: PathBuf,
+    remote_object_store: Arc<DynObjectStore>,
+    local_object_store: Arc<DynObjectStore>,
+    manifest: Manifest,
+    concurrency: NonZeroUsize,
+}
+
+impl StateSnapshotReaderV1 {
+    /// Downloads and verifies the MANIFEST of the snapshot for `epoch`
+    pub async fn new(
+        epoch: u64,
+        remote_store_config: ObjectStoreConfig,
+        local_store_config: ObjectStoreConfig,
+        concurrency: NonZeroUsize,
+    ) -> Result<Self> {
+        let epoch_dir = Path::from(format!("epoch_{epoch}"));
+        let remote_object_store = remote_store_config.make()?;
+        let local_object_store = local_store_config.make()?;
+        let local_staging_dir_root = local_store_config
+            .directory
+            .context("No local directory specified")?;
+        let manifest_file_path = epoch_dir.child("MANIFEST");
+        copy_file(
+            manifest_file_path.clone(),
+            manifest_file_path.clone(),
+            remote_object_store.clone(),
+            local_object_store.clone(),
+        )
+        .await?;
+        let manifest = Self::read_manifest(path_to_filesystem(
+            local_staging_dir_root.clone(),
+            &manifest_file_path,
+        )?)?;
+        if manifest.epoch() != epoch {
+            return Err(anyhow!(
+                "Unexpected epoch in snapshot manifest: {}, expected: {epoch}",
+                manifest.epoch()
+            ));
+        }
+        Ok(StateSnapshotReaderV1 {
+            epoch,
+            local_staging_dir_root,
+            remote_object_store,
+            local_object_store,
+            manifest,
+            concurrency,
+        })
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Downloads all snapshot files, verifies them against the manifest and the expected root
+    /// state hash of the epoch, and inserts the live object set into `perpetual_db`, which is
+    /// expected to be empty. Every object is checked against its verified reference before it is
+    /// inserted, but an error midway leaves a partial set behind, so the db should be discarded.
+    pub async fn read(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        expected_root: &ECMHLiveObjectSetDigest,
+        indirect_objects_threshold: usize,
+    ) -> Result<()> {
+        self.download().await?;
+        let buckets = self.buckets()?;
+        self.verify_buckets(&buckets, expected_root)?;
+        let mut num_objects = 0;
+        for (bucket_num, (object_files, ref_file)) in buckets {
+            let mut refs = self.refs(ref_file)?.into_iter();
+            let mut batch = Vec::with_capacity(RESTORE_BATCH_SIZE);
+            for file_metadata in object_files {
+                for object in self.objects(file_metadata)? {
+                    let object = object?;
+                    let expected_ref = refs.next().context(format!(
+                        "More objects than references in bucket {bucket_num}"
+                    ))?;
+                    if object.object_reference() != expected_ref {
+                        return Err(anyhow!(
+                            "Object {:?} does not match its reference {:?} in bucket {bucket_num}",
+                            object.object_reference(),
+                            expected_ref
+                        ));
+                    }
+                    batch.push(object);
+                    if batch.len() == RESTORE_BATCH_SIZE {
+                        perpetual_db
+                            .insert_live_objects_for_restore(&batch, indirect_objects_threshold)?;
+                        num_objects += batch.len();
+                        batch.clear();
+                    }
+                }
+            }
+            if refs.next().is_some() {
+                return Err(anyhow!(
+                    "More references than objects in bucket {bucket_num}"
+                ));
+            }
+            perpetual_db.insert_live_objects_for_restore(&batch, indirect_objects_threshold)?;
+            num_objects += batch.len();
+        }
+        info!(
+            "Restored {num_objects} live objects from state snapshot of epoch {}",
+            self.epoch
+        );
+        Ok(())
+    }
+
+    /// Downloads all files listed in the manifest and checks their sha3 digests
+    pub async fn download(&self) -> Result<()> {
+        let epoch_dir = self.epoch_dir();
+        let files: Vec<_> = self
+            .manifest
+            .file_metadata()
+            .iter()
+            .map(|file_metadata| file_metadata.file_path(&epoch_dir))
+            .collect();
+        copy_files(
+            &files,
+            &files,
+            self.remote_object_store.clone(),
+            self.local_object_store.clone(),
+            self.concurrency,
+        )
+        .await?;
+        for file_metadata in self.manifest.file_metadata() {
+            let local_path = self.local_path(file_metadata)?;
+            let sha3_digest = compute_sha3_checksum(&local_path)?;
+            if sha3_digest != file_metadata.sha3_digest {
+                return Err(anyhow!(
+                    "Checksum mismatch for snapshot file {}",
+                    local_path.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Accumulates the object references of the snapshot and checks the result against the
+    /// root state hash committed to at the end of the epoch
+    pub fn verify_root(&self, expected_root: &ECMHLiveObjectSetDigest) -> Result<()> {
+        self.verify_buckets(&self.buckets()?, expected_root)
+    }
+
+    fn verify_buckets(
+        &self,
+        buckets: &BTreeMap<u32, (Vec<&FileMetadata>, &FileMetadata)>,
+        expected_root: &ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        let mut acc = Accumulator::default();
+        for (_, ref_file) in buckets.values() {
+            for (id, version, digest) in self.refs(ref_file)? {
+                if digest == ObjectDigest::OBJECT_DIGEST_WRAPPED {
+                    acc.insert(bcs::to_bytes(&WrappedObject::new(id, version))?);
+                } else {
+                    acc.insert(digest);
+                }
+            }
+        }
+        let root: ECMHLiveObjectSetDigest = acc.digest().into();
+        if root != *expected_root {
+            return Err(anyhow!(
+                "Root state hash mismatch for epoch {}: snapshot has {}, expected {}",
+                self.epoch,
+                root.digest,
+                expected_root.digest
+            ));
+        }
+        Ok(())
+    }
+
+    fn read_manifest(path: PathBuf) -> Result<Manifest> {
+        let bytes = fs::read(&path)?;
+        if bytes.len() < MAGIC_BYTES + SHA3_BYTES {
+            return Err(anyhow!("Manifest file is too short: {}", path.display()));
+        }
+        if BigEndian::read_u32(&bytes[..MAGIC_BYTES]) != MANIFEST_FILE_MAGIC {
+            return Err(anyhow!("Unexpected magic in manifest: {}", path.display()));
+        }
+        let (content, sha3_digest) = bytes.split_at(bytes.len() - SHA3_BYTES);
+        let mut hasher = Sha3_256::default();
+        hasher.update(content);
+        if hasher.finalize().digest != sha3_digest {
+            return Err(anyhow!(
+                "Checksum mismatch for manifest: {}",
+                path.display()
+            ));
+        }
+        Ok(bcs::from_bytes(&content[MAGIC_BYTES..])?)
+    }
+
+    /// Object files of each bucket in partition order, along with the bucket's reference file
+    fn buckets(&self) -> Result<BTreeMap<u32, (Vec<&FileMetadata>, &FileMetadata)>> {
+        let mut object_files: BTreeMap<u32, Vec<&FileMetadata>> = BTreeMap::new();
+        let mut ref_files = BTreeMap::new();
+        for file_metadata in self.manifest.file_metadata() {
+            match file_metadata.file_type {
+                FileType::Object => object_files
+                    .entry(file_metadata.bucket_num)
+                    .or_default()
+                    .push(file_metadata),
+                FileType::Reference => {
+                    ref_files.insert(file_metadata.bucket_num, file_metadata);
+                }
+            }
+        }
+        object_files
+            .into_iter()
+            .map(|(bucket_num, mut files)| {
+                files.sort_by_key(|f| f.part_num);
+                let ref_file = ref_files
+                    .remove(&bucket_num)
+                    .context(format!("Missing reference file for bucket {bucket_num}"))?;
+                Ok((bucket_num, (files, ref_file)))
+            })
+            .collect()
+    }
+
+    /// All object references in a bucket's reference file, in the order the objects were written
+    fn refs(&self, ref_file: &FileMetadata) -> Result<Vec<ObjectRef>> {
+        let mut reader = self.open(ref_file, REFERENCE_FILE_MAGIC)?;
+        let mut refs = vec![];
+        let mut buf = [0u8; OBJECT_REF_BYTES];
+        loop {
+            match reader.read_exact(&mut buf) {
+                Ok(()) => (),
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e.into()),
+            }
+            // Partitions are delimited by an all zero reference
+            if buf.iter().all(|b| *b == 0) {
+                continue;
+            }
+            let id = ObjectID::from_bytes(&buf[..OBJECT_ID_BYTES])?;
+            let version = SequenceNumber::from_u64(BigEndian::read_u64(
+                &buf[OBJECT_ID_BYTES..OBJECT_ID_BYTES + SEQUENCE_NUM_BYTES],
+            ));
+            let digest = ObjectDigest::try_from(&buf[OBJECT_ID_BYTES + SEQUENCE_NUM_BYTES..])?;
+            refs.push((id, version, digest));
+        }
+        Ok(refs)
+    }
+
+    /// Iterates the live objects of one object file
+    fn objects(
+        &self,
+        file_metadata: &FileMetadata,
+    ) -> Result<impl Iterator<Item = Result<LiveObject>>> {
+        let mut reader = self.open(file_metadata, OBJECT_FILE_MAGIC)?;
+        Ok(std::iter::from_fn(move || {
+            match reader.fill_buf() {
+                Ok([]) => return None,
+                Ok(_) => (),
+                Err(e) => return Some(Err(e.into())),
+            }
+            Some(Self::read_blob(&mut reader).and_then(|blob| blob.decode()))
+        }))
+    }
+
+    fn read_blob(reader: &mut impl Read) -> Result<Blob> {
+        let len: u64 = reader.read_varint()?;
+        let mut encoding = [0u8; 1];
+        reader.read_exact(&mut encoding)?;
+        let mut data = vec![0u8; len as usize];
+        reader.read_exact(&mut data)?;
+        Ok(Blob {
+            data,
+            encoding: Encoding::try_from(encoding[0])?,
+        })
+    }
+
+    /// Opens a decompressed reader over a local snapshot file, positioned past its magic
+    fn open(&self, file_metadata: &FileMetadata, magic: u32) -> Result<BufReader<Box<dyn Read>>> {
+        let local_path = self.local_path(file_metadata)?;
+        let mut reader = BufReader::new(file_metadata.file_compression.decompress(&local_path)?);
+        let mut magic_bytes = [0u8; MAGIC_BYTES];
+        reader.read_exact(&mut magic_bytes)?;
+        if BigEndian::read_u32(&magic_bytes) != magic {
+            return Err(anyhow!(
+                "Unexpected magic in snapshot file {}",
+                local_path.display()
+            ));
+        }
+        Ok(reader)
+    }
+
+    fn local_path(&self, file_metadata: &FileMetadata) -> Result<PathBuf> {
+        path_to_filesystem(
+            self.local_staging_dir_root.clone(),
+            &file_metadata.file_path(&self.epoch_dir()),
+        )
+    }
+
+    fn epoch_dir(&self) -> Path {
+        Path::from(format!("epoch_{}", self.epoch))
+    }
+}
@@ -1,12 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::reader::StateSnapshotReaderV1;
 use crate::writer::StateSnapshotWriterV1;
 use crate::FileCompression;
+use fastcrypto::hash::MultisetHash;
 use std::num::NonZeroUsize;
-use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::state_accumulator::WrappedObject;
 use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::accumulator::Accumulator;
 use sui_types::base_types::ObjectID;
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::object::Object;
 use tempfile::tempdir;
 
@@ -28,6 +33,20 @@ fn insert_keys(
     Ok(())
 }
 
+fn root_state_hash(db: &AuthorityPerpetualTables) -> ECMHLiveObjectSetDigest {
+    let mut acc = Accumulator::default();
+    for live_object in db.iter_live_object_set() {
+        match live_object {
+            LiveObject::Normal(object) => acc.insert(object.compute_object_reference().2),
+            LiveObject::Wrapped(key) => acc.insert(
+                bcs::to_bytes(&WrappedObject::new(key.0, key.1))
+                    .expect("Failed to serialize WrappedObject"),
+            ),
+        }
+    }
+    acc.digest().into()
+}
+
 #[tokio::test]
 async fn test_snapshot_basic() -> Result<(), anyhow::Error> {
     let db_path = temp_dir();
@@ -46,7 +65,7 @@ async fn test_snapshot_basic() -> Result<(), anyhow::Error> {
     let snapshot_writer = StateSnapshotWriterV1::new(
         0,
         local_store_config,
-        remote_store_config,
+        remote_store_config.clone(),
         FileCompression::Zstd,
         NonZeroUsize::new(1).unwrap(),
     )
@@ -54,7 +73,72 @@ async fn test_snapshot_basic() -> Result<(), anyhow::Error> {
     let perpetual_db = AuthorityPerpetualTables::open(&db_path, None);
     insert_keys(&perpetual_db, 1000)?;
     snapshot_writer.write(&perpetual_db).await?;
-    // TODO: Read the live object set from remote store and assert it is the same as local
+    let restored_db = restore(remote_store_config, 0, &root_state_hash(&perpetual_db)).await?;
+    assert_eq!(
+        perpetual_db
+            .iter_live_object_set()
+            .map(|o| o.object_reference())
+            .collect::<Vec<_>>(),
+        restored_db
+            .iter_live_object_set()
+            .map(|o| o.object_reference())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+async fn restore(
+    remote_store_config: ObjectStoreConfig,
+    epoch: u64,
+    expected_root: &ECMHLiveObjectSetDigest,
+) -> Result<AuthorityPerpetualTables, anyhow::Error> {
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("restore_dir")),
+        ..Default::default()
+    };
+    let snapshot_reader = StateSnapshotReaderV1::new(
+        epoch,
+        remote_store_config,
+        local_store_config,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let restored_db = AuthorityPerpetualTables::open(&temp_dir(), None);
+    snapshot_reader
+        .read(&restored_db, expected_root, usize::MAX)
+        .await?;
+    Ok(restored_db)
+}
+
+#[tokio::test]
+async fn test_snapshot_root_mismatch() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote),
+        ..Default::default()
+    };
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        0,
+        local_store_config,
+        remote_store_config.clone(),
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path, None);
+    insert_keys(&perpetual_db, 10)?;
+    snapshot_writer.write(&perpetual_db).await?;
+    let restored = restore(remote_store_config, 0, &ECMHLiveObjectSetDigest::default()).await;
+    assert!(restored.is_err());
     Ok(())
 }
 
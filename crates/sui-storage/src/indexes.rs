@@ -6,6 +6,7 @@
 
 use std::cmp::{max, min};
 use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -29,11 +30,12 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{self, DynamicFieldInfo};
 use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::parse_sui_struct_tag;
 use sui_types::temporary_store::TxCoins;
 use tokio::task::spawn_blocking;
-use tracing::{debug, trace};
+use tracing::{debug, info, trace};
 use typed_store::rocks::{
     default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf, ReadWriteOptions,
 };
@@ -44,9 +46,12 @@ use typed_store_derive::DBMapUtils;
 type OwnerIndexKey = (SuiAddress, ObjectID);
 type CoinIndexKey = (SuiAddress, String, ObjectID);
 type DynamicFieldKey = (ObjectID, ObjectID);
+type ChildObjectKey = (ObjectID, ObjectID);
 type EventId = (TxSequenceNumber, usize);
 type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
 type AllBalance = HashMap<TypeTag, TotalBalance>;
+type TxIndexItem = SuiResult<(TxSequenceNumber, TransactionDigest)>;
+type TxIndexIter<'a> = Box<dyn Iterator<Item = TxIndexItem> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
@@ -61,12 +66,35 @@ pub struct TotalBalance {
     pub num_coins: i64,
 }
 
+/// Progress of backfilling `child_object_index` on stores which indexed objects before it was
+/// added, so that an interrupted backfill resumes where it stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ChildObjectIndexBackfill {
+    /// The child objects up to this object ID, in live object set order, are indexed.
+    InProgress(ObjectID),
+    Done,
+}
+
 #[derive(Debug)]
 pub struct ObjectIndexChanges {
     pub deleted_owners: Vec<OwnerIndexKey>,
     pub deleted_dynamic_fields: Vec<DynamicFieldKey>,
+    pub deleted_child_objects: Vec<ChildObjectKey>,
     pub new_owners: Vec<(OwnerIndexKey, ObjectInfo)>,
     pub new_dynamic_fields: Vec<(DynamicFieldKey, DynamicFieldInfo)>,
+    pub new_child_objects: Vec<(ChildObjectKey, ObjectInfo)>,
+}
+
+/// Transaction data needed to remove a transaction from the transaction and event indexes. The
+/// fields mirror the arguments of `IndexStore::index_tx`.
+#[derive(Debug)]
+pub struct IndexedTransaction {
+    pub digest: TransactionDigest,
+    pub sender: SuiAddress,
+    pub input_objects: Vec<ObjectID>,
+    pub changed_objects: Vec<(ObjectRef, Owner)>,
+    pub move_functions: Vec<(ObjectID, Identifier, Identifier)>,
+    pub events: TransactionEvents,
 }
 
 #[derive(Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Debug)]
@@ -183,6 +211,15 @@ pub struct IndexStoreTables {
     #[default_options_override_fn = "dynamic_field_index_table_default_config"]
     dynamic_field_index: DBMap<DynamicFieldKey, DynamicFieldInfo>,
 
+    /// This is an index of object references to currently existing objects owned by an object,
+    /// dynamic fields or not, indexed by the composite key of the object ID of their parent and
+    /// the object ID of the child object.
+    #[default_options_override_fn = "child_object_index_table_default_config"]
+    child_object_index: DBMap<ChildObjectKey, ObjectInfo>,
+
+    /// Progress of the backfill of `child_object_index`, absent until it starts.
+    child_object_index_backfill: DBMap<(), ChildObjectIndexBackfill>,
+
     /// This is an index of all the versions of loaded child objects
     loaded_child_object_versions: DBMap<TransactionDigest, Vec<(ObjectID, SequenceNumber)>>,
 
@@ -239,6 +276,9 @@ fn owner_index_table_default_config() -> DBOptions {
 fn dynamic_field_index_table_default_config() -> DBOptions {
     default_db_options()
 }
+fn child_object_index_table_default_config() -> DBOptions {
+    default_db_options()
+}
 fn index_table_default_config() -> DBOptions {
     default_db_options()
 }
@@ -508,6 +548,10 @@ impl IndexStore {
             &self.tables.dynamic_field_index,
             object_index_changes.deleted_dynamic_fields.into_iter(),
         )?;
+        batch.delete_batch(
+            &self.tables.child_object_index,
+            object_index_changes.deleted_child_objects.into_iter(),
+        )?;
 
         batch.insert_batch(
             &self.tables.owner_index,
@@ -519,6 +563,11 @@ impl IndexStore {
             object_index_changes.new_dynamic_fields.into_iter(),
         )?;
 
+        batch.insert_batch(
+            &self.tables.child_object_index,
+            object_index_changes.new_child_objects.into_iter(),
+        )?;
+
         // events
         let event_digest = events.digest();
         batch.insert_batch(
@@ -625,16 +674,117 @@ impl IndexStore {
         Ok(sequence)
     }
 
+    /// Removes the transactions, and the events they emitted, from all indexes populated by
+    /// `index_tx`. Transactions which have not been indexed are skipped. The owner, coin and
+    /// dynamic field indexes track live objects, so they are left untouched.
+    pub fn prune_transactions(&self, transactions: &[IndexedTransaction]) -> SuiResult {
+        let digests = transactions.iter().map(|tx| tx.digest).collect::<Vec<_>>();
+        let sequences = self.tables.transactions_seq.multi_get(&digests)?;
+        let timestamps = self.tables.timestamps.multi_get(&digests)?;
+        let mut batch = self.tables.transactions_from_addr.batch();
+
+        for ((tx, sequence), timestamp_ms) in transactions.iter().zip(sequences).zip(timestamps) {
+            let Some(sequence) = sequence else {
+                continue;
+            };
+            batch.delete_batch(&self.tables.transaction_order, std::iter::once(sequence))?;
+            batch.delete_batch(&self.tables.transactions_seq, std::iter::once(tx.digest))?;
+            batch.delete_batch(
+                &self.tables.transactions_from_addr,
+                std::iter::once((tx.sender, sequence)),
+            )?;
+            batch.delete_batch(
+                &self.tables.transactions_by_input_object_id,
+                tx.input_objects.iter().map(|id| (*id, sequence)),
+            )?;
+            batch.delete_batch(
+                &self.tables.transactions_by_mutated_object_id,
+                tx.changed_objects
+                    .iter()
+                    .map(|(obj_ref, _)| (obj_ref.0, sequence)),
+            )?;
+            batch.delete_batch(
+                &self.tables.transactions_by_move_function,
+                tx.move_functions.iter().map(|(obj_id, module, function)| {
+                    (*obj_id, module.to_string(), function.to_string(), sequence)
+                }),
+            )?;
+            batch.delete_batch(
+                &self.tables.transactions_to_addr,
+                tx.changed_objects.iter().filter_map(|(_, owner)| {
+                    owner
+                        .get_address_owner_address()
+                        .ok()
+                        .map(|addr| (addr, sequence))
+                }),
+            )?;
+            batch.delete_batch(&self.tables.timestamps, std::iter::once(tx.digest))?;
+            batch.delete_batch(
+                &self.tables.loaded_child_object_versions,
+                std::iter::once(tx.digest),
+            )?;
+
+            // events
+            let events = tx.events.data.iter().enumerate();
+            batch.delete_batch(
+                &self.tables.event_order,
+                events.clone().map(|(i, _)| (sequence, i)),
+            )?;
+            batch.delete_batch(
+                &self.tables.event_by_move_module,
+                events.clone().map(|(i, e)| {
+                    (
+                        ModuleId::new(e.package_id.into(), e.transaction_module.clone()),
+                        (sequence, i),
+                    )
+                }),
+            )?;
+            batch.delete_batch(
+                &self.tables.event_by_sender,
+                events.clone().map(|(i, e)| (e.sender, (sequence, i))),
+            )?;
+            batch.delete_batch(
+                &self.tables.event_by_move_event,
+                events
+                    .clone()
+                    .map(|(i, e)| (e.type_.clone(), (sequence, i))),
+            )?;
+            if let Some(timestamp_ms) = timestamp_ms {
+                batch.delete_batch(
+                    &self.tables.event_by_time,
+                    events.clone().map(|(i, _)| (timestamp_ms, (sequence, i))),
+                )?;
+            }
+            batch.delete_batch(
+                &self.tables.event_by_event_module,
+                events.map(|(i, e)| {
+                    (
+                        ModuleId::new(e.type_.address, e.type_.module.clone()),
+                        (sequence, i),
+                    )
+                }),
+            )?;
+        }
+
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn next_sequence_number(&self) -> TxSequenceNumber {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
 
+    /// Returns the transactions matching the filter. `transaction_checkpoint` looks up the
+    /// checkpoint a transaction was executed in, for filters on checkpoints.
     pub fn get_transactions(
         &self,
         filter: Option<TransactionFilter>,
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
+        transaction_checkpoint: &dyn Fn(
+            &TransactionDigest,
+        ) -> SuiResult<Option<CheckpointSequenceNumber>>,
     ) -> SuiResult<Vec<TransactionDigest>> {
         // Lookup TransactionDigest sequence number,
         let cursor = if let Some(cursor) = cursor {
@@ -665,6 +815,18 @@ impl IndexStore {
             Some(TransactionFilter::ToAddress(address)) => {
                 Ok(self.get_transactions_to_addr(address, cursor, limit, reverse)?)
             }
+            Some(
+                filter @ (TransactionFilter::FromAndToAddress { .. }
+                | TransactionFilter::TimeRange { .. }
+                | TransactionFilter::All(_)
+                | TransactionFilter::Any(_)
+                | TransactionFilter::And(_, _)
+                | TransactionFilter::Or(_, _)),
+            ) => self
+                .get_transactions_iter(&filter, cursor, reverse, transaction_checkpoint)?
+                .take(limit.unwrap_or(usize::MAX))
+                .map(|item| item.map(|(_, digest)| digest))
+                .collect(),
             // NOTE: filter via checkpoint sequence number is implemented in
             // `get_transactions` of authority.rs.
             Some(_) => Err(SuiError::UserInputError {
@@ -699,6 +861,306 @@ impl IndexStore {
         }
     }
 
+    /// Returns an iterator over the transactions matching a compound filter, in sequence order.
+    ///
+    /// The indexes of the criteria combined by `All`/`And` are intersected, and those combined by
+    /// `Any`/`Or` are merged. Time and checkpoint ranges have no index, so they are checked on the
+    /// transactions found through the other criteria of an `All`/`And`, and can't be used alone.
+    fn get_transactions_iter<'a>(
+        &'a self,
+        filter: &TransactionFilter,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+        transaction_checkpoint: &'a dyn Fn(
+            &TransactionDigest,
+        ) -> SuiResult<Option<CheckpointSequenceNumber>>,
+    ) -> SuiResult<TxIndexIter<'a>> {
+        Ok(match filter {
+            TransactionFilter::FromAddress(address) => Self::transactions_index_iter(
+                &self.tables.transactions_from_addr,
+                *address,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::ToAddress(address) => Self::transactions_index_iter(
+                &self.tables.transactions_to_addr,
+                *address,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::InputObject(object_id) => Self::transactions_index_iter(
+                &self.tables.transactions_by_input_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::ChangedObject(object_id) => Self::transactions_index_iter(
+                &self.tables.transactions_by_mutated_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => {
+                if function.is_some() && module.is_none() {
+                    return Err(SuiError::UserInputError {
+                        error: UserInputError::MoveFunctionInputError(
+                            "Cannot supply function without supplying module".to_string(),
+                        ),
+                    });
+                }
+                // Calls to different functions are ordered by function first, so each function
+                // is read separately and merged back into sequence order.
+                let iters = self
+                    .get_move_functions(*package, module.as_deref(), function.as_deref())?
+                    .into_iter()
+                    .map(|(module, function)| {
+                        self.transactions_by_move_function_iter(
+                            *package, module, function, cursor, reverse,
+                        )
+                    })
+                    .collect::<SuiResult<_>>()?;
+                Box::new(TxIndexUnion::new(iters, reverse))
+            }
+            TransactionFilter::FromAndToAddress { from, to } => self.get_transactions_iter(
+                &TransactionFilter::All(vec![
+                    TransactionFilter::FromAddress(*from),
+                    TransactionFilter::ToAddress(*to),
+                ]),
+                cursor,
+                reverse,
+                transaction_checkpoint,
+            )?,
+            TransactionFilter::And(f1, f2) => self.get_transactions_iter(
+                &TransactionFilter::All(vec![*f1.clone(), *f2.clone()]),
+                cursor,
+                reverse,
+                transaction_checkpoint,
+            )?,
+            TransactionFilter::Or(f1, f2) => self.get_transactions_iter(
+                &TransactionFilter::Any(vec![*f1.clone(), *f2.clone()]),
+                cursor,
+                reverse,
+                transaction_checkpoint,
+            )?,
+            TransactionFilter::All(filters) => {
+                let (predicates, indexed): (Vec<_>, Vec<_>) =
+                    filters.iter().partition(|f| Self::is_unindexed_filter(f));
+                if indexed.is_empty() {
+                    return Err(SuiError::UserInputError {
+                        error: UserInputError::Unsupported(format!(
+                            "{:?} must include at least one indexed filter",
+                            filter
+                        )),
+                    });
+                }
+                let iters = indexed
+                    .into_iter()
+                    .map(|f| self.get_transactions_iter(f, cursor, reverse, transaction_checkpoint))
+                    .collect::<SuiResult<Vec<_>>>()?;
+                let iter: TxIndexIter<'a> = if iters.len() == 1 {
+                    iters.into_iter().next().unwrap()
+                } else {
+                    Box::new(TxIndexIntersection::new(iters, reverse))
+                };
+                if predicates.is_empty() {
+                    iter
+                } else {
+                    let predicates = predicates.into_iter().cloned().collect::<Vec<_>>();
+                    Box::new(iter.filter_map(move |item| {
+                        let digest = match &item {
+                            Ok((_, digest)) => *digest,
+                            Err(_) => return Some(item),
+                        };
+                        for predicate in &predicates {
+                            match self.matches_unindexed_filter(
+                                predicate,
+                                &digest,
+                                transaction_checkpoint,
+                            ) {
+                                Ok(true) => {}
+                                Ok(false) => return None,
+                                Err(e) => return Some(Err(e)),
+                            }
+                        }
+                        Some(item)
+                    }))
+                }
+            }
+            TransactionFilter::Any(filters) => {
+                if let Some(f) = filters.iter().find(|f| Self::is_unindexed_filter(f)) {
+                    return Err(SuiError::UserInputError {
+                        error: UserInputError::Unsupported(format!(
+                            "{:?} must be combined with an indexed filter",
+                            f
+                        )),
+                    });
+                }
+                let iters = filters
+                    .iter()
+                    .map(|f| self.get_transactions_iter(f, cursor, reverse, transaction_checkpoint))
+                    .collect::<SuiResult<_>>()?;
+                Box::new(TxIndexUnion::new(iters, reverse))
+            }
+            TransactionFilter::Checkpoint(_)
+            | TransactionFilter::CheckpointRange { .. }
+            | TransactionFilter::TimeRange { .. } => {
+                return Err(SuiError::UserInputError {
+                    error: UserInputError::Unsupported(format!(
+                        "{:?} must be combined with an indexed filter",
+                        filter
+                    )),
+                })
+            }
+            TransactionFilter::TransactionKind(_) => {
+                return Err(SuiError::UserInputError {
+                    error: UserInputError::Unsupported(format!("{:?}", filter)),
+                })
+            }
+        })
+    }
+
+    fn is_unindexed_filter(filter: &TransactionFilter) -> bool {
+        matches!(
+            filter,
+            TransactionFilter::Checkpoint(_)
+                | TransactionFilter::CheckpointRange { .. }
+                | TransactionFilter::TimeRange { .. }
+        )
+    }
+
+    fn matches_unindexed_filter(
+        &self,
+        filter: &TransactionFilter,
+        digest: &TransactionDigest,
+        transaction_checkpoint: &dyn Fn(
+            &TransactionDigest,
+        ) -> SuiResult<Option<CheckpointSequenceNumber>>,
+    ) -> SuiResult<bool> {
+        Ok(match filter {
+            TransactionFilter::Checkpoint(checkpoint) => {
+                transaction_checkpoint(digest)? == Some(*checkpoint)
+            }
+            TransactionFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => matches!(
+                transaction_checkpoint(digest)?,
+                Some(checkpoint) if *start_checkpoint <= checkpoint && checkpoint < *end_checkpoint
+            ),
+            TransactionFilter::TimeRange {
+                start_time,
+                end_time,
+            } => matches!(
+                self.get_timestamp_ms(digest)?,
+                Some(timestamp) if *start_time <= timestamp && timestamp < *end_time
+            ),
+            _ => false,
+        })
+    }
+
+    /// Iterates over the transactions of an index for one key, strictly after the cursor
+    fn transactions_index_iter<'a, KeyT>(
+        index: &'a DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
+        key: KeyT,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+    ) -> SuiResult<TxIndexIter<'a>>
+    where
+        KeyT: Clone + Serialize + DeserializeOwned + PartialEq + 'a,
+    {
+        let start = (
+            key.clone(),
+            cursor.unwrap_or(if reverse {
+                TxSequenceNumber::MAX
+            } else {
+                TxSequenceNumber::MIN
+            }),
+        );
+        let iter: Box<dyn Iterator<Item = _> + 'a> = if reverse {
+            Box::new(index.iter().skip_prior_to(&start)?.reverse())
+        } else {
+            Box::new(index.iter().skip_to(&start)?)
+        };
+        // The cursor may come from another index of a compound filter, so it is skipped only if
+        // this index contains it
+        Ok(Box::new(
+            iter.skip_while(move |((_, seq), _)| Some(*seq) == cursor)
+                .take_while(move |((id, _), _)| *id == key)
+                .map(|((_, seq), digest)| Ok((seq, digest))),
+        ))
+    }
+
+    fn transactions_by_move_function_iter(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+    ) -> SuiResult<TxIndexIter<'_>> {
+        let start = (
+            package,
+            module.clone(),
+            function.clone(),
+            cursor.unwrap_or(if reverse {
+                TxSequenceNumber::MAX
+            } else {
+                TxSequenceNumber::MIN
+            }),
+        );
+        let iter = self.tables.transactions_by_move_function.iter();
+        let iter: Box<dyn Iterator<Item = _> + '_> = if reverse {
+            Box::new(iter.skip_prior_to(&start)?.reverse())
+        } else {
+            Box::new(iter.skip_to(&start)?)
+        };
+        Ok(Box::new(
+            iter.skip_while(move |((_, _, _, seq), _)| Some(*seq) == cursor)
+                .take_while(move |((p, m, f, _), _)| {
+                    *p == package && *m == module && *f == function
+                })
+                .map(|((_, _, _, seq), digest)| Ok((seq, digest))),
+        ))
+    }
+
+    /// Returns the functions of a package that have been called, restricted to a module and a
+    /// function if given, by seeking to the first entry of every function in the index.
+    fn get_move_functions(
+        &self,
+        package: ObjectID,
+        module: Option<&str>,
+        function: Option<&str>,
+    ) -> SuiResult<Vec<(String, String)>> {
+        if let (Some(module), Some(function)) = (module, function) {
+            return Ok(vec![(module.to_string(), function.to_string())]);
+        }
+        let mut functions = vec![];
+        let mut key = (
+            package,
+            module.unwrap_or_default().to_string(),
+            String::new(),
+            TxSequenceNumber::MIN,
+        );
+        while let Some(((p, m, f, _), _)) = self
+            .tables
+            .transactions_by_move_function
+            .iter()
+            .skip_to(&key)?
+            .next()
+        {
+            if p != package || module.map_or(false, |module| module != m) {
+                break;
+            }
+            key = (p, m.clone(), f.clone(), TxSequenceNumber::MAX);
+            functions.push((m, f));
+        }
+        Ok(functions)
+    }
+
     /// Return loaded child objects table for a tx
     pub fn loaded_child_object_versions(
         &self,
@@ -1189,6 +1651,36 @@ impl IndexStore {
         Ok(None)
     }
 
+    /// Returns the objects owned by `object`, dynamic fields or not, starting after `cursor`.
+    pub fn get_child_objects(
+        &self,
+        object: ObjectID,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        filter: Option<SuiObjectDataFilter>,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        debug!(?object, "get_child_objects");
+        Ok(self
+            .tables
+            .child_object_index
+            .iter()
+            // The object id 0 is the smallest possible
+            .skip_to(&(object, cursor.unwrap_or(ObjectID::ZERO)))?
+            // skip an extra b/c the cursor is exclusive
+            .skip(usize::from(cursor.is_some()))
+            .take_while(move |((parent, _), _)| parent == &object)
+            .filter(move |(_, o)| {
+                if let Some(filter) = filter.as_ref() {
+                    filter.matches(o)
+                } else {
+                    true
+                }
+            })
+            .map(|(_, object_info)| object_info)
+            .take(limit)
+            .collect())
+    }
+
     pub fn get_owner_objects(
         &self,
         owner: SuiAddress,
@@ -1295,6 +1787,15 @@ impl IndexStore {
             &self.tables.dynamic_field_index,
             object_index_changes.new_dynamic_fields.into_iter(),
         )?;
+        batch.insert_batch(
+            &self.tables.child_object_index,
+            object_index_changes.new_child_objects.into_iter(),
+        )?;
+        // Child objects are indexed from genesis onwards, there is nothing to backfill.
+        batch.insert_batch(
+            &self.tables.child_object_index_backfill,
+            [((), ChildObjectIndexBackfill::Done)],
+        )?;
         batch.write()?;
         Ok(())
     }
@@ -1303,6 +1804,56 @@ impl IndexStore {
         self.tables.owner_index.is_empty()
     }
 
+    /// Progress of the backfill of `child_object_index`, `None` if it has not started.
+    pub fn child_object_index_backfill(&self) -> SuiResult<Option<ChildObjectIndexBackfill>> {
+        Ok(self.tables.child_object_index_backfill.get(&())?)
+    }
+
+    /// Indexes `child_objects`, which must be in live object set order, in `child_object_index`
+    /// and returns how many were indexed. The progress is recorded along with each batch, so an
+    /// interrupted backfill can resume after the last recorded child object.
+    pub fn insert_child_objects(
+        &self,
+        child_objects: impl Iterator<Item = (ChildObjectKey, ObjectInfo)>,
+    ) -> SuiResult<usize> {
+        const BATCH_SIZE: usize = 10_000;
+        self.insert_child_objects_in_batches(child_objects, BATCH_SIZE)
+    }
+
+    fn insert_child_objects_in_batches(
+        &self,
+        child_objects: impl Iterator<Item = (ChildObjectKey, ObjectInfo)>,
+        batch_size: usize,
+    ) -> SuiResult<usize> {
+        let mut count = 0;
+        for chunk in &child_objects.chunks(batch_size) {
+            let chunk = chunk.collect::<Vec<_>>();
+            let Some(&((_, cursor), _)) = chunk.last() else {
+                continue;
+            };
+            let progress = ChildObjectIndexBackfill::InProgress(cursor);
+            count += chunk.len();
+            let mut batch = self.tables.child_object_index.batch();
+            batch.insert_batch(&self.tables.child_object_index, chunk)?;
+            batch.insert_batch(&self.tables.child_object_index_backfill, [((), progress)])?;
+            batch.write()?;
+            info!(
+                count,
+                ?cursor,
+                "Backfilled a batch of the child object index"
+            );
+        }
+        Ok(count)
+    }
+
+    /// Records that `child_object_index` is complete, it is maintained by `index_tx` from then on.
+    pub fn set_child_object_index_backfilled(&self) -> SuiResult {
+        Ok(self
+            .tables
+            .child_object_index_backfill
+            .insert(&(), &ChildObjectIndexBackfill::Done)?)
+    }
+
     pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
         // We are checkpointing the whole db
         self.tables
@@ -1569,22 +2120,536 @@ impl IndexStore {
     }
 }
 
+/// Returns whether `a` comes before `b` in the order transactions are returned
+fn precedes(a: TxSequenceNumber, b: TxSequenceNumber, reverse: bool) -> bool {
+    if reverse {
+        a > b
+    } else {
+        a < b
+    }
+}
+
+/// Transactions present in all of the iterators, which must be in sequence order
+struct TxIndexIntersection<'a> {
+    iters: Vec<Peekable<TxIndexIter<'a>>>,
+    reverse: bool,
+}
+
+impl<'a> TxIndexIntersection<'a> {
+    fn new(iters: Vec<TxIndexIter<'a>>, reverse: bool) -> Self {
+        Self {
+            iters: iters.into_iter().map(Iterator::peekable).collect(),
+            reverse,
+        }
+    }
+}
+
+impl Iterator for TxIndexIntersection<'_> {
+    type Item = TxIndexItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // The furthest head is the first transaction that can be in all of the iterators
+            let mut target = None;
+            for iter in &mut self.iters {
+                match iter.peek()? {
+                    Ok((seq, _)) => {
+                        if target.map_or(true, |target| precedes(target, *seq, self.reverse)) {
+                            target = Some(*seq);
+                        }
+                    }
+                    Err(_) => return iter.next(),
+                }
+            }
+            let target = target?;
+            let mut found = true;
+            for iter in &mut self.iters {
+                while matches!(iter.peek(), Some(Ok((seq, _))) if precedes(*seq, target, self.reverse))
+                {
+                    iter.next();
+                }
+                found &= matches!(iter.peek(), Some(Ok((seq, _))) if *seq == target);
+            }
+            if found {
+                let mut item = None;
+                for iter in &mut self.iters {
+                    item = iter.next();
+                }
+                return item;
+            }
+        }
+    }
+}
+
+/// Transactions present in any of the iterators, which must be in sequence order
+struct TxIndexUnion<'a> {
+    iters: Vec<Peekable<TxIndexIter<'a>>>,
+    reverse: bool,
+}
+
+impl<'a> TxIndexUnion<'a> {
+    fn new(iters: Vec<TxIndexIter<'a>>, reverse: bool) -> Self {
+        Self {
+            iters: iters.into_iter().map(Iterator::peekable).collect(),
+            reverse,
+        }
+    }
+}
+
+impl Iterator for TxIndexUnion<'_> {
+    type Item = TxIndexItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next = None;
+        for iter in &mut self.iters {
+            match iter.peek() {
+                Some(Ok((seq, _))) => {
+                    if next.map_or(true, |next| precedes(*seq, next, self.reverse)) {
+                        next = Some(*seq);
+                    }
+                }
+                Some(Err(_)) => return iter.next(),
+                None => {}
+            }
+        }
+        let next = next?;
+        // Advance every iterator holding this transaction, so that it is returned only once
+        let mut item = None;
+        for iter in &mut self.iters {
+            if matches!(iter.peek(), Some(Ok((seq, _))) if *seq == next) {
+                item = iter.next();
+            }
+        }
+        item
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::indexes::ObjectIndexChanges;
+    use crate::indexes::{ChildObjectIndexBackfill, IndexedTransaction, ObjectIndexChanges};
     use crate::IndexStore;
     use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
     use prometheus::Registry;
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::env::temp_dir;
-    use sui_types::base_types::{ObjectInfo, ObjectType, SuiAddress};
+    use sui_json_rpc_types::TransactionFilter;
+    use sui_types::base_types::{ObjectID, ObjectInfo, ObjectType, SuiAddress};
     use sui_types::digests::TransactionDigest;
     use sui_types::effects::TransactionEvents;
+    use sui_types::error::SuiResult;
+    use sui_types::event::Event;
     use sui_types::gas_coin::GAS;
     use sui_types::object;
     use sui_types::object::Owner;
     use sui_types::storage::WriteKind;
 
+    #[tokio::test]
+    async fn test_compound_transaction_filters() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let sender: SuiAddress = AccountAddress::random().into();
+        let other_sender: SuiAddress = AccountAddress::random().into();
+        let package = ObjectID::random();
+        let module = Identifier::new("m").unwrap();
+        let f1 = Identifier::new("f1").unwrap();
+        let f2 = Identifier::new("f2").unwrap();
+
+        // (sender, called function, timestamp, checkpoint)
+        let transactions = [
+            (sender, Some(&f1), 1000, 1),
+            (other_sender, Some(&f1), 2000, 1),
+            (sender, Some(&f2), 3000, 2),
+            (sender, None, 4000, 2),
+        ];
+        let mut digests = vec![];
+        let mut checkpoints = HashMap::new();
+        for (sender, function, timestamp_ms, checkpoint) in transactions {
+            let digest = TransactionDigest::random();
+            let move_functions = function
+                .map(|f| (package, module.clone(), f.clone()))
+                .into_iter()
+                .collect::<Vec<_>>();
+            index_store
+                .index_tx(
+                    sender,
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    move_functions.into_iter(),
+                    &TransactionEvents { data: vec![] },
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        deleted_child_objects: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                        new_child_objects: vec![],
+                    },
+                    &digest,
+                    timestamp_ms,
+                    None,
+                    BTreeMap::new(),
+                )
+                .await?;
+            digests.push(digest);
+            checkpoints.insert(digest, checkpoint);
+        }
+        let transaction_checkpoint = |digest: &TransactionDigest| -> SuiResult<Option<u64>> {
+            Ok(checkpoints.get(digest).copied())
+        };
+        let query = |filter: TransactionFilter,
+                     cursor: Option<TransactionDigest>,
+                     limit: Option<usize>,
+                     reverse: bool| {
+            index_store.get_transactions(
+                Some(filter),
+                cursor,
+                limit,
+                reverse,
+                &transaction_checkpoint,
+            )
+        };
+        let calls_from_sender = TransactionFilter::MoveFunction {
+            package,
+            module: None,
+            function: None,
+        }
+        .and(TransactionFilter::FromAddress(sender));
+
+        assert_eq!(
+            query(calls_from_sender.clone(), None, None, false)?,
+            vec![digests[0], digests[2]]
+        );
+        assert_eq!(
+            query(calls_from_sender.clone(), None, None, true)?,
+            vec![digests[2], digests[0]]
+        );
+        assert_eq!(
+            query(calls_from_sender.clone(), Some(digests[0]), None, false)?,
+            vec![digests[2]]
+        );
+        assert_eq!(
+            query(calls_from_sender.clone(), None, Some(1), false)?,
+            vec![digests[0]]
+        );
+        // The cursor is not part of the sender's transactions
+        assert_eq!(
+            query(calls_from_sender, Some(digests[1]), None, false)?,
+            vec![digests[2]]
+        );
+
+        assert_eq!(
+            query(
+                TransactionFilter::FromAndToAddress {
+                    from: sender,
+                    to: other_sender
+                },
+                None,
+                None,
+                false
+            )?,
+            vec![]
+        );
+
+        let time_range = TransactionFilter::TimeRange {
+            start_time: 1500,
+            end_time: 4000,
+        };
+        assert_eq!(
+            query(
+                TransactionFilter::FromAddress(sender).and(time_range.clone()),
+                None,
+                None,
+                false
+            )?,
+            vec![digests[2]]
+        );
+        let checkpoint_range = TransactionFilter::CheckpointRange {
+            start_checkpoint: 1,
+            end_checkpoint: 2,
+        };
+        assert_eq!(
+            query(
+                TransactionFilter::And(
+                    Box::new(TransactionFilter::FromAddress(sender)),
+                    Box::new(checkpoint_range),
+                ),
+                None,
+                None,
+                false
+            )?,
+            vec![digests[0]]
+        );
+
+        let f2_or_other_sender = TransactionFilter::Or(
+            Box::new(TransactionFilter::FromAddress(other_sender)),
+            Box::new(TransactionFilter::MoveFunction {
+                package,
+                module: Some("m".to_string()),
+                function: Some("f2".to_string()),
+            }),
+        );
+        assert_eq!(
+            query(f2_or_other_sender.clone(), None, None, false)?,
+            vec![digests[1], digests[2]]
+        );
+        assert_eq!(
+            query(f2_or_other_sender, None, None, true)?,
+            vec![digests[2], digests[1]]
+        );
+
+        // Ranges need an indexed filter to be combined with
+        assert!(query(time_range.clone(), None, None, false).is_err());
+        assert!(query(
+            TransactionFilter::FromAddress(sender).or(time_range),
+            None,
+            None,
+            false
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prune_transactions() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let sender: SuiAddress = AccountAddress::random().into();
+        let package = ObjectID::random();
+        let module = Identifier::new("m").unwrap();
+        let function = Identifier::new("f").unwrap();
+
+        let mut transactions = vec![];
+        for timestamp_ms in [1000, 2000] {
+            let digest = TransactionDigest::random();
+            let input_object = ObjectID::random();
+            let events = TransactionEvents {
+                data: vec![Event {
+                    package_id: package,
+                    transaction_module: module.clone(),
+                    sender,
+                    type_: StructTag {
+                        address: package.into(),
+                        module: module.clone(),
+                        name: Identifier::new("E").unwrap(),
+                        type_params: vec![],
+                    },
+                    contents: vec![],
+                }],
+            };
+            index_store
+                .index_tx(
+                    sender,
+                    std::iter::once(input_object),
+                    vec![].into_iter(),
+                    std::iter::once((package, module.clone(), function.clone())),
+                    &events,
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        deleted_child_objects: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                        new_child_objects: vec![],
+                    },
+                    &digest,
+                    timestamp_ms,
+                    None,
+                    BTreeMap::new(),
+                )
+                .await?;
+            transactions.push(IndexedTransaction {
+                digest,
+                sender,
+                input_objects: vec![input_object],
+                changed_objects: vec![],
+                move_functions: vec![(package, module.clone(), function.clone())],
+                events,
+            });
+        }
+        let pruned = transactions.remove(0);
+        let retained = transactions[0].digest;
+        index_store.prune_transactions(&[pruned])?;
+
+        let query = |filter: TransactionFilter| {
+            index_store.get_transactions(Some(filter), None, None, false, &|_| Ok(None))
+        };
+        assert_eq!(
+            query(TransactionFilter::FromAddress(sender))?,
+            vec![retained]
+        );
+        assert_eq!(
+            query(TransactionFilter::MoveFunction {
+                package,
+                module: None,
+                function: None,
+            })?,
+            vec![retained]
+        );
+        assert_eq!(
+            query(TransactionFilter::InputObject(
+                transactions[0].input_objects[0]
+            ))?,
+            vec![retained]
+        );
+        let events = index_store.all_events(0, 0, 10, false)?;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1, retained);
+        let events = index_store.events_by_sender(&sender, 0, 0, 10, false)?;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1, retained);
+        assert_eq!(
+            index_store.event_iterator(0, 3000, 0, 0, 10, false)?.len(),
+            1
+        );
+
+        // Pruning is idempotent, transactions which are no longer indexed are skipped
+        index_store.prune_transactions(&transactions)?;
+        index_store.prune_transactions(&transactions)?;
+        assert!(query(TransactionFilter::FromAddress(sender))?.is_empty());
+        assert!(index_store.all_events(0, 0, 10, false)?.is_empty());
+        assert_eq!(index_store.get_timestamp_ms(&retained)?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_child_object_index() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        let sender: SuiAddress = AccountAddress::random().into();
+        let parent = ObjectID::random();
+        let other_parent = ObjectID::random();
+
+        let child_info = |parent: ObjectID| {
+            let object = object::Object::new_gas_with_balance_and_owner_for_testing(100, sender);
+            (
+                (parent, object.id()),
+                ObjectInfo {
+                    object_id: object.id(),
+                    version: object.version(),
+                    digest: object.digest(),
+                    type_: ObjectType::Struct(object.type_().unwrap().clone()),
+                    owner: Owner::ObjectOwner(parent.into()),
+                    previous_transaction: object.previous_transaction,
+                },
+            )
+        };
+        let mut children = (0..5).map(|_| child_info(parent)).collect::<Vec<_>>();
+        children.sort_by_key(|(key, _)| *key);
+        let mut new_child_objects = children.clone();
+        new_child_objects.push(child_info(other_parent));
+
+        async fn index(
+            index_store: &IndexStore,
+            sender: SuiAddress,
+            object_index_changes: ObjectIndexChanges,
+        ) -> SuiResult<u64> {
+            index_store
+                .index_tx(
+                    sender,
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    vec![].into_iter(),
+                    &TransactionEvents { data: vec![] },
+                    object_index_changes,
+                    &TransactionDigest::random(),
+                    1234,
+                    None,
+                    BTreeMap::new(),
+                )
+                .await
+        }
+        let object_index_changes = ObjectIndexChanges {
+            deleted_owners: vec![],
+            deleted_dynamic_fields: vec![],
+            deleted_child_objects: vec![],
+            new_owners: vec![],
+            new_dynamic_fields: vec![],
+            new_child_objects,
+        };
+        index(&index_store, sender, object_index_changes).await?;
+
+        let ids = |infos: Vec<ObjectInfo>| infos.iter().map(|o| o.object_id).collect::<Vec<_>>();
+        let expected = children.iter().map(|((_, id), _)| *id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, None, 10, None)?),
+            expected
+        );
+        // Pages are exclusive of their cursor
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, None, 2, None)?),
+            expected[..2]
+        );
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, Some(expected[1]), 2, None)?),
+            expected[2..4]
+        );
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, Some(expected[4]), 2, None)?),
+            Vec::<ObjectID>::new()
+        );
+
+        // Children transferred away from or deleted under the parent are no longer listed
+        let object_index_changes = ObjectIndexChanges {
+            deleted_owners: vec![],
+            deleted_dynamic_fields: vec![],
+            deleted_child_objects: vec![children[0].0, children[3].0],
+            new_owners: vec![],
+            new_dynamic_fields: vec![],
+            new_child_objects: vec![],
+        };
+        index(&index_store, sender, object_index_changes).await?;
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, None, 10, None)?),
+            vec![expected[1], expected[2], expected[4]]
+        );
+        assert_eq!(
+            index_store
+                .get_child_objects(other_parent, None, 10, None)?
+                .len(),
+            1
+        );
+
+        // Stores which indexed objects before the child object index existed can backfill it
+        let dir = tempfile::tempdir()?;
+        let index_store = IndexStore::new(dir.path().to_path_buf(), &Registry::default(), None);
+        assert_eq!(index_store.child_object_index_backfill()?, None);
+        // Interrupted once the first batch is written
+        assert_eq!(
+            index_store.insert_child_objects_in_batches(children.iter().cloned().take(2), 2)?,
+            2
+        );
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, None, 10, None)?),
+            expected[..2]
+        );
+        let cursor = expected[1];
+        assert_eq!(
+            index_store.child_object_index_backfill()?,
+            Some(ChildObjectIndexBackfill::InProgress(cursor))
+        );
+
+        // Resumed after the last indexed child object
+        let remaining = children
+            .into_iter()
+            .skip_while(|((_, id), _)| *id <= cursor);
+        assert_eq!(
+            index_store.insert_child_objects_in_batches(remaining, 2)?,
+            3
+        );
+        index_store.set_child_object_index_backfilled()?;
+        assert_eq!(
+            index_store.child_object_index_backfill()?,
+            Some(ChildObjectIndexBackfill::Done)
+        );
+        assert_eq!(
+            ids(index_store.get_child_objects(parent, None, 10, None)?),
+            expected
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_index_cache() -> anyhow::Result<()> {
         // This test is going to invoke `index_tx()`where 10 coins each with balance 100
@@ -1622,8 +2687,10 @@ mod tests {
         let object_index_changes = ObjectIndexChanges {
             deleted_owners: vec![],
             deleted_dynamic_fields: vec![],
+            deleted_child_objects: vec![],
             new_owners: new_objects,
             new_dynamic_fields: vec![],
+            new_child_objects: vec![],
         };
 
         let tx_coins = (object_map.clone(), written_objects.clone());
@@ -1675,8 +2742,10 @@ mod tests {
         let object_index_changes = ObjectIndexChanges {
             deleted_owners: deleted_objects,
             deleted_dynamic_fields: vec![],
+            deleted_child_objects: vec![],
             new_owners: vec![],
             new_dynamic_fields: vec![],
+            new_child_objects: vec![],
         };
         let tx_coins = (object_map, written_objects);
         index_store
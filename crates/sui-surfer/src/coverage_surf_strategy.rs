@@ -0,0 +1,269 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use move_binary_format::normalized::Type;
+use move_core_types::language_storage::StructTag;
+use rand::{
+    distributions::{Distribution, Standard},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+};
+use serde::Serialize;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef},
+    transaction::{CallArg, ObjectArg},
+};
+use tokio::sync::watch;
+use tracing::debug;
+
+use crate::{
+    default_surf_strategy::InputObjectPassKind,
+    surf_strategy::SurfStrategy,
+    surfer_state::{EntryFunction, SurferState},
+};
+
+/// Number of calls to a function in a row that reached nothing new, after which the function is
+/// considered explored.
+const EXPLORED_AFTER_CALLS: u64 = 4;
+
+/// A strategy that remembers which functions, abort codes and object types have been reached,
+/// and prefers exploring new ones:
+/// - Functions that never succeeded are called first, then the ones that reached something new
+///   most recently. Once a function has been explored, it is only called in rounds where
+///   nothing else could be called.
+/// - Objects created by earlier calls are preferred as inputs over objects that existed before
+///   surfing started.
+/// - Integer arguments are often picked among boundary values, which are more likely to hit
+///   the argument checks of the called function.
+#[derive(Default)]
+pub struct CoverageSurfStrategy {
+    /// Number of calls to each function in a row that reached nothing new.
+    calls_without_progress: HashMap<(ObjectID, String, String), u64>,
+}
+
+#[async_trait]
+impl SurfStrategy for CoverageSurfStrategy {
+    async fn surf_for_a_while(
+        &mut self,
+        state: &mut SurferState,
+        mut entry_functions: Vec<EntryFunction>,
+        exit: &watch::Receiver<()>,
+    ) {
+        // Shuffle first so that ties are broken randomly.
+        entry_functions.shuffle(&mut state.rng);
+        entry_functions.sort_by_key(|entry| {
+            let key = entry.key();
+            (
+                state.stats.unique_move_functions_succeeded.contains(&key),
+                self.calls_without_progress.get(&key).copied().unwrap_or(0),
+            )
+        });
+
+        let mut called_any = false;
+        for entry in entry_functions {
+            let key = entry.key();
+            let explored =
+                self.calls_without_progress.get(&key).copied().unwrap_or(0) >= EXPLORED_AFTER_CALLS;
+            if explored && called_any {
+                continue;
+            }
+            let Some(args) = Self::choose_function_call_args(state, entry.parameters).await else {
+                debug!("Failed to choose arguments for Move function {:?}::{:?}", entry.module, entry.function);
+                continue;
+            };
+
+            let coverage = state.stats.coverage();
+            let object_types = state.stats.unique_object_types_created.len();
+            state
+                .execute_move_transaction(entry.package, entry.module, entry.function, args)
+                .await;
+            called_any = true;
+
+            if state.stats.coverage() > coverage {
+                self.calls_without_progress.remove(&key);
+            } else {
+                *self.calls_without_progress.entry(key).or_default() += 1;
+            }
+            if state.stats.unique_object_types_created.len() > object_types {
+                // Functions that never succeeded may succeed with the new objects.
+                let succeeded = &state.stats.unique_move_functions_succeeded;
+                self.calls_without_progress
+                    .retain(|key, _| succeeded.contains(key));
+            }
+
+            if exit.has_changed().unwrap() {
+                return;
+            }
+        }
+    }
+}
+
+impl CoverageSurfStrategy {
+    async fn choose_function_call_args(
+        state: &mut SurferState,
+        params: Vec<Type>,
+    ) -> Option<Vec<CallArg>> {
+        let mut args = vec![];
+        let mut chosen_owned_objects = vec![];
+        for param in params {
+            let rng = &mut state.rng;
+            let arg = match param {
+                Type::Bool => Some(pure_arg(rng, &[false, true])),
+                Type::U8 => Some(pure_arg(rng, &[0, 1, u8::MAX])),
+                Type::U16 => Some(pure_arg(rng, &[0, 1, u16::MAX])),
+                Type::U32 => Some(pure_arg(rng, &[0, 1, u32::MAX])),
+                Type::U64 => Some(pure_arg(rng, &[0, 1, u64::MAX])),
+                Type::U128 => Some(pure_arg(rng, &[0, 1, u128::MAX])),
+                Type::Address => Some(CallArg::Pure(
+                    bcs::to_bytes(state.cluster.accounts.choose(rng).unwrap()).unwrap(),
+                )),
+                ty @ Type::Struct { .. } => {
+                    Self::choose_object_call_arg(
+                        state,
+                        InputObjectPassKind::Value,
+                        ty,
+                        &mut chosen_owned_objects,
+                    )
+                    .await
+                }
+                Type::Reference(ty) => {
+                    Self::choose_object_call_arg(
+                        state,
+                        InputObjectPassKind::ByRef,
+                        *ty,
+                        &mut chosen_owned_objects,
+                    )
+                    .await
+                }
+                Type::MutableReference(ty) => {
+                    Self::choose_object_call_arg(
+                        state,
+                        InputObjectPassKind::MutRef,
+                        *ty,
+                        &mut chosen_owned_objects,
+                    )
+                    .await
+                }
+                Type::U256 | Type::Signer | Type::Vector(_) | Type::TypeParameter(_) => None,
+            };
+            let Some(arg) = arg else {
+                for (struct_tag, obj_ref) in chosen_owned_objects {
+                    state
+                        .owned_objects
+                        .get_mut(&struct_tag)
+                        .unwrap()
+                        .insert(obj_ref);
+                }
+                return None;
+            };
+            args.push(arg);
+        }
+        Some(args)
+    }
+
+    async fn choose_object_call_arg(
+        state: &mut SurferState,
+        kind: InputObjectPassKind,
+        arg_type: Type,
+        chosen_owned_objects: &mut Vec<(StructTag, ObjectRef)>,
+    ) -> Option<CallArg> {
+        let Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } = arg_type else {
+            return None;
+        };
+        let type_tag = StructTag {
+            address,
+            module,
+            name,
+            type_params: type_arguments
+                .into_iter()
+                .map(|t| t.into_type_tag().unwrap())
+                .collect(),
+        };
+
+        // Candidates are listed owned objects first, then shared, then immutable ones, matching
+        // the indices expected by the `choose_nth_*` functions of the state.
+        let mut candidates: Vec<ObjectID> = state
+            .owned_objects
+            .get(&type_tag)
+            .into_iter()
+            .flatten()
+            .map(|obj_ref| obj_ref.0)
+            .collect();
+        let owned = candidates.len();
+        if !matches!(kind, InputObjectPassKind::Value) {
+            candidates.extend(
+                state
+                    .shared_objects
+                    .read()
+                    .await
+                    .get(&type_tag)
+                    .into_iter()
+                    .flatten()
+                    .map(|(id, _)| *id),
+            );
+        }
+        let shared = candidates.len() - owned;
+        if matches!(kind, InputObjectPassKind::ByRef) {
+            candidates.extend(
+                state
+                    .immutable_objects
+                    .read()
+                    .await
+                    .get(&type_tag)
+                    .into_iter()
+                    .flatten()
+                    .map(|obj_ref| obj_ref.0),
+            );
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let created: Vec<_> = (0..candidates.len())
+            .filter(|n| state.created_objects.contains(&candidates[*n]))
+            .collect();
+        let mut n = match created.choose(&mut state.rng) {
+            Some(n) => *n,
+            None => state.rng.gen_range(0..candidates.len()),
+        };
+        if n < owned {
+            let obj_ref = state.choose_nth_owned_object(&type_tag, n);
+            chosen_owned_objects.push((type_tag, obj_ref));
+            return Some(CallArg::Object(ObjectArg::ImmOrOwnedObject(obj_ref)));
+        }
+        n -= owned;
+        if n < shared {
+            let (id, initial_shared_version) = state.choose_nth_shared_object(&type_tag, n).await;
+            return Some(CallArg::Object(ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable: matches!(kind, InputObjectPassKind::MutRef),
+            }));
+        }
+        n -= shared;
+        let obj_ref = state.choose_nth_immutable_object(&type_tag, n).await;
+        Some(CallArg::Object(ObjectArg::ImmOrOwnedObject(obj_ref)))
+    }
+}
+
+/// A pure argument that is one of `boundary_values` half of the time, and random otherwise.
+fn pure_arg<T: Serialize>(rng: &mut StdRng, boundary_values: &[T]) -> CallArg
+where
+    Standard: Distribution<T>,
+{
+    let bytes = if rng.gen_bool(0.5) {
+        bcs::to_bytes(boundary_values.choose(rng).unwrap())
+    } else {
+        bcs::to_bytes(&rng.gen::<T>())
+    };
+    CallArg::Pure(bytes.unwrap())
+}
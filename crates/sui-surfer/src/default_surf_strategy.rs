@@ -17,7 +17,7 @@ use crate::{
     surfer_state::{EntryFunction, SurferState},
 };
 
-enum InputObjectPassKind {
+pub(crate) enum InputObjectPassKind {
     Value,
     ByRef,
     MutRef,
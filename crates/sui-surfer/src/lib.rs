@@ -17,6 +17,7 @@ use tracing::info;
 use crate::surfer_state::SurfStatistics;
 use crate::surfer_task::SurferTask;
 
+pub mod coverage_surf_strategy;
 pub mod default_surf_strategy;
 mod surf_strategy;
 mod surfer_state;
@@ -70,6 +71,7 @@ pub async fn run<S: SurfStrategy + Default>(
             .await;
     }
 
+    let entry_functions = tasks[0].state.entry_functions.clone();
     let mut handles = vec![];
     for task in tasks {
         handles.push(tokio::task::spawn(task.surf()));
@@ -77,7 +79,9 @@ pub async fn run<S: SurfStrategy + Default>(
     tokio::time::sleep(run_duration).await;
     exit_sender.send(()).unwrap();
     let all_stats: Result<Vec<_>, _> = join_all(handles).await.into_iter().collect();
-    SurfStatistics::aggregate(all_stats.unwrap())
+    let mut stats = SurfStatistics::aggregate(all_stats.unwrap());
+    stats.entry_functions = entry_functions.read().await.clone();
+    stats
 
     // TODO: Right now it will panic here complaining about dropping a tokio runtime
     // inside of another tokio runtime. Reason unclear.
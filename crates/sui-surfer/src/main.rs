@@ -1,10 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 use std::{path::PathBuf, time::Duration};
 use tracing::info;
 
+use sui_surfer::coverage_surf_strategy::CoverageSurfStrategy;
 use sui_surfer::default_surf_strategy::DefaultSurfStrategy;
 
 #[derive(Parser)]
@@ -15,6 +16,28 @@ struct Args {
 
     #[clap(long, help = "List of package paths to surf")]
     packages: Vec<PathBuf>,
+
+    #[clap(
+        long,
+        arg_enum,
+        default_value = "default",
+        help = "Strategy used to pick functions and arguments to call"
+    )]
+    strategy: Strategy,
+
+    #[clap(
+        long,
+        help = "Write a report of the functions never called successfully to this path"
+    )]
+    coverage_report: Option<PathBuf>,
+}
+
+#[derive(Clone, ArgEnum)]
+enum Strategy {
+    /// Pick functions and arguments at random
+    Default,
+    /// Prefer functions, abort codes and object types not reached yet
+    Coverage,
 }
 
 const DEFAULT_RUN_DURATION: u64 = 30;
@@ -32,11 +55,21 @@ async fn main() {
         .with_env()
         .init();
 
-    let results = sui_surfer::run::<DefaultSurfStrategy>(
-        Duration::from_secs(args.run_duration.unwrap_or(DEFAULT_RUN_DURATION)),
-        args.packages,
-    )
-    .await;
+    let run_duration = Duration::from_secs(args.run_duration.unwrap_or(DEFAULT_RUN_DURATION));
+    let results = match args.strategy {
+        Strategy::Default => {
+            sui_surfer::run::<DefaultSurfStrategy>(run_duration, args.packages).await
+        }
+        Strategy::Coverage => {
+            sui_surfer::run::<CoverageSurfStrategy>(run_duration, args.packages).await
+        }
+    };
     results.print_stats();
+    if let Some(path) = args.coverage_report {
+        results
+            .write_coverage_report(&path)
+            .expect("Failed to write coverage report");
+        info!("Wrote coverage report to {}", path.display());
+    }
     info!("Finished surfing");
 }
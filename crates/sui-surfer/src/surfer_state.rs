@@ -4,16 +4,19 @@
 use indexmap::IndexSet;
 use move_binary_format::file_format::Visibility;
 use move_binary_format::normalized::Type;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{ModuleId, StructTag};
 use rand::rngs::StdRng;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
 use sui_move_build::BuildConfig;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
 use sui_types::object::{Object, Owner};
 use sui_types::storage::WriteKind;
 use sui_types::transaction::{CallArg, ObjectArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_PUBLISH};
@@ -30,6 +33,13 @@ pub struct EntryFunction {
     pub parameters: Vec<Type>,
 }
 
+impl EntryFunction {
+    /// The key of this function in `SurfStatistics`.
+    pub fn key(&self) -> (ObjectID, String, String) {
+        (self.package, self.module.clone(), self.function.clone())
+    }
+}
+
 #[derive(Default)]
 pub struct SurfStatistics {
     pub num_successful_transactions: u64,
@@ -37,6 +47,14 @@ pub struct SurfStatistics {
     pub num_owned_obj_transactions: u64,
     pub num_shared_obj_transactions: u64,
     pub unique_move_functions_called: HashSet<(ObjectID, String, String)>,
+    pub unique_move_functions_succeeded: HashSet<(ObjectID, String, String)>,
+    /// Abort codes reached, with the module that aborted.
+    pub unique_abort_codes: HashSet<(ModuleId, u64)>,
+    /// Types of the objects created by surfed transactions, including package publishing.
+    pub unique_object_types_created: HashSet<StructTag>,
+    /// All the entry functions discovered, only set on the statistics aggregated at the end of
+    /// the run.
+    pub entry_functions: Vec<EntryFunction>,
 }
 
 impl SurfStatistics {
@@ -58,10 +76,25 @@ impl SurfStatistics {
         } else {
             self.num_owned_obj_transactions += 1;
         }
+        if tx_succeeded {
+            self.unique_move_functions_succeeded.insert((
+                package,
+                module.clone(),
+                function.clone(),
+            ));
+        }
         self.unique_move_functions_called
             .insert((package, module, function));
     }
 
+    /// Number of distinct functions, abort codes and object types reached so far. Grows
+    /// whenever a transaction explores something new.
+    pub fn coverage(&self) -> usize {
+        self.unique_move_functions_succeeded.len()
+            + self.unique_abort_codes.len()
+            + self.unique_object_types_created.len()
+    }
+
     pub fn aggregate(stats: Vec<Self>) -> Self {
         let mut result = Self::default();
         for stat in stats {
@@ -72,6 +105,13 @@ impl SurfStatistics {
             result
                 .unique_move_functions_called
                 .extend(stat.unique_move_functions_called);
+            result
+                .unique_move_functions_succeeded
+                .extend(stat.unique_move_functions_succeeded);
+            result.unique_abort_codes.extend(stat.unique_abort_codes);
+            result
+                .unique_object_types_created
+                .extend(stat.unique_object_types_created);
         }
         result
     }
@@ -88,10 +128,51 @@ impl SurfStatistics {
             self.num_owned_obj_transactions, self.num_shared_obj_transactions
         );
         info!(
-            "Unique move functions called: {}",
-            self.unique_move_functions_called.len()
+            "Unique move functions called: {}, {} of which succeeded",
+            self.unique_move_functions_called.len(),
+            self.unique_move_functions_succeeded.len()
+        );
+        info!(
+            "Unique abort codes reached: {}, unique object types created: {}",
+            self.unique_abort_codes.len(),
+            self.unique_object_types_created.len()
         );
     }
+
+    /// Write a report of the entry functions that were never called successfully, telling apart
+    /// the ones that always failed from the ones that could not be called at all (usually
+    /// because no object of the right type was available), followed by the abort codes reached.
+    pub fn write_coverage_report(&self, path: &Path) -> std::io::Result<()> {
+        let mut never_succeeded = BTreeSet::new();
+        let mut never_called = BTreeSet::new();
+        for entry in &self.entry_functions {
+            let key = entry.key();
+            let name = format!("{}::{}::{}", entry.package, entry.module, entry.function);
+            if !self.unique_move_functions_called.contains(&key) {
+                never_called.insert(name);
+            } else if !self.unique_move_functions_succeeded.contains(&key) {
+                never_succeeded.insert(name);
+            }
+        }
+        let abort_codes: BTreeSet<_> = self
+            .unique_abort_codes
+            .iter()
+            .map(|(module, code)| format!("{} {code}", module.short_str_lossless()))
+            .collect();
+
+        let mut report = String::new();
+        for (title, lines) in [
+            ("Functions never called", never_called),
+            ("Functions called, but never successfully", never_succeeded),
+            ("Abort codes reached", abort_codes),
+        ] {
+            writeln!(report, "{title} ({}):", lines.len()).unwrap();
+            for line in lines {
+                writeln!(report, "  {line}").unwrap();
+            }
+        }
+        std::fs::write(path, report)
+    }
 }
 
 pub type OwnedObjects = HashMap<StructTag, IndexSet<ObjectRef>>;
@@ -112,6 +193,8 @@ pub struct SurferState {
     pub immutable_objects: ImmObjects,
     pub shared_objects: SharedObjects,
     pub entry_functions: Arc<RwLock<Vec<EntryFunction>>>,
+    /// Objects created by the transactions of this surfer.
+    pub created_objects: HashSet<ObjectID>,
 
     pub stats: SurfStatistics,
 }
@@ -136,6 +219,7 @@ impl SurferState {
             immutable_objects,
             shared_objects,
             entry_functions,
+            created_objects: HashSet::new(),
             stats: Default::default(),
         }
     }
@@ -185,6 +269,23 @@ impl SurferState {
             function,
             effects.status()
         );
+        if let Ok(effects) = self
+            .cluster
+            .fullnode_handle
+            .sui_node
+            .state()
+            .get_executed_effects(*effects.transaction_digest())
+        {
+            if let ExecutionStatus::Failure {
+                error: ExecutionFailureStatus::MoveAbort(location, code),
+                ..
+            } = effects.status()
+            {
+                self.stats
+                    .unique_abort_codes
+                    .insert((location.module.clone(), *code));
+            }
+        }
         self.stats.record_transaction(
             use_shared_object,
             effects.status().is_ok(),
@@ -208,6 +309,12 @@ impl SurferState {
                 continue;
             }
             let struct_tag = object.struct_tag().unwrap();
+            if write_kind == WriteKind::Create {
+                self.created_objects.insert(obj_ref.0);
+                self.stats
+                    .unique_object_types_created
+                    .insert(struct_tag.clone());
+            }
             match owned_ref.owner {
                 Owner::Immutable => {
                     self.immutable_objects
@@ -4,6 +4,7 @@
 use std::path::PathBuf;
 use std::time::Duration;
 use sui_macros::sim_test;
+use sui_surfer::coverage_surf_strategy::CoverageSurfStrategy;
 use sui_surfer::default_surf_strategy::DefaultSurfStrategy;
 
 #[sim_test]
@@ -16,3 +17,19 @@ async fn smoke_test() {
     assert!(results.num_successful_transactions > 0);
     assert!(!results.unique_move_functions_called.is_empty());
 }
+
+#[sim_test]
+async fn coverage_smoke_test() {
+    // This test makes sure that the sui surfer runs with the coverage-guided strategy.
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "move_building_blocks"]);
+    let results =
+        sui_surfer::run::<CoverageSurfStrategy>(Duration::from_secs(30), vec![path]).await;
+    assert!(results.num_successful_transactions > 0);
+    assert!(!results.unique_move_functions_succeeded.is_empty());
+    assert!(!results.unique_object_types_created.is_empty());
+    assert!(results.entry_functions.iter().any(|entry| results
+        .unique_move_functions_succeeded
+        .contains(&entry.key())));
+}
@@ -449,6 +449,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     transaction_deny_config: Default::default(),
                     certificate_deny_config: Default::default(),
                     state_debug_dump_config: self.state_debug_dump_config.clone(),
+                    event_sink_config: None,
                 }
             })
             .collect();
@@ -720,6 +721,7 @@ impl<'a> FullnodeConfigBuilder<'a> {
             transaction_deny_config: Default::default(),
             certificate_deny_config: Default::default(),
             state_debug_dump_config: Default::default(),
+            event_sink_config: None,
         })
     }
 }
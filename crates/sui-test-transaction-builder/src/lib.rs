@@ -5,11 +5,12 @@ use move_core_types::ident_str;
 use shared_crypto::intent::Intent;
 use std::path::PathBuf;
 use sui_move_build::BuildConfig;
-use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::base_types::{EpochId, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
 use sui_types::crypto::{Signature, Signer};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    CallArg, ObjectArg, Transaction, TransactionData, VerifiedTransaction,
+    CallArg, ObjectArg, Transaction, TransactionData, TransactionExpiration, VerifiedTransaction,
     TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use sui_types::{TypeTag, SUI_SYSTEM_PACKAGE_ID};
@@ -19,6 +20,7 @@ pub struct TestTransactionBuilder {
     sender: SuiAddress,
     gas_object: ObjectRef,
     gas_price: u64,
+    expiration: TransactionExpiration,
 }
 
 impl TestTransactionBuilder {
@@ -28,6 +30,7 @@ impl TestTransactionBuilder {
             sender,
             gas_object,
             gas_price,
+            expiration: TransactionExpiration::None,
         }
     }
 
@@ -118,6 +121,23 @@ impl TestTransactionBuilder {
         self
     }
 
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    pub fn expire_at_epoch(self, epoch: EpochId) -> Self {
+        self.with_expiration(TransactionExpiration::Epoch(epoch))
+    }
+
+    pub fn expire_at_timestamp(self, timestamp_ms: u64) -> Self {
+        self.with_expiration(TransactionExpiration::Timestamp(timestamp_ms))
+    }
+
+    pub fn expire_at_checkpoint(self, checkpoint: CheckpointSequenceNumber) -> Self {
+        self.with_expiration(TransactionExpiration::Checkpoint(checkpoint))
+    }
+
     pub fn transfer(mut self, object: ObjectRef, recipient: SuiAddress) -> Self {
         self.test_data = TestTransactionData::Transfer(TransferData { object, recipient });
         self
@@ -153,7 +173,7 @@ impl TestTransactionBuilder {
     }
 
     pub fn build(self) -> TransactionData {
-        match self.test_data {
+        let data = match self.test_data {
             TestTransactionData::Move(data) => TransactionData::new_move_call(
                 self.sender,
                 data.package_id,
@@ -200,7 +220,8 @@ impl TestTransactionBuilder {
             TestTransactionData::Empty => {
                 panic!("Cannot build empty transaction");
             }
-        }
+        };
+        data.with_expiration(self.expiration)
     }
 
     pub fn build_and_sign(self, signer: &dyn Signer<Signature>) -> VerifiedTransaction {
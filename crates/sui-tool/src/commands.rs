@@ -4,9 +4,10 @@
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     get_object, get_transaction_block, make_clients, restore_from_db_checkpoint,
-    ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
+    restore_from_state_snapshot, ConciseObjectOutput, GroupedObjectOutput, VerboseObjectOutput,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
@@ -19,7 +20,7 @@ use fastcrypto::encoding::Encoding;
 use sui_config::Config;
 use sui_core::authority_aggregator::AuthorityAggregatorBuilder;
 use sui_types::messages_checkpoint::{
-    CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
+    CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber, ECMHLiveObjectSetDigest,
 };
 use sui_types::transaction::{SenderSignedData, Transaction};
 
@@ -165,10 +166,28 @@ pub enum ToolCommand {
         db_checkpoint_path: PathBuf,
     },
 
+    /// Restore the live object set of a node's db from the state snapshot of an epoch, verified
+    /// against the root state hash committed to at the end of that epoch
+    #[clap(name = "restore-snapshot")]
+    RestoreFromStateSnapshot {
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        #[clap(long = "epoch")]
+        epoch: u64,
+        /// Directory holding the `epoch_<N>` snapshot directories
+        #[clap(long = "snapshot-path")]
+        snapshot_path: PathBuf,
+        /// Base58 encoded root state hash of the epoch
+        #[clap(long = "root-state-hash")]
+        root_state_hash: String,
+        #[clap(long = "concurrency", default_value = "5")]
+        concurrency: NonZeroUsize,
+    },
+
     #[clap(name = "replay")]
     Replay {
         #[clap(long = "rpc")]
-        rpc_url: String,
+        rpc_url: Option<String>,
         #[clap(long = "safety-checks")]
         safety_checks: bool,
         #[clap(long = "authority")]
@@ -349,6 +368,29 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::RestoreFromStateSnapshot {
+                config_path,
+                epoch,
+                snapshot_path,
+                root_state_hash,
+                concurrency,
+            } => {
+                let config = sui_config::NodeConfig::load(config_path)?;
+                let digest: [u8; 32] = fastcrypto::encoding::Base58::decode(&root_state_hash)?
+                    .try_into()
+                    .map_err(|_| anyhow!("Root state hash must be 32 bytes"))?;
+                let expected_root = ECMHLiveObjectSetDigest {
+                    digest: digest.into(),
+                };
+                restore_from_state_snapshot(
+                    &config,
+                    epoch,
+                    &snapshot_path,
+                    &expected_root,
+                    concurrency,
+                )
+                .await?;
+            }
             ToolCommand::Replay {
                 rpc_url,
                 safety_checks,
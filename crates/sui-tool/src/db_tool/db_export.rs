@@ -0,0 +1,825 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context};
+use arrow_array::builder::{BinaryBuilder, BooleanBuilder, StringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use clap::{Parser, ValueEnum};
+use fastcrypto::encoding::{Base58, Encoding};
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::authority_store_types::{
+    try_construct_object, StoreData, StoreObject, StoreObjectWrapper,
+};
+use sui_core::checkpoints::CheckpointStore;
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber};
+use sui_types::digests::{
+    CheckpointContentsDigest, TransactionDigest, TransactionEffectsDigest, TransactionEventsDigest,
+};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::object::{Data, Object};
+use sui_types::storage::ObjectKey;
+use sui_types::transaction::{TransactionDataAPI, TransactionExpiration, TransactionKind};
+use tracing::info;
+use typed_store::traits::Map;
+
+/// Number of rows buffered in memory before they are written out as one parquet row group
+const PARQUET_ROW_GROUP_SIZE: usize = 8192;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportTable {
+    /// All object versions in the perpetual store, including deleted and wrapped ones
+    Objects,
+    /// Transactions in the perpetual store
+    Transactions,
+    /// Transaction effects in the perpetual store
+    Effects,
+    /// Events in the perpetual store
+    Events,
+    /// Shared object versions assigned by consensus, from the epoch store
+    SharedObjectVersions,
+    /// Certified checkpoint summaries in the checkpoint store
+    Checkpoints,
+    /// Checkpoint contents in the checkpoint store, one row per transaction
+    CheckpointContents,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Parquet,
+    Csv,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct ExportOptions {
+    /// The table to export
+    #[clap(long = "table", short = 't', value_enum)]
+    table: ExportTable,
+    #[clap(long = "format", short = 'f', value_enum, default_value = "parquet")]
+    format: ExportFormat,
+    /// The directory to write the output files to
+    #[clap(long = "output-dir", short = 'o')]
+    output_dir: PathBuf,
+    /// Inclusive start of the key range to export. Object ID for objects, sequence number for
+    /// checkpoints, and base58 digest for the other tables
+    #[clap(long = "start", short = 's')]
+    start: Option<String>,
+    /// Exclusive end of the key range to export, in the same format as `--start`
+    #[clap(long = "end", short = 'e')]
+    end: Option<String>,
+    /// Maximum number of rows per output file
+    #[clap(long = "rows-per-file", default_value = "1000000")]
+    rows_per_file: usize,
+    /// Also export the raw BCS bytes of each value
+    #[clap(long = "with-bcs")]
+    with_bcs: bool,
+    /// The epoch to use when loading AuthorityEpochTables
+    #[clap(long = "epoch")]
+    epoch: Option<EpochId>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ColumnType {
+    U64,
+    Bool,
+    String,
+    Binary,
+}
+
+impl From<ColumnType> for DataType {
+    fn from(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::U64 => DataType::UInt64,
+            ColumnType::Bool => DataType::Boolean,
+            ColumnType::String => DataType::Utf8,
+            ColumnType::Binary => DataType::Binary,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Value {
+    U64(u64),
+    Bool(bool),
+    String(String),
+    Binary(Vec<u8>),
+    Null,
+}
+
+impl From<u64> for Value {
+    fn from(v: u64) -> Self {
+        Value::U64(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::String(v)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Value {
+    fn to_csv_field(&self) -> String {
+        match self {
+            Value::U64(v) => v.to_string(),
+            Value::Bool(v) => v.to_string(),
+            Value::String(v) => v.clone(),
+            Value::Binary(v) => hex::encode(v),
+            Value::Null => String::new(),
+        }
+    }
+}
+
+type Columns = Vec<(&'static str, ColumnType)>;
+
+fn columns(table: ExportTable, with_bcs: bool) -> Columns {
+    use ColumnType::*;
+    let mut columns = match table {
+        ExportTable::Objects => vec![
+            ("object_id", String),
+            ("version", U64),
+            ("status", String),
+            ("digest", String),
+            ("owner", String),
+            ("object_type", String),
+            ("is_package", Bool),
+            ("previous_transaction", String),
+            ("storage_rebate", U64),
+            ("size", U64),
+        ],
+        ExportTable::Transactions => vec![
+            ("transaction_digest", String),
+            ("sender", String),
+            ("gas_owner", String),
+            ("gas_budget", U64),
+            ("gas_price", U64),
+            ("kind", String),
+            ("num_commands", U64),
+            ("expiration_epoch", U64),
+            ("expiration_timestamp_ms", U64),
+            ("expiration_checkpoint", U64),
+        ],
+        ExportTable::Effects => vec![
+            ("effects_digest", String),
+            ("transaction_digest", String),
+            ("success", Bool),
+            ("error", String),
+            ("executed_epoch", U64),
+            ("computation_cost", U64),
+            ("storage_cost", U64),
+            ("storage_rebate", U64),
+            ("non_refundable_storage_fee", U64),
+            ("gas_object_id", String),
+            ("num_created", U64),
+            ("num_mutated", U64),
+            ("num_deleted", U64),
+            ("events_digest", String),
+        ],
+        ExportTable::Events => vec![
+            ("events_digest", String),
+            ("event_seq", U64),
+            ("package_id", String),
+            ("transaction_module", String),
+            ("sender", String),
+            ("event_type", String),
+        ],
+        ExportTable::SharedObjectVersions => vec![
+            ("transaction_digest", String),
+            ("object_id", String),
+            ("version", U64),
+        ],
+        ExportTable::Checkpoints => vec![
+            ("sequence_number", U64),
+            ("digest", String),
+            ("epoch", U64),
+            ("network_total_transactions", U64),
+            ("content_digest", String),
+            ("previous_digest", String),
+            ("timestamp_ms", U64),
+            ("epoch_computation_cost", U64),
+            ("epoch_storage_cost", U64),
+            ("epoch_storage_rebate", U64),
+            ("is_end_of_epoch", Bool),
+        ],
+        ExportTable::CheckpointContents => vec![
+            ("content_digest", String),
+            ("position", U64),
+            ("transaction_digest", String),
+            ("effects_digest", String),
+        ],
+    };
+    if with_bcs {
+        columns.push(("bcs", Binary));
+    }
+    columns
+}
+
+/// Streams rows into a sequence of output files, starting a new file every `rows_per_file` rows
+struct ChunkedWriter {
+    output_dir: PathBuf,
+    prefix: String,
+    format: ExportFormat,
+    columns: Columns,
+    rows_per_file: usize,
+    current: Option<FileWriter>,
+    rows_in_current: usize,
+    num_files: usize,
+    num_rows: usize,
+}
+
+enum FileWriter {
+    Csv(csv::Writer<File>),
+    Parquet {
+        writer: ArrowWriter<File>,
+        schema: Arc<Schema>,
+        rows: Vec<Vec<Value>>,
+    },
+}
+
+impl ChunkedWriter {
+    fn new(
+        output_dir: PathBuf,
+        prefix: String,
+        format: ExportFormat,
+        columns: Columns,
+        rows_per_file: usize,
+    ) -> anyhow::Result<Self> {
+        if rows_per_file == 0 {
+            return Err(anyhow!("--rows-per-file must be positive"));
+        }
+        std::fs::create_dir_all(&output_dir)?;
+        Ok(Self {
+            output_dir,
+            prefix,
+            format,
+            columns,
+            rows_per_file,
+            current: None,
+            rows_in_current: 0,
+            num_files: 0,
+            num_rows: 0,
+        })
+    }
+
+    fn write(&mut self, row: Vec<Value>) -> anyhow::Result<()> {
+        debug_assert_eq!(row.len(), self.columns.len());
+        if self.rows_in_current == self.rows_per_file {
+            self.finish_file()?;
+        }
+        if self.current.is_none() {
+            self.current = Some(self.open_file()?);
+        }
+        match self.current.as_mut().unwrap() {
+            FileWriter::Csv(writer) => {
+                writer.write_record(row.iter().map(Value::to_csv_field))?;
+            }
+            FileWriter::Parquet {
+                writer,
+                schema,
+                rows,
+            } => {
+                rows.push(row);
+                if rows.len() == PARQUET_ROW_GROUP_SIZE {
+                    writer.write(&record_batch(schema, &self.columns, rows)?)?;
+                    rows.clear();
+                }
+            }
+        }
+        self.rows_in_current += 1;
+        self.num_rows += 1;
+        Ok(())
+    }
+
+    fn open_file(&mut self) -> anyhow::Result<FileWriter> {
+        let path = self.output_dir.join(format!(
+            "{}_{:05}.{}",
+            self.prefix,
+            self.num_files,
+            self.format.extension()
+        ));
+        info!("Writing {}", path.display());
+        self.num_files += 1;
+        let file = File::create(&path)?;
+        Ok(match self.format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(file);
+                writer.write_record(self.columns.iter().map(|(name, _)| name))?;
+                FileWriter::Csv(writer)
+            }
+            ExportFormat::Parquet => {
+                let schema = Arc::new(Schema::new(
+                    self.columns
+                        .iter()
+                        .map(|(name, column_type)| Field::new(*name, (*column_type).into(), true))
+                        .collect::<Vec<_>>(),
+                ));
+                FileWriter::Parquet {
+                    writer: ArrowWriter::try_new(file, schema.clone(), None)?,
+                    schema,
+                    rows: Vec::with_capacity(PARQUET_ROW_GROUP_SIZE),
+                }
+            }
+        })
+    }
+
+    fn finish_file(&mut self) -> anyhow::Result<()> {
+        match self.current.take() {
+            Some(FileWriter::Csv(mut writer)) => writer.flush()?,
+            Some(FileWriter::Parquet {
+                mut writer,
+                schema,
+                rows,
+            }) => {
+                if !rows.is_empty() {
+                    writer.write(&record_batch(&schema, &self.columns, &rows)?)?;
+                }
+                writer.close()?;
+            }
+            None => (),
+        }
+        self.rows_in_current = 0;
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<()> {
+        self.finish_file()?;
+        println!(
+            "Exported {} rows to {} file(s) in {}",
+            self.num_rows,
+            self.num_files,
+            self.output_dir.display()
+        );
+        Ok(())
+    }
+}
+
+fn record_batch(
+    schema: &Arc<Schema>,
+    columns: &Columns,
+    rows: &[Vec<Value>],
+) -> anyhow::Result<RecordBatch> {
+    let arrays = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (name, column_type))| {
+            let values = rows.iter().map(|row| &row[i]);
+            let mismatch = |v: &Value| anyhow!("Unexpected value {v:?} in column {name}");
+            let array: ArrayRef = match column_type {
+                ColumnType::U64 => {
+                    let mut builder = UInt64Builder::with_capacity(rows.len());
+                    for v in values {
+                        match v {
+                            Value::U64(v) => builder.append_value(*v),
+                            Value::Null => builder.append_null(),
+                            v => return Err(mismatch(v)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                ColumnType::Bool => {
+                    let mut builder = BooleanBuilder::with_capacity(rows.len());
+                    for v in values {
+                        match v {
+                            Value::Bool(v) => builder.append_value(*v),
+                            Value::Null => builder.append_null(),
+                            v => return Err(mismatch(v)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                ColumnType::String => {
+                    let mut builder = StringBuilder::new();
+                    for v in values {
+                        match v {
+                            Value::String(v) => builder.append_value(v),
+                            Value::Null => builder.append_null(),
+                            v => return Err(mismatch(v)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                ColumnType::Binary => {
+                    let mut builder = BinaryBuilder::new();
+                    for v in values {
+                        match v {
+                            Value::Binary(v) => builder.append_value(v),
+                            Value::Null => builder.append_null(),
+                            v => return Err(mismatch(v)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+            };
+            Ok(array)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(RecordBatch::try_new(schema.clone(), arrays)?)
+}
+
+fn parse_digest(s: &str) -> anyhow::Result<[u8; 32]> {
+    Base58::decode(s)
+        .map_err(|e| anyhow!("Invalid base58 digest {s}: {e}"))?
+        .try_into()
+        .map_err(|_| anyhow!("Digest {s} is not 32 bytes long"))
+}
+
+fn parse_range<K>(
+    opt: &ExportOptions,
+    parse: impl Fn(&str) -> anyhow::Result<K>,
+) -> anyhow::Result<(Option<K>, Option<K>)> {
+    let start = opt.start.as_deref().map(&parse).transpose()?;
+    let end = opt.end.as_deref().map(&parse).transpose()?;
+    Ok((start, end))
+}
+
+/// Exports a table of the node db at `path` (e.g. /opt/sui/db/authorities_db/live) to parquet or
+/// csv files, with columns decoded from the BCS values.
+/// Run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live export --table effects --output-dir /tmp/effects
+pub fn export_table(path: &Path, opt: ExportOptions) -> anyhow::Result<()> {
+    let mut writer = ChunkedWriter::new(
+        opt.output_dir.clone(),
+        opt.table
+            .to_possible_value()
+            .context("Unexpected table")?
+            .get_name()
+            .to_string(),
+        opt.format,
+        columns(opt.table, opt.with_bcs),
+        opt.rows_per_file,
+    )?;
+    let bcs_column = |bytes: anyhow::Result<Vec<u8>>| -> anyhow::Result<Option<Value>> {
+        Ok(if opt.with_bcs {
+            Some(Value::Binary(bytes?))
+        } else {
+            None
+        })
+    };
+
+    match opt.table {
+        ExportTable::Objects => {
+            let perpetual_db = AuthorityPerpetualTables::open_readonly(&path.join("store"));
+            let (start, end) = parse_range(&opt, |s| {
+                Ok(ObjectKey(ObjectID::from_str(s)?, SequenceNumber::MIN))
+            })?;
+            for (key, store_object) in perpetual_db.objects.iter_with_bounds(start, end) {
+                let mut row: Vec<Value> = vec![key.0.to_string().into(), key.1.value().into()];
+                let object = match store_object.clone().migrate().into_inner() {
+                    StoreObject::Value(store_object) => {
+                        let indirect_object = match &store_object.data {
+                            StoreData::IndirectObject(metadata) => perpetual_db
+                                .indirect_move_objects
+                                .get(&metadata.digest)?
+                                .map(|o| o.migrate().into_inner()),
+                            _ => None,
+                        };
+                        row.push("live".to_string().into());
+                        Some(try_construct_object(&key, store_object, indirect_object)?)
+                    }
+                    StoreObject::Deleted => {
+                        row.push("deleted".to_string().into());
+                        None
+                    }
+                    StoreObject::Wrapped => {
+                        row.push("wrapped".to_string().into());
+                        None
+                    }
+                };
+                row.extend(object_columns(object.as_ref()));
+                row.extend(bcs_column(
+                    bcs::to_bytes::<StoreObjectWrapper>(&store_object).map_err(Into::into),
+                )?);
+                writer.write(row)?;
+            }
+        }
+        ExportTable::Transactions => {
+            let perpetual_db = AuthorityPerpetualTables::open_readonly(&path.join("store"));
+            let (start, end) = parse_range(&opt, |s| Ok(TransactionDigest::new(parse_digest(s)?)))?;
+            for (digest, transaction) in perpetual_db.transactions.iter_with_bounds(start, end) {
+                let data = transaction.inner().data().transaction_data();
+                let num_commands = match data.kind() {
+                    TransactionKind::ProgrammableTransaction(p) => Some(p.commands.len() as u64),
+                    _ => None,
+                };
+                let (expiration_epoch, expiration_timestamp_ms, expiration_checkpoint) =
+                    match data.expiration() {
+                        TransactionExpiration::None => (None, None, None),
+                        TransactionExpiration::Epoch(epoch) => (Some(*epoch), None, None),
+                        TransactionExpiration::Timestamp(timestamp_ms) => {
+                            (None, Some(*timestamp_ms), None)
+                        }
+                        TransactionExpiration::Checkpoint(checkpoint) => {
+                            (None, None, Some(*checkpoint))
+                        }
+                    };
+                let kind: &'static str = data.kind().into();
+                let mut row: Vec<Value> = vec![
+                    digest.to_string().into(),
+                    data.sender().to_string().into(),
+                    data.gas_owner().to_string().into(),
+                    data.gas_budget().into(),
+                    data.gas_price().into(),
+                    kind.to_string().into(),
+                    num_commands.into(),
+                    expiration_epoch.into(),
+                    expiration_timestamp_ms.into(),
+                    expiration_checkpoint.into(),
+                ];
+                row.extend(bcs_column(
+                    bcs::to_bytes(transaction.inner().data()).map_err(Into::into),
+                )?);
+                writer.write(row)?;
+            }
+        }
+        ExportTable::Effects => {
+            let perpetual_db = AuthorityPerpetualTables::open_readonly(&path.join("store"));
+            let (start, end) = parse_range(&opt, |s| {
+                Ok(TransactionEffectsDigest::new(parse_digest(s)?))
+            })?;
+            for (digest, effects) in perpetual_db.effects.iter_with_bounds(start, end) {
+                let gas_cost = effects.gas_cost_summary();
+                let error = (!effects.status().is_ok()).then(|| format!("{:?}", effects.status()));
+                let mut row: Vec<Value> = vec![
+                    digest.to_string().into(),
+                    effects.transaction_digest().to_string().into(),
+                    effects.status().is_ok().into(),
+                    error.into(),
+                    effects.executed_epoch().into(),
+                    gas_cost.computation_cost.into(),
+                    gas_cost.storage_cost.into(),
+                    gas_cost.storage_rebate.into(),
+                    gas_cost.non_refundable_storage_fee.into(),
+                    effects.gas_object().0 .0.to_string().into(),
+                    (effects.created().len() as u64).into(),
+                    (effects.mutated().len() as u64).into(),
+                    (effects.deleted().len() as u64).into(),
+                    effects.events_digest().map(|d| d.to_string()).into(),
+                ];
+                row.extend(bcs_column(bcs::to_bytes(&effects).map_err(Into::into))?);
+                writer.write(row)?;
+            }
+        }
+        ExportTable::Events => {
+            let perpetual_db = AuthorityPerpetualTables::open_readonly(&path.join("store"));
+            let (start, end) = parse_range(&opt, |s| {
+                Ok((TransactionEventsDigest::new(parse_digest(s)?), 0))
+            })?;
+            for ((digest, seq), event) in perpetual_db.events.iter_with_bounds(start, end) {
+                let mut row: Vec<Value> = vec![
+                    digest.to_string().into(),
+                    (seq as u64).into(),
+                    event.package_id.to_string().into(),
+                    event.transaction_module.to_string().into(),
+                    event.sender.to_string().into(),
+                    event.type_.to_string().into(),
+                ];
+                row.extend(bcs_column(bcs::to_bytes(&event).map_err(Into::into))?);
+                writer.write(row)?;
+            }
+        }
+        ExportTable::SharedObjectVersions => {
+            let epoch = opt.epoch.context("--epoch is required")?;
+            let epoch_db = AuthorityEpochTables::open_readonly(epoch, &path.join("store"));
+            let (start, end) = parse_range(&opt, |s| Ok(TransactionDigest::new(parse_digest(s)?)))?;
+            for (digest, versions) in epoch_db
+                .assigned_shared_object_versions
+                .iter_with_bounds(start, end)
+            {
+                let bcs = bcs_column(bcs::to_bytes(&versions).map_err(Into::into))?;
+                for (id, version) in versions {
+                    let mut row: Vec<Value> = vec![
+                        digest.to_string().into(),
+                        id.to_string().into(),
+                        version.value().into(),
+                    ];
+                    row.extend(bcs.clone());
+                    writer.write(row)?;
+                }
+            }
+        }
+        ExportTable::Checkpoints => {
+            let checkpoint_db = CheckpointStore::open_readonly(&path.join("checkpoints"));
+            let (start, end) = parse_range(&opt, |s| Ok(u64::from_str(s)?))?;
+            for (seq, checkpoint) in checkpoint_db
+                .certified_checkpoints
+                .iter_with_bounds(start, end)
+            {
+                let summary = checkpoint.inner().data();
+                let gas_cost = &summary.epoch_rolling_gas_cost_summary;
+                let mut row: Vec<Value> = vec![
+                    seq.into(),
+                    checkpoint.inner().digest().to_string().into(),
+                    summary.epoch.into(),
+                    summary.network_total_transactions.into(),
+                    summary.content_digest.to_string().into(),
+                    summary.previous_digest.map(|d| d.to_string()).into(),
+                    summary.timestamp_ms.into(),
+                    gas_cost.computation_cost.into(),
+                    gas_cost.storage_cost.into(),
+                    gas_cost.storage_rebate.into(),
+                    summary.end_of_epoch_data.is_some().into(),
+                ];
+                row.extend(bcs_column(bcs::to_bytes(summary).map_err(Into::into))?);
+                writer.write(row)?;
+            }
+        }
+        ExportTable::CheckpointContents => {
+            let checkpoint_db = CheckpointStore::open_readonly(&path.join("checkpoints"));
+            let (start, end) = parse_range(&opt, |s| {
+                Ok(CheckpointContentsDigest::new(parse_digest(s)?))
+            })?;
+            for (digest, contents) in checkpoint_db
+                .checkpoint_content
+                .iter_with_bounds(start, end)
+            {
+                for (position, execution_digests) in contents.iter().enumerate() {
+                    let mut row: Vec<Value> = vec![
+                        digest.to_string().into(),
+                        (position as u64).into(),
+                        execution_digests.transaction.to_string().into(),
+                        execution_digests.effects.to_string().into(),
+                    ];
+                    row.extend(bcs_column(
+                        bcs::to_bytes(execution_digests).map_err(Into::into),
+                    )?);
+                    writer.write(row)?;
+                }
+            }
+        }
+    }
+    writer.finish()
+}
+
+/// The object columns following the status column, all null for deleted and wrapped versions
+fn object_columns(object: Option<&Object>) -> Vec<Value> {
+    let Some(object) = object else {
+        return vec![Value::Null; 7];
+    };
+    let object_type = match &object.data {
+        Data::Move(o) => o.type_().to_string(),
+        Data::Package(_) => "package".to_string(),
+    };
+    vec![
+        object.compute_object_reference().2.to_string().into(),
+        object.owner.to_string().into(),
+        object_type.into(),
+        object.is_package().into(),
+        object.previous_transaction.to_string().into(),
+        object.storage_rebate.into(),
+        (object.object_size_for_gas_metering() as u64).into(),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{columns, export_table, ExportFormat, ExportOptions, ExportTable};
+    use arrow_array::{Array, BinaryArray, BooleanArray, StringArray, UInt64Array};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::fs::File;
+    use std::str::FromStr;
+    use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+    use sui_core::authority::authority_store_types::StoreObjectWrapper;
+    use sui_types::base_types::ObjectID;
+    use sui_types::object::Object;
+
+    #[tokio::test]
+    async fn export_objects_in_chunks() -> Result<(), anyhow::Error> {
+        let db_path = tempfile::tempdir()?.into_path();
+        let output_dir = tempfile::tempdir()?.into_path();
+        let perpetual_db = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+        let ids = ObjectID::in_range(ObjectID::ZERO, 25)?;
+        for id in &ids {
+            perpetual_db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+        }
+
+        export_table(
+            &db_path,
+            ExportOptions {
+                table: ExportTable::Objects,
+                format: ExportFormat::Csv,
+                output_dir: output_dir.clone(),
+                start: Some(ids[5].to_string()),
+                end: None,
+                rows_per_file: 10,
+                with_bcs: true,
+                epoch: None,
+            },
+        )?;
+
+        let mut files = std::fs::read_dir(&output_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        files.sort();
+        assert_eq!(files.len(), 2);
+        let num_rows = files
+            .iter()
+            .map(|f| csv::Reader::from_path(f).unwrap().records().count())
+            .sum::<usize>();
+        assert_eq!(num_rows, 20);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn export_objects_to_parquet() -> Result<(), anyhow::Error> {
+        let db_path = tempfile::tempdir()?.into_path();
+        let output_dir = tempfile::tempdir()?.into_path();
+        let perpetual_db = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+        let objects = ObjectID::in_range(ObjectID::ZERO, 15)?
+            .into_iter()
+            .map(Object::immutable_with_id_for_testing)
+            .collect::<Vec<_>>();
+        for object in &objects {
+            perpetual_db.insert_object_test_only(object.clone())?;
+        }
+
+        export_table(
+            &db_path,
+            ExportOptions {
+                table: ExportTable::Objects,
+                format: ExportFormat::Parquet,
+                output_dir: output_dir.clone(),
+                start: None,
+                end: None,
+                rows_per_file: 10,
+                with_bcs: true,
+                epoch: None,
+            },
+        )?;
+
+        let mut files = std::fs::read_dir(&output_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        files.sort();
+        assert_eq!(files.len(), 2);
+
+        let expected_columns = columns(ExportTable::Objects, true)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        let mut exported = vec![];
+        for file in &files {
+            for batch in ParquetRecordBatchReaderBuilder::try_new(File::open(file)?)?.build()? {
+                let batch = batch?;
+                let schema = batch.schema();
+                let column_names = schema
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().as_str())
+                    .collect::<Vec<_>>();
+                assert_eq!(column_names, expected_columns);
+
+                let column = |name: &str| batch.column(schema.index_of(name).unwrap()).clone();
+                let object_ids = column("object_id");
+                let object_ids = object_ids.as_any().downcast_ref::<StringArray>().unwrap();
+                let versions = column("version");
+                let versions = versions.as_any().downcast_ref::<UInt64Array>().unwrap();
+                let is_package = column("is_package");
+                let is_package = is_package.as_any().downcast_ref::<BooleanArray>().unwrap();
+                let bcs_values = column("bcs");
+                let bcs_values = bcs_values.as_any().downcast_ref::<BinaryArray>().unwrap();
+                for row in 0..batch.num_rows() {
+                    assert!(!is_package.value(row));
+                    assert!(!bcs_values.is_null(row));
+                    bcs::from_bytes::<StoreObjectWrapper>(bcs_values.value(row))?;
+                    exported.push((
+                        ObjectID::from_str(object_ids.value(row))?,
+                        versions.value(row),
+                    ));
+                }
+            }
+        }
+        let expected = objects
+            .iter()
+            .map(|object| (object.id(), object.version().value()))
+            .collect::<Vec<_>>();
+        assert_eq!(exported, expected);
+        Ok(())
+    }
+}
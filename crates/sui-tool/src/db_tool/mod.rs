@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
+use self::db_export::{export_table, ExportOptions};
 use self::index_search::{search_index, SearchRange};
 use crate::db_tool::db_dump::{compact, print_table_metadata};
 use anyhow::bail;
@@ -15,6 +16,7 @@ use sui_types::effects::TransactionEffectsAPI;
 use sui_types::storage::ObjectKey;
 use typed_store::rocks::MetricConf;
 pub mod db_dump;
+mod db_export;
 mod index_search;
 
 #[derive(Parser)]
@@ -33,6 +35,7 @@ pub enum DbToolCommand {
     ResetDB,
     RewindCheckpointExecution(RewindCheckpointExecutionOptions),
     Compact,
+    Export(ExportOptions),
 }
 
 #[derive(Parser)]
@@ -147,6 +150,7 @@ pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::
             rewind_checkpoint_execution(&db_path, d.epoch, d.checkpoint_sequence_number)
         }
         DbToolCommand::Compact => compact(db_path),
+        DbToolCommand::Export(d) => export_table(&db_path, d),
         DbToolCommand::IndexSearchKeyRange(rg) => {
             let res = search_index(
                 db_path,
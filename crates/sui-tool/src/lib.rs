@@ -8,14 +8,19 @@ use futures::future::join_all;
 use itertools::Itertools;
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{fs, io};
 use sui_config::{genesis::Genesis, NodeConfig};
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use sui_network::default_mysten_network_config;
 use sui_sdk::SuiClientBuilder;
+use sui_snapshot::reader::StateSnapshotReaderV1;
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
 use sui_types::crypto::AuthorityPublicKeyBytes;
+use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::multiaddr::Multiaddr;
 use sui_types::object::ObjectFormatOptions;
 use sui_types::{base_types::*, object::Owner};
@@ -612,3 +617,121 @@ pub async fn restore_from_db_checkpoint(
     copy_dir_all(db_checkpoint_path, config.db_path(), vec![])?;
     Ok(())
 }
+
+/// Restores the live object set of `epoch` from a state snapshot under `snapshot_path` into the
+/// perpetual tables of the node's db, after verifying it against `expected_root`, the root state
+/// hash committed to at the end of that epoch
+pub async fn restore_from_state_snapshot(
+    config: &NodeConfig,
+    epoch: u64,
+    snapshot_path: &Path,
+    expected_root: &ECMHLiveObjectSetDigest,
+    concurrency: NonZeroUsize,
+) -> Result<(), anyhow::Error> {
+    let perpetual_db = AuthorityPerpetualTables::open(&config.db_path().join("store"), None);
+    if !perpetual_db.database_is_empty()? {
+        return Err(anyhow!(
+            "Db at {} is not empty, refusing to restore a state snapshot into it",
+            config.db_path().display()
+        ));
+    }
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(snapshot_path.to_path_buf()),
+        ..Default::default()
+    };
+    let staging_dir = tempfile::tempdir()?;
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(staging_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let reader =
+        StateSnapshotReaderV1::new(epoch, remote_store_config, local_store_config, concurrency)
+            .await?;
+    reader
+        .read(
+            &perpetual_db,
+            expected_root,
+            config.indirect_objects_threshold,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::hash::MultisetHash;
+    use sui_snapshot::writer::StateSnapshotWriterV1;
+    use sui_snapshot::FileCompression;
+    use sui_swarm_config::network_config_builder::ConfigBuilder;
+    use sui_types::accumulator::Accumulator;
+    use sui_types::object::Object;
+
+    fn object_store_config(directory: PathBuf) -> ObjectStoreConfig {
+        ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(directory),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_state_snapshot() -> Result<(), anyhow::Error> {
+        let source_db =
+            AuthorityPerpetualTables::open(tempfile::tempdir()?.into_path().as_path(), None);
+        for id in ObjectID::in_range(ObjectID::ZERO, 100)? {
+            source_db.insert_object_test_only(Object::immutable_with_id_for_testing(id))?;
+        }
+        let snapshot_path = tempfile::tempdir()?.into_path();
+        StateSnapshotWriterV1::new(
+            0,
+            object_store_config(tempfile::tempdir()?.into_path()),
+            object_store_config(snapshot_path.clone()),
+            FileCompression::Zstd,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await?
+        .write(&source_db)
+        .await?;
+        let mut acc = Accumulator::default();
+        for object in source_db.iter_live_object_set() {
+            acc.insert(object.object_reference().2);
+        }
+        let expected_root: ECMHLiveObjectSetDigest = acc.digest().into();
+
+        let config = ConfigBuilder::new_with_temp_dir().build().validator_configs[0].clone();
+        restore_from_state_snapshot(
+            &config,
+            0,
+            &snapshot_path,
+            &expected_root,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await?;
+        let restored_db = AuthorityPerpetualTables::open(&config.db_path().join("store"), None);
+        assert_eq!(
+            source_db
+                .iter_live_object_set()
+                .map(|o| o.object_reference())
+                .collect::<Vec<_>>(),
+            restored_db
+                .iter_live_object_set()
+                .map(|o| o.object_reference())
+                .collect::<Vec<_>>()
+        );
+        drop(restored_db);
+
+        // A second restore must not touch the now populated db
+        assert!(restore_from_state_snapshot(
+            &config,
+            0,
+            &snapshot_path,
+            &expected_root,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await
+        .is_err());
+        Ok(())
+    }
+}
@@ -13,7 +13,9 @@ use move_binary_format::file_format::SignatureToken;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 
-use sui_json::{resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
+use sui_json::{
+    resolve_move_function_args, resolve_partial_move_function_args, ResolvedCallArg, SuiJsonValue,
+};
 use sui_json_rpc_types::{
     RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
     SuiTypeTag,
@@ -28,7 +30,8 @@ use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionDataAPI, TransactionKind,
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
@@ -49,6 +52,15 @@ pub trait DataReader {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// An argument to a Move call in a programmable transaction. JSON values are resolved against the
+/// signature of the called function, while arguments that are already known to the caller (the gas
+/// coin or the results of earlier commands) are passed through as is.
+#[derive(Clone)]
+pub enum MoveCallArg {
+    Json(SuiJsonValue),
+    Argument(Argument),
+}
+
 #[derive(Clone)]
 pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
 
@@ -320,6 +332,196 @@ impl TransactionBuilder {
         Ok(())
     }
 
+    /// Wrap an already built programmable transaction into `TransactionData`, selecting a gas coin
+    /// that is not used as an input object by the transaction if none is provided.
+    pub async fn programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let input_objects = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(signer, gas, gas_budget, input_objects, gas_price)
+            .await?;
+
+        Ok(TransactionData::new(
+            TransactionKind::programmable(pt),
+            signer,
+            gas,
+            gas_budget,
+            gas_price,
+        ))
+    }
+
+    /// Wrap a transaction kind into `TransactionData` sent by `sender` whose gas is paid by
+    /// `sponsor`. The gas is paid with the `gas_payment` coins, which must be owned by the
+    /// sponsor, or with a coin of the sponsor that is not an input of the transaction if none
+    /// are provided. The resulting transaction must be signed by both the sender and the sponsor.
+    pub async fn sponsored_transaction(
+        &self,
+        sender: SuiAddress,
+        kind: TransactionKind,
+        sponsor: SuiAddress,
+        gas_payment: Vec<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_payment = if gas_payment.is_empty() {
+            let input_objects = kind
+                .input_objects()?
+                .iter()
+                .flat_map(|obj| match obj {
+                    InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                    _ => None,
+                })
+                .collect();
+            vec![
+                self.select_gas(sponsor, None, gas_budget, input_objects, gas_price)
+                    .await?,
+            ]
+        } else {
+            if gas_budget < gas_price {
+                bail!("Gas budget {gas_budget} is less than the reference gas price {gas_price}. The gas budget must be at least the current reference gas price of {gas_price}.")
+            }
+            let handles: Vec<_> = gas_payment
+                .into_iter()
+                .map(|id| self.get_gas_payment_ref(id, sponsor))
+                .collect();
+            join_all(handles)
+                .await
+                .into_iter()
+                .collect::<anyhow::Result<Vec<ObjectRef>>>()?
+        };
+
+        Ok(TransactionData::new_with_gas_coins_allow_sponsor(
+            kind,
+            sender,
+            gas_payment,
+            gas_budget,
+            gas_price,
+            sponsor,
+        ))
+    }
+
+    /// Wrap an already built programmable transaction into `TransactionData` whose gas is paid by
+    /// `sponsor`, see [`TransactionBuilder::sponsored_transaction`].
+    pub async fn sponsored_programmable_transaction(
+        &self,
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+        sponsor: SuiAddress,
+        gas_payment: Vec<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        self.sponsored_transaction(
+            sender,
+            TransactionKind::programmable(pt),
+            sponsor,
+            gas_payment,
+            gas_budget,
+        )
+        .await
+    }
+
+    /// Replace the gas data of `tx_data` so that its gas is paid by `sponsor`, keeping its kind,
+    /// sender and expiration. Any signature of the original transaction is invalidated.
+    pub async fn sponsor(
+        &self,
+        tx_data: TransactionData,
+        sponsor: SuiAddress,
+        gas_payment: Vec<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_budget = gas_budget.unwrap_or_else(|| tx_data.gas_budget());
+        let expiration = *tx_data.expiration();
+        let data = self
+            .sponsored_transaction(
+                tx_data.sender(),
+                tx_data.into_kind(),
+                sponsor,
+                gas_payment,
+                gas_budget,
+            )
+            .await?;
+        Ok(data.with_expiration(expiration))
+    }
+
+    /// Add a Move call to `builder` whose arguments may refer to results of earlier commands, and
+    /// return the argument referring to its result.
+    pub async fn programmable_move_call(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<MoveCallArg>,
+    ) -> anyhow::Result<Argument> {
+        let module = Identifier::from_str(module)?;
+        let function = Identifier::from_str(function)?;
+
+        let type_args = type_args
+            .into_iter()
+            .map(|ty| ty.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let package_obj = self.get_move_package(package).await?;
+        let json_args = call_args
+            .iter()
+            .map(|arg| match arg {
+                MoveCallArg::Json(value) => Some(value.clone()),
+                MoveCallArg::Argument(_) => None,
+            })
+            .collect();
+        let resolved_args = resolve_partial_move_function_args(
+            &package_obj,
+            module.clone(),
+            function.clone(),
+            &type_args,
+            json_args,
+        )?;
+
+        let mut args = Vec::new();
+        let mut objects = BTreeMap::new();
+        for (call_arg, (resolved, expected_type)) in call_args.into_iter().zip(resolved_args) {
+            args.push(match (call_arg, resolved) {
+                (MoveCallArg::Argument(arg), _) => arg,
+                (MoveCallArg::Json(_), Some(resolved)) => {
+                    self.resolved_call_arg(builder, resolved, &expected_type, &mut objects)
+                        .await?
+                }
+                (MoveCallArg::Json(value), None) => {
+                    bail!("Unable to resolve argument {:?}", value.to_json_value())
+                }
+            });
+        }
+
+        Ok(builder.programmable_move_call(package, module, function, type_args, args))
+    }
+
+    /// Add the object `id` as an input to `builder`, looking up whether it is owned or shared.
+    pub async fn object_input(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        id: ObjectID,
+        is_mutable_ref: bool,
+    ) -> anyhow::Result<Argument> {
+        let obj_arg = self
+            .get_object_arg(id, &mut BTreeMap::new(), is_mutable_ref)
+            .await?;
+        builder.obj(obj_arg)
+    }
+
     async fn get_object_arg(
         &self,
         id: ObjectID,
@@ -358,22 +560,7 @@ impl TransactionBuilder {
         type_args: &[TypeTag],
         json_args: Vec<SuiJsonValue>,
     ) -> Result<Vec<Argument>, anyhow::Error> {
-        let object = self
-            .0
-            .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
-            .await?
-            .into_object()?;
-        let Some(SuiRawData::Package(package)) = object.bcs else {
-            bail!("Bcs field in object [{}] is missing or not a package.", package_id);
-        };
-        let package: MovePackage = MovePackage::new(
-            package.id,
-            object.version,
-            package.module_map,
-            ProtocolConfig::get_for_min_version().max_move_package_size(),
-            package.type_origin_table,
-            package.linkage_table,
-        )?;
+        let package = self.get_move_package(package_id).await?;
 
         let json_args_and_tokens = resolve_move_function_args(
             &package,
@@ -386,34 +573,67 @@ impl TransactionBuilder {
         let mut args = Vec::new();
         let mut objects = BTreeMap::new();
         for (arg, expected_type) in json_args_and_tokens {
-            args.push(match arg {
-                ResolvedCallArg::Pure(p) => builder.input(CallArg::Pure(p)),
-
-                ResolvedCallArg::Object(id) => builder.input(CallArg::Object(
-                    self.get_object_arg(
-                        id,
-                        &mut objects,
-                        matches!(expected_type, SignatureToken::MutableReference(_)),
-                    )
+            args.push(
+                self.resolved_call_arg(builder, arg, &expected_type, &mut objects)
                     .await?,
-                )),
-
-                ResolvedCallArg::ObjVec(v) => {
-                    let mut object_ids = vec![];
-                    for id in v {
-                        object_ids.push(
-                            self.get_object_arg(id, &mut objects, /* is_mutable_ref */ false)
-                                .await?,
-                        )
-                    }
-                    builder.make_obj_vec(object_ids)
-                }
-            }?);
+            );
         }
 
         Ok(args)
     }
 
+    async fn resolved_call_arg(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        arg: ResolvedCallArg,
+        expected_type: &SignatureToken,
+        objects: &mut BTreeMap<ObjectID, Object>,
+    ) -> Result<Argument, anyhow::Error> {
+        match arg {
+            ResolvedCallArg::Pure(p) => builder.input(CallArg::Pure(p)),
+
+            ResolvedCallArg::Object(id) => builder.input(CallArg::Object(
+                self.get_object_arg(
+                    id,
+                    objects,
+                    matches!(expected_type, SignatureToken::MutableReference(_)),
+                )
+                .await?,
+            )),
+
+            ResolvedCallArg::ObjVec(v) => {
+                let mut object_ids = vec![];
+                for id in v {
+                    object_ids.push(
+                        self.get_object_arg(id, objects, /* is_mutable_ref */ false)
+                            .await?,
+                    )
+                }
+                builder.make_obj_vec(object_ids)
+            }
+        }
+    }
+
+    async fn get_move_package(&self, package_id: ObjectID) -> Result<MovePackage, anyhow::Error> {
+        let object = self
+            .0
+            .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let Some(SuiRawData::Package(package)) = object.bcs else {
+            bail!("Bcs field in object [{}] is missing or not a package.", package_id);
+        };
+        MovePackage::new(
+            package.id,
+            object.version,
+            package.module_map,
+            ProtocolConfig::get_for_min_version().max_move_package_size(),
+            package.type_origin_table,
+            package.linkage_table,
+        )
+        .map_err(|e| anyhow!(e))
+    }
+
     pub async fn publish(
         &self,
         sender: SuiAddress,
@@ -755,6 +975,24 @@ impl TransactionBuilder {
             .map(|(oref, _)| oref)
     }
 
+    /// Look up a coin used to pay for gas, checking that it is owned by the gas owner.
+    async fn get_gas_payment_ref(
+        &self,
+        object_id: ObjectID,
+        gas_owner: SuiAddress,
+    ) -> anyhow::Result<ObjectRef> {
+        let object = self
+            .0
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?
+            .into_object()?;
+        ensure!(
+            object.owner == Some(Owner::AddressOwner(gas_owner)),
+            "Gas object {object_id} is not owned by the gas owner [{gas_owner}]"
+        );
+        Ok(object.object_ref())
+    }
+
     async fn get_object_ref_and_type(
         &self,
         object_id: ObjectID,
@@ -405,6 +405,18 @@ pub enum SuiError {
     TransactionsNotFound { digests: Vec<TransactionDigest> },
     #[error("Could not find the referenced transaction events [{digest:?}].")]
     TransactionEventsNotFound { digest: TransactionEventsDigest },
+    #[error(
+        "Transaction [{digest:?}] was executed in checkpoint {checkpoint}, whose data has been pruned from this node."
+    )]
+    TransactionPruned {
+        digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+    },
+    #[error("Contents of checkpoint {checkpoint} [{digest}] have been pruned from this node.")]
+    CheckpointContentsPruned {
+        digest: CheckpointContentsDigest,
+        checkpoint: CheckpointSequenceNumber,
+    },
     #[error(
         "Attempt to move to `Executed` state an transaction that has already been executed: {:?}.",
         digest
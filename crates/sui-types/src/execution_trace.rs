@@ -0,0 +1,267 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types describing a trace of the execution of a programmable transaction, as collected when
+//! dev-inspecting or replaying a transaction with tracing enabled.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::base_types::{ObjectID, SequenceNumber};
+use crate::object::{Object, Owner};
+use crate::storage::{DeleteKind, WriteKind};
+use crate::sui_serde::{BigInt, Readable, SequenceNumber as AsSequenceNumber};
+use crate::temporary_store::WrittenObjects;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionTrace {
+    /// The Move calls made by each command of the transaction, in execution order.
+    pub commands: Vec<CommandTrace>,
+    /// The Move call stack at the point execution aborted, outermost call first. Empty if
+    /// execution did not abort inside a Move call.
+    pub abort_stack: Vec<MoveFrame>,
+    /// Objects read, created, mutated, wrapped or deleted by the transaction.
+    pub objects: Vec<ObjectAccess>,
+    /// Dynamic fields (and other child objects) looked up through their parent, or added, during
+    /// execution.
+    pub dynamic_fields: Vec<DynamicFieldAccess>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandTrace {
+    /// Index of the command in the programmable transaction.
+    pub command: u16,
+    /// Top-level Move calls made by the command. A `MoveCall` makes a single call, a `Publish`
+    /// or `Upgrade` makes one call per `init` function it runs, and other commands make none.
+    pub calls: Vec<MoveCallTrace>,
+}
+
+/// A call to a Move function, and the calls it made in turn.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveCallTrace {
+    #[serde(flatten)]
+    pub frame: MoveFrame,
+    /// Whether the function is implemented natively.
+    pub native: bool,
+    /// Computation gas charged while executing this call, including its callees, in internal
+    /// gas units (1/1000th of a gas unit).
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub gas_used: u64,
+    /// Number of bytecode instructions executed by this call, excluding its callees.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub instructions: u64,
+    /// Objects created, transferred or added as a child object by this call, excluding its
+    /// callees, in execution order. Only natives write objects.
+    pub objects_written: Vec<ObjectID>,
+    /// Calls made from this function, in execution order.
+    pub calls: Vec<MoveCallTrace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveFrame {
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+    pub type_arguments: Vec<String>,
+}
+
+/// An object read or written by the transaction.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectAccess {
+    pub object_id: ObjectID,
+    /// The version read for `Read` accesses, the version written for `Created`, `Mutated`
+    /// and `Unwrapped`, and the version at deletion otherwise.
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub version: SequenceNumber,
+    /// The Move type of the object, or `None` for packages and objects deleted or wrapped by
+    /// the transaction.
+    pub object_type: Option<String>,
+    pub kind: ObjectAccessKind,
+    /// The storage cost charged for the written version of the object, in MIST. Only set for
+    /// writes.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<Readable<BigInt<u64>, _>>")]
+    pub storage_cost: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectAccessKind {
+    Read,
+    Created,
+    Mutated,
+    Unwrapped,
+    Deleted,
+    UnwrappedThenDeleted,
+    Wrapped,
+}
+
+/// A dynamic field (or any other child object) accessed through its parent.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicFieldAccess {
+    pub parent: ObjectID,
+    pub field_id: ObjectID,
+    /// The version and type of the field object as it was loaded, or `None` if the field did
+    /// not exist when it was looked up.
+    #[schemars(with = "Option<AsSequenceNumber>")]
+    #[serde_as(as = "Option<AsSequenceNumber>")]
+    pub version: Option<SequenceNumber>,
+    pub object_type: Option<String>,
+}
+
+impl ExecutionTrace {
+    /// Fill in the objects accessed by the transaction, and the dynamic fields it added, given
+    /// the contents of the temporary store it executed against.
+    pub fn record_object_accesses(
+        &mut self,
+        input_objects: &BTreeMap<ObjectID, Object>,
+        runtime_read_objects: &BTreeMap<ObjectID, Object>,
+        written: &WrittenObjects,
+        deleted: &BTreeMap<ObjectID, (SequenceNumber, DeleteKind)>,
+    ) {
+        let reads = input_objects
+            .values()
+            .chain(runtime_read_objects.values())
+            .map(|obj| ObjectAccess {
+                object_id: obj.id(),
+                version: obj.version(),
+                object_type: obj.type_().map(|t| t.to_string()),
+                kind: ObjectAccessKind::Read,
+                storage_cost: None,
+            });
+
+        let writes = written.values().map(|(_, obj, kind)| ObjectAccess {
+            object_id: obj.id(),
+            version: obj.version(),
+            object_type: obj.type_().map(|t| t.to_string()),
+            kind: match kind {
+                WriteKind::Create => ObjectAccessKind::Created,
+                WriteKind::Mutate => ObjectAccessKind::Mutated,
+                WriteKind::Unwrap => ObjectAccessKind::Unwrapped,
+            },
+            storage_cost: Some(obj.storage_rebate),
+        });
+
+        let deletes = deleted.iter().map(|(id, (version, kind))| ObjectAccess {
+            object_id: *id,
+            version: *version,
+            object_type: None,
+            kind: match kind {
+                DeleteKind::Normal => ObjectAccessKind::Deleted,
+                DeleteKind::UnwrapThenDelete => ObjectAccessKind::UnwrappedThenDeleted,
+                DeleteKind::Wrap => ObjectAccessKind::Wrapped,
+            },
+            storage_cost: None,
+        });
+
+        self.objects = reads.chain(writes).chain(deletes).collect();
+
+        // Fields added by the transaction are never loaded through their parent, so they are
+        // only visible as writes of objects owned by another object.
+        for (id, (_, obj, _)) in written {
+            let Owner::ObjectOwner(parent) = obj.owner else {
+                continue;
+            };
+            if self.dynamic_fields.iter().any(|f| f.field_id == *id) {
+                continue;
+            }
+            self.dynamic_fields.push(DynamicFieldAccess {
+                parent: parent.into(),
+                field_id: *id,
+                version: None,
+                object_type: obj.type_().map(|t| t.to_string()),
+            })
+        }
+    }
+
+    /// Render the gas charged by the transaction as a profile in the folded stack format
+    /// (`frame;frame;frame weight` per line), which can be loaded into speedscope or rendered
+    /// with flamegraph tools.
+    ///
+    /// Computation is attributed to the Move function that was executing when it was charged,
+    /// under a frame for the command that made the call. Storage is attributed per object, as
+    /// `[storage];<type>` under the call stack that last created, transferred or added it as a
+    /// child object. Objects only mutated through a reference, or written by commands outside
+    /// of Move calls, are attributed to a top-level `[storage]` frame instead.
+    ///
+    /// Weights are in MIST, with computation converted at `gas_price`. They are taken before
+    /// computation is rounded up to the next gas bucket, and storage rebates are not included,
+    /// so they do not add up exactly to the gas charged.
+    pub fn folded_gas_profile(&self, gas_price: u64) -> String {
+        let mut lines = vec![];
+        let mut writers = BTreeMap::new();
+        for command in &self.commands {
+            let mut stack = vec![format!("[command {}]", command.command)];
+            for call in &command.calls {
+                fold_call(call, gas_price, &mut stack, &mut lines, &mut writers);
+            }
+        }
+        for object in &self.objects {
+            let Some(storage_cost) = object.storage_cost else {
+                continue;
+            };
+            let object_type = object.object_type.as_deref().unwrap_or("[package]");
+            match writers.get(&object.object_id) {
+                Some(stack) => {
+                    lines.push(format!("{stack};[storage];{object_type} {storage_cost}"))
+                }
+                None => lines.push(format!("[storage];{object_type} {storage_cost}")),
+            }
+        }
+        lines.retain(|line| !line.ends_with(" 0"));
+        let mut profile = lines.join("\n");
+        profile.push('\n');
+        profile
+    }
+}
+
+/// Append a line for the gas charged by `call` itself (excluding its callees) to `lines`, record
+/// the stack of the objects it wrote in `writers`, and recurse into its callees. Calls are
+/// visited in execution order, and only natives (which make no calls) write objects, so the last
+/// stack recorded for an object is the last one that wrote it.
+fn fold_call(
+    call: &MoveCallTrace,
+    gas_price: u64,
+    stack: &mut Vec<String>,
+    lines: &mut Vec<String>,
+    writers: &mut BTreeMap<ObjectID, String>,
+) {
+    let MoveFrame {
+        package,
+        module,
+        function,
+        ..
+    } = &call.frame;
+    stack.push(format!(
+        "{}::{module}::{function}",
+        package.to_hex_literal()
+    ));
+    let callees_gas: u64 = call.calls.iter().map(|c| c.gas_used).sum();
+    let self_gas = call.gas_used.saturating_sub(callees_gas);
+    // Internal gas units are 1/1000th of a gas unit
+    let self_cost = (self_gas as u128 * gas_price as u128 / 1000) as u64;
+    let folded = stack.join(";");
+    for id in &call.objects_written {
+        writers.insert(*id, folded.clone());
+    }
+    lines.push(format!("{folded} {self_cost}"));
+    for callee in &call.calls {
+        fold_call(callee, gas_price, stack, lines, writers);
+    }
+    stack.pop();
+}
@@ -40,6 +40,7 @@ pub mod effects;
 pub mod event;
 pub mod executable_transaction;
 pub mod execution_status;
+pub mod execution_trace;
 pub mod gas;
 pub mod gas_coin;
 pub mod gas_model;
@@ -8,6 +8,7 @@ use crate::digests::{
 };
 use crate::effects::{TransactionEffects, TransactionEvents};
 use crate::error::SuiError;
+use crate::execution_trace::ExecutionTrace;
 use crate::message_envelope::Message;
 use crate::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
@@ -80,6 +81,9 @@ pub trait Storage {
         &mut self,
         loaded_child_objects: BTreeMap<ObjectID, SequenceNumber>,
     );
+
+    /// Save the trace collected while executing in a tracing execution mode
+    fn save_execution_trace(&mut self, execution_trace: ExecutionTrace);
 }
 
 pub type PackageFetchResults<Package> = Result<Vec<Package>, Vec<ObjectID>>;
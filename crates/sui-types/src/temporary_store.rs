@@ -17,6 +17,7 @@ use tracing::trace;
 use crate::committee::EpochId;
 use crate::effects::{TransactionEffects, TransactionEvents};
 use crate::execution_status::ExecutionStatus;
+use crate::execution_trace::ExecutionTrace;
 use crate::layout_resolver::LayoutResolver;
 use crate::storage::ObjectStore;
 use crate::sui_system_state::{
@@ -57,6 +58,8 @@ pub struct InnerTemporaryStore {
     pub max_binary_format_version: u32,
     pub no_extraneous_module_bytes: bool,
     pub runtime_read_objects: BTreeMap<ObjectID, Object>,
+    /// Only populated when executing in a tracing execution mode
+    pub execution_trace: Option<ExecutionTrace>,
 }
 
 impl InnerTemporaryStore {
@@ -165,6 +168,9 @@ pub struct TemporaryStore<S> {
 
     // Every object that was read from store during exec
     runtime_read_objects: RwLock<BTreeMap<ObjectID, Object>>,
+
+    /// Trace of the execution, if it was executed in a tracing execution mode
+    execution_trace: Option<ExecutionTrace>,
 }
 
 impl<S> TemporaryStore<S> {
@@ -193,6 +199,7 @@ impl<S> TemporaryStore<S> {
             protocol_config: protocol_config.clone(),
             loaded_child_objects: BTreeMap::new(),
             runtime_read_objects: RwLock::new(BTreeMap::new()),
+            execution_trace: None,
         }
     }
 
@@ -225,6 +232,7 @@ impl<S> TemporaryStore<S> {
             protocol_config: protocol_config.clone(),
             loaded_child_objects: BTreeMap::new(),
             runtime_read_objects: RwLock::new(BTreeMap::new()),
+            execution_trace: None,
         }
     }
 
@@ -286,6 +294,17 @@ impl<S> TemporaryStore<S> {
             deleted.insert(id, (version, kind));
         }
 
+        let runtime_read_objects = self.runtime_read_objects.read().clone();
+        let execution_trace = self.execution_trace.map(|mut trace| {
+            trace.record_object_accesses(
+                &self.input_objects,
+                &runtime_read_objects,
+                &written,
+                &deleted,
+            );
+            trace
+        });
+
         // Combine object events with move events.
 
         InnerTemporaryStore {
@@ -297,7 +316,8 @@ impl<S> TemporaryStore<S> {
             max_binary_format_version: self.protocol_config.move_binary_format_version(),
             loaded_child_objects: self.loaded_child_objects,
             no_extraneous_module_bytes: self.protocol_config.no_extraneous_module_bytes(),
-            runtime_read_objects: self.runtime_read_objects.read().clone(),
+            runtime_read_objects,
+            execution_trace,
         }
     }
 
@@ -591,6 +611,10 @@ impl<S> TemporaryStore<S> {
         self.loaded_child_objects = loaded_child_objects;
     }
 
+    pub fn save_execution_trace(&mut self, execution_trace: ExecutionTrace) {
+        self.execution_trace = Some(execution_trace);
+    }
+
     pub fn estimate_effects_size_upperbound(&self) -> usize {
         // In the worst case, the number of deps is equal to the number of input objects
         TransactionEffects::estimate_effects_size_upperbound(
@@ -1530,6 +1554,10 @@ impl<S: ChildObjectResolver> Storage for TemporaryStore<S> {
     ) {
         TemporaryStore::save_loaded_child_objects(self, loaded_child_objects)
     }
+
+    fn save_execution_trace(&mut self, execution_trace: ExecutionTrace) {
+        TemporaryStore::save_execution_trace(self, execution_trace)
+    }
 }
 
 impl<S: BackingPackageStore> BackingPackageStore for &TemporaryStore<S> {
@@ -11,7 +11,7 @@ use crate::crypto::{
 };
 use crate::digests::{CertificateDigest, SenderSignedDataDigest};
 use crate::message_envelope::{Envelope, Message, TrustedEnvelope, VerifiedEnvelope};
-use crate::messages_checkpoint::CheckpointTimestamp;
+use crate::messages_checkpoint::{CheckpointSequenceNumber, CheckpointTimestamp};
 use crate::messages_consensus::ConsensusCommitPrologue;
 use crate::object::{MoveObject, Object, Owner};
 use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
@@ -971,6 +971,24 @@ pub enum TransactionExpiration {
     /// Validators wont sign a transaction unless the expiration Epoch
     /// is greater than or equal to the current epoch
     Epoch(EpochId),
+    /// Validators wont sign a transaction once their clock is past the expiration
+    /// timestamp, in milliseconds since the Unix epoch, and drop its certificate if
+    /// it is sequenced by a consensus commit with a later timestamp
+    Timestamp(u64),
+    /// Validators wont sign a transaction once they have executed a checkpoint
+    /// with a sequence number greater than the expiration checkpoint, and drop its
+    /// certificate if such a checkpoint was certified before it was sequenced
+    Checkpoint(CheckpointSequenceNumber),
+}
+
+impl TransactionExpiration {
+    /// Whether the expiration requires `ProtocolConfig::transaction_expiration_bounds`
+    pub fn is_bound_by_time_or_checkpoint(&self) -> bool {
+        matches!(
+            self,
+            TransactionExpiration::Timestamp(_) | TransactionExpiration::Checkpoint(_)
+        )
+    }
 }
 
 #[enum_dispatch(TransactionDataAPI)]
@@ -1098,6 +1116,14 @@ impl TransactionData {
         })
     }
 
+    /// Sets the bound after which validators no longer sign the transaction
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        match &mut self {
+            TransactionData::V1(data) => data.expiration = expiration,
+        }
+        self
+    }
+
     pub fn new_with_gas_data(kind: TransactionKind, sender: SuiAddress, gas_data: GasData) -> Self {
         TransactionData::V1(TransactionDataV1 {
             kind,
@@ -1546,6 +1572,13 @@ impl TransactionDataAPI for TransactionDataV1 {
     // Keep all the logic for validity here, we need this for dry run where the gas
     // may not be provided and created "on the fly"
     fn validity_check_no_gas_check(&self, config: &ProtocolConfig) -> UserInputResult {
+        fp_ensure!(
+            config.transaction_expiration_bounds()
+                || !self.expiration.is_bound_by_time_or_checkpoint(),
+            UserInputError::Unsupported(
+                "transaction expiration by timestamp or checkpoint is not enabled".to_string()
+            )
+        );
         self.kind().validity_check(config)?;
         self.check_sponsorship()
     }
@@ -1785,6 +1818,19 @@ impl<S> Envelope<SenderSignedData, S> {
         self.shared_input_objects().next().is_some()
     }
 
+    /// Whether a certificate of the transaction can only be executed once consensus has sequenced
+    /// it, either to assign its shared object versions, or to check its expiration at a timestamp
+    /// or checkpoint
+    pub fn requires_sequencing(&self) -> bool {
+        self.contains_shared_object()
+            || self
+                .data()
+                .intent_message()
+                .value
+                .expiration()
+                .is_bound_by_time_or_checkpoint()
+    }
+
     pub fn shared_input_objects(&self) -> impl Iterator<Item = SharedInputObject> + '_ {
         self.data()
             .inner()
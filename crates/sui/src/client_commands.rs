@@ -4,6 +4,7 @@
 use core::fmt;
 use std::{
     fmt::{Debug, Display, Formatter, Write},
+    fs,
     path::PathBuf,
     sync::Arc,
 };
@@ -35,8 +36,9 @@ use sui_verifier::meter::SuiVerifierMeter;
 use shared_crypto::intent::Intent;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiData, SuiObjectResponse, SuiObjectResponseQuery, SuiRawData,
-    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, DynamicFieldPage, SuiData,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiRawData, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
 use sui_keys::keystore::AccountKeystore;
@@ -47,11 +49,15 @@ use sui_move_build::{
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
 use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::SuiClient;
+use sui_types::committee::EpochId;
 use sui_types::crypto::SignatureScheme;
 use sui_types::dynamic_field::DynamicFieldType;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::UpgradeCap;
 use sui_types::signature::GenericSignature;
-use sui_types::transaction::{SenderSignedData, TransactionData, TransactionDataAPI};
+use sui_types::transaction::{
+    SenderSignedData, TransactionData, TransactionDataAPI, TransactionExpiration, TransactionKind,
+};
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
     gas_coin::GasCoin,
@@ -61,6 +67,8 @@ use sui_types::{
 };
 use tracing::info;
 
+use crate::client_ptb::{parse_ptb_commands, PtbBuilder};
+
 macro_rules! serialize_or_execute {
     ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident) => {{
         assert!(
@@ -70,16 +78,25 @@ macro_rules! serialize_or_execute {
         if $serialize_unsigned {
             SuiClientCommandResult::SerializedUnsignedTransaction($tx_data)
         } else {
-            let signature = $context.config.keystore.sign_secure(
-                &$tx_data.sender(),
-                &$tx_data,
-                Intent::sui_transaction(),
-            )?;
-            let sender_signed_data = SenderSignedData::new_from_sender_signature(
-                $tx_data,
-                Intent::sui_transaction(),
-                signature,
-            );
+            $context.unlock_keystore()?;
+            // Sponsored transactions are signed by both the sender and the gas owner.
+            let mut signatures = Vec::new();
+            for signer in $tx_data.signers() {
+                let signature = $context
+                    .config
+                    .keystore
+                    .sign_secure(&signer, &$tx_data, Intent::sui_transaction())
+                    .map_err(|e| {
+                        anyhow!(
+                            "Cannot sign the transaction as [{signer}]: {e}. Use \
+                            --serialize-unsigned-transaction and execute-signed-tx to collect \
+                            the signatures of the sender and the sponsor separately"
+                        )
+                    })?;
+                signatures.push(signature.into());
+            }
+            let sender_signed_data =
+                SenderSignedData::new($tx_data, Intent::sui_transaction(), signatures);
             if $serialize_signed {
                 SuiClientCommandResult::SerializedSignedTransaction(sender_signed_data)
             } else {
@@ -334,6 +351,9 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas_budget: u64,
 
+        #[clap(flatten)]
+        expiration: ExpirationArgs,
+
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
         #[clap(long, required = false)]
@@ -603,18 +623,162 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Build and execute a programmable transaction block from a sequence of commands.
+    /// Each command is a JSON object such as
+    /// `{"assign": "coins", "split_coins": {"coin": "$gas", "amounts": [1000]}}`,
+    /// where `$gas` refers to the gas coin and `$name` (or `$name.i`) to the result of an
+    /// earlier command bound with `assign`. Supported commands are `move_call`, `split_coins`,
+    /// `merge_coins`, `transfer_objects`, `make_move_vec`, `publish` and `upgrade`.
+    #[clap(name = "ptb")]
+    Ptb {
+        /// Path to a JSON file containing the list of commands
+        #[clap(long, parse(from_os_str), conflicts_with = "commands")]
+        file: Option<PathBuf>,
+
+        /// A command of the transaction block, as a JSON object. Can be repeated, the commands
+        /// are executed in the order they are given.
+        #[clap(long = "command", multiple_occurrences = true)]
+        commands: Vec<String>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long, conflicts_with = "dev-inspect")]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction block, required unless it is dev-inspected
+        #[clap(
+            long,
+            required_unless_present = "dev-inspect",
+            conflicts_with = "dev-inspect"
+        )]
+        gas_budget: Option<u64>,
+
+        #[clap(flatten)]
+        expiration: ExpirationArgs,
+
+        #[clap(flatten)]
+        sponsor: SponsorArgs,
+
+        /// Instead of executing the transaction, run it in dry-run mode and print its effects.
+        #[clap(long, conflicts_with = "dev-inspect")]
+        dry_run: bool,
+
+        /// Instead of executing the transaction, run it in dev-inspect mode and print its effects
+        /// along with the return values of each command. Dev-inspect does not charge gas, so
+        /// --gas and --gas-budget cannot be given.
+        #[clap(long)]
+        dev_inspect: bool,
+
+        /// When dev-inspecting, also print a trace of the Move calls made, the gas they used, and
+        /// the objects and dynamic fields accessed.
+        #[clap(long, requires = "dev-inspect")]
+        trace: bool,
+
+        /// When dev-inspecting, write a profile of the gas charged by the transaction to this
+        /// path, in the folded stack format read by speedscope and flamegraph tools. Computation
+        /// and the storage of the objects written are attributed to Move call stacks.
+        #[clap(long, parse(from_os_str), requires = "dev-inspect")]
+        gas_profile: Option<PathBuf>,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
+        /// (TransactionData) using base64 encoding, and print out the string.
+        #[clap(long, alias = "serialize-unsigned", required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the signed transaction data
+        /// (SenderSignedData) using base64 encoding, and print out the string.
+        #[clap(long, alias = "serialize-signed", required = false)]
+        serialize_signed_transaction: bool,
+    },
+
+    /// Make another address pay for the gas of an unsigned transaction, e.g. one printed with
+    /// --serialize-unsigned-transaction, and print the resulting unsigned transaction. It must
+    /// then be signed by both the sender and the sponsor (`sui keytool sign`, combined with
+    /// `sui keytool multi-sig-combine-partial-sig` for a multisig) and executed with
+    /// execute-signed-tx.
+    SponsorTx {
+        /// BCS serialized unsigned transaction data bytes, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+
+        /// Address paying for the gas of the transaction.
+        #[clap(long)]
+        sponsor: SuiAddress,
+
+        /// IDs of the sponsor's gas objects for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object of the sponsor with at least gas_budget value will be selected
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        gas: Vec<ObjectID>,
+
+        /// Gas budget for the transaction, defaults to the budget it already has
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
     /// Execute a Signed Transaction. This is useful when the user prefers to sign elsewhere and use this command to execute.
     ExecuteSignedTx {
         /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
         #[clap(long)]
         tx_bytes: String,
 
-        /// A list of Base64 encoded signatures `flag || signature || pubkey`.
-        #[clap(long)]
+        /// A list of Base64 encoded signatures `flag || signature || pubkey`, one for each signer
+        /// of the transaction: the sender and, for a sponsored transaction, the sponsor.
+        #[clap(long, multiple_values = true)]
         signatures: Vec<String>,
     },
 }
 
+/// Bound after which validators no longer sign the transaction, at most one can be given.
+#[derive(Args, Debug, Default)]
+#[clap(rename_all = "kebab-case")]
+pub struct ExpirationArgs {
+    /// Expire the transaction after this epoch
+    #[clap(long, conflicts_with_all = &["expiration-timestamp-ms", "expiration-checkpoint"])]
+    pub expiration_epoch: Option<EpochId>,
+    /// Expire the transaction once the validators' clocks are past this timestamp, in
+    /// milliseconds since the Unix epoch
+    #[clap(long, conflicts_with = "expiration-checkpoint")]
+    pub expiration_timestamp_ms: Option<u64>,
+    /// Expire the transaction once validators have executed a checkpoint with a higher
+    /// sequence number
+    #[clap(long)]
+    pub expiration_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// Address other than the sender paying for the gas of the transaction.
+#[derive(Args, Debug, Default)]
+#[clap(rename_all = "kebab-case")]
+pub struct SponsorArgs {
+    /// Address paying for the gas of the transaction, which must then be signed by both the
+    /// sender and the sponsor
+    #[clap(long, conflicts_with = "gas")]
+    pub sponsor: Option<SuiAddress>,
+    /// IDs of the sponsor's gas objects for gas payment, in 20 bytes Hex string
+    /// If not provided, a gas object of the sponsor with at least gas_budget value will be selected
+    #[clap(
+        long,
+        requires = "sponsor",
+        multiple_occurrences = false,
+        multiple_values = true
+    )]
+    pub sponsor_gas: Vec<ObjectID>,
+}
+
+impl ExpirationArgs {
+    fn apply(&self, data: TransactionData) -> TransactionData {
+        let expiration = if let Some(epoch) = self.expiration_epoch {
+            TransactionExpiration::Epoch(epoch)
+        } else if let Some(timestamp_ms) = self.expiration_timestamp_ms {
+            TransactionExpiration::Timestamp(timestamp_ms)
+        } else if let Some(checkpoint) = self.expiration_checkpoint {
+            TransactionExpiration::Checkpoint(checkpoint)
+        } else {
+            return data;
+        };
+        data.with_expiration(expiration)
+    }
+}
+
 impl SuiClientCommands {
     pub async fn execute(
         self,
@@ -843,6 +1007,7 @@ impl SuiClientCommands {
                 type_args,
                 gas,
                 gas_budget,
+                expiration,
                 args,
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
@@ -851,6 +1016,7 @@ impl SuiClientCommands {
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
                 .await?;
+                let tx_data = expiration.apply(tx_data);
                 serialize_or_execute!(
                     tx_data,
                     serialize_unsigned_transaction,
@@ -1013,6 +1179,94 @@ impl SuiClientCommands {
                 )
             }
 
+            SuiClientCommands::Ptb {
+                file,
+                commands,
+                gas,
+                gas_budget,
+                expiration,
+                sponsor,
+                dry_run,
+                dev_inspect,
+                trace,
+                gas_profile,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                ensure!(
+                    !(dry_run || dev_inspect)
+                        || !(serialize_unsigned_transaction || serialize_signed_transaction),
+                    "Cannot serialize a transaction that is only dry-run or dev-inspected"
+                );
+                let specs = parse_ptb_commands(file, commands)?;
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+
+                let client = context.get_client().await?;
+                let pt = PtbBuilder::new(&client).build(specs).await?;
+
+                if dev_inspect {
+                    let gas_price = client.read_api().get_reference_gas_price().await?;
+                    let mut results = client
+                        .read_api()
+                        .dev_inspect_transaction_block(
+                            sender,
+                            TransactionKind::programmable(pt),
+                            Some(gas_price.into()),
+                            None,
+                            (trace || gas_profile.is_some()).then(DevInspectArgs::with_trace),
+                        )
+                        .await?;
+                    if let Some(path) = gas_profile {
+                        let profile = results
+                            .trace
+                            .as_ref()
+                            .ok_or_else(|| anyhow!("No execution trace returned by the node"))?
+                            .folded_gas_profile(gas_price);
+                        fs::write(&path, profile)?;
+                        if !trace {
+                            results.trace = None;
+                        }
+                    }
+                    SuiClientCommandResult::DevInspect(results)
+                } else {
+                    let gas_budget =
+                        gas_budget.ok_or_else(|| anyhow!("--gas-budget is required"))?;
+                    let builder = client.transaction_builder();
+                    let data = match sponsor.sponsor {
+                        Some(gas_owner) => {
+                            builder
+                                .sponsored_programmable_transaction(
+                                    sender,
+                                    pt,
+                                    gas_owner,
+                                    sponsor.sponsor_gas,
+                                    gas_budget,
+                                )
+                                .await?
+                        }
+                        None => {
+                            builder
+                                .programmable_transaction(sender, pt, gas, gas_budget)
+                                .await?
+                        }
+                    };
+                    let data = expiration.apply(data);
+                    if dry_run {
+                        let response = client.read_api().dry_run_transaction_block(data).await?;
+                        SuiClientCommandResult::DryRun(response)
+                    } else {
+                        serialize_or_execute!(
+                            data,
+                            serialize_unsigned_transaction,
+                            serialize_signed_transaction,
+                            context,
+                            Ptb
+                        )
+                    }
+                }
+            }
+
             SuiClientCommands::Addresses => SuiClientCommandResult::Addresses(
                 context.config.keystore.addresses(),
                 context.active_address().ok(),
@@ -1051,6 +1305,7 @@ impl SuiClientCommands {
                 derivation_path,
                 word_length,
             } => {
+                context.unlock_keystore()?;
                 let (address, phrase, scheme) = context.config.keystore.generate_and_add_new_key(
                     key_scheme,
                     derivation_path,
@@ -1150,11 +1405,32 @@ impl SuiClientCommands {
                 SuiClientCommandResult::ActiveAddress(context.active_address().ok())
             }
 
+            SuiClientCommands::SponsorTx {
+                tx_bytes,
+                sponsor,
+                gas,
+                gas_budget,
+            } => {
+                let data: TransactionData = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )?;
+                let data = context
+                    .get_client()
+                    .await?
+                    .transaction_builder()
+                    .sponsor(data, sponsor, gas, gas_budget)
+                    .await?;
+                SuiClientCommandResult::SerializedUnsignedTransaction(data)
+            }
+
             SuiClientCommands::ExecuteSignedTx {
                 tx_bytes,
                 signatures,
             } => {
-                let data = bcs::from_bytes(
+                let data: TransactionData = bcs::from_bytes(
                     &Base64::try_from(tx_bytes)
                         .map_err(|e| anyhow!(e))?
                         .to_vec()
@@ -1173,6 +1449,22 @@ impl SuiClientCommands {
                         .map_err(|e| anyhow!(e))?,
                     );
                 }
+                let signers = data.signers();
+                ensure!(
+                    sigs.len() == signers.len(),
+                    "The transaction must be signed by {} ({}), but {} signature(s) were provided",
+                    if signers.len() == 1 {
+                        "its sender"
+                    } else {
+                        "its sender and its sponsor"
+                    },
+                    signers
+                        .iter()
+                        .map(|signer| signer.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    sigs.len()
+                );
                 let verified =
                     Transaction::from_generic_sig_data(data, Intent::sui_transaction(), sigs)
                         .verify()?;
@@ -1270,7 +1562,7 @@ fn compile_package_simple(
     )?)
 }
 
-async fn compile_package(
+pub(crate) async fn compile_package(
     client: &SuiClient,
     build_config: MoveBuildConfig,
     package_path: PathBuf,
@@ -1405,6 +1697,35 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::PayAllSui(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::Ptb(response) => {
+                write!(writer, "{}", write_transaction_response(response)?)?;
+            }
+            SuiClientCommandResult::DryRun(response) => {
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", response.effects)?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.events))?;
+                writeln!(writer, "{}", "----- Object changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.object_changes))?;
+                writeln!(writer, "{}", "----- Balance changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.balance_changes))?;
+            }
+            SuiClientCommandResult::DevInspect(results) => {
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", results.effects)?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(results.events))?;
+                writeln!(writer, "{}", "----- Execution Results ----".bold())?;
+                writeln!(writer, "{:#?}", json!(results.results))?;
+                if let Some(trace) = &results.trace {
+                    writeln!(writer, "{}", "----- Execution Trace ----".bold())?;
+                    writeln!(writer, "{:#?}", json!(trace))?;
+                }
+                if let Some(error) = &results.error {
+                    writeln!(writer, "{}", "----- Execution Error ----".bold())?;
+                    writeln!(writer, "{}", error.red())?;
+                }
+            }
             SuiClientCommandResult::Addresses(addresses, active_address) => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
                 for address in addresses {
@@ -1611,7 +1932,7 @@ async fn construct_move_call_transaction(
         .await
 }
 
-fn convert_number_to_string(value: Value) -> Value {
+pub(crate) fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
         Value::Array(a) => Value::Array(a.into_iter().map(convert_number_to_string).collect()),
@@ -1702,7 +2023,7 @@ impl SuiClientCommandResult {
         match self {
             Upgrade(b) | Publish(b) | TransactionBlock(b) | Call(b) | Transfer(b)
             | TransferSui(b) | Pay(b) | PaySui(b) | PayAllSui(b) | SplitCoin(b) | MergeCoin(b)
-            | ExecuteSignedTx(b) => Some(b),
+            | Ptb(b) | ExecuteSignedTx(b) => Some(b),
             _ => None,
         }
     }
@@ -1754,6 +2075,9 @@ pub enum SuiClientCommandResult {
     Envs(Vec<SuiEnv>, Option<String>),
     ExecuteSignedTx(SuiTransactionBlockResponse),
     NewEnv(SuiEnv),
+    Ptb(SuiTransactionBlockResponse),
+    DryRun(DryRunTransactionBlockResponse),
+    DevInspect(DevInspectResults),
 }
 
 #[derive(Serialize, Clone, Debug)]
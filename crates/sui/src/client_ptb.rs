@@ -0,0 +1,545 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for `sui client ptb`: building a programmable transaction block from a sequence of
+//! commands written on the command line (or in a JSON file).
+//!
+//! Each command is a JSON object naming one of the programmable transaction commands, optionally
+//! with an `assign` key that binds its result to a name, e.g.
+//!
+//! ```json
+//! [
+//!   { "assign": "coins", "split_coins": { "coin": "$gas", "amounts": [1000, 2000] } },
+//!   { "transfer_objects": { "objects": ["$coins.0", "$coins.1"], "address": "0x42" } }
+//! ]
+//! ```
+//!
+//! Arguments are written as JSON values. Strings starting with `$` refer to the gas coin
+//! (`$gas`), to the result of an earlier command (`$name`), or to one of the results of a Move
+//! call returning several values (`$name.1`). All other values are literals: object IDs where the
+//! command expects an object, and pure values otherwise. `make_move_vec` expects objects unless
+//! its `type` is a primitive type (e.g. `u64`, `address` or `vector<u8>`).
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use move_core_types::language_storage::TypeTag;
+use move_core_types::value::MoveTypeLayout;
+use move_package::BuildConfig as MoveBuildConfig;
+use serde::Deserialize;
+use serde_json::Value;
+
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTypeTag};
+use sui_sdk::SuiClient;
+use sui_transaction_builder::{MoveCallArg, TransactionBuilder};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::move_package::UpgradeCap;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, Command, ProgrammableTransaction};
+use sui_types::{parse_sui_type_tag, Identifier, SUI_FRAMEWORK_PACKAGE_ID};
+
+use crate::client_commands::{compile_package, convert_number_to_string};
+
+const GAS_COIN_ARG: &str = "gas";
+
+/// A command of a programmable transaction block, as written by the user.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PtbCommandSpec {
+    /// Name under which the result of this command can be referred to by later commands.
+    #[serde(default)]
+    pub assign: Option<String>,
+    #[serde(flatten)]
+    pub command: PtbCommand,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PtbCommand {
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<Value>,
+    },
+    SplitCoins {
+        coin: Value,
+        amounts: Vec<Value>,
+    },
+    MergeCoins {
+        coin: Value,
+        coins: Vec<Value>,
+    },
+    TransferObjects {
+        objects: Vec<Value>,
+        address: Value,
+    },
+    MakeMoveVec {
+        #[serde(default, rename = "type")]
+        type_: Option<String>,
+        elements: Vec<Value>,
+    },
+    Publish {
+        package_path: PathBuf,
+        #[serde(default)]
+        with_unpublished_dependencies: bool,
+        #[serde(default)]
+        skip_dependency_verification: bool,
+    },
+    Upgrade {
+        package_path: PathBuf,
+        upgrade_capability: ObjectID,
+        #[serde(default)]
+        with_unpublished_dependencies: bool,
+        #[serde(default)]
+        skip_dependency_verification: bool,
+        #[serde(default)]
+        legacy_digest: bool,
+    },
+}
+
+impl PtbCommand {
+    fn name(&self) -> &'static str {
+        match self {
+            PtbCommand::MoveCall { .. } => "move_call",
+            PtbCommand::SplitCoins { .. } => "split_coins",
+            PtbCommand::MergeCoins { .. } => "merge_coins",
+            PtbCommand::TransferObjects { .. } => "transfer_objects",
+            PtbCommand::MakeMoveVec { .. } => "make_move_vec",
+            PtbCommand::Publish { .. } => "publish",
+            PtbCommand::Upgrade { .. } => "upgrade",
+        }
+    }
+
+    /// Whether the command produces a result that later commands can refer to.
+    fn has_result(&self) -> bool {
+        !matches!(
+            self,
+            PtbCommand::MergeCoins { .. }
+                | PtbCommand::TransferObjects { .. }
+                | PtbCommand::Upgrade { .. }
+        )
+    }
+}
+
+/// A parsed command argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbArg {
+    /// `$gas`
+    GasCoin,
+    /// `$name`
+    Result(String),
+    /// `$name.index`
+    NestedResult(String, u16),
+    /// Any other JSON value.
+    Value(Value),
+}
+
+impl PtbArg {
+    pub fn parse(value: Value) -> Result<Self, anyhow::Error> {
+        let Value::String(s) = &value else {
+            return Ok(PtbArg::Value(value));
+        };
+        let Some(reference) = s.strip_prefix('$') else {
+            return Ok(PtbArg::Value(value));
+        };
+        if reference == GAS_COIN_ARG {
+            return Ok(PtbArg::GasCoin);
+        }
+        let (name, index) = match reference.split_once('.') {
+            Some((name, index)) => (
+                name,
+                Some(
+                    index
+                        .parse::<u16>()
+                        .map_err(|e| anyhow!("Invalid result index in [{s}]: {e}"))?,
+                ),
+            ),
+            None => (reference, None),
+        };
+        ensure!(
+            Identifier::is_valid(name),
+            "Invalid result name in [{s}], names must be valid identifiers"
+        );
+        Ok(match index {
+            Some(index) => PtbArg::NestedResult(name.to_string(), index),
+            None => PtbArg::Result(name.to_string()),
+        })
+    }
+}
+
+/// Parse the commands of a programmable transaction block, either from a JSON file containing a
+/// list of commands or from a list of JSON objects given on the command line.
+pub fn parse_ptb_commands(
+    file: Option<PathBuf>,
+    commands: Vec<String>,
+) -> Result<Vec<PtbCommandSpec>, anyhow::Error> {
+    let specs: Vec<PtbCommandSpec> = match file {
+        Some(path) => {
+            ensure!(
+                commands.is_empty(),
+                "Cannot specify both --file and --command"
+            );
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| anyhow!("Cannot read PTB file {}: {e}", path.display()))?;
+            serde_json::from_str(&content)
+                .map_err(|e| anyhow!("Cannot parse PTB file {}: {e}", path.display()))?
+        }
+        None => commands
+            .iter()
+            .map(|command| {
+                serde_json::from_str(command)
+                    .map_err(|e| anyhow!("Cannot parse PTB command [{command}]: {e}"))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    ensure!(
+        !specs.is_empty(),
+        "A programmable transaction block requires at least one command"
+    );
+    Ok(specs)
+}
+
+/// Builds a `ProgrammableTransaction` from a list of commands, resolving objects and Move function
+/// signatures through the RPC client.
+pub struct PtbBuilder<'a> {
+    client: &'a SuiClient,
+    tx_builder: TransactionBuilder,
+    builder: ProgrammableTransactionBuilder,
+    results: BTreeMap<String, Argument>,
+}
+
+impl<'a> PtbBuilder<'a> {
+    pub fn new(client: &'a SuiClient) -> Self {
+        Self {
+            client,
+            tx_builder: client.transaction_builder().clone(),
+            builder: ProgrammableTransactionBuilder::new(),
+            results: BTreeMap::new(),
+        }
+    }
+
+    pub async fn build(
+        mut self,
+        specs: Vec<PtbCommandSpec>,
+    ) -> Result<ProgrammableTransaction, anyhow::Error> {
+        for (idx, PtbCommandSpec { assign, command }) in specs.into_iter().enumerate() {
+            let name = command.name();
+            if let Some(assign) = &assign {
+                ensure!(
+                    command.has_result(),
+                    "Command {idx} ({name}) has no result that can be assigned to [{assign}]"
+                );
+                ensure!(
+                    Identifier::is_valid(assign) && assign != GAS_COIN_ARG,
+                    "Invalid name [{assign}] for the result of command {idx}"
+                );
+                ensure!(
+                    !self.results.contains_key(assign),
+                    "Result name [{assign}] is already used by an earlier command"
+                );
+            }
+            let result = self
+                .command(command)
+                .await
+                .map_err(|e| anyhow!("Error in command {idx} ({name}): {e}"))?;
+            if let Some(assign) = assign {
+                self.results.insert(assign, result);
+            }
+        }
+        Ok(self.builder.finish())
+    }
+
+    async fn command(&mut self, command: PtbCommand) -> Result<Argument, anyhow::Error> {
+        Ok(match command {
+            PtbCommand::MoveCall {
+                package,
+                module,
+                function,
+                type_args,
+                args,
+            } => {
+                let type_args = type_args.into_iter().map(SuiTypeTag::new).collect();
+                let mut call_args = vec![];
+                for arg in args {
+                    call_args.push(match PtbArg::parse(arg)? {
+                        PtbArg::Value(value) => {
+                            MoveCallArg::Json(SuiJsonValue::new(convert_number_to_string(value))?)
+                        }
+                        arg => MoveCallArg::Argument(self.reference(&arg)?),
+                    });
+                }
+                self.tx_builder
+                    .programmable_move_call(
+                        &mut self.builder,
+                        package,
+                        &module,
+                        &function,
+                        type_args,
+                        call_args,
+                    )
+                    .await?
+            }
+            PtbCommand::SplitCoins { coin, amounts } => {
+                let coin = self.object_arg(coin, /* mutable */ true).await?;
+                let mut amount_args = vec![];
+                for amount in amounts {
+                    amount_args.push(self.pure_arg(amount, |v| parse_u64(&v))?);
+                }
+                self.builder.command(Command::SplitCoins(coin, amount_args))
+            }
+            PtbCommand::MergeCoins { coin, coins } => {
+                let coin = self.object_arg(coin, /* mutable */ true).await?;
+                let coins = self.object_args(coins, /* mutable */ true).await?;
+                self.builder.command(Command::MergeCoins(coin, coins))
+            }
+            PtbCommand::TransferObjects { objects, address } => {
+                ensure!(
+                    !objects.is_empty(),
+                    "transfer_objects requires at least one object"
+                );
+                let objects = self.object_args(objects, /* mutable */ true).await?;
+                let address = self.pure_arg(address, |v| parse_address(&v))?;
+                self.builder
+                    .command(Command::TransferObjects(objects, address))
+            }
+            PtbCommand::MakeMoveVec { type_, elements } => {
+                let type_: Option<TypeTag> = type_.map(|ty| parse_sui_type_tag(&ty)).transpose()?;
+                ensure!(
+                    type_.is_some() || !elements.is_empty(),
+                    "make_move_vec requires a type for an empty vector"
+                );
+                let elements = match type_.as_ref().and_then(pure_layout) {
+                    Some(layout) => {
+                        let mut args = vec![];
+                        for element in elements {
+                            args.push(self.pure_bytes_arg(element, |v| {
+                                SuiJsonValue::new(convert_number_to_string(v))?
+                                    .to_bcs_bytes(&layout)
+                            })?);
+                        }
+                        args
+                    }
+                    None => {
+                        if let Some(value) = elements.iter().find(|v| !v.is_string()) {
+                            bail!(
+                                "Expected an object ID, found {value}. A vector of pure values \
+                                 requires a primitive type"
+                            );
+                        }
+                        // Elements are taken by value, which needs shared objects to be mutable
+                        self.object_args(elements, /* mutable */ true).await?
+                    }
+                };
+                self.builder.command(Command::MakeMoveVec(type_, elements))
+            }
+            PtbCommand::Publish {
+                package_path,
+                with_unpublished_dependencies,
+                skip_dependency_verification,
+            } => {
+                let (dependencies, compiled_modules, _, _) = compile_package(
+                    self.client,
+                    MoveBuildConfig::default(),
+                    package_path,
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                )
+                .await?;
+                self.builder.publish_upgradeable(
+                    compiled_modules,
+                    dependencies.published.into_values().collect(),
+                )
+            }
+            PtbCommand::Upgrade {
+                package_path,
+                upgrade_capability,
+                with_unpublished_dependencies,
+                skip_dependency_verification,
+                legacy_digest,
+            } => {
+                self.upgrade(
+                    package_path,
+                    upgrade_capability,
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                    legacy_digest,
+                )
+                .await?
+            }
+        })
+    }
+
+    /// Authorize, perform and commit an upgrade of the package at `package_path`, using its
+    /// existing upgrade policy.
+    async fn upgrade(
+        &mut self,
+        package_path: PathBuf,
+        upgrade_capability: ObjectID,
+        with_unpublished_dependencies: bool,
+        skip_dependency_verification: bool,
+        legacy_digest: bool,
+    ) -> Result<Argument, anyhow::Error> {
+        let (dependencies, compiled_modules, compiled_package, package_id) = compile_package(
+            self.client,
+            MoveBuildConfig::default(),
+            package_path,
+            with_unpublished_dependencies,
+            skip_dependency_verification,
+        )
+        .await?;
+        let package_id = package_id.map_err(|e| {
+            anyhow!("Invalid or missing 'published-at' field in manifest for package to be upgraded: {e:?}")
+        })?;
+
+        let upgrade_cap: UpgradeCap = self
+            .client
+            .read_api()
+            .get_object_with_options(upgrade_capability, SuiObjectDataOptions::new().with_bcs())
+            .await?
+            .into_object()?
+            .bcs
+            .ok_or_else(|| anyhow!("Fetch upgrade capability object but no data was returned"))?
+            .try_as_move()
+            .ok_or_else(|| anyhow!("Upgrade capability is not a Move Object"))?
+            .deserialize()?;
+        let package_digest =
+            compiled_package.get_package_digest(with_unpublished_dependencies, !legacy_digest);
+
+        let cap = self
+            .tx_builder
+            .object_input(&mut self.builder, upgrade_capability, true)
+            .await?;
+        let policy = self.builder.pure(upgrade_cap.policy)?;
+        let digest = self.builder.pure(package_digest.to_vec())?;
+        let ticket = self.builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::from_str("package")?,
+            Identifier::from_str("authorize_upgrade")?,
+            vec![],
+            vec![cap, policy, digest],
+        );
+        let receipt = self.builder.upgrade(
+            package_id,
+            ticket,
+            dependencies.published.into_values().collect(),
+            compiled_modules,
+        );
+        Ok(self.builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::from_str("package")?,
+            Identifier::from_str("commit_upgrade")?,
+            vec![],
+            vec![cap, receipt],
+        ))
+    }
+
+    fn reference(&self, arg: &PtbArg) -> Result<Argument, anyhow::Error> {
+        let result = |name: &str| {
+            self.results
+                .get(name)
+                .copied()
+                .ok_or_else(|| anyhow!("Unknown result name [{name}]"))
+        };
+        Ok(match arg {
+            PtbArg::GasCoin => Argument::GasCoin,
+            PtbArg::Result(name) => result(name)?,
+            PtbArg::NestedResult(name, index) => match result(name)? {
+                Argument::Result(command) => Argument::NestedResult(command, *index),
+                _ => bail!("Result [{name}] does not refer to a command"),
+            },
+            PtbArg::Value(value) => bail!("{value} is not a reference to a result"),
+        })
+    }
+
+    /// An object argument. `mutable` tells whether a shared object is used mutably, and is
+    /// ignored for other objects.
+    async fn object_arg(&mut self, value: Value, mutable: bool) -> Result<Argument, anyhow::Error> {
+        match PtbArg::parse(value)? {
+            PtbArg::Value(value) => {
+                let id = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Expected an object ID, found {value}"))
+                    .and_then(|id| Ok(ObjectID::from_hex_literal(id)?))?;
+                self.tx_builder
+                    .object_input(&mut self.builder, id, mutable)
+                    .await
+            }
+            arg => self.reference(&arg),
+        }
+    }
+
+    async fn object_args(
+        &mut self,
+        values: Vec<Value>,
+        mutable: bool,
+    ) -> Result<Vec<Argument>, anyhow::Error> {
+        let mut args = vec![];
+        for value in values {
+            args.push(self.object_arg(value, mutable).await?);
+        }
+        Ok(args)
+    }
+
+    fn pure_arg<T: serde::Serialize>(
+        &mut self,
+        value: Value,
+        parse: impl FnOnce(Value) -> Result<T, anyhow::Error>,
+    ) -> Result<Argument, anyhow::Error> {
+        self.pure_bytes_arg(value, |v| Ok(bcs::to_bytes(&parse(v)?)?))
+    }
+
+    /// Like `pure_arg`, for values that `encode` serializes to BCS itself.
+    fn pure_bytes_arg(
+        &mut self,
+        value: Value,
+        encode: impl FnOnce(Value) -> Result<Vec<u8>, anyhow::Error>,
+    ) -> Result<Argument, anyhow::Error> {
+        match PtbArg::parse(value)? {
+            PtbArg::Value(value) => Ok(self.builder.pure_bytes(encode(value)?, false)),
+            arg => self.reference(&arg),
+        }
+    }
+}
+
+/// The layout of `type_` if it is a primitive type, whose values are passed as pure arguments.
+fn pure_layout(type_: &TypeTag) -> Option<MoveTypeLayout> {
+    Some(match type_ {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(pure_layout(inner)?)),
+        TypeTag::Struct(_) | TypeTag::Signer => return None,
+    })
+}
+
+fn parse_u64(value: &Value) -> Result<u64, anyhow::Error> {
+    match value {
+        Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| anyhow!("Expected a u64 value, found {n}")),
+        Value::String(s) => Ok(s.parse::<u64>()?),
+        _ => bail!("Expected a u64 value, found {value}"),
+    }
+}
+
+fn parse_address(value: &Value) -> Result<SuiAddress, anyhow::Error> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("Expected an address, found {value}"))
+        .and_then(|s| SuiAddress::from_str(s).map_err(|e| anyhow!(e)))
+}
+
+#[cfg(test)]
+#[path = "unit_tests/client_ptb_tests.rs"]
+mod client_ptb_tests;
@@ -12,6 +12,7 @@ use rusoto_kms::{Kms, KmsClient, SignRequest};
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::fs;
 use std::path::{Path, PathBuf};
+use sui_keys::encryption::KEYSTORE_NEW_PASSPHRASE_ENV;
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
@@ -103,6 +104,14 @@ pub enum KeyToolCommand {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
     },
+    /// Encrypt the keys in sui.keystore with a passphrase, which is read from
+    /// SUI_KEYSTORE_NEW_PASSPHRASE or prompted for. Public keys are left in the clear, so
+    /// addresses can still be listed without the passphrase.
+    EncryptKeystore,
+    /// Re-encrypt an encrypted sui.keystore with a new passphrase. The current passphrase is read
+    /// from SUI_KEYSTORE_PASSPHRASE and the new one from SUI_KEYSTORE_NEW_PASSPHRASE, or both are
+    /// prompted for.
+    ReencryptKeystore,
     /// Convert private key from wallet format (hex of 32 byte private key) to sui.keystore format
     /// (base64 of 33 byte flag || private key) or vice versa.
     Convert {
@@ -299,6 +308,32 @@ impl KeyToolCommand {
                 info!("Key imported for address [{address}]");
             }
 
+            KeyToolCommand::EncryptKeystore => match keystore {
+                Keystore::File(file) if file.is_encrypted() => {
+                    return Err(anyhow!(
+                        "Keystore is already encrypted, use `sui keytool reencrypt-keystore` to change its passphrase"
+                    ));
+                }
+                Keystore::File(file) => {
+                    file.encrypt(&read_new_keystore_passphrase()?)?;
+                    println!("Keystore encrypted");
+                }
+                _ => return Err(anyhow!("Only file based keystores can be encrypted")),
+            },
+
+            KeyToolCommand::ReencryptKeystore => match keystore {
+                Keystore::File(file) if file.is_encrypted() => {
+                    file.encrypt(&read_new_keystore_passphrase()?)?;
+                    println!("Keystore re-encrypted");
+                }
+                Keystore::File(_) => {
+                    return Err(anyhow!(
+                        "Keystore is not encrypted, use `sui keytool encrypt-keystore` to encrypt it"
+                    ));
+                }
+                _ => return Err(anyhow!("Only file based keystores can be encrypted")),
+            },
+
             KeyToolCommand::Convert { value } => match Base64::decode(&value) {
                 Ok(decoded) => {
                     assert_eq!(decoded.len(), 33);
@@ -546,3 +581,19 @@ fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
         path.to_str().unwrap()
     );
 }
+
+fn read_new_keystore_passphrase() -> Result<String, anyhow::Error> {
+    let passphrase = match std::env::var(KEYSTORE_NEW_PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => inquire::Password::new("New keystore passphrase:")
+            .with_custom_confirmation_message("Confirm new keystore passphrase:")
+            .prompt()
+            .map_err(|e| {
+                anyhow!("Cannot read the new keystore passphrase, set {KEYSTORE_NEW_PASSPHRASE_ENV} to provide it: {e}")
+            })?,
+    };
+    if passphrase.is_empty() {
+        return Err(anyhow!("Keystore passphrase cannot be empty"));
+    }
+    Ok(passphrase)
+}
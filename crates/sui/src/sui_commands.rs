@@ -26,7 +26,7 @@ use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_move::{self, execute_move_command};
 use sui_move_build::SuiPackageHooks;
 use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
-use sui_sdk::wallet_context::WalletContext;
+use sui_sdk::wallet_context::{read_keystore_passphrase, WalletContext};
 use sui_swarm::memory::Swarm;
 use sui_swarm_config::genesis_config::{GenesisConfig, DEFAULT_NUMBER_OF_AUTHORITIES};
 use sui_swarm_config::network_config::NetworkConfig;
@@ -250,6 +250,10 @@ impl SuiCommand {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
                 let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                // Listing keys does not need the private keys of an encrypted keystore
+                if keystore.is_locked() && !matches!(cmd, KeyToolCommand::List) {
+                    keystore.unlock(&read_keystore_passphrase()?)?;
+                }
                 cmd.execute(&mut keystore).await
             }
             SuiCommand::Console { config } => {
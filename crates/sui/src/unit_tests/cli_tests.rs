@@ -5,19 +5,24 @@ use std::io::Read;
 use std::os::unix::prelude::FileExt;
 use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
 
+use clap::{CommandFactory, Parser};
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
+use move_core_types::language_storage::TypeTag;
 use serde_json::json;
+use shared_crypto::intent::Intent;
 use sui_types::object::Owner;
+use sui_types::signature::GenericSignature;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
-    TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-    TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    Transaction, TransactionDataAPI, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+    TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use tokio::time::sleep;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands},
+    client_commands::{ExpirationArgs, SponsorArgs, SuiClientCommandResult, SuiClientCommands},
     sui_commands::SuiCommand,
 };
 use sui_config::{Config, NodeConfig, SUI_BENCHMARK_GENESIS_GAS_KEYSTORE_FILENAME};
@@ -200,6 +205,33 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_read_only_commands_keep_keystore_locked() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+    SuiCommand::Genesis {
+        working_dir: Some(working_dir.to_path_buf()),
+        write_config: None,
+        force: false,
+        from_config: None,
+        epoch_duration_ms: None,
+        benchmark_ips: None,
+    }
+    .execute()
+    .await?;
+    FileBasedKeystore::new(&working_dir.join(SUI_KEYSTORE_FILENAME))?.encrypt("correct horse")?;
+
+    // Neither loading the wallet nor listing addresses needs the passphrase
+    let mut context = WalletContext::new(&working_dir.join(SUI_CLIENT_CONFIG), None, None).await?;
+    assert!(context.config.keystore.is_locked());
+    match SuiClientCommands::Addresses.execute(&mut context).await? {
+        SuiClientCommandResult::Addresses(addresses, _) => assert_eq!(5, addresses.len()),
+        _ => panic!("Expected addresses"),
+    }
+    assert!(context.config.keystore.is_locked());
+    Ok(())
+}
+
 #[sim_test]
 async fn test_objects_command() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -518,6 +550,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         gas: None,
         gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        expiration: ExpirationArgs::default(),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -557,6 +590,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        expiration: ExpirationArgs::default(),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -583,6 +617,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        expiration: ExpirationArgs::default(),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -611,6 +646,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+        expiration: ExpirationArgs::default(),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -1821,6 +1857,247 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_split_and_transfer() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address2 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let commands = vec![
+        json!({"assign": "coins", "split_coins": {"coin": "$gas", "amounts": [1000, 10]}})
+            .to_string(),
+        json!({"transfer_objects": {"objects": ["$coins.0", "$coins.1"], "address": address2}})
+            .to_string(),
+    ];
+
+    // Dry run first, nothing should be created.
+    let resp = SuiClientCommands::Ptb {
+        file: None,
+        commands: commands.clone(),
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        expiration: ExpirationArgs::default(),
+        sponsor: SponsorArgs::default(),
+        dry_run: true,
+        dev_inspect: false,
+        trace: false,
+        gas_profile: None,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.status().is_ok());
+    assert_eq!(response.effects.created().len(), 2);
+
+    let resp = SuiClientCommands::Ptb {
+        file: None,
+        commands,
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        expiration: ExpirationArgs::default(),
+        sponsor: SponsorArgs::default(),
+        dry_run: false,
+        dev_inspect: false,
+        trace: false,
+        gas_profile: None,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(
+        response.status_ok().unwrap(),
+        "Command failed: {:?}",
+        response
+    );
+
+    let created = response.effects.unwrap().created().to_vec();
+    assert_eq!(created.len(), 2);
+    let mut values = vec![];
+    for obj_ref in created {
+        assert_eq!(obj_ref.owner, Owner::AddressOwner(address2));
+        values.push(get_gas_value(
+            &get_parsed_object_assert_existence(obj_ref.reference.object_id, context).await,
+        ));
+    }
+    values.sort();
+    assert_eq!(values, vec![10, 1000]);
+
+    // Referring to an unknown result fails before anything is sent.
+    let resp = SuiClientCommands::Ptb {
+        file: None,
+        commands: vec![
+            json!({"transfer_objects": {"objects": ["$coins"], "address": address2}}).to_string(),
+        ],
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        expiration: ExpirationArgs::default(),
+        sponsor: SponsorArgs::default(),
+        dry_run: false,
+        dev_inspect: false,
+        trace: false,
+        gas_profile: None,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_make_move_vec() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+
+    let ptb = |commands: Vec<String>| SuiClientCommands::Ptb {
+        file: None,
+        commands,
+        gas: None,
+        gas_budget: None,
+        expiration: ExpirationArgs::default(),
+        sponsor: SponsorArgs::default(),
+        dry_run: false,
+        dev_inspect: true,
+        trace: false,
+        gas_profile: None,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    };
+
+    // Literal elements of a primitive vector are pure values, given as numbers or strings.
+    let resp = ptb(vec![
+        json!({"make_move_vec": {"type": "u64", "elements": [1, "2", 3]}}).to_string(),
+    ])
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DevInspect(response) = resp else {
+        panic!("Command failed")
+    };
+    assert_eq!(response.error, None);
+    let (bytes, type_) = response.results.unwrap()[0].return_values[0].clone();
+    assert_eq!(bcs::from_bytes::<Vec<u64>>(&bytes)?, vec![1, 2, 3]);
+    assert_eq!(
+        TryInto::<TypeTag>::try_into(type_)?,
+        TypeTag::Vector(Box::new(TypeTag::U64))
+    );
+
+    // Without a primitive type, elements must be objects.
+    let resp = ptb(vec![
+        json!({"make_move_vec": {"elements": [1, 2]}}).to_string()
+    ])
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_sponsored_ptb() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let commands = vec![
+        json!({"assign": "coins", "split_coins": {"coin": "$gas", "amounts": [1000]}}).to_string(),
+        json!({"transfer_objects": {"objects": ["$coins"], "address": sender}}).to_string(),
+    ];
+    let resp = SuiClientCommands::Ptb {
+        file: None,
+        commands,
+        gas: None,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
+        expiration: ExpirationArgs::default(),
+        sponsor: SponsorArgs {
+            sponsor: Some(sponsor),
+            sponsor_gas: vec![],
+        },
+        dry_run: false,
+        dev_inspect: false,
+        trace: false,
+        gas_profile: None,
+        serialize_unsigned_transaction: true,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::SerializedUnsignedTransaction(data) = resp else {
+        panic!("Command failed")
+    };
+    assert_eq!(data.sender(), sender);
+    assert_eq!(data.gas_owner(), sponsor);
+
+    // Sponsoring an already built transaction only replaces its gas.
+    let resp = SuiClientCommands::SponsorTx {
+        tx_bytes: Base64::encode(bcs::to_bytes(&data)?),
+        sponsor,
+        gas: vec![],
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::SerializedUnsignedTransaction(responsored) = resp else {
+        panic!("Command failed")
+    };
+    assert_eq!(responsored.kind(), data.kind());
+    assert_eq!(responsored.gas_owner(), sponsor);
+    assert_eq!(
+        responsored.gas_budget(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER
+    );
+
+    let signatures = [sender, sponsor]
+        .iter()
+        .map(|signer| {
+            context
+                .config
+                .keystore
+                .sign_secure(signer, &data, Intent::sui_transaction())
+                .map(GenericSignature::from)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (tx_bytes, signatures) =
+        Transaction::from_generic_sig_data(data, Intent::sui_transaction(), signatures)
+            .to_tx_bytes_and_signatures();
+
+    // The sponsor's signature is required.
+    let resp = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: tx_bytes.encoded(),
+        signatures: vec![signatures[0].encoded()],
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    let resp = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: tx_bytes.encoded(),
+        signatures: signatures.into_iter().map(|s| s.encoded()).collect(),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::ExecuteSignedTx(response) = resp else {
+        panic!("Command failed")
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+    assert_eq!(effects.created()[0].owner, Owner::AddressOwner(sender));
+    Ok(())
+}
+
 #[sim_test]
 async fn test_signature_flag() -> Result<(), anyhow::Error> {
     let res = SignatureScheme::from_flag("0");
@@ -2095,3 +2372,22 @@ async fn test_get_owned_objects_owned_by_address_and_check_pagination() -> Resul
 
     Ok(())
 }
+
+#[test]
+fn test_cli_arguments() {
+    SuiCommand::command().debug_assert();
+
+    let ptb =
+        |args: &[&str]| SuiCommand::try_parse_from(["sui", "client", "ptb"].iter().chain(args));
+    assert!(ptb(&["--gas-budget", "1000", "--dry-run"]).is_ok());
+    assert!(ptb(&["--dev-inspect"]).is_ok());
+    assert!(ptb(&["--dry-run"]).is_err());
+    assert!(ptb(&["--gas-budget", "1000", "--dry-run", "--dev-inspect"]).is_err());
+    // Dev-inspect does not use the gas payment
+    assert!(ptb(&["--gas-budget", "1000", "--dev-inspect"]).is_err());
+    assert!(ptb(&["--gas", "0x2", "--dev-inspect"]).is_err());
+    assert!(ptb(&["--dev-inspect", "--trace"]).is_ok());
+    assert!(ptb(&["--gas-budget", "1000", "--trace"]).is_err());
+    assert!(ptb(&["--dev-inspect", "--gas-profile", "gas.folded"]).is_ok());
+    assert!(ptb(&["--gas-budget", "1000", "--gas-profile", "gas.folded"]).is_err());
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::json;
+
+use super::{parse_ptb_commands, PtbArg, PtbCommand};
+
+#[test]
+fn test_parse_ptb_arg() {
+    assert_eq!(PtbArg::parse(json!("$gas")).unwrap(), PtbArg::GasCoin);
+    assert_eq!(
+        PtbArg::parse(json!("$coins")).unwrap(),
+        PtbArg::Result("coins".to_string())
+    );
+    assert_eq!(
+        PtbArg::parse(json!("$coins.1")).unwrap(),
+        PtbArg::NestedResult("coins".to_string(), 1)
+    );
+    assert_eq!(
+        PtbArg::parse(json!("0x42")).unwrap(),
+        PtbArg::Value(json!("0x42"))
+    );
+    assert_eq!(
+        PtbArg::parse(json!(100)).unwrap(),
+        PtbArg::Value(json!(100))
+    );
+
+    assert!(PtbArg::parse(json!("$coins.x")).is_err());
+    assert!(PtbArg::parse(json!("$1coins")).is_err());
+    assert!(PtbArg::parse(json!("$")).is_err());
+}
+
+#[test]
+fn test_parse_ptb_commands() {
+    let commands = vec![
+        r#"{"assign": "coins", "split_coins": {"coin": "$gas", "amounts": [1000, "2000"]}}"#
+            .to_string(),
+        r#"{"transfer_objects": {"objects": ["$coins.0", "$coins.1"], "address": "0x42"}}"#
+            .to_string(),
+    ];
+    let specs = parse_ptb_commands(None, commands).unwrap();
+    assert_eq!(specs.len(), 2);
+    assert_eq!(specs[0].assign.as_deref(), Some("coins"));
+    assert!(matches!(
+        &specs[0].command,
+        PtbCommand::SplitCoins { amounts, .. } if amounts.len() == 2
+    ));
+    assert_eq!(specs[1].assign, None);
+    assert!(matches!(
+        &specs[1].command,
+        PtbCommand::TransferObjects { objects, .. } if objects.len() == 2
+    ));
+
+    assert!(parse_ptb_commands(None, vec![]).is_err());
+    assert!(parse_ptb_commands(None, vec![r#"{"unknown_command": {}}"#.to_string()]).is_err());
+}
@@ -210,6 +210,7 @@ impl SuiValidatorCommand {
             } => {
                 let dir = std::env::current_dir()?;
                 let protocol_key_file_name = dir.join("protocol.key");
+                context.unlock_keystore()?;
                 let account_key = match context.config.keystore.get_key(&sui_address)? {
                     SuiKeyPair::Ed25519(account_key) => SuiKeyPair::Ed25519(account_key.copy()),
                     _ => panic!(
@@ -570,6 +571,7 @@ async fn call_0x5(
         rgp,
     )
     .unwrap();
+    context.unlock_keystore()?;
     let signature =
         context
             .config
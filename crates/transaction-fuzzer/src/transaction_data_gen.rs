@@ -31,6 +31,8 @@ pub fn gen_transaction_expiration() -> impl Strategy<Value = TransactionExpirati
     prop_oneof![
         Just(TransactionExpiration::None),
         (0u64..=u64::MAX).prop_map(TransactionExpiration::Epoch),
+        (0u64..=u64::MAX).prop_map(TransactionExpiration::Timestamp),
+        (0u64..=u64::MAX).prop_map(TransactionExpiration::Checkpoint),
     ]
 }
 
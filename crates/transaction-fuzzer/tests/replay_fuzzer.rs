@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rand::thread_rng;
-use sui_replay::fuzz::{ReplayFuzzer, ReplayFuzzerConfig, ShuffleMutator};
+use sui_replay::fuzz::{
+    MultiMutator, MutatorKind, ReplayFuzzer, ReplayFuzzerConfig, ShuffleMutator,
+};
 const TESTNET_FULLNODE_URL: &str = "https://fullnode.testnet.sui.io:443";
 
 #[ignore]
@@ -17,6 +19,23 @@ async fn test_replay_fuzzer() {
             rng: thread_rng(),
             num_mutations_per_base_left: 3,
         }),
+        reproducer_dir: None,
+    };
+    let fuzzer = ReplayFuzzer::new(TESTNET_FULLNODE_URL.to_string(), None, config)
+        .await
+        .unwrap();
+    fuzzer.run(4).await.unwrap();
+}
+
+#[ignore]
+#[tokio::test]
+async fn test_replay_fuzzer_all_mutators() {
+    let config = ReplayFuzzerConfig {
+        checkpoint_id_start: Some(2_000_000),
+        checkpoint_id_end: Some(2_000_100),
+        num_mutations_per_base: 16,
+        mutator: Box::new(MultiMutator::new(MutatorKind::all(), 16)),
+        reproducer_dir: Some(std::env::temp_dir().join("replay_fuzzer")),
     };
     let fuzzer = ReplayFuzzer::new(TESTNET_FULLNODE_URL.to_string(), None, config)
         .await
@@ -486,6 +486,30 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 }
             }
 
+            /// Opens a set of tables in secondary mode, which only reads the DB and can be done
+            /// while another process has it open in read-write mode
+            /// The secondary instance keeps its own files at `with_secondary_path`, or in a temporary directory if it is not set
+            /// Writes to the tables fail
+            #[allow(unused_parens)]
+            pub fn open_tables_secondary(
+                primary_path: std::path::PathBuf,
+                with_secondary_path: Option<std::path::PathBuf>,
+                metric_conf: typed_store::rocks::MetricConf,
+                global_db_options_override: Option<rocksdb::Options>,
+            ) -> Self {
+                let secondary_path = with_secondary_path.unwrap_or_else(|| {
+                    tempfile::tempdir()
+                        .expect("Failed to open temporary directory")
+                        .into_path()
+                });
+                let inner = #intermediate_db_map_struct_name::open_tables_impl(primary_path, Some(secondary_path), false, metric_conf, global_db_options_override, None);
+                Self {
+                    #(
+                        #field_names: #post_process_fn(inner.#field_names),
+                    )*
+                }
+            }
+
             /// Returns a list of the tables name and type pairs
             pub fn describe_tables() -> std::collections::BTreeMap<String, (String, String)> {
                 vec![#(
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::marker::PhantomData;
+
 use move_core_types::language_storage::TypeTag;
 use sui_types::{error::ExecutionError, transaction::Argument};
 
@@ -28,6 +30,10 @@ pub trait ExecutionMode {
     /// UpgradeCap is produced
     fn packages_are_predefined() -> bool;
 
+    /// If set, an `ExecutionTrace` of the Move calls made, gas charged, and objects accessed is
+    /// collected and saved to the storage view
+    fn trace_execution() -> bool;
+
     fn empty_arguments() -> Self::ArgumentUpdates;
 
     fn empty_results() -> Self::ExecutionResults;
@@ -70,6 +76,10 @@ impl ExecutionMode for Normal {
         false
     }
 
+    fn trace_execution() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {}
 
     fn empty_results() -> Self::ExecutionResults {}
@@ -118,6 +128,10 @@ impl ExecutionMode for Genesis {
         true
     }
 
+    fn trace_execution() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {}
 
     fn empty_results() -> Self::ExecutionResults {}
@@ -169,6 +183,10 @@ impl ExecutionMode for System {
         true
     }
 
+    fn trace_execution() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {}
 
     fn empty_results() -> Self::ExecutionResults {}
@@ -224,6 +242,10 @@ impl ExecutionMode for DevInspect {
         false
     }
 
+    fn trace_execution() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {
         vec![]
     }
@@ -264,6 +286,65 @@ impl ExecutionMode for DevInspect {
     }
 }
 
+/// Same as `M`, additionally collecting an `ExecutionTrace` of the transaction.
+pub struct Trace<M: ExecutionMode>(PhantomData<M>);
+
+/// Same as `DevInspect`, additionally collecting an `ExecutionTrace` of the transaction.
+pub type DevInspectTrace = Trace<DevInspect>;
+
+impl<M: ExecutionMode> ExecutionMode for Trace<M> {
+    type ArgumentUpdates = M::ArgumentUpdates;
+    type ExecutionResults = M::ExecutionResults;
+
+    fn allow_arbitrary_function_calls() -> bool {
+        M::allow_arbitrary_function_calls()
+    }
+
+    fn allow_arbitrary_values() -> bool {
+        M::allow_arbitrary_values()
+    }
+
+    fn packages_are_predefined() -> bool {
+        M::packages_are_predefined()
+    }
+
+    fn trace_execution() -> bool {
+        true
+    }
+
+    fn empty_arguments() -> Self::ArgumentUpdates {
+        M::empty_arguments()
+    }
+
+    fn empty_results() -> Self::ExecutionResults {
+        M::empty_results()
+    }
+
+    fn add_argument_update<'vm, 'state, 'a, S: StorageView>(
+        context: &mut ExecutionContext<'vm, 'state, 'a, S>,
+        acc: &mut Self::ArgumentUpdates,
+        arg: Argument,
+        new_value: &Value,
+    ) -> Result<(), ExecutionError>
+    where
+        &'state S: SuiResolver,
+    {
+        M::add_argument_update(context, acc, arg, new_value)
+    }
+
+    fn finish_command<'vm, 'state, 'a, S: StorageView>(
+        context: &mut ExecutionContext<'vm, 'state, 'a, S>,
+        acc: &mut Self::ExecutionResults,
+        argument_updates: Self::ArgumentUpdates,
+        command_result: &[Value],
+    ) -> Result<(), ExecutionError>
+    where
+        &'state S: SuiResolver,
+    {
+        M::finish_command(context, acc, argument_updates, command_result)
+    }
+}
+
 fn value_to_bytes_and_tag<'vm, 'state, 'a, S: StorageView>(
     context: &mut ExecutionContext<'vm, 'state, 'a, S>,
     value: &Value,
@@ -39,6 +39,7 @@ use crate::{
 };
 
 use super::linkage_view::{LinkageInfo, LinkageView, SavedLinkage};
+use super::trace::ExecutionTracer;
 use super::types::*;
 
 sui_macros::checked_arithmetic! {
@@ -60,6 +61,8 @@ pub struct ExecutionContext<'vm, 'state, 'a, S: StorageView> {
     pub gas_status: &'a mut SuiGasStatus,
     /// The session used for interacting with Move types and calls
     pub session: Session<'state, 'vm, LinkageView<&'state S>>,
+    /// Collects a trace of the Move calls made, if the execution mode traces execution
+    pub tracer: Option<ExecutionTracer>,
     /// Additional transfers not from the Move runtime
     additional_transfers: Vec<(/* new owner */ SuiAddress, ObjectValue)>,
     /// Newly published packages
@@ -194,6 +197,7 @@ where
             tx_context,
             gas_status,
             session,
+            tracer: None,
             gas,
             inputs,
             results: vec![],
@@ -23,7 +23,10 @@ use move_vm_runtime::{
     move_vm::MoveVM,
     session::{LoadedFunctionInstantiation, SerializedReturnValues},
 };
-use move_vm_types::loaded_data::runtime_types::{StructType, Type};
+use move_vm_types::{
+    gas::GasMeter,
+    loaded_data::runtime_types::{StructType, Type},
+};
 use serde::{de::DeserializeSeed, Deserialize};
 use sui_move_natives::object_runtime::ObjectRuntime;
 use sui_protocol_config::ProtocolConfig;
@@ -56,7 +59,11 @@ use sui_verifier::{
 
 use crate::{adapter::substitute_package_id, execution_mode::ExecutionMode};
 
-use super::{context::*, types::*};
+use super::{
+    context::*,
+    trace::{ExecutionTracer, TracingGasMeter},
+    types::*,
+};
 
 sui_macros::checked_arithmetic! {
 
@@ -84,15 +91,31 @@ where
         gas_coin,
         inputs,
     )?;
+    if Mode::trace_execution() {
+        let tracer = ExecutionTracer::default();
+        let object_runtime: &mut ObjectRuntime = context.session.get_native_extensions().get_mut();
+        object_runtime.set_write_log(tracer.write_log());
+        context.tracer = Some(tracer);
+    }
     // execute commands
     let mut mode_results = Mode::empty_results();
     for (idx, command) in commands.into_iter().enumerate() {
+        if let Some(tracer) = &mut context.tracer {
+            tracer.start_command(idx);
+        }
         if let Err(err) = execute_command::<_, Mode>(&mut context, &mut mode_results, command) {
             let object_runtime: &ObjectRuntime = context.session.get_native_extensions().get();
             // We still need to record the loaded child objects for replay
             let loaded_child_objects = object_runtime.loaded_child_objects();
+            let execution_trace = context
+                .tracer
+                .take()
+                .map(|tracer| tracer.finish(object_runtime.dynamic_field_accesses()));
             drop(context);
             state_view.save_loaded_child_objects(loaded_child_objects);
+            if let Some(execution_trace) = execution_trace {
+                state_view.save_execution_trace(execution_trace);
+            }
             return Err(err.with_command_index(idx));
         };
     }
@@ -101,11 +124,18 @@ where
     let object_runtime: &ObjectRuntime = context.session.get_native_extensions().get();
     // We still need to record the loaded child objects for replay
     let loaded_child_objects = object_runtime.loaded_child_objects();
+    let execution_trace = context
+        .tracer
+        .take()
+        .map(|tracer| tracer.finish(object_runtime.dynamic_field_accesses()));
 
     // apply changes
     let finished = context.finish::<Mode>();
     // Save loaded objects for debug. We dont want to lose the info
     state_view.save_loaded_child_objects(loaded_child_objects);
+    if let Some(execution_trace) = execution_trace {
+        state_view.save_execution_trace(execution_trace);
+    }
 
     let ExecutionResults {
         object_changes,
@@ -811,16 +841,44 @@ where
         }
     }
     // script visibility checked manually for entry points
-    let mut result = context
-        .session
-        .execute_function_bypass_visibility(
+    let type_tags = if context.tracer.is_some() {
+        let type_tags = type_arguments
+            .iter()
+            .map(|ty| context.session.get_type_tag(ty))
+            .collect::<VMResult<Vec<_>>>()
+            .map_err(|e| context.convert_vm_error(e))?;
+        Some(type_tags)
+    } else {
+        None
+    };
+    let result = match (&mut context.tracer, type_tags) {
+        (Some(tracer), Some(type_tags)) => {
+            let gas_status = context.gas_status.move_gas_status();
+            tracer.enter_call(
+                module_id,
+                function.as_str(),
+                &type_tags,
+                gas_status.remaining_gas(),
+            );
+            let result = context.session.execute_function_bypass_visibility(
+                module_id,
+                function,
+                type_arguments,
+                serialized_arguments,
+                &mut TracingGasMeter::new(gas_status, tracer),
+            );
+            tracer.finish_call(gas_status.remaining_gas());
+            result
+        }
+        _ => context.session.execute_function_bypass_visibility(
             module_id,
             function,
             type_arguments,
             serialized_arguments,
             context.gas_status.move_gas_status(),
-        )
-        .map_err(|e| context.convert_vm_error(e))?;
+        ),
+    };
+    let mut result = result.map_err(|e| context.convert_vm_error(e))?;
 
     // When this function is used during publishing, it
     // may be executed several times, with objects being
@@ -4,4 +4,5 @@
 pub mod context;
 pub mod execution;
 pub mod linkage_view;
+pub mod trace;
 pub mod types;
@@ -0,0 +1,451 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    language_storage::{ModuleId, TypeTag},
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use sui_move_natives::object_runtime::ObjectWriteLog;
+use sui_types::{
+    base_types::ObjectID,
+    execution_trace::{CommandTrace, DynamicFieldAccess, ExecutionTrace, MoveCallTrace, MoveFrame},
+};
+
+/// Builds an `ExecutionTrace` for a programmable transaction, from the calls and gas charges
+/// observed by a `TracingGasMeter`, and the objects written by the `ObjectRuntime`.
+#[derive(Default)]
+pub struct ExecutionTracer {
+    trace: ExecutionTrace,
+    /// Calls that have been entered but not returned from yet, outermost first.
+    stack: Vec<OpenCall>,
+    /// Objects written by the object runtime that are not attributed to a call yet.
+    write_log: ObjectWriteLog,
+}
+
+struct OpenCall {
+    call: MoveCallTrace,
+    /// Gas remaining when the call was entered.
+    gas_at_entry: u64,
+}
+
+impl ExecutionTracer {
+    /// The log the object runtime should record object writes in, for them to be attributed to
+    /// the call that made them.
+    pub fn write_log(&self) -> ObjectWriteLog {
+        self.write_log.clone()
+    }
+
+    /// Start recording the calls made by the command at `index`.
+    pub fn start_command(&mut self, index: usize) {
+        self.trace.commands.push(CommandTrace {
+            command: index as u16,
+            calls: vec![],
+        });
+    }
+
+    /// Record entering a top-level Move call, made directly by the current command.
+    pub fn enter_call(
+        &mut self,
+        module_id: &ModuleId,
+        function: &str,
+        type_arguments: &[TypeTag],
+        remaining_gas: InternalGas,
+    ) {
+        let type_arguments = type_arguments.iter().map(|t| t.to_string()).collect();
+        self.push(
+            move_frame(module_id, function, type_arguments),
+            remaining_gas,
+        )
+    }
+
+    /// Record returning from a top-level Move call. Calls that are still open at this point did
+    /// not return because execution aborted: they are recorded as the abort stack and closed.
+    pub fn finish_call(&mut self, remaining_gas: InternalGas) {
+        if self.stack.is_empty() {
+            return;
+        }
+        self.trace.abort_stack = self.stack.iter().map(|c| c.call.frame.clone()).collect();
+        while !self.stack.is_empty() {
+            self.pop(remaining_gas)
+        }
+    }
+
+    /// Finish the trace, adding the dynamic fields loaded during execution. Object accesses are
+    /// filled in once the transaction's writes are final, by the temporary store.
+    pub fn finish(mut self, dynamic_fields: Vec<DynamicFieldAccess>) -> ExecutionTrace {
+        self.trace.dynamic_fields = dynamic_fields;
+        self.trace
+    }
+
+    fn push(&mut self, frame: MoveFrame, remaining_gas: InternalGas) {
+        self.attribute_writes();
+        self.stack.push(OpenCall {
+            call: MoveCallTrace {
+                frame,
+                native: false,
+                gas_used: 0,
+                instructions: 0,
+                objects_written: vec![],
+                calls: vec![],
+            },
+            gas_at_entry: remaining_gas.into(),
+        })
+    }
+
+    fn pop(&mut self, remaining_gas: InternalGas) {
+        self.attribute_writes();
+        let Some(OpenCall { mut call, gas_at_entry }) = self.stack.pop() else {
+            return;
+        };
+        call.gas_used = gas_at_entry.saturating_sub(remaining_gas.into());
+        if let Some(parent) = self.stack.last_mut() {
+            parent.call.calls.push(call)
+        } else if let Some(command) = self.trace.commands.last_mut() {
+            command.calls.push(call)
+        }
+    }
+
+    /// Attribute the objects written since a call was last entered or returned from to the
+    /// innermost open call. Objects written by commands outside of any call are not attributed.
+    fn attribute_writes(&mut self) {
+        let written = std::mem::take(&mut *self.write_log.lock().unwrap());
+        if let Some(open) = self.stack.last_mut() {
+            open.call.objects_written.extend(written);
+        }
+    }
+
+    fn count_instruction(&mut self) {
+        if let Some(open) = self.stack.last_mut() {
+            open.call.instructions += 1;
+        }
+    }
+
+    fn mark_native(&mut self) {
+        if let Some(open) = self.stack.last_mut() {
+            open.call.native = true;
+        }
+    }
+}
+
+fn move_frame(module_id: &ModuleId, function: &str, type_arguments: Vec<String>) -> MoveFrame {
+    MoveFrame {
+        package: ObjectID::from(*module_id.address()),
+        module: module_id.name().to_string(),
+        function: function.to_string(),
+        type_arguments,
+    }
+}
+
+/// A `GasMeter` that forwards every charge to the meter it wraps, and reports calls, returns and
+/// instructions to an `ExecutionTracer`.
+pub struct TracingGasMeter<'a, G: GasMeter> {
+    gas_meter: &'a mut G,
+    tracer: &'a mut ExecutionTracer,
+}
+
+impl<'a, G: GasMeter> TracingGasMeter<'a, G> {
+    pub fn new(gas_meter: &'a mut G, tracer: &'a mut ExecutionTracer) -> Self {
+        Self { gas_meter, tracer }
+    }
+
+    fn instruction(&mut self, charge: PartialVMResult<()>) -> PartialVMResult<()> {
+        self.tracer.count_instruction();
+        charge
+    }
+
+    /// Record a call once it has been charged for. `remaining_gas` is the gas remaining before
+    /// the charge, so the cost of the call itself is attributed to the callee.
+    fn call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        type_arguments: Vec<String>,
+        remaining_gas: InternalGas,
+        charge: PartialVMResult<()>,
+    ) -> PartialVMResult<()> {
+        self.tracer.count_instruction();
+        charge?;
+        self.tracer.push(
+            move_frame(module_id, func_name, type_arguments),
+            remaining_gas,
+        );
+        Ok(())
+    }
+}
+
+impl<'a, G: GasMeter> GasMeter for TracingGasMeter<'a, G> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_simple_instr(instr);
+        self.instruction(charge)
+    }
+
+    fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_pop(popped_val);
+        self.instruction(charge)
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let remaining_gas = self.gas_meter.remaining_gas();
+        let charge = self
+            .gas_meter
+            .charge_call(module_id, func_name, args, num_locals);
+        self.call(module_id, func_name, vec![], remaining_gas, charge)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let ty_args: Vec<_> = ty_args.collect();
+        let type_arguments = ty_args
+            .iter()
+            .map(|ty| ty.to_type_tag().to_string())
+            .collect();
+        let remaining_gas = self.gas_meter.remaining_gas();
+        let charge = self.gas_meter.charge_call_generic(
+            module_id,
+            func_name,
+            ty_args.into_iter(),
+            args,
+            num_locals,
+        );
+        self.call(module_id, func_name, type_arguments, remaining_gas, charge)
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_ld_const(size);
+        self.instruction(charge)
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        // Part of the same `LdConst` instruction as `charge_ld_const`
+        self.gas_meter.charge_ld_const_after_deserialization(val)
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_copy_loc(val);
+        self.instruction(charge)
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_move_loc(val);
+        self.instruction(charge)
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_store_loc(val);
+        self.instruction(charge)
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_pack(is_generic, args);
+        self.instruction(charge)
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_unpack(is_generic, args);
+        self.instruction(charge)
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_read_ref(val);
+        self.instruction(charge)
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_write_ref(new_val, old_val);
+        self.instruction(charge)
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_eq(lhs, rhs);
+        self.instruction(charge)
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_neq(lhs, rhs);
+        self.instruction(charge)
+    }
+
+    fn charge_borrow_global(
+        &mut self,
+        is_mut: bool,
+        is_generic: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let charge = self
+            .gas_meter
+            .charge_borrow_global(is_mut, is_generic, ty, is_success);
+        self.instruction(charge)
+    }
+
+    fn charge_exists(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        exists: bool,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_exists(is_generic, ty, exists);
+        self.instruction(charge)
+    }
+
+    fn charge_move_from(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_move_from(is_generic, ty, val);
+        self.instruction(charge)
+    }
+
+    fn charge_move_to(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: impl ValueView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let charge = self
+            .gas_meter
+            .charge_move_to(is_generic, ty, val, is_success);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_pack<'b>(
+        &mut self,
+        ty: impl TypeView + 'b,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_pack(ty, args);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_len(ty);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_borrow(is_mut, ty, is_success);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_push_back(ty, val);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_pop_back(ty, val);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self
+            .gas_meter
+            .charge_vec_unpack(ty, expect_num_elements, elems);
+        self.instruction(charge)
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_vec_swap(ty);
+        self.instruction(charge)
+    }
+
+    fn charge_load_resource(
+        &mut self,
+        loaded: Option<(NumBytes, impl ValueView)>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_load_resource(loaded)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        // Natives have no frame to drop: they return once they are charged for, unless they
+        // aborted (in which case there are no return values)
+        let returned = ret_vals.is_some();
+        let charge = self.gas_meter.charge_native_function(amount, ret_vals);
+        if returned && charge.is_ok() {
+            self.tracer.pop(self.gas_meter.remaining_gas());
+        }
+        charge
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.tracer.mark_native();
+        self.gas_meter
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charge = self.gas_meter.charge_drop_frame(locals);
+        if charge.is_ok() {
+            self.tracer.pop(self.gas_meter.remaining_gas());
+        }
+        charge
+    }
+
+    fn remaining_gas(&self) -> InternalGas {
+        self.gas_meter.remaining_gas()
+    }
+}
@@ -14,12 +14,13 @@ use move_vm_types::{
 };
 use std::{
     collections::{BTreeMap, BTreeSet},
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 use sui_protocol_config::{check_limit_by_meter, LimitThresholdCrossed, ProtocolConfig};
 use sui_types::{
     base_types::{MoveObjectType, ObjectID, SequenceNumber, SuiAddress},
     error::{ExecutionError, ExecutionErrorKind, VMMemoryLimitExceededSubStatusCode},
+    execution_trace::DynamicFieldAccess,
     metrics::LimitsMetrics,
     object::{MoveObject, Owner},
     storage::{ChildObjectResolver, DeleteKind, WriteKind},
@@ -44,6 +45,11 @@ pub enum ObjectEvent {
 // LinkedHashSet has a bug for accessing the back/last element
 type Set<K> = LinkedHashMap<K, ()>;
 
+/// IDs of the objects created, transferred or added as child objects during execution, in the
+/// order it happened. Shared with an execution tracer, which attributes each write to the call
+/// that was executing.
+pub type ObjectWriteLog = Arc<Mutex<Vec<ObjectID>>>;
+
 #[derive(Default)]
 pub(crate) struct TestInventories {
     pub(crate) objects: BTreeMap<ObjectID, Value>,
@@ -135,6 +141,8 @@ pub struct ObjectRuntime<'a> {
 
     pub(crate) constants: LocalProtocolConfig,
     pub(crate) metrics: Arc<LimitsMetrics>,
+    // only set when the execution is traced
+    write_log: Option<ObjectWriteLog>,
 }
 
 pub enum TransferResult {
@@ -176,6 +184,18 @@ impl<'a> ObjectRuntime<'a> {
             loaded_child_objects_fixed: protocol_config.loaded_child_objects_fixed(),
             constants: LocalProtocolConfig::new(protocol_config),
             metrics,
+            write_log: None,
+        }
+    }
+
+    /// Record the objects written from now on in `write_log`.
+    pub fn set_write_log(&mut self, write_log: ObjectWriteLog) {
+        self.write_log = Some(write_log);
+    }
+
+    fn log_write(&self, id: ObjectID) {
+        if let Some(write_log) = &self.write_log {
+            write_log.lock().unwrap().push(id);
         }
     }
 
@@ -201,6 +221,7 @@ impl<'a> ObjectRuntime<'a> {
         self.state.deleted_ids.remove(&id);
         // mark the id as new
         self.state.new_ids.insert(id, ());
+        self.log_write(id);
         Ok(())
     }
 
@@ -280,6 +301,7 @@ impl<'a> ObjectRuntime<'a> {
         };
 
         self.state.transfers.insert(id, (owner, ty, obj));
+        self.log_write(id);
         Ok(transfer_result)
     }
 
@@ -343,7 +365,9 @@ impl<'a> ObjectRuntime<'a> {
         child_value: Value,
     ) -> PartialVMResult<()> {
         self.object_store
-            .add_object(parent, child, child_ty, child_move_type, child_value)
+            .add_object(parent, child, child_ty, child_move_type, child_value)?;
+        self.log_write(child);
+        Ok(())
     }
 
     // returns None if a child object is still borrowed
@@ -379,6 +403,24 @@ impl<'a> ObjectRuntime<'a> {
             .filter_map(|(id, obj_opt)| Some((*id, obj_opt.as_ref()?.version())))
             .collect()
     }
+
+    /// Child objects looked up during execution, with the parent they were looked up through.
+    /// Includes lookups of fields that did not exist.
+    pub fn dynamic_field_accesses(&self) -> Vec<DynamicFieldAccess> {
+        let parents = self.object_store.cached_object_parents();
+        self.object_store
+            .cached_objects()
+            .iter()
+            .filter_map(|(id, obj_opt)| {
+                Some(DynamicFieldAccess {
+                    parent: *parents.get(id)?,
+                    field_id: *id,
+                    version: obj_opt.as_ref().map(|obj| obj.version()),
+                    object_type: obj_opt.as_ref().map(|obj| obj.type_().to_string()),
+                })
+            })
+            .collect()
+    }
 }
 
 pub fn max_event_error(max_events: u64) -> PartialVMError {
@@ -42,6 +42,8 @@ struct Inner<'a> {
     // cached objects from the resolver. An object might be in this map but not in the store
     // if it's existence was queried, but the value was not used.
     cached_objects: BTreeMap<ObjectID, Option<MoveObject>>,
+    // the parent each cached object was looked up through
+    cached_object_parents: BTreeMap<ObjectID, ObjectID>,
     // whether or not this TX is gas metered
     is_metered: bool,
     // Local protocol config used to enforce limits
@@ -139,6 +141,7 @@ impl<'a> Inner<'a> {
                     ));
             };
 
+            self.cached_object_parents.insert(child, parent);
             e.insert(obj_opt);
         }
         Ok(self.cached_objects.get(&child).unwrap().as_ref())
@@ -202,6 +205,7 @@ impl<'a> ObjectStore<'a> {
             inner: Inner {
                 resolver,
                 cached_objects: BTreeMap::new(),
+                cached_object_parents: BTreeMap::new(),
                 is_metered,
                 constants: constants.clone(),
                 metrics,
@@ -357,6 +361,10 @@ impl<'a> ObjectStore<'a> {
         &self.inner.cached_objects
     }
 
+    pub(super) fn cached_object_parents(&self) -> &BTreeMap<ObjectID, ObjectID> {
+        &self.inner.cached_object_parents
+    }
+
     // retrieve the `Op` effects for the child objects
     pub(super) fn take_effects(
         &mut self,